@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE track_mappings (
+    song_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    mapped_id TEXT NOT NULL,
+    score DOUBLE NOT NULL DEFAULT 0,
+    user_override BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (song_id, provider)
+);
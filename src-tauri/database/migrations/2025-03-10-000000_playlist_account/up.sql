@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE playlists
+ADD COLUMN account_id TEXT;
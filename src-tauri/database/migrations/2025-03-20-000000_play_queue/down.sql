@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE queue_items;
+DROP TABLE queue_state;
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE queue_items (
+    position INTEGER NOT NULL PRIMARY KEY,
+    song_id TEXT NOT NULL,
+    song TEXT NOT NULL
+);
+
+CREATE TABLE queue_state (
+    id INTEGER NOT NULL PRIMARY KEY,
+    current_index INTEGER NOT NULL DEFAULT 0,
+    repeat TEXT NOT NULL DEFAULT 'None',
+    shuffle BOOLEAN NOT NULL DEFAULT FALSE,
+    original_order TEXT
+);
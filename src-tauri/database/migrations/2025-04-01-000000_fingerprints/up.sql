@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE TABLE fingerprints (
+    song_id TEXT NOT NULL PRIMARY KEY,
+    fingerprint TEXT NOT NULL,
+    duration DOUBLE NOT NULL
+);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE allsongs ADD COLUMN modified DOUBLE;
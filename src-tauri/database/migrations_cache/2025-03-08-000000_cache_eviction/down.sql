@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX `cache_last_accessed_idx`;
+DROP INDEX `cache_expires_idx`;
+ALTER TABLE `cache` DROP COLUMN `size`;
+ALTER TABLE `cache` DROP COLUMN `last_accessed`;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE `cache` ADD COLUMN `last_accessed` INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE `cache` ADD COLUMN `size` INTEGER NOT NULL DEFAULT 0;
+
+UPDATE `cache` SET `size` = length(`blob`), `last_accessed` = strftime('%s', 'now');
+
+CREATE INDEX `cache_expires_idx` on `cache`(`expires`);
+CREATE INDEX `cache_last_accessed_idx` on `cache`(`last_accessed`);
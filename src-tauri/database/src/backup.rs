@@ -0,0 +1,101 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    ffi::{CStr, CString},
+    path::Path,
+    ptr, thread,
+    time::Duration,
+};
+
+use libsqlite3_sys as ffi;
+use tracing::{debug, trace};
+use types::errors::Result;
+
+/// Pages copied per backup step. Locks are released between steps so
+/// playback and scans can keep writing while the backup runs.
+const PAGES_PER_STEP: i32 = 256;
+
+struct Handle(*mut ffi::sqlite3);
+
+impl Handle {
+    #[tracing::instrument(level = "trace", skip())]
+    fn open(path: &Path, flags: i32) -> Result<Self> {
+        let c_path = CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|e| format!("Invalid database path: {}", e))?;
+        let mut db = ptr::null_mut();
+        let rc = unsafe { ffi::sqlite3_open_v2(c_path.as_ptr(), &mut db, flags, ptr::null()) };
+        let handle = Self(db);
+        if rc != ffi::SQLITE_OK {
+            return Err(format!("Failed to open {:?}: {}", path, handle.error()).into());
+        }
+        unsafe { ffi::sqlite3_busy_timeout(db, 250) };
+        Ok(handle)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn error(&self) -> String {
+        if self.0.is_null() {
+            return "out of memory".to_string();
+        }
+        unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(self.0)) }
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_close(self.0) };
+    }
+}
+
+#[tracing::instrument(level = "trace", skip())]
+pub(crate) fn backup_sqlite(src: &Path, dest: &Path) -> Result<()> {
+    if dest.exists() {
+        std::fs::remove_file(dest)?;
+    }
+
+    let src = Handle::open(src, ffi::SQLITE_OPEN_READONLY)?;
+    let dest = Handle::open(dest, ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE)?;
+
+    let main = c"main";
+    let backup = unsafe { ffi::sqlite3_backup_init(dest.0, main.as_ptr(), src.0, main.as_ptr()) };
+    if backup.is_null() {
+        return Err(format!("Failed to start backup: {}", dest.error()).into());
+    }
+
+    let rc = loop {
+        let rc = unsafe { ffi::sqlite3_backup_step(backup, PAGES_PER_STEP) };
+        match rc {
+            ffi::SQLITE_OK => {
+                trace!("Backup remaining pages: {}", unsafe {
+                    ffi::sqlite3_backup_remaining(backup)
+                });
+            }
+            ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => thread::sleep(Duration::from_millis(50)),
+            _ => break rc,
+        }
+    };
+
+    unsafe { ffi::sqlite3_backup_finish(backup) };
+    if rc != ffi::SQLITE_DONE {
+        return Err(format!("Failed to back up database: {}", dest.error()).into());
+    }
+
+    debug!("Database backup complete");
+    Ok(())
+}
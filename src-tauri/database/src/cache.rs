@@ -16,31 +16,71 @@
 
 use std::{
     path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
     time::{Duration, SystemTime},
 };
 
 use diesel::{
     connection::SimpleConnection,
+    delete,
+    dsl::{count_star, sum},
     insert_into,
-    query_dsl::methods::FilterDsl,
     r2d2::{self, ConnectionManager, Pool},
-    ExpressionMethods, RunQueryDsl, SqliteConnection,
+    update, EscapeExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection,
+    TextExpressionMethods,
 };
 
 use serde::{Deserialize, Serialize};
 use tracing::debug;
-use types::cache::CacheModel;
+use types::cache::{CacheModel, CacheStats};
 use types::errors::Result;
 
 use super::migrations::run_migration_cache;
 use types::cache_schema::{
     self,
-    cache::{dsl::cache, url},
+    cache::{dsl::cache, expires, id, last_accessed, size, url},
 };
 
-#[derive(Debug)]
+pub const DEFAULT_MAX_CACHE_SIZE: u64 = 100 * 1024 * 1024;
+
+// How often expired and least recently used entries are pruned
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+// Free pages worth more than this are reclaimed with VACUUM
+const VACUUM_THRESHOLD: i64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    max_size: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Clone)]
 pub struct CacheHolder {
     pool: Pool<ConnectionManager<SqliteConnection>>,
+    counters: Arc<CacheCounters>,
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn now() -> Result<i64> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs() as i64)
+}
+
+#[tracing::instrument(level = "trace", skip(prefix))]
+fn like_prefix(prefix: &str) -> String {
+    format!(
+        "{}%",
+        prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    )
 }
 
 impl CacheHolder {
@@ -48,6 +88,10 @@ impl CacheHolder {
     pub fn new(path: PathBuf) -> Self {
         let db = Self {
             pool: Self::connect(path),
+            counters: Arc::new(CacheCounters {
+                max_size: AtomicU64::new(DEFAULT_MAX_CACHE_SIZE),
+                ..Default::default()
+            }),
         };
 
         run_migration_cache(&mut db.pool.get().expect("Failed to get connection to DB"));
@@ -70,21 +114,42 @@ impl CacheHolder {
             .expect("Failed to create pool.")
     }
 
-    #[tracing::instrument(level = "trace", skip(self, _url, blob, expires))]
-    pub fn set<T>(&self, _url: &str, blob: &T, expires: i32) -> Result<()>
+    /// Periodically prunes the cache and reclaims the freed space
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn spawn_maintenance_task(&self) {
+        let holder = self.clone();
+        thread::spawn(move || loop {
+            if let Err(e) = holder.prune() {
+                tracing::error!("Failed to prune cache: {:?}", e);
+            }
+            if let Err(e) = holder.vacuum(false) {
+                tracing::error!("Failed to vacuum cache: {:?}", e);
+            }
+            thread::sleep(MAINTENANCE_INTERVAL);
+        });
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_max_size(&self, max_size: u64) {
+        self.counters.max_size.store(max_size, Ordering::Relaxed);
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, _url, blob, expires_in))]
+    pub fn set<T>(&self, _url: &str, blob: &T, expires_in: i32) -> Result<()>
     where
         T: Serialize,
     {
         let mut conn = self.pool.get().unwrap();
 
-        let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
-        let new_expires = current_time + Duration::from_secs(expires.unsigned_abs() as u64);
-
+        let current_time = now()?;
+        let blob = serde_json::to_vec(blob)?;
         let cache_model = CacheModel {
             id: None,
             url: _url.to_string(),
-            blob: serde_json::to_vec(blob)?,
-            expires: new_expires.as_secs() as i64,
+            size: blob.len() as i32,
+            blob,
+            expires: current_time + expires_in.unsigned_abs() as i64,
+            last_accessed: current_time,
         };
         insert_into(cache)
             .values(&cache_model)
@@ -97,21 +162,140 @@ impl CacheHolder {
 
     #[tracing::instrument(level = "trace", skip(self, _url))]
     pub fn get<T>(&self, _url: &str) -> Result<T>
+    where
+        T: for<'a> Deserialize<'a>,
+    {
+        let res = self.get_inner(_url);
+        let counter = if res.is_ok() {
+            &self.counters.hits
+        } else {
+            &self.counters.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        res
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, _url))]
+    fn get_inner<T>(&self, _url: &str) -> Result<T>
     where
         T: for<'a> Deserialize<'a>,
     {
         let mut conn = self.pool.get().unwrap();
 
         let data: CacheModel = cache.filter(url.eq(_url)).first::<CacheModel>(&mut conn)?;
-        let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+        let current_time = now()?;
 
-        let expires = Duration::from_secs(data.expires as u64);
-        if current_time > expires {
+        if current_time > data.expires {
             debug!("Cache expired for {}", _url);
+            delete(cache.filter(id.eq(data.id))).execute(&mut conn)?;
             return Err("Cache expired".into());
         }
 
+        update(cache.filter(id.eq(data.id)))
+            .set(last_accessed.eq(current_time))
+            .execute(&mut conn)?;
+
         let parsed: T = serde_json::from_slice(&data.blob)?;
         Ok(parsed)
     }
+
+    /// Removes expired entries, then evicts least recently used entries until the cache fits its max size.
+    /// Returns the number of entries removed.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn prune(&self) -> Result<usize> {
+        let mut conn = self.pool.get().unwrap();
+
+        let mut removed = delete(cache.filter(expires.lt(now()?))).execute(&mut conn)?;
+
+        let max_size = self.counters.max_size.load(Ordering::Relaxed) as i64;
+        let total_size = cache
+            .select(sum(size))
+            .first::<Option<i64>>(&mut conn)?
+            .unwrap_or_default();
+
+        if total_size > max_size {
+            let entries = cache
+                .select((id, size))
+                .order(last_accessed.asc())
+                .load::<(Option<i32>, i32)>(&mut conn)?;
+
+            let mut excess = total_size - max_size;
+            let mut evicted = vec![];
+            for (entry_id, entry_size) in entries {
+                if excess <= 0 {
+                    break;
+                }
+                excess -= entry_size as i64;
+                evicted.push(entry_id);
+            }
+
+            for chunk in evicted.chunks(500) {
+                removed += delete(cache.filter(id.eq_any(chunk.to_vec()))).execute(&mut conn)?;
+            }
+        }
+
+        if removed > 0 {
+            tracing::info!("Pruned {} cache entries", removed);
+        }
+        Ok(removed)
+    }
+
+    /// Runs VACUUM if enough space is free, or always if `force` is set
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn vacuum(&self, force: bool) -> Result<()> {
+        let mut conn = self.pool.get().unwrap();
+
+        if !force {
+            let free_pages = diesel::select(diesel::dsl::sql::<diesel::sql_types::BigInt>(
+                "(SELECT freelist_count FROM pragma_freelist_count()) * (SELECT page_size FROM pragma_page_size())",
+            ))
+            .get_result::<i64>(&mut conn)?;
+            if free_pages < VACUUM_THRESHOLD {
+                return Ok(());
+            }
+        }
+
+        tracing::info!("Vacuuming cache");
+        conn.batch_execute("VACUUM;")?;
+        Ok(())
+    }
+
+    /// Removes all entries whose url starts with `prefix`, or every entry if it is None.
+    /// Returns the number of entries removed.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn clear_cache(&self, prefix: Option<String>) -> Result<usize> {
+        let mut conn = self.pool.get().unwrap();
+
+        let removed = match prefix {
+            Some(prefix) => delete(cache.filter(url.like(like_prefix(&prefix)).escape('\\')))
+                .execute(&mut conn)?,
+            None => delete(cache).execute(&mut conn)?,
+        };
+        Ok(removed)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_cache_stats(&self, prefix: Option<String>) -> Result<CacheStats> {
+        let mut conn = self.pool.get().unwrap();
+
+        let pattern = like_prefix(&prefix.unwrap_or_default());
+        let (entries, total_size) = cache
+            .filter(url.like(&pattern).escape('\\'))
+            .select((count_star(), sum(size)))
+            .first::<(i64, Option<i64>)>(&mut conn)?;
+        let expired_entries = cache
+            .filter(url.like(&pattern).escape('\\'))
+            .filter(expires.lt(now()?))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+
+        Ok(CacheStats {
+            entries,
+            expired_entries,
+            size: total_size.unwrap_or_default(),
+            max_size: self.counters.max_size.load(Ordering::Relaxed),
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+        })
+    }
 }
@@ -14,30 +14,48 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::cmp::min;
+use std::cmp::{min, Ordering};
+use std::collections::{HashMap, HashSet};
 
-use std::fmt::Write;
 use std::str::FromStr;
-use std::{path::PathBuf, vec};
+use std::{
+    path::{Path, PathBuf},
+    vec,
+};
 
 use diesel::{
     connection::SimpleConnection,
     delete, insert_into,
+    migration::MigrationVersion,
     r2d2::{self, ConnectionManager, Pool, PooledConnection},
-    update, Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection,
+    replace_into,
+    sql_types::Text,
+    update, Connection, ExpressionMethods, OptionalExtension, QueryDsl, QueryableByName,
+    RunQueryDsl, SqliteConnection,
+};
+use diesel::{
+    dsl::{count_star, sum},
+    BoolExpressionMethods, Insertable, TextExpressionMethods,
 };
-use diesel::{BoolExpressionMethods, Insertable, TextExpressionMethods};
 use diesel_logger::LoggingConnection;
+use diesel_migrations::MigrationHarness;
 use macros::{filter_field, filter_field_like};
 use serde_json::Value;
 use tracing::{debug, info, trace};
 use uuid::Uuid;
 
 use types::common::{BridgeUtils, SearchByTerm};
-use types::entities::{Analytics, EntityInfo, PlaylistBridge, SearchResult};
+use types::entities::{
+    Analytics, DuplicateReport, EntityInfo, LibraryStats, PlaylistBridge, QueueItem, QueueStateRow,
+    SearchResult, SongFingerprint, TrackMapping,
+};
 use types::errors::{MoosyncError, Result};
 use types::schema::analytics::dsl::analytics;
+use types::schema::fingerprints::dsl::fingerprints;
 use types::schema::playlists::dsl::playlists;
+use types::schema::queue_items::dsl::queue_items;
+use types::schema::queue_state::dsl::queue_state;
+use types::schema::track_mappings::dsl::track_mappings;
 use types::songs::SearchableSong;
 use types::{
     schema::{
@@ -56,45 +74,59 @@ use types::{
             AlbumBridge, ArtistBridge, GenreBridge, GetEntityOptions, QueryableAlbum,
             QueryableArtist, QueryableGenre, QueryablePlaylist,
         },
-        songs::{GetSongOptions, QueryableSong, Song},
+        songs::{GetSongOptions, QueryableSong, Song, SongType},
     },
 };
 
-use super::migrations::run_migrations;
+use super::{backup::backup_sqlite, fingerprint, migrations::run_migrations};
 
 #[derive(Debug, Clone)]
 pub struct Database {
     pool: Pool<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+    path: PathBuf,
 }
 
 impl Database {
     #[tracing::instrument(level = "trace", skip(path))]
     pub fn new(path: PathBuf) -> Self {
+        Self::open(path).expect("Failed to open database")
+    }
+
+    /// Like [Database::new] but returns an error if the database can't be
+    /// opened or migrated, e.g. when checking a restored backup
+    #[tracing::instrument(level = "trace", skip(path))]
+    pub fn open(path: PathBuf) -> Result<Self> {
         let db = Self {
-            pool: Self::connect(path),
+            pool: Self::connect(&path)?,
+            path,
         };
 
-        run_migrations(&mut db.pool.get().expect("Failed to get connection to DB"));
-        db.pool.get().unwrap().batch_execute("
+        let mut conn = db.pool.get().map_err(|e| e.to_string())?;
+        run_migrations(&mut conn)?;
+        conn.batch_execute("
             PRAGMA journal_mode = WAL;          -- better write-concurrency
             PRAGMA synchronous = NORMAL;        -- fsync only in critical moments
             PRAGMA wal_autocheckpoint = 1000;   -- write WAL changes back every 1000 pages, for an in average 1MB WAL file. May affect readers if number is increased
             PRAGMA wal_checkpoint(TRUNCATE);    -- free some space by truncating possibly massive WAL files from the last run.
             PRAGMA busy_timeout = 250;          -- sleep if the database is busy
-        ").expect("Failed to set DB options");
+        ")?;
+        drop(conn);
 
         info!("Created DB instance");
-        db
+        Ok(db)
     }
 
     #[tracing::instrument(level = "trace", skip(path))]
-    fn connect(path: PathBuf) -> Pool<ConnectionManager<LoggingConnection<SqliteConnection>>> {
-        let manager =
-            ConnectionManager::<LoggingConnection<SqliteConnection>>::new(path.to_str().unwrap());
+    fn connect(
+        path: &Path,
+    ) -> Result<Pool<ConnectionManager<LoggingConnection<SqliteConnection>>>> {
+        let manager = ConnectionManager::<LoggingConnection<SqliteConnection>>::new(
+            path.to_str().ok_or("Invalid database path")?,
+        );
 
-        r2d2::Pool::builder()
+        Ok(r2d2::Pool::builder()
             .build(manager)
-            .expect("Failed to create pool.")
+            .map_err(|e| format!("Failed to create pool: {}", e))?)
     }
 
     #[tracing::instrument(level = "trace", skip(self, conn))]
@@ -213,6 +245,15 @@ impl Database {
                 song.song._id = Some(Uuid::new_v4().to_string());
             }
 
+            if let Some(moved_id) = self.find_moved_song(&song.song, &mut conn)? {
+                info!("Song {} moved to {:?}", moved_id, song.song.path);
+                update(QueryDsl::filter(allsongs, _id.eq(&moved_id)))
+                    .set(song_path.eq(&song.song.path))
+                    .execute(&mut conn)?;
+            }
+
+            self.clear_stale_fingerprint(&song.song, &mut conn)?;
+
             let changed = insert_into(allsongs)
                 .values(&song.song)
                 .on_conflict(song_path)
@@ -224,73 +265,198 @@ impl Database {
                 continue;
             }
 
-            if let Some(_album) = &mut song.album {
-                let album_id_ = self
-                    .get_albums(
-                        QueryableAlbum::search_by_term(_album.album_name.clone()),
-                        false,
-                        &mut conn,
-                    )?
-                    .first()
-                    .map(|v| v.album_id.clone().unwrap())
-                    .unwrap_or_else(|| self.insert_album(&mut conn, _album).unwrap());
+            // Updating a known path keeps the id already in the library
+            if let Some(path) = &song.song.path {
+                song.song._id = QueryDsl::filter(allsongs, song_path.eq(path))
+                    .select(_id)
+                    .first(&mut conn)?;
+            }
 
-                AlbumBridge::insert_value(album_id_.clone(), song.song._id.clone().unwrap())
-                    .insert_into(album_bridge)
-                    .on_conflict_do_nothing()
-                    .execute(&mut conn)?;
+            self.link_song_entities(&mut conn, song)?;
 
-                _album.album_id = Some(album_id_);
-            }
+            trace!("Inserted song, {:?}", song);
+        }
+        info!("Inserted all songs");
+        Ok(())
+    }
 
-            if let Some(_artists) = &mut song.artists {
-                for mut _artist in _artists {
-                    let artist_id_ = self
+    /// Removes the fingerprint of the song at the path of `song` when the
+    /// file's hash or modification time changed, so it is fingerprinted again
+    #[tracing::instrument(level = "trace", skip(self, conn, song))]
+    fn clear_stale_fingerprint(
+        &self,
+        song: &QueryableSong,
+        conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+    ) -> Result<()> {
+        let Some(path) = &song.path else {
+            return Ok(());
+        };
+        let Some((Some(stored_id), hash, modified)) =
+            QueryDsl::filter(allsongs, song_path.eq(path))
+                .select((_id, schema::allsongs::hash, schema::allsongs::modified))
+                .first::<(Option<String>, Option<String>, Option<f64>)>(conn)
+                .optional()?
+        else {
+            return Ok(());
+        };
+
+        // Unknown values, e.g. of songs scanned before they were stored, and
+        // hashes skipped for unchanged files don't count as changes
+        let hash_changed = matches!((&song.hash, &hash), (Some(new), Some(old)) if new != old);
+        let modified_changed =
+            matches!((song.modified, modified), (Some(new), Some(old)) if new != old);
+        if hash_changed || modified_changed {
+            info!(
+                "Song {} changed on disk, clearing its fingerprint",
+                stored_id
+            );
+            delete(QueryDsl::filter(
+                fingerprints,
+                schema::fingerprints::song_id.eq(stored_id),
+            ))
+            .execute(conn)?;
+        }
+        Ok(())
+    }
+
+    /// Links the album, artists and genres of `song` to it, creating the ones
+    /// not in the library yet. Known artists are matched by MBID first.
+    #[tracing::instrument(level = "trace", skip(self, conn, song))]
+    fn link_song_entities(
+        &self,
+        conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+        song: &mut Song,
+    ) -> Result<()> {
+        if let Some(_album) = &mut song.album {
+            let album_id_ = self
+                .get_albums(
+                    QueryableAlbum::search_by_term(_album.album_name.clone()),
+                    false,
+                    conn,
+                )?
+                .first()
+                .map(|v| v.album_id.clone().unwrap())
+                .unwrap_or_else(|| self.insert_album(conn, _album).unwrap());
+
+            AlbumBridge::insert_value(album_id_.clone(), song.song._id.clone().unwrap())
+                .insert_into(album_bridge)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+
+            _album.album_id = Some(album_id_);
+        }
+
+        if let Some(_artists) = &mut song.artists {
+            for mut _artist in _artists {
+                let mut existing = None;
+                if _artist.artist_mbid.is_some() {
+                    existing = self
+                        .get_artists(
+                            QueryableArtist {
+                                artist_mbid: _artist.artist_mbid.clone(),
+                                ..Default::default()
+                            },
+                            false,
+                            conn,
+                        )?
+                        .into_iter()
+                        .next();
+                }
+                if existing.is_none() {
+                    existing = self
                         .get_artists(
                             QueryableArtist::search_by_term(_artist.artist_name.clone()),
                             false,
-                            &mut conn,
+                            conn,
                         )?
-                        .first()
-                        .map(|v| v.artist_id.clone().unwrap())
-                        .unwrap_or_else(|| self.insert_artist(&mut conn, _artist).unwrap());
+                        .into_iter()
+                        .next();
+                }
 
-                    ArtistBridge::insert_value(artist_id_.clone(), song.song._id.clone().unwrap())
-                        .insert_into(artist_bridge)
-                        .on_conflict_do_nothing()
-                        .execute(&mut conn)?;
+                let artist_id_ = match existing {
+                    Some(existing) => {
+                        let existing_id = existing.artist_id.clone().unwrap();
+                        if existing.artist_mbid.is_none() && _artist.artist_mbid.is_some() {
+                            update(QueryDsl::filter(artists, artist_id.eq(&existing_id)))
+                                .set(schema::artists::artist_mbid.eq(&_artist.artist_mbid))
+                                .execute(conn)?;
+                        }
+                        existing_id
+                    }
+                    None => self.insert_artist(conn, _artist)?,
+                };
 
-                    _artist.artist_id = Some(artist_id_);
-                }
+                ArtistBridge::insert_value(artist_id_.clone(), song.song._id.clone().unwrap())
+                    .insert_into(artist_bridge)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+
+                _artist.artist_id = Some(artist_id_);
             }
+        }
 
-            if let Some(_genres) = &mut song.genre {
-                for mut _genre in _genres {
-                    let genre_id_ = self
-                        .get_genres(
-                            QueryableGenre::search_by_term(_genre.genre_name.clone()),
-                            false,
-                            &mut conn,
-                        )?
-                        .first()
-                        .map(|v| v.genre_id.clone().unwrap())
-                        .unwrap_or_else(|| self.insert_genre(&mut conn, _genre).unwrap());
+        if let Some(_genres) = &mut song.genre {
+            for mut _genre in _genres {
+                let genre_id_ = self
+                    .get_genres(
+                        QueryableGenre::search_by_term(_genre.genre_name.clone()),
+                        false,
+                        conn,
+                    )?
+                    .first()
+                    .map(|v| v.genre_id.clone().unwrap())
+                    .unwrap_or_else(|| self.insert_genre(conn, _genre).unwrap());
 
-                    GenreBridge::insert_value(genre_id_.clone(), song.song._id.clone().unwrap())
-                        .insert_into(genre_bridge)
-                        .on_conflict_do_nothing()
-                        .execute(&mut conn)?;
+                GenreBridge::insert_value(genre_id_.clone(), song.song._id.clone().unwrap())
+                    .insert_into(genre_bridge)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
 
-                    _genre.genre_id = Some(genre_id_);
-                }
+                _genre.genre_id = Some(genre_id_);
             }
-
-            trace!("Inserted song, {:?}", song);
         }
-        info!("Inserted all songs");
         Ok(())
     }
 
+    /// Finds the library entry of a local file that was moved or renamed to
+    /// the path of `song`, by its content hash. Entries whose file still
+    /// exists are copies rather than moves and are left alone.
+    #[tracing::instrument(level = "trace", skip(self, song, conn))]
+    fn find_moved_song(
+        &self,
+        song: &QueryableSong,
+        conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+    ) -> Result<Option<String>> {
+        let (Some(path), Some(hash)) = (&song.path, &song.hash) else {
+            return Ok(None);
+        };
+        if song.type_ != SongType::LOCAL {
+            return Ok(None);
+        }
+
+        let known: i64 = QueryDsl::filter(allsongs, song_path.eq(path))
+            .count()
+            .get_result(conn)?;
+        if known > 0 {
+            return Ok(None);
+        }
+
+        let candidates: Vec<(Option<String>, Option<String>)> =
+            QueryDsl::filter(allsongs, schema::allsongs::hash.eq(hash))
+                .filter(schema::allsongs::type_.eq(SongType::LOCAL))
+                .select((_id, song_path))
+                .load(conn)?;
+        Ok(candidates
+            .into_iter()
+            .find(|(_, old_path)| {
+                old_path
+                    .as_ref()
+                    .map(|p| !Path::new(p).exists())
+                    .unwrap_or_default()
+            })
+            .and_then(|(id, _)| id))
+    }
+
     // TODO: Remove album
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn remove_songs(&self, ids: Vec<String>) -> Result<()> {
@@ -300,33 +466,81 @@ impl Database {
             .unwrap()
             .transaction::<(), MoosyncError, _>(|conn| {
                 for id in ids {
-                    delete(QueryDsl::filter(allsongs, _id.eq(id.clone()))).execute(conn)?;
+                    self.delete_song(conn, id)?;
+                }
+                Ok(())
+            })?;
+
+        info!("Removed song");
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, conn))]
+    fn delete_song(
+        &self,
+        conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+        id: String,
+    ) -> Result<()> {
+        delete(QueryDsl::filter(allsongs, _id.eq(id.clone()))).execute(conn)?;
+        delete(QueryDsl::filter(
+            album_bridge,
+            schema::album_bridge::song.eq(id.clone()),
+        ))
+        .execute(conn)?;
+        delete(QueryDsl::filter(
+            artist_bridge,
+            schema::artist_bridge::song.eq(id.clone()),
+        ))
+        .execute(conn)?;
+        delete(QueryDsl::filter(
+            genre_bridge,
+            schema::genre_bridge::song.eq(id.clone()),
+        ))
+        .execute(conn)?;
+        delete(QueryDsl::filter(
+            playlist_bridge,
+            schema::playlist_bridge::song.eq(id.clone()),
+        ))
+        .execute(conn)?;
+        delete(QueryDsl::filter(
+            fingerprints,
+            schema::fingerprints::song_id.eq(id.clone()),
+        ))
+        .execute(conn)?;
+        Ok(())
+    }
+
+    /// Replaces the tags of a song along with its album and artists, e.g. with
+    /// the ones of a MusicBrainz recording. Genres are left as they are.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn retag_song(&self, mut song: Song) -> Result<()> {
+        let id = song.song._id.clone().ok_or("Song does not have an ID")?;
+        self.pool
+            .get()
+            .unwrap()
+            .transaction::<(), MoosyncError, _>(|conn| {
+                update(QueryDsl::filter(allsongs, _id.eq(&id)))
+                    .set(&song.song)
+                    .execute(conn)?;
+                if song.album.is_some() {
                     delete(QueryDsl::filter(
                         album_bridge,
-                        schema::album_bridge::song.eq(id.clone()),
+                        schema::album_bridge::song.eq(&id),
                     ))
                     .execute(conn)?;
+                }
+                if song.artists.is_some() {
                     delete(QueryDsl::filter(
                         artist_bridge,
-                        schema::artist_bridge::song.eq(id.clone()),
-                    ))
-                    .execute(conn)?;
-                    delete(QueryDsl::filter(
-                        genre_bridge,
-                        schema::genre_bridge::song.eq(id.clone()),
-                    ))
-                    .execute(conn)?;
-                    delete(QueryDsl::filter(
-                        playlist_bridge,
-                        schema::playlist_bridge::song.eq(id.clone()),
+                        schema::artist_bridge::song.eq(&id),
                     ))
                     .execute(conn)?;
                 }
-                Ok(())
+                song.genre = None;
+                self.link_song_entities(conn, &mut song)
             })?;
-
-        info!("Removed song");
-
+        info!("Retagged song {}", id);
         Ok(())
     }
 
@@ -835,6 +1049,7 @@ impl Database {
                 extension: None,
                 icon: None,
                 library_item: None,
+                account_id: None,
             },
             false,
             &mut conn,
@@ -1088,13 +1303,26 @@ impl Database {
         Ok(())
     }
 
+    /// Play count and time of a song, if it was played before
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_analytics(&self, song_id: String) -> Result<Option<Analytics>> {
+        let mut conn = self.pool.get().unwrap();
+        Ok(
+            QueryDsl::filter(analytics, schema::analytics::song_id.eq(song_id))
+                .first::<Analytics>(&mut conn)
+                .optional()?,
+        )
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn increment_play_count(&self, id: String) -> Result<()> {
         trace!("Incrementing play count");
         let mut conn = self.pool.get().unwrap();
         let play_count: Option<i32> = QueryDsl::select(analytics, schema::analytics::play_count)
             .filter(schema::analytics::song_id.eq(id.clone()))
-            .first(&mut conn)?;
+            .first::<Option<i32>>(&mut conn)
+            .optional()?
+            .flatten();
 
         if play_count.is_none() {
             insert_into(analytics)
@@ -1123,7 +1351,9 @@ impl Database {
         let mut conn = self.pool.get().unwrap();
         let play_time: Option<f64> = QueryDsl::select(analytics, schema::analytics::play_time)
             .filter(schema::analytics::song_id.eq(id.clone()))
-            .first(&mut conn)?;
+            .first::<Option<f64>>(&mut conn)
+            .optional()?
+            .flatten();
 
         if play_time.is_none() {
             insert_into(analytics)
@@ -1148,25 +1378,26 @@ impl Database {
         Ok(())
     }
 
+    /// Returns the playlist along with its songs, e.g. to write it to a file
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn export_playlist(&self, playlist_id: String) -> Result<String> {
+    pub fn get_playlist_with_songs(
+        &self,
+        playlist_id: String,
+    ) -> Result<(QueryablePlaylist, Vec<Song>)> {
         let mut conn = self.pool.get().unwrap();
 
-        let binding = self.get_playlists(
-            QueryablePlaylist {
-                playlist_id: Some(playlist_id.clone()),
-                ..Default::default()
-            },
-            true,
-            &mut conn,
-        )?;
-        let playlist = binding.first();
-
-        if playlist.is_none() {
-            return Err("Playlist not found".into());
-        }
-
-        let playlist = playlist.unwrap();
+        let playlist = self
+            .get_playlists(
+                QueryablePlaylist {
+                    playlist_id: Some(playlist_id.clone()),
+                    ..Default::default()
+                },
+                true,
+                &mut conn,
+            )?
+            .into_iter()
+            .next()
+            .ok_or("Playlist not found")?;
 
         let playlist_songs = self.get_songs_by_options(GetSongOptions {
             playlist: Some(QueryablePlaylist {
@@ -1176,81 +1407,737 @@ impl Database {
             ..Default::default()
         })?;
 
-        let mut ret = format!("#EXTM3U\n#PLAYLIST:{}\n", playlist.playlist_name);
+        Ok((playlist, playlist_songs))
+    }
 
-        for s in playlist_songs {
-            if let Some(path) = &s.song.path {
-                let duration = s.song.duration.unwrap_or(0f64);
-                let title = s.song.title.unwrap_or_default();
-                let album_info = s.album.as_ref().map_or(String::new(), |album| {
-                    format!("#EXTALB:{}", album.album_name.clone().unwrap_or_default())
-                });
-                let genre_info = if let Some(genre) = &s.genre {
-                    if !genre.is_empty() {
-                        format!(
-                            "#EXTGENRE:{}",
-                            genre
-                                .iter()
-                                .filter_map(|g| g.genre_name.clone())
-                                .collect::<Vec<String>>()
-                                .join(",")
-                        )
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_track_mapping(
+        &self,
+        song_id: String,
+        provider: String,
+    ) -> Result<Option<TrackMapping>> {
+        let mut conn = self.pool.get().unwrap();
+        let mapping = track_mappings
+            .filter(schema::track_mappings::song_id.eq(song_id))
+            .filter(schema::track_mappings::provider.eq(provider))
+            .first::<TrackMapping>(&mut conn)
+            .optional()?;
+        Ok(mapping)
+    }
+
+    /// Remembers which track of a provider plays a song.
+    /// Matches chosen by the user are never replaced by automatic ones.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_track_mapping(&self, mapping: TrackMapping) -> Result<()> {
+        if !mapping.user_override {
+            let existing =
+                self.get_track_mapping(mapping.song_id.clone(), mapping.provider.clone())?;
+            if existing.is_some_and(|m| m.user_override) {
+                return Ok(());
+            }
+        }
+
+        let mut conn = self.pool.get().unwrap();
+        replace_into(track_mappings)
+            .values(&mapping)
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn remove_track_mapping(&self, song_id: String, provider: String) -> Result<()> {
+        let mut conn = self.pool.get().unwrap();
+        delete(track_mappings)
+            .filter(schema::track_mappings::song_id.eq(song_id))
+            .filter(schema::track_mappings::provider.eq(provider))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_fingerprint(&self, song_id: String) -> Result<Option<SongFingerprint>> {
+        let mut conn = self.pool.get().unwrap();
+        Ok(fingerprints
+            .filter(schema::fingerprints::song_id.eq(song_id))
+            .first::<SongFingerprint>(&mut conn)
+            .optional()?)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, fingerprint))]
+    pub fn set_fingerprint(&self, fingerprint: SongFingerprint) -> Result<()> {
+        let mut conn = self.pool.get().unwrap();
+        replace_into(fingerprints)
+            .values(&fingerprint)
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    /// Size and modification time of hashed local songs by path, so scans
+    /// only hash files that are new or changed
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn local_file_stats(&self) -> Result<HashMap<String, (f64, f64)>> {
+        let mut conn = self.pool.get().unwrap();
+        let rows: Vec<(Option<String>, Option<f64>, Option<f64>)> =
+            QueryDsl::filter(allsongs, schema::allsongs::type_.eq(SongType::LOCAL))
+                .filter(schema::allsongs::hash.is_not_null())
+                .select((
+                    song_path,
+                    schema::allsongs::size,
+                    schema::allsongs::modified,
+                ))
+                .load(&mut conn)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(path, size, modified)| Some((path?, (size?, modified?))))
+            .collect())
+    }
+
+    /// Ids and paths of local songs which haven't been fingerprinted yet
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn songs_without_fingerprint(&self) -> Result<Vec<(String, String)>> {
+        #[derive(QueryableByName)]
+        struct Unfingerprinted {
+            #[diesel(sql_type = Text)]
+            _id: String,
+            #[diesel(sql_type = Text)]
+            path: String,
+        }
+
+        let mut conn = self.pool.get().unwrap();
+        let rows = diesel::sql_query(
+            "SELECT _id, path FROM allsongs WHERE type = 'LOCAL' AND _id IS NOT NULL \
+             AND path IS NOT NULL AND _id NOT IN (SELECT song_id FROM fingerprints)",
+        )
+        .load::<Unfingerprinted>(&mut conn)?;
+        Ok(rows.into_iter().map(|row| (row._id, row.path)).collect())
+    }
+
+    /// Songs of the persisted play queue in order along with its position and modes
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn load_queue(&self) -> Result<(Vec<Song>, Option<QueueStateRow>)> {
+        let mut conn = self.pool.get().unwrap();
+        let items = queue_items
+            .order(schema::queue_items::position.asc())
+            .load::<QueueItem>(&mut conn)?;
+        let songs = items
+            .into_iter()
+            .filter_map(|item| match serde_json::from_str(&item.song) {
+                Ok(song) => Some(song),
+                Err(e) => {
+                    tracing::warn!("Dropping unreadable queue item {}: {:?}", item.song_id, e);
+                    None
+                }
+            })
+            .collect();
+
+        let state = queue_state.first::<QueueStateRow>(&mut conn).optional()?;
+        Ok((songs, state))
+    }
+
+    /// Replaces the persisted play queue
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn save_queue(&self, songs: &[Song], state: QueueStateRow) -> Result<()> {
+        let items = songs
+            .iter()
+            .enumerate()
+            .map(|(position, song)| {
+                Ok(QueueItem {
+                    position: position as i32,
+                    song_id: song.song._id.clone().unwrap_or_default(),
+                    song: serde_json::to_string(song)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.pool
+            .get()
+            .unwrap()
+            .transaction::<(), MoosyncError, _>(|conn| {
+                delete(queue_items).execute(conn)?;
+                if !items.is_empty() {
+                    insert_into(queue_items).values(&items).execute(conn)?;
+                }
+                replace_into(queue_state).values(&state).execute(conn)?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Inserts `songs` into the persisted play queue at `index`, the songs after it move back
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn queue_insert(&self, index: usize, songs: &[Song], state: QueueStateRow) -> Result<()> {
+        let items = songs
+            .iter()
+            .enumerate()
+            .map(|(offset, song)| {
+                Ok(QueueItem {
+                    position: (index + offset) as i32,
+                    song_id: song.song._id.clone().unwrap_or_default(),
+                    song: serde_json::to_string(song)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.pool
+            .get()
+            .unwrap()
+            .transaction::<(), MoosyncError, _>(|conn| {
+                if !items.is_empty() {
+                    shift_queue_items(conn, index as i32, None, items.len() as i32)?;
+                    insert_into(queue_items).values(&items).execute(conn)?;
+                }
+                replace_into(queue_state).values(&state).execute(conn)?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Removes the song at `index` from the persisted play queue
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_remove(&self, index: usize, state: QueueStateRow) -> Result<()> {
+        self.pool
+            .get()
+            .unwrap()
+            .transaction::<(), MoosyncError, _>(|conn| {
+                delete(queue_items.filter(schema::queue_items::position.eq(index as i32)))
+                    .execute(conn)?;
+                shift_queue_items(conn, index as i32 + 1, None, -1)?;
+                replace_into(queue_state).values(&state).execute(conn)?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Moves the song at `from` in the persisted play queue to `to`
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_move(&self, from: usize, to: usize, state: QueueStateRow) -> Result<()> {
+        let (from, to) = (from as i32, to as i32);
+        self.pool
+            .get()
+            .unwrap()
+            .transaction::<(), MoosyncError, _>(|conn| {
+                if from != to {
+                    // Parked like the shifted rows, see shift_queue_items
+                    update(queue_items.filter(schema::queue_items::position.eq(from)))
+                        .set(schema::queue_items::position.eq(-to - 1))
+                        .execute(conn)?;
+                    if from < to {
+                        shift_queue_items(conn, from + 1, Some(to), -1)?;
                     } else {
-                        String::new()
+                        shift_queue_items(conn, to, Some(from - 1), 1)?;
                     }
-                } else {
-                    String::new()
-                };
-                let cover_path = match s.song.song_cover_path_high {
-                    Some(cover) => format!("#EXTIMG:{}", cover),
-                    None => String::new(),
-                };
-                let song_info = format!("#MOOSINF:{}", s.song.type_);
-                let file_path = format!("file://{}", path);
-
-                write!(
-                    ret,
-                    "#EXTINF:{},{}\n{}\n{}\n{}\n{}\n{}\n",
-                    duration, title, album_info, genre_info, cover_path, song_info, file_path
-                )?;
-            } else if let Some(url) = &s.song.url {
-                let duration = s.song.duration.unwrap_or(0f64);
-                let title = s.song.title.unwrap_or_default();
-                let album_info = s.album.as_ref().map_or(String::new(), |album| {
-                    format!("#EXTALB:{}", album.album_name.clone().unwrap_or_default())
-                });
-                let genre_info = if let Some(genre) = &s.genre {
-                    if !genre.is_empty() {
-                        format!(
-                            "#EXTGENRE:{}",
-                            genre
-                                .iter()
-                                .filter_map(|g| g.genre_name.clone())
-                                .collect::<Vec<String>>()
-                                .join(",")
-                        )
-                    } else {
-                        String::new()
+                }
+                replace_into(queue_state).values(&state).execute(conn)?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    /// Updates the position and modes of the persisted play queue, leaving its songs untouched
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn save_queue_state(&self, state: QueueStateRow) -> Result<()> {
+        let mut conn = self.pool.get().unwrap();
+        replace_into(queue_state)
+            .values(&state)
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    /// Counts and totals across the whole library
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_library_stats(&self) -> Result<LibraryStats> {
+        let mut conn = self.pool.get().unwrap();
+
+        let (songs, total_duration, total_size) = allsongs
+            .select((
+                count_star(),
+                sum(schema::allsongs::duration),
+                sum(schema::allsongs::size),
+            ))
+            .first::<(i64, Option<f64>, Option<f64>)>(&mut conn)?;
+
+        Ok(LibraryStats {
+            songs,
+            albums: albums.count().get_result(&mut conn)?,
+            artists: artists.count().get_result(&mut conn)?,
+            genres: genres.count().get_result(&mut conn)?,
+            playlists: playlists.count().get_result(&mut conn)?,
+            total_duration: total_duration.unwrap_or_default(),
+            total_size: total_size.unwrap_or_default(),
+        })
+    }
+
+    /// Finds songs that are probably the same recording, e.g. the same file in
+    /// two folders, rips at different bitrates or a local file and its
+    /// YouTube upload
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn find_duplicates(&self) -> Result<DuplicateReport> {
+        let songs = self.get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong::default()),
+            ..Default::default()
+        })?;
+
+        let mut reported = HashSet::new();
+        let exact = group_duplicates(&songs, &mut reported, None, |song| song.song.hash.clone());
+        let mut likely = self.group_by_fingerprint(&songs, &mut reported)?;
+        likely.extend(group_duplicates(
+            &songs,
+            &mut reported,
+            Some(2f64),
+            |song| {
+                let album = normalize_title(song.album.as_ref()?.album_name.as_deref()?);
+                Some(format!("{}\0{}", duplicate_key(song)?, album)).filter(|_| !album.is_empty())
+            },
+        ));
+        let possible = group_duplicates(&songs, &mut reported, Some(5f64), duplicate_key);
+
+        info!(
+            "Found {} exact, {} likely and {} possible duplicates",
+            exact.len(),
+            likely.len(),
+            possible.len()
+        );
+        Ok(DuplicateReport {
+            exact,
+            likely,
+            possible,
+        })
+    }
+
+    /// Groups songs whose fingerprints match, e.g. the same recording ripped
+    /// in different formats. Songs in `reported` are skipped and songs in the
+    /// returned groups are added to it.
+    #[tracing::instrument(level = "trace", skip(self, songs, reported))]
+    fn group_by_fingerprint(
+        &self,
+        songs: &[Song],
+        reported: &mut HashSet<String>,
+    ) -> Result<Vec<Vec<Song>>> {
+        let mut conn = self.pool.get().unwrap();
+        let stored = fingerprints.load::<SongFingerprint>(&mut conn)?;
+
+        let by_id = songs
+            .iter()
+            .filter_map(|song| Some((song.song._id.clone()?, song)))
+            .collect::<HashMap<_, _>>();
+        let mut candidates = stored
+            .into_iter()
+            .filter(|f| by_id.contains_key(&f.song_id) && !reported.contains(&f.song_id))
+            .filter_map(|f| {
+                let items = fingerprint::decompress(&f.fingerprint)?;
+                Some((f, items)).filter(|(_, items)| !items.is_empty())
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(a, _), (b, _)| {
+            a.duration
+                .partial_cmp(&b.duration)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut groups = vec![];
+        let mut grouped = HashSet::new();
+        for (i, (first, first_items)) in candidates.iter().enumerate() {
+            if grouped.contains(&i) {
+                continue;
+            }
+
+            let mut group = vec![i];
+            for (j, (other, other_items)) in candidates.iter().enumerate().skip(i + 1) {
+                // Sorted by duration, so all songs after this are longer too
+                if other.duration - first.duration > 2f64 {
+                    break;
+                }
+                if !grouped.contains(&j) && fingerprint::same_audio(first_items, other_items) {
+                    group.push(j);
+                }
+            }
+            if group.len() < 2 {
+                continue;
+            }
+
+            grouped.extend(group.iter().copied());
+            groups.push(
+                group
+                    .into_iter()
+                    .map(|k| {
+                        let song_id = &candidates[k].0.song_id;
+                        reported.insert(song_id.clone());
+                        by_id[song_id].clone()
+                    })
+                    .collect(),
+            );
+        }
+        Ok(groups)
+    }
+
+    /// Folds `duplicates` into the song `keep`. Playlists, play counts, lyrics
+    /// and track mappings of the duplicates move to `keep` before they are
+    /// removed from the library. Files on disk are left alone.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn merge_songs(&self, keep: String, duplicates: Vec<String>) -> Result<()> {
+        self.pool
+            .get()
+            .unwrap()
+            .transaction::<(), MoosyncError, _>(|conn| {
+                // Merging into a missing song would drop the duplicates' data
+                QueryDsl::filter(allsongs, _id.eq(&keep))
+                    .select(_id)
+                    .first::<Option<String>>(conn)
+                    .optional()?
+                    .ok_or("Song to keep not found")?;
+
+                for duplicate in duplicates {
+                    if duplicate == keep {
+                        continue;
                     }
-                } else {
-                    String::new()
-                };
-                let cover_path = match s.song.song_cover_path_high {
-                    Some(cover) => format!("#EXTIMG:{}", cover),
-                    None => String::new(),
-                };
-                let song_info = format!("#MOOSINF:{}", s.song.type_);
+                    self.merge_song(conn, &keep, duplicate)?;
+                }
+                Ok(())
+            })?;
+
+        info!("Merged duplicates into {}", keep);
+        Ok(())
+    }
+
+    /// Moves playlists, play counts, lyrics and track mappings of `duplicate`
+    /// to `keep`, then removes `duplicate` from the library
+    #[tracing::instrument(level = "trace", skip(self, conn))]
+    fn merge_song(
+        &self,
+        conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+        keep: &str,
+        duplicate: String,
+    ) -> Result<()> {
+        diesel::sql_query(
+            "UPDATE playlist_bridge SET song = ?1 WHERE song = ?2 \
+             AND playlist NOT IN (SELECT playlist FROM playlist_bridge WHERE song = ?1)",
+        )
+        .bind::<Text, _>(keep)
+        .bind::<Text, _>(&duplicate)
+        .execute(conn)?;
+
+        diesel::sql_query("UPDATE OR IGNORE track_mappings SET song_id = ?1 WHERE song_id = ?2")
+            .bind::<Text, _>(keep)
+            .bind::<Text, _>(&duplicate)
+            .execute(conn)?;
+        delete(QueryDsl::filter(
+            track_mappings,
+            schema::track_mappings::song_id.eq(&duplicate),
+        ))
+        .execute(conn)?;
+
+        self.merge_analytics(conn, keep, &duplicate)?;
+
+        let lyrics: Option<String> = QueryDsl::filter(allsongs, _id.eq(&duplicate))
+            .select(schema::allsongs::lyrics)
+            .first::<Option<String>>(conn)
+            .optional()?
+            .flatten();
+        if let Some(lyrics) = lyrics.filter(|l| !l.trim().is_empty()) {
+            update(
+                QueryDsl::filter(allsongs, _id.eq(keep)).filter(
+                    schema::allsongs::lyrics
+                        .is_null()
+                        .or(schema::allsongs::lyrics.eq("")),
+                ),
+            )
+            .set(schema::allsongs::lyrics.eq(lyrics))
+            .execute(conn)?;
+        }
+
+        self.delete_song(conn, duplicate)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, conn))]
+    fn merge_analytics(
+        &self,
+        conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+        keep: &str,
+        duplicate: &str,
+    ) -> Result<()> {
+        let Some(from) = QueryDsl::filter(analytics, schema::analytics::song_id.eq(duplicate))
+            .first::<Analytics>(conn)
+            .optional()?
+        else {
+            return Ok(());
+        };
+
+        let kept = QueryDsl::filter(analytics, schema::analytics::song_id.eq(keep))
+            .first::<Analytics>(conn)
+            .optional()?;
+        match kept {
+            Some(kept) => {
+                update(QueryDsl::filter(
+                    analytics,
+                    schema::analytics::id.eq(&kept.id),
+                ))
+                .set(
+                    (
+                        schema::analytics::play_count.eq(kept.play_count.unwrap_or_default()
+                            + from.play_count.unwrap_or_default()),
+                        schema::analytics::play_time
+                            .eq(kept.play_time.unwrap_or_default()
+                                + from.play_time.unwrap_or_default()),
+                    ),
+                )
+                .execute(conn)?;
+                delete(QueryDsl::filter(
+                    analytics,
+                    schema::analytics::id.eq(&from.id),
+                ))
+                .execute(conn)?;
+            }
+            None => {
+                update(QueryDsl::filter(
+                    analytics,
+                    schema::analytics::id.eq(&from.id),
+                ))
+                .set(schema::analytics::song_id.eq(keep))
+                .execute(conn)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the database file to reclaim unused space
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn vacuum(&self) -> Result<()> {
+        let mut conn = self.pool.get().unwrap();
+        info!("Vacuuming database");
+        conn.batch_execute("VACUUM;")?;
+        Ok(())
+    }
+
+    /// Runs SQLite's integrity check. Returns the problems found, or just "ok"
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn check_integrity(&self) -> Result<Vec<String>> {
+        #[derive(QueryableByName)]
+        struct IntegrityCheck {
+            #[diesel(sql_type = Text)]
+            integrity_check: String,
+        }
+
+        let mut conn = self.pool.get().unwrap();
+        let rows =
+            diesel::sql_query("PRAGMA integrity_check;").load::<IntegrityCheck>(&mut conn)?;
+        Ok(rows.into_iter().map(|row| row.integrity_check).collect())
+    }
+
+    /// Copies the database to `dest` with SQLite's online backup API. The
+    /// copy is consistent even while songs are being played or scanned.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn backup_to(&self, dest: &Path) -> Result<()> {
+        info!("Backing up database to {:?}", dest);
+        backup_sqlite(&self.path, dest)
+    }
+
+    /// Version of the last migration applied to this database
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn schema_version(&self) -> Result<Option<String>> {
+        let mut conn = self.pool.get().unwrap();
+        let applied: Vec<MigrationVersion> = conn
+            .applied_migrations()
+            .map_err(|e| format!("Failed to read migrations: {}", e))?;
+        Ok(applied.iter().map(|v| v.to_string()).max())
+    }
+
+    /// Moves every stored file path under `from` to `to`, e.g. after
+    /// restoring a backup onto a machine with a different music folder.
+    /// Songs already stored under `to` are merged into the moved ones.
+    /// Returns the number of values changed.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn rewrite_path_prefix(&self, from: &str, to: &str) -> Result<usize> {
+        let from = from.trim_end_matches(['/', '\\']);
+        let to = to.trim_end_matches(['/', '\\']);
+        if from.is_empty() || from == to {
+            return Ok(0);
+        }
+
+        // Convert separators when moving between Windows and Unix style roots
+        let is_windows = |p: &str| p.contains('\\') && !p.contains('/');
+        let (old_separator, new_separator) = match (is_windows(from), is_windows(to)) {
+            (false, true) => ("/", "\\"),
+            (true, false) => ("\\", "/"),
+            _ => ("", ""),
+        };
+
+        #[derive(QueryableByName)]
+        struct Conflict {
+            #[diesel(sql_type = Text)]
+            keep: String,
+            #[diesel(sql_type = Text)]
+            duplicate: String,
+        }
+
+        let mut conn = self.pool.get().unwrap();
+        let mut changed = 0;
+        conn.transaction::<(), MoosyncError, _>(|conn| {
+            // Files scanned at the new location before relocating would clash with
+            // the moved paths. Those fold into the old entries, which keep their ids.
+            let conflicts = diesel::sql_query(
+                "SELECT moved._id AS keep, scanned._id AS duplicate FROM allsongs moved \
+                 JOIN allsongs scanned ON scanned.path = ?2 || replace(substr(moved.path, length(?1) + 1), ?3, ?4) \
+                 WHERE (moved.path = ?1 OR substr(moved.path, 1, length(?1) + 1) IN (?1 || '/', ?1 || '\\')) \
+                 AND moved._id IS NOT NULL AND scanned._id IS NOT NULL AND moved._id != scanned._id",
+            )
+            .bind::<Text, _>(from)
+            .bind::<Text, _>(to)
+            .bind::<Text, _>(old_separator)
+            .bind::<Text, _>(new_separator)
+            .load::<Conflict>(conn)?;
+            for conflict in conflicts {
+                debug!("Merging {} into moved song {}", conflict.duplicate, conflict.keep);
+                self.merge_song(conn, &conflict.keep, conflict.duplicate)?;
+            }
 
-                write!(
-                    ret,
-                    "#EXTINF:{},{}\n{}\n{}\n{}\n{}\n{}\n",
-                    duration, title, album_info, genre_info, cover_path, song_info, url
-                )?;
+            for (table, column) in [
+                ("allsongs", "path"),
+                ("allsongs", "song_coverpath_high"),
+                ("allsongs", "song_coverpath_low"),
+                ("albums", "album_coverpath_high"),
+                ("albums", "album_coverpath_low"),
+                ("artists", "artist_coverpath"),
+                ("playlists", "playlist_coverpath"),
+                ("playlists", "playlist_path"),
+            ] {
+                changed += diesel::sql_query(format!(
+                    "UPDATE {table} SET {column} = ?2 || replace(substr({column}, length(?1) + 1), ?3, ?4) \
+                     WHERE {column} = ?1 OR substr({column}, 1, length(?1) + 1) IN (?1 || '/', ?1 || '\\')"
+                ))
+                .bind::<Text, _>(from)
+                .bind::<Text, _>(to)
+                .bind::<Text, _>(old_separator)
+                .bind::<Text, _>(new_separator)
+                .execute(conn)?;
             }
+            Ok(())
+        })?;
+
+        info!("Rewrote {} paths from {} to {}", changed, from, to);
+        Ok(changed)
+    }
+}
+
+/// Lowercases and strips decorations like "(Remastered)", "[Official Video]"
+/// or "feat. X" so differently named copies of a song compare equal
+#[tracing::instrument(level = "trace", skip())]
+fn normalize_title(title: &str) -> String {
+    let mut normalized = String::new();
+    let mut depth = 0usize;
+    for c in title.to_lowercase().chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            c if depth == 0 => normalized.push(if c.is_alphanumeric() { c } else { ' ' }),
+            _ => {}
         }
+    }
 
-        Ok(ret.replace("\n\n", "\n"))
+    let mut words = vec![];
+    for word in normalized.split_whitespace() {
+        if matches!(word, "feat" | "ft" | "featuring") {
+            break;
+        }
+        words.push(word);
     }
+    words.join(" ")
+}
+
+/// Title and artists of a song, ignoring case and decorations. Songs without
+/// a title or artists can't be matched reliably and have no key.
+#[tracing::instrument(level = "trace", skip(song))]
+fn duplicate_key(song: &Song) -> Option<String> {
+    let mut artist_names = song
+        .artists
+        .as_ref()?
+        .iter()
+        .filter_map(|a| a.artist_name.as_deref().map(normalize_title))
+        .filter(|a| !a.is_empty())
+        .collect::<Vec<_>>();
+    if artist_names.is_empty() {
+        return None;
+    }
+    artist_names.sort();
+    artist_names.dedup();
+
+    let mut title = normalize_title(song.song.title.as_deref()?);
+    // Uploads are often titled "Artist - Title"
+    for artist in &artist_names {
+        if let Some(rest) = title.strip_prefix(&format!("{} ", artist)) {
+            title = rest.trim().to_string();
+        }
+    }
+    if title.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}\0{}", title, artist_names.join("\0")))
+}
+
+/// Groups songs sharing a key, then splits each group where durations differ
+/// by more than `tolerance` seconds. Songs already in `reported` are skipped
+/// and songs in the returned groups are added to it.
+#[tracing::instrument(level = "trace", skip(songs, reported, key))]
+fn group_duplicates(
+    songs: &[Song],
+    reported: &mut HashSet<String>,
+    tolerance: Option<f64>,
+    key: impl Fn(&Song) -> Option<String>,
+) -> Vec<Vec<Song>> {
+    let mut by_key: HashMap<String, Vec<&Song>> = HashMap::new();
+    for song in songs {
+        let Some(id) = song.song._id.as_ref() else {
+            continue;
+        };
+        if reported.contains(id) {
+            continue;
+        }
+        if let Some(key) = key(song) {
+            by_key.entry(key).or_default().push(song);
+        }
+    }
+
+    let mut groups = vec![];
+    for (_, mut candidates) in by_key {
+        if candidates.len() < 2 {
+            continue;
+        }
+
+        let duration = |song: &Song| song.song.duration.filter(|d| *d > 0f64);
+        candidates.sort_by(|a, b| {
+            duration(a)
+                .partial_cmp(&duration(b))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        // Songs with an unknown duration sort first and join the first group
+        let mut group: Vec<&Song> = vec![];
+        let mut last = None;
+        for song in candidates {
+            let current = duration(song);
+            if let (Some(tolerance), Some(last), Some(current)) = (tolerance, last, current) {
+                if current - last > tolerance {
+                    groups.push(std::mem::take(&mut group));
+                }
+            }
+            last = current.or(last);
+            group.push(song);
+        }
+        groups.push(group);
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|song| {
+                    reported.extend(song.song._id.clone());
+                    song.clone()
+                })
+                .collect()
+        })
+        .collect()
 }
 
 #[tracing::instrument(level = "trace", skip())]
@@ -1271,3 +2158,25 @@ fn merge(a: &mut Value, b: Value) {
 
     *a = b;
 }
+
+/// Moves the queue items at positions `start..=end` by `delta`.
+/// Positions are the primary key, so the rows are parked at negative positions
+/// first and flipped back once none of them can collide.
+#[tracing::instrument(level = "trace", skip(conn))]
+fn shift_queue_items(
+    conn: &mut PooledConnection<ConnectionManager<LoggingConnection<SqliteConnection>>>,
+    start: i32,
+    end: Option<i32>,
+    delta: i32,
+) -> Result<()> {
+    use schema::queue_items::position;
+
+    let end = end.unwrap_or(i32::MAX);
+    update(queue_items.filter(position.ge(start).and(position.le(end))))
+        .set(position.eq(position * -1 - (delta + 1)))
+        .execute(conn)?;
+    update(queue_items.filter(position.lt(0)))
+        .set(position.eq(position * -1 - 1))
+        .execute(conn)?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+/// Furthest the two fingerprints are shifted against each other when
+/// comparing them, in items of about 124ms each
+const MAX_OFFSET: usize = 8;
+/// Items compared before the rest of the fingerprint, to rule out unrelated
+/// songs quickly
+const QUICK_ITEMS: usize = 64;
+const QUICK_SIMILARITY: f64 = 0.7;
+/// Unrelated audio matches about half of the bits, reencodes of the same
+/// recording almost all of them
+const SAME_AUDIO_SIMILARITY: f64 = 0.85;
+
+/// Decodes a base64 encoded, compressed Chromaprint fingerprint into its
+/// items. Inverse of chromaprint's FingerprintCompressor.
+#[tracing::instrument(level = "trace", skip(encoded))]
+pub fn decompress(encoded: &str) -> Option<Vec<u32>> {
+    let data = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+    if data.len() < 4 {
+        return None;
+    }
+    let count = ((data[1] as usize) << 16) | ((data[2] as usize) << 8) | data[3] as usize;
+    let body = &data[4..];
+
+    // Distances between the set bits of each item XORed with the previous
+    // one, ending with 0. Distances of 7 and above continue in 5 bit values
+    // after all 3 bit values.
+    let mut bits = vec![];
+    let mut items = 0;
+    while items < count {
+        let bit = read_bits(body, bits.len() * 3, 3)?;
+        if bit == 0 {
+            items += 1;
+        }
+        bits.push(bit);
+    }
+
+    let exceptional_start = (bits.len() * 3).div_ceil(8) * 8;
+    for (i, bit) in bits.iter_mut().filter(|b| **b == 7).enumerate() {
+        *bit += read_bits(body, exceptional_start + i * 5, 5)?;
+    }
+
+    let mut result: Vec<u32> = Vec::with_capacity(count);
+    let mut value = 0u32;
+    let mut last_bit = 0;
+    for bit in bits {
+        if bit == 0 {
+            result.push(result.last().map_or(value, |prev| prev ^ value));
+            value = 0;
+            last_bit = 0;
+            continue;
+        }
+        last_bit += bit;
+        value |= 1u32.checked_shl(last_bit - 1)?;
+    }
+    Some(result)
+}
+
+/// Reads `len` bits starting at bit `offset`, least significant bit first
+fn read_bits(data: &[u8], offset: usize, len: usize) -> Option<u32> {
+    let mut value = 0;
+    for i in 0..len {
+        let pos = offset + i;
+        let byte = data.get(pos / 8)?;
+        value |= (((byte >> (pos % 8)) & 1) as u32) << i;
+    }
+    Some(value)
+}
+
+/// Share of equal bits in the first `items` items of `a` and `b` at their best
+/// alignment, from about 0.5 for unrelated audio to 1 for identical audio
+#[tracing::instrument(level = "trace", skip(a, b))]
+pub fn similarity(a: &[u32], b: &[u32], items: usize) -> f64 {
+    let mut best = 0f64;
+    for offset in 0..=MAX_OFFSET {
+        for (a, b) in [(a.get(offset..), Some(b)), (Some(a), b.get(offset..))] {
+            let (Some(a), Some(b)) = (a, b) else {
+                continue;
+            };
+            let len = a.len().min(b.len()).min(items);
+            if len == 0 {
+                continue;
+            }
+            let differing: u32 = a
+                .iter()
+                .zip(b)
+                .take(len)
+                .map(|(a, b)| (a ^ b).count_ones())
+                .sum();
+            best = best.max(1f64 - differing as f64 / (len * 32) as f64);
+        }
+    }
+    best
+}
+
+/// Whether two fingerprints are of the same recording
+#[tracing::instrument(level = "trace", skip(a, b))]
+pub fn same_audio(a: &[u32], b: &[u32]) -> bool {
+    similarity(a, b, QUICK_ITEMS) >= QUICK_SIMILARITY
+        && similarity(a, b, usize::MAX) >= SAME_AUDIO_SIMILARITY
+}
@@ -14,8 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+mod backup;
 pub mod cache;
 pub mod database;
+mod fingerprint;
 pub mod migrations;
 
 #[cfg(test)]
@@ -14,17 +14,29 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use diesel::sqlite::Sqlite;
+use diesel::{migration::MigrationSource, sqlite::Sqlite};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use types::errors::Result;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 pub const CACHE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations_cache");
 
 #[tracing::instrument(level = "trace", skip(databse))]
-pub fn run_migrations(databse: &mut impl MigrationHarness<Sqlite>) {
+pub fn run_migrations(databse: &mut impl MigrationHarness<Sqlite>) -> Result<()> {
     databse
         .run_pending_migrations(MIGRATIONS)
-        .expect("Failed to run migrations");
+        .map_err(|e| format!("Failed to run migrations: {}", e))?;
+    Ok(())
+}
+
+/// Version of the newest migration this build knows about
+#[tracing::instrument(level = "trace", skip())]
+pub fn latest_schema_version() -> Option<String> {
+    MigrationSource::<Sqlite>::migrations(&MIGRATIONS)
+        .ok()?
+        .iter()
+        .map(|m| m.name().version().to_string())
+        .max()
 }
 
 #[tracing::instrument(level = "trace", skip(databse))]
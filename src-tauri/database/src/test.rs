@@ -14,25 +14,26 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{fs, path::PathBuf, str::FromStr};
+use std::fs;
 
-use crate::database::Database;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rusty_chromaprint::{Configuration, FingerprintCompressor};
+use test_utils::TempDb;
+
+use crate::{cache::CacheHolder, database::Database, fingerprint};
 use types::{
     common::SearchByTerm,
-    entities::{QueryableAlbum, QueryableArtist},
-    songs::Song,
+    entities::{
+        QueryableAlbum, QueryableArtist, QueryablePlaylist, QueueStateRow, SongFingerprint,
+        TrackMapping,
+    },
+    songs::{GetSongOptions, QueryableSong, SearchableSong, Song, SongType},
 };
 
-#[tracing::instrument(level = "trace", skip())]
-fn cleanup() {
-    fs::remove_file(PathBuf::from_str("test.db").unwrap()).unwrap();
-    fs::remove_file(PathBuf::from_str("test.db-shm").unwrap()).unwrap();
-    fs::remove_file(PathBuf::from_str("test.db-wal").unwrap()).unwrap();
-}
-
 #[test]
 fn test_insert() {
-    let db = Database::new(PathBuf::from_str("test.db").unwrap());
+    let db_file = TempDb::new("insert");
+    let db = Database::new(db_file.path());
 
     let res = db
         .insert_songs(vec![Song {
@@ -47,11 +48,754 @@ fn test_insert() {
         }])
         .unwrap();
 
-    cleanup();
-
     assert_eq!(res.len(), 1);
     assert_eq!(res[0].genre.clone().unwrap().len(), 0);
     assert!(res[0].song._id.is_some());
     assert!(res[0].album.clone().unwrap().album_id.is_some());
     assert!(res[0].artists.clone().unwrap()[0].artist_id.is_some());
 }
+
+#[test]
+fn test_analytics_for_unplayed_song() {
+    let db_file = TempDb::new("analytics");
+    let db = Database::new(db_file.path());
+
+    // Songs get an analytics row on their first play
+    db.increment_play_count("song".into()).unwrap();
+    db.increment_play_time("song".into(), 10f64).unwrap();
+    db.increment_play_count("song".into()).unwrap();
+    db.increment_play_time("song".into(), 10f64).unwrap();
+    let played = db.get_analytics("song".into()).unwrap();
+    let unplayed = db.get_analytics("unplayed".into()).unwrap();
+
+    let played = played.unwrap();
+    assert_eq!(played.play_count, Some(2));
+    assert_eq!(played.play_time, Some(20f64));
+    assert!(unplayed.is_none());
+}
+
+#[test]
+fn test_track_mapping_override() {
+    let db_file = TempDb::new("mapping");
+    let db = Database::new(db_file.path());
+
+    let mapping = |mapped_id: &str, user_override| TrackMapping {
+        song_id: "song".into(),
+        provider: "youtube".into(),
+        mapped_id: mapped_id.into(),
+        score: 0.9,
+        user_override,
+    };
+
+    db.set_track_mapping(mapping("user", true)).unwrap();
+    db.set_track_mapping(mapping("auto", false)).unwrap();
+    let res = db
+        .get_track_mapping("song".into(), "youtube".into())
+        .unwrap();
+
+    assert_eq!(res.unwrap().mapped_id, "user");
+}
+
+#[test]
+fn test_cache_prefix_and_eviction() {
+    let db_file = TempDb::new("cache");
+    let cache = CacheHolder::new(db_file.path());
+
+    let blob = "a".repeat(100);
+    cache.set("get_lyrics_1", &blob, 60).unwrap();
+    cache.set("get_lyricsX2", &blob, 60).unwrap();
+    cache.set("get_canvaz_1", &blob, 60).unwrap();
+
+    let hit = cache.get::<String>("get_lyrics_1");
+    let miss = cache.get::<String>("get_lyrics_3");
+    let stats = cache.get_cache_stats(Some("get_lyrics_".into())).unwrap();
+
+    // "_" must not act as a wildcard
+    let cleared = cache.clear_cache(Some("get_lyrics_".into())).unwrap();
+
+    cache.set_max_size(150);
+    let pruned = cache.prune().unwrap();
+    let remaining = cache.get_cache_stats(None).unwrap();
+
+    assert_eq!(hit.unwrap(), blob);
+    assert!(miss.is_err());
+    assert_eq!(stats.entries, 1);
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 1);
+    assert_eq!(cleared, 1);
+    assert_eq!(pruned, 1);
+    assert_eq!(remaining.entries, 1);
+    assert!(remaining.size <= 150);
+}
+
+#[test]
+fn test_queue_persistence() {
+    let db_file = TempDb::new("queue");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let state = |current_index| QueueStateRow {
+        id: 0,
+        current_index,
+        repeat: "Loop".into(),
+        shuffle: false,
+        original_order: None,
+    };
+
+    db.save_queue(&[song("a"), song("b"), song("c")], state(0))
+        .unwrap();
+    db.save_queue(&[song("c"), song("a")], state(1)).unwrap();
+    db.save_queue_state(state(0)).unwrap();
+    let (songs, row) = db.load_queue().unwrap();
+
+    let ids = songs
+        .into_iter()
+        .map(|s| s.song._id.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["c", "a"]);
+    assert_eq!(row.unwrap(), state(0));
+}
+
+#[test]
+fn test_local_file_stats() {
+    let db_file = TempDb::new("file_stats");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str, hash: Option<&str>, modified: Option<f64>, type_: SongType| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            path: Some(format!("/music/{}.mp3", id)),
+            size: Some(100f64),
+            hash: hash.map(|h| h.into()),
+            modified,
+            type_,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    db.insert_songs(vec![
+        song("hashed", Some("abc"), Some(1000f64), SongType::LOCAL),
+        song("unhashed", None, Some(1000f64), SongType::LOCAL),
+        song("no_mtime", Some("def"), None, SongType::LOCAL),
+        song("url", Some("ghi"), Some(1000f64), SongType::URL),
+    ])
+    .unwrap();
+    // Rescanning an unchanged file without a hash keeps the stored one
+    db.insert_songs(vec![song("hashed", None, Some(1000f64), SongType::LOCAL)])
+        .unwrap();
+
+    let stats = db.local_file_stats().unwrap();
+
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats.get("/music/hashed.mp3"), Some(&(100f64, 1000f64)));
+}
+
+#[test]
+fn test_backup_sqlite_round_trip() {
+    let src_file = TempDb::new("backup_ffi");
+    let dest_file = TempDb::new("backup_ffi_copy");
+    let missing_file = TempDb::new("backup_ffi_missing");
+    let (src, dest) = (src_file.path(), dest_file.path());
+
+    let db = Database::open(src.clone()).unwrap();
+    db.insert_songs(
+        (0..500)
+            .map(|i| Song {
+                song: QueryableSong {
+                    _id: Some(format!("song{}", i)),
+                    path: Some(format!("/music/{}.mp3", i)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect(),
+    )
+    .unwrap();
+    db.create_playlist(QueryablePlaylist {
+        playlist_id: Some("playlist".into()),
+        playlist_name: "Playlist".into(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    // Overwrites whatever is at the destination
+    fs::write(&dest, "not a database").unwrap();
+    // Runs while the source is open, so rows still in the WAL must be copied too
+    crate::backup::backup_sqlite(&src, &dest).unwrap();
+
+    let copy = Database::open(dest.clone()).unwrap();
+    let ids = |db: &Database| {
+        let mut ids = db
+            .get_songs_by_options(GetSongOptions {
+                song: Some(SearchableSong::default()),
+                ..Default::default()
+            })
+            .unwrap()
+            .into_iter()
+            .filter_map(|s| s.song._id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    };
+    let copied_ids = ids(&copy);
+    let original_ids = ids(&db);
+    let (playlist, _) = copy.get_playlist_with_songs("playlist".into()).unwrap();
+    let integrity = copy.check_integrity().unwrap();
+
+    // A missing source is an error rather than an empty backup
+    let missing = crate::backup::backup_sqlite(&missing_file.path(), &dest);
+
+    assert_eq!(copied_ids.len(), 500);
+    assert_eq!(copied_ids, original_ids);
+    assert_eq!(playlist.playlist_name, "Playlist");
+    assert_eq!(integrity, vec!["ok"]);
+    assert!(missing.is_err());
+}
+
+#[test]
+fn test_backup_and_rewrite_paths() {
+    let db_file = TempDb::new("backup");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str, path: &str| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            path: Some(path.into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    db.insert_songs(vec![
+        song("a", "/home/old/Music/a.mp3"),
+        song("b", "/home/old/Music/sub/b.mp3"),
+        song("c", "/home/old/Music2/c.mp3"),
+    ])
+    .unwrap();
+
+    let copy_file = TempDb::new("backup_copy");
+    db.backup_to(&copy_file.path()).unwrap();
+    let restored = Database::open(copy_file.path()).unwrap();
+    let changed = restored
+        .rewrite_path_prefix("/home/old/Music/", "D:\\Music")
+        .unwrap();
+    let mut paths = restored
+        .get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong::default()),
+            ..Default::default()
+        })
+        .unwrap()
+        .into_iter()
+        .map(|s| s.song.path.unwrap())
+        .collect::<Vec<_>>();
+    paths.sort();
+    let schema_version = restored.schema_version().unwrap();
+
+    assert_eq!(changed, 2);
+    assert_eq!(
+        paths,
+        vec![
+            "/home/old/Music2/c.mp3",
+            "D:\\Music\\a.mp3",
+            "D:\\Music\\sub\\b.mp3"
+        ]
+    );
+    assert_eq!(schema_version, crate::migrations::latest_schema_version());
+}
+
+#[test]
+fn test_rewrite_paths_after_rescan() {
+    let db_file = TempDb::new("relocate_rescanned");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str, path: &str| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            path: Some(path.into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    db.insert_songs(vec![
+        song("a", "/old/Music/a.mp3"),
+        song("b", "/old/Music/b.mp3"),
+    ])
+    .unwrap();
+    db.increment_play_count("a".into()).unwrap();
+
+    // The files were moved and scanned again before relocating
+    db.insert_songs(vec![song("rescanned_a", "/new/Music/a.mp3")])
+        .unwrap();
+    db.increment_play_count("rescanned_a".into()).unwrap();
+
+    let changed = db.rewrite_path_prefix("/old/Music", "/new/Music").unwrap();
+    let mut songs = db
+        .get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong::default()),
+            ..Default::default()
+        })
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.song._id.unwrap(), s.song.path.unwrap()))
+        .collect::<Vec<_>>();
+    songs.sort();
+    let analytics = db.get_analytics("a".into()).unwrap().unwrap();
+
+    assert_eq!(changed, 2);
+    assert_eq!(
+        songs,
+        vec![
+            ("a".to_string(), "/new/Music/a.mp3".to_string()),
+            ("b".to_string(), "/new/Music/b.mp3".to_string())
+        ]
+    );
+    assert_eq!(analytics.play_count, Some(2));
+    assert!(db.get_analytics("rescanned_a".into()).unwrap().is_none());
+}
+
+#[test]
+fn test_moved_song_keeps_identity() {
+    let db_file = TempDb::new("moved");
+    let db = Database::new(db_file.path());
+
+    let song = |id: Option<&str>, path: &str| Song {
+        song: QueryableSong {
+            _id: id.map(|id| id.into()),
+            path: Some(path.into()),
+            hash: Some("content".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    db.insert_songs(vec![song(Some("original"), "/missing/old/a.mp3")])
+        .unwrap();
+    let moved = db
+        .insert_songs(vec![song(None, "/missing/new/a.mp3")])
+        .unwrap();
+    let rescanned = db
+        .insert_songs(vec![song(None, "/missing/new/a.mp3")])
+        .unwrap();
+    let all = db
+        .get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong::default()),
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(moved[0].song._id.as_deref(), Some("original"));
+    assert_eq!(rescanned[0].song._id.as_deref(), Some("original"));
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].song.path.as_deref(), Some("/missing/new/a.mp3"));
+}
+
+#[test]
+fn test_find_and_merge_duplicates() {
+    let db_file = TempDb::new("duplicates");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str, title: &str, album: Option<&str>, duration: f64| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            path: Some(format!("/music/{}.mp3", id)),
+            title: Some(title.into()),
+            duration: Some(duration),
+            ..Default::default()
+        },
+        album: album.map(|a| QueryableAlbum::search_by_term(Some(a.into()))),
+        artists: Some(vec![QueryableArtist::search_by_term(Some(
+            "The Band".into(),
+        ))]),
+        genre: Some(vec![]),
+    };
+    // Copies of a file that still exists, so they aren't mistaken for a move
+    let mut copy = song("copy", "Copy", None, 100.0);
+    copy.song.path = Some("Cargo.toml".into());
+    copy.song.hash = Some("same".into());
+    let mut copy2 = song("copy2", "Renamed copy", None, 100.0);
+    copy2.song.path = Some("src/lib.rs".into());
+    copy2.song.hash = Some("same".into());
+    db.insert_songs(vec![
+        copy,
+        copy2,
+        song("rip", "Song (Remastered 2011)", Some("Album"), 200.0),
+        song("rip2", "song", Some("album"), 201.0),
+        song("upload", "The Band - Other [Official Video]", None, 124.0),
+        song("local", "Other feat. Someone", Some("Single"), 120.0),
+        song("live", "Other", None, 300.0),
+    ])
+    .unwrap();
+
+    let playlist = db
+        .create_playlist(QueryablePlaylist {
+            playlist_name: "Playlist".into(),
+            ..Default::default()
+        })
+        .unwrap();
+    db.add_to_playlist_bridge(playlist.clone(), "rip2".into())
+        .unwrap();
+    db.increment_play_count("rip".into()).unwrap();
+    db.increment_play_count("rip2".into()).unwrap();
+    db.update_lyrics("rip2".into(), "Lyrics".into()).unwrap();
+    db.set_track_mapping(TrackMapping {
+        song_id: "rip2".into(),
+        provider: "youtube".into(),
+        mapped_id: "video".into(),
+        score: 1.0,
+        user_override: true,
+    })
+    .unwrap();
+
+    let report = db.find_duplicates().unwrap();
+    db.merge_songs("rip".into(), vec!["rip2".into()]).unwrap();
+    let kept = db
+        .get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong {
+                _id: Some("rip".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+    let in_playlist = db.is_song_in_playlist(playlist, "rip".into()).unwrap();
+    let mapping = db
+        .get_track_mapping("rip".into(), "youtube".into())
+        .unwrap();
+    let remaining = db.find_duplicates().unwrap();
+    let analytics = db.get_analytics("rip".into()).unwrap();
+    let merged_analytics = db.get_analytics("rip2".into()).unwrap();
+
+    let ids = |groups: Vec<Vec<Song>>| {
+        groups
+            .into_iter()
+            .map(|g| {
+                let mut ids = g
+                    .into_iter()
+                    .map(|s| s.song._id.unwrap())
+                    .collect::<Vec<_>>();
+                ids.sort();
+                ids
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(report.exact), vec![vec!["copy", "copy2"]]);
+    assert_eq!(ids(report.likely), vec![vec!["rip", "rip2"]]);
+    assert_eq!(ids(report.possible), vec![vec!["local", "upload"]]);
+
+    assert_eq!(kept[0].song.lyrics.as_deref(), Some("Lyrics"));
+    assert!(in_playlist);
+    assert_eq!(mapping.unwrap().mapped_id, "video");
+    assert!(remaining.likely.is_empty());
+    assert_eq!(analytics.unwrap().play_count, Some(2));
+    assert!(merged_analytics.is_none());
+}
+
+#[test]
+fn test_merge_into_missing_song() {
+    let db_file = TempDb::new("merge_missing");
+    let db = Database::new(db_file.path());
+    db.insert_songs(vec![Song {
+        song: QueryableSong {
+            _id: Some("duplicate".into()),
+            path: Some("/music/duplicate.mp3".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }])
+    .unwrap();
+
+    let res = db.merge_songs("missing".into(), vec!["duplicate".into()]);
+    let remaining = db
+        .get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong {
+                _id: Some("duplicate".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert!(res.is_err());
+    assert_eq!(remaining.len(), 1);
+}
+
+/// Pseudo random fingerprint items
+fn fingerprint_items(seed: u32, len: usize) -> Vec<u32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        })
+        .collect()
+}
+
+fn compress_fingerprint(items: &[u32]) -> String {
+    let config = Configuration::preset_test2();
+    URL_SAFE_NO_PAD.encode(FingerprintCompressor::from(&config).compress(items))
+}
+
+#[test]
+fn test_fingerprint_decompress() {
+    let items = fingerprint_items(1, 500);
+    assert_eq!(
+        fingerprint::decompress(&compress_fingerprint(&items)),
+        Some(items)
+    );
+    assert_eq!(
+        fingerprint::decompress(&compress_fingerprint(&[0, u32::MAX, 1 << 31])),
+        Some(vec![0, u32::MAX, 1 << 31])
+    );
+    assert_eq!(fingerprint::decompress(""), None);
+    assert_eq!(fingerprint::decompress("AQAAAA"), Some(vec![]));
+}
+
+#[test]
+fn test_find_duplicates_by_fingerprint() {
+    let db_file = TempDb::new("fingerprint_duplicates");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str, title: &str, artist: &str| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            path: Some(format!("/music/{}.mp3", id)),
+            title: Some(title.into()),
+            duration: Some(200.0),
+            ..Default::default()
+        },
+        album: None,
+        artists: Some(vec![QueryableArtist::search_by_term(Some(artist.into()))]),
+        genre: Some(vec![]),
+    };
+    db.insert_songs(vec![
+        song("flac", "track01", "Unknown"),
+        song("mp3", "Song", "The Band"),
+        song("shifted", "Song (Radio)", "The Band"),
+        song("other", "Other", "Someone"),
+    ])
+    .unwrap();
+
+    let original = fingerprint_items(1, 500);
+    // A lossy encode flips a few bits
+    let mut encoded = original.clone();
+    for item in encoded.iter_mut().step_by(3) {
+        *item ^= 1 << (*item % 32);
+    }
+    // Starts a few items later
+    let shifted = original[4..].to_vec();
+    for (song_id, items, duration) in [
+        ("flac", original, 200.0),
+        ("mp3", encoded, 200.5),
+        ("shifted", shifted, 201.0),
+        ("other", fingerprint_items(2, 500), 200.0),
+    ] {
+        db.set_fingerprint(SongFingerprint {
+            song_id: song_id.into(),
+            fingerprint: compress_fingerprint(&items),
+            duration,
+        })
+        .unwrap();
+    }
+
+    let report = db.find_duplicates().unwrap();
+
+    let mut likely = report
+        .likely
+        .into_iter()
+        .map(|g| {
+            let mut ids = g
+                .into_iter()
+                .map(|s| s.song._id.unwrap())
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        })
+        .collect::<Vec<_>>();
+    likely.sort();
+    assert_eq!(likely, vec![vec!["flac", "mp3", "shifted"]]);
+    assert!(report.exact.is_empty());
+    assert!(report.possible.is_empty());
+}
+
+#[test]
+fn test_changed_file_clears_fingerprint() {
+    let db_file = TempDb::new("stale_fingerprint");
+    let db = Database::new(db_file.path());
+
+    let song = |hash: Option<&str>, modified: f64| Song {
+        song: QueryableSong {
+            _id: Some("song".into()),
+            path: Some("/music/song.mp3".into()),
+            hash: hash.map(|h| h.into()),
+            modified: Some(modified),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let fingerprinted = || {
+        db.set_fingerprint(SongFingerprint {
+            song_id: "song".into(),
+            fingerprint: "AQAAAA".into(),
+            duration: 200.0,
+        })
+    };
+
+    db.insert_songs(vec![song(Some("a"), 1000f64)]).unwrap();
+    fingerprinted().unwrap();
+    // Unchanged files are rescanned without a hash
+    db.insert_songs(vec![song(None, 1000f64)]).unwrap();
+    let unchanged = db.get_fingerprint("song".into()).unwrap();
+
+    db.insert_songs(vec![song(Some("b"), 2000f64)]).unwrap();
+    let rewritten = db.get_fingerprint("song".into()).unwrap();
+
+    fingerprinted().unwrap();
+    // Touched, e.g. by a tag editor
+    db.insert_songs(vec![song(Some("b"), 1500f64)]).unwrap();
+    let touched = db.get_fingerprint("song".into()).unwrap();
+
+    assert!(unchanged.is_some());
+    assert!(rewritten.is_none());
+    assert!(touched.is_none());
+}
+
+#[test]
+fn test_fingerprint_and_retag() {
+    let db_file = TempDb::new("retag");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str, title: &str, artist: &str| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            path: Some(format!("/music/{}.mp3", id)),
+            title: Some(title.into()),
+            ..Default::default()
+        },
+        album: None,
+        artists: Some(vec![QueryableArtist::search_by_term(Some(artist.into()))]),
+        genre: Some(vec![]),
+    };
+    db.insert_songs(vec![
+        song("untagged", "track01", "Unknown"),
+        song("tagged", "Other", "The Band"),
+    ])
+    .unwrap();
+
+    db.set_fingerprint(SongFingerprint {
+        song_id: "tagged".into(),
+        fingerprint: "AQAA".into(),
+        duration: 200.0,
+    })
+    .unwrap();
+    let pending = db.songs_without_fingerprint().unwrap();
+
+    let mut retagged = song("untagged", "Song", "The Band");
+    retagged.album = Some(QueryableAlbum::search_by_term(Some("Album".into())));
+    retagged.artists.as_mut().unwrap()[0].artist_mbid = Some("band-mbid".into());
+    retagged.artists.as_mut().unwrap().push(QueryableArtist {
+        artist_name: Some("Guest".into()),
+        artist_mbid: Some("guest-mbid".into()),
+        ..Default::default()
+    });
+    db.retag_song(retagged).unwrap();
+
+    let get = |id: &str| {
+        db.get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong {
+                _id: Some(id.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap()
+        .remove(0)
+    };
+    let untagged = get("untagged");
+    let tagged = get("tagged");
+
+    db.remove_songs(vec!["tagged".into()]).unwrap();
+    let removed_fingerprint = db.get_fingerprint("tagged".into()).unwrap();
+
+    assert_eq!(
+        pending,
+        vec![("untagged".to_string(), "/music/untagged.mp3".to_string())]
+    );
+    assert_eq!(untagged.song.title.as_deref(), Some("Song"));
+    assert_eq!(untagged.song.path.as_deref(), Some("/music/untagged.mp3"));
+    assert_eq!(untagged.album.unwrap().album_name.as_deref(), Some("Album"));
+    let mut artists = untagged
+        .artists
+        .unwrap()
+        .into_iter()
+        .map(|a| (a.artist_name.unwrap(), a.artist_mbid))
+        .collect::<Vec<_>>();
+    artists.sort();
+    assert_eq!(
+        artists,
+        vec![
+            ("Guest".to_string(), Some("guest-mbid".to_string())),
+            ("The Band".to_string(), Some("band-mbid".to_string())),
+        ]
+    );
+    // The existing artist got the MBID instead of a second entry
+    assert_eq!(
+        tagged.artists.unwrap()[0].artist_mbid.as_deref(),
+        Some("band-mbid")
+    );
+    assert!(removed_fingerprint.is_none());
+}
+
+#[test]
+fn test_queue_incremental_updates() {
+    let db_file = TempDb::new("queue_incremental");
+    let db = Database::new(db_file.path());
+
+    let song = |id: &str| Song {
+        song: QueryableSong {
+            _id: Some(id.into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let state = QueueStateRow {
+        id: 0,
+        current_index: 0,
+        repeat: "None".into(),
+        shuffle: false,
+        original_order: None,
+    };
+    let ids = |db: &Database| {
+        db.load_queue()
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|s| s.song._id.unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    db.save_queue(&[song("a"), song("b"), song("c")], state.clone())
+        .unwrap();
+    db.queue_insert(1, &[song("x"), song("y")], state.clone())
+        .unwrap();
+    let after_insert = ids(&db);
+    db.queue_insert(5, &[song("z")], state.clone()).unwrap();
+    let after_append = ids(&db);
+    db.queue_remove(0, state.clone()).unwrap();
+    let after_remove = ids(&db);
+    db.queue_move(0, 3, state.clone()).unwrap();
+    let after_move_back = ids(&db);
+    db.queue_move(4, 1, state.clone()).unwrap();
+    let after_move_forward = ids(&db);
+
+    assert_eq!(after_insert, vec!["a", "x", "y", "b", "c"]);
+    assert_eq!(after_append, vec!["a", "x", "y", "b", "c", "z"]);
+    assert_eq!(after_remove, vec!["x", "y", "b", "c", "z"]);
+    assert_eq!(after_move_back, vec!["y", "b", "c", "x", "z"]);
+    assert_eq!(after_move_forward, vec!["y", "z", "b", "c", "x"]);
+}
@@ -46,7 +46,7 @@ use types::{
         ExtensionCommandResponse, ExtensionManifest, GenericExtensionHostRequest, MainCommand,
         MainCommandResponse, RunnerCommand, RunnerCommandResp,
     },
-    ui::extensions::ExtensionDetail,
+    ui::extensions::{ExtensionDetail, ExtensionOAuthClient},
 };
 
 // Ext handler inner
@@ -68,6 +68,21 @@ host_fn!(send_main_command(user_data: MainCommandUserData; command: MainCommand)
     let user_data = user_data.get()?;
     let user_data = user_data.lock().unwrap();
     tracing::debug!("Got extension command {:?}", command);
+    // Redirects are routed to the extension which registered them, tag them with its name
+    let command = match command {
+        MainCommand::RegisterOAuth(redirect) => {
+            MainCommand::RegisterOAuth(format!("{}:{}", user_data.extension_name, redirect))
+        }
+        MainCommand::RegisterOAuthClient(client) => MainCommand::RegisterOAuthClient(ExtensionOAuthClient {
+            package_name: user_data.extension_name.clone(),
+            ..client
+        }),
+        // Extensions may only act on their own tokens
+        MainCommand::OAuthLogin(_) => MainCommand::OAuthLogin(user_data.extension_name.clone()),
+        MainCommand::GetOAuthToken(_) => MainCommand::GetOAuthToken(user_data.extension_name.clone()),
+        MainCommand::OAuthSignout(_) => MainCommand::OAuthSignout(user_data.extension_name.clone()),
+        command => command,
+    };
     match command.to_request() {
         Ok(request) => {
             let reply_map = user_data.reply_map.clone();
@@ -0,0 +1,69 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fs::File, io::BufReader, path::Path};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use lofty::{prelude::AudioFile, probe::Probe};
+use rodio::{Decoder, Source};
+use rusty_chromaprint::{Configuration, FingerprintCompressor, Fingerprinter};
+use types::errors::Result;
+
+/// AcoustID only looks at the start of a song
+const MAX_FINGERPRINT_SECS: u64 = 120;
+
+#[derive(Debug, Clone, Default)]
+pub struct AudioFingerprint {
+    /// In seconds
+    pub duration: f64,
+    /// Compressed and base64 encoded the way AcoustID expects it
+    pub fingerprint: String,
+}
+
+/// Decodes the first two minutes of `path` and computes its Chromaprint
+/// fingerprint with the algorithm used by AcoustID
+#[tracing::instrument(level = "trace", skip())]
+pub fn fingerprint_file(path: &Path) -> Result<AudioFingerprint> {
+    let duration = Probe::open(path)?
+        .read()?
+        .properties()
+        .duration()
+        .as_secs_f64();
+
+    let decoder = Decoder::new(BufReader::new(File::open(path)?)).map_err(|e| e.to_string())?;
+    let sample_rate = decoder.sample_rate();
+    let channels = decoder.channels();
+
+    let config = Configuration::preset_test2();
+    let mut printer = Fingerprinter::new(&config);
+    printer
+        .start(sample_rate, channels as u32)
+        .map_err(|e| format!("Failed to start fingerprinter: {:?}", e))?;
+
+    let max_samples = (MAX_FINGERPRINT_SECS * sample_rate as u64 * channels as u64) as usize;
+    let samples = decoder.take(max_samples).collect::<Vec<i16>>();
+    if samples.is_empty() {
+        return Err(format!("No audio decoded from {:?}", path).into());
+    }
+    printer.consume(&samples);
+    printer.finish();
+
+    let compressed = FingerprintCompressor::from(&config).compress(printer.fingerprint());
+    Ok(AudioFingerprint {
+        duration,
+        fingerprint: URL_SAFE_NO_PAD.encode(compressed),
+    })
+}
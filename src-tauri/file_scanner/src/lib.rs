@@ -22,8 +22,20 @@ mod scanner;
 pub use scanner::{ScanState, ScannerHolder};
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod song_scanner;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub use song_scanner::scan_song;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod fingerprint;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub use fingerprint::{fingerprint_file, AudioFingerprint};
+pub mod playlists;
 mod types;
+pub use crate::types::KnownFiles;
 mod utils;
+pub use utils::store_cover;
+
+#[cfg(test)]
+mod test;
 
 #[cfg(target_os = "android")]
 mod scanner_android;
@@ -14,25 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{
-    fs::{self, File},
-    io::{self, BufRead},
-    path::PathBuf,
-    str::FromStr,
-    sync::mpsc::Sender,
-};
+use std::{path::PathBuf, str::FromStr, sync::mpsc::Sender};
 
 use types::{
-    entities::{QueryableArtist, QueryablePlaylist},
-    songs::{QueryableSong, Song, SongType},
+    entities::QueryablePlaylist,
+    errors::{MoosyncError, Result},
+    songs::{Song, SongType},
 };
 
-use substring::Substring;
-use types::errors::{MoosyncError, Result};
-
-use uuid::Uuid;
-
 use crate::{
+    playlists::import_playlist,
     song_scanner::SongScanner,
     utils::{check_directory, get_files_recursively},
 };
@@ -58,150 +49,6 @@ impl<'a> PlaylistScanner<'a> {
         check_directory(self.thumbnail_dir.clone())
     }
 
-    #[tracing::instrument(level = "trace", skip(self, artists))]
-    fn parse_artists(&self, artists: Option<String>) -> Vec<QueryableArtist> {
-        let mut ret: Vec<QueryableArtist> = vec![];
-        if artists.is_some() {
-            for artist in artists.unwrap().split(';') {
-                ret.push(QueryableArtist {
-                    artist_id: Some(Uuid::new_v4().to_string()),
-                    artist_name: Some(artist.to_string()),
-                    ..Default::default()
-                })
-            }
-        }
-        ret
-    }
-
-    #[tracing::instrument(level = "trace", skip(self, path))]
-    fn scan_playlist(&self, path: &PathBuf) -> Result<(QueryablePlaylist, Vec<Song>)> {
-        let file = File::open(path)?;
-        let lines = io::BufReader::new(file).lines();
-
-        let mut songs: Vec<Song> = vec![];
-
-        let mut song_type: Option<String> = None;
-        let mut duration: Option<f64> = None;
-        let mut title: Option<String> = None;
-        let mut artists: Option<String> = None;
-        let mut playlist_title: String = "".to_string();
-
-        let playlist_id = Uuid::new_v4().to_string();
-        for line_res in lines {
-            let mut line = line_res.unwrap();
-            if line.starts_with("#EXTINF:") {
-                let metadata = line.substring(8, line.len());
-                let split_index = metadata.find(',').unwrap_or_default();
-
-                duration = Some(metadata.substring(0, split_index).parse::<f64>()?);
-
-                let non_duration = metadata.substring(split_index + 1, metadata.len());
-
-                let mut artists_str = "";
-                let title_str;
-
-                let separator_with_space = non_duration.find(" - ");
-                if separator_with_space.is_some() {
-                    (artists_str, title_str) =
-                        non_duration.split_at(separator_with_space.unwrap() + 1);
-                } else {
-                    let separator_without_space = non_duration.find('-');
-                    if separator_without_space.is_some() {
-                        (artists_str, title_str) =
-                            non_duration.split_at(separator_without_space.unwrap());
-                    } else {
-                        title_str = non_duration;
-                    }
-                }
-
-                artists = Some(artists_str.trim().to_string());
-                title = Some(title_str.replacen('-', "", 1).trim().to_string());
-
-                continue;
-            }
-
-            if line.starts_with("#MOOSINF:") {
-                song_type = Some(line.substring(9, line.len()).to_string());
-                continue;
-            }
-
-            if line.starts_with("#PLAYLIST:") {
-                playlist_title = line.substring(10, line.len()).to_string();
-                continue;
-            }
-
-            if !line.starts_with('#') {
-                if line.starts_with("file://") {
-                    line = line[8..].to_string();
-                } else {
-                    continue;
-                }
-
-                let mut song = QueryableSong::empty();
-
-                let s_type = song_type.clone();
-
-                song.type_ = SongType::from_str(s_type.unwrap_or("LOCAL".to_string()).as_str())?;
-                song._id = Some(Uuid::new_v4().to_string());
-
-                if song.type_ == SongType::LOCAL {
-                    let song_path = PathBuf::from_str(line.as_str());
-                    let mut path_parsed = song_path.unwrap();
-                    if path_parsed.is_relative() {
-                        path_parsed = path.parent().unwrap().join(path_parsed).canonicalize()?;
-                    }
-
-                    if !path_parsed.exists() {
-                        artists = None;
-                        duration = None;
-                        title = None;
-                        song_type = None;
-                        continue;
-                    }
-
-                    let metadata = fs::metadata(&path_parsed)?;
-                    song.size = Some(metadata.len() as f64);
-                    song.path = Some(path_parsed.to_string_lossy().to_string());
-
-                    if song.path.is_none() {
-                        song.path = Some(line);
-                    }
-
-                    song.playback_url = None;
-                } else {
-                    song._id = Some(format!("{}:{}", song.type_, line));
-                    song.playback_url = Some(line);
-                }
-
-                // song.artists = ;
-                song.duration = duration;
-                song.title = title;
-                // song.playlist_id = Some(playlist_id.clone());
-                songs.push(Song {
-                    song,
-                    album: None,
-                    artists: Some(self.parse_artists(artists)),
-                    genre: Some(vec![]),
-                });
-
-                artists = None;
-                duration = None;
-                title = None;
-                song_type = None;
-            }
-        }
-
-        Ok((
-            QueryablePlaylist {
-                playlist_id: Some(playlist_id),
-                playlist_name: playlist_title,
-                playlist_path: Some(path.to_string_lossy().to_string()),
-                ..Default::default()
-            },
-            songs,
-        ))
-    }
-
     #[tracing::instrument(level = "trace", skip(self, tx_song, s, playlist_id))]
     fn scan_song_in_pool(
         &self,
@@ -236,19 +83,29 @@ impl<'a> PlaylistScanner<'a> {
         let mut len = 0;
 
         for playlist in file_list.playlist_list {
-            let playlist_scan_res = self.scan_playlist(&playlist);
-            if playlist_scan_res.is_err() {
-                tx_playlist
-                    .send(Err(MoosyncError::String(format!(
-                        "Failed to scan {}: {:?}",
-                        playlist.display(),
-                        playlist_scan_res.unwrap_err()
-                    ))))
-                    .expect("channel will be there waiting for the pool");
-                continue;
+            let imported = match import_playlist(&playlist) {
+                Ok(imported) => imported,
+                Err(e) => {
+                    tx_playlist
+                        .send(Err(MoosyncError::String(format!(
+                            "Failed to scan {}: {:?}",
+                            playlist.display(),
+                            e
+                        ))))
+                        .expect("channel will be there waiting for the pool");
+                    continue;
+                }
+            };
+
+            if !imported.unresolved.is_empty() {
+                tracing::warn!(
+                    "Skipped {} missing entries in {}",
+                    imported.unresolved.len(),
+                    playlist.display()
+                );
             }
 
-            let (playlist_dets, songs) = playlist_scan_res.unwrap();
+            let (playlist_dets, songs) = (imported.playlist, imported.songs);
             tx_playlist
                 .send(Ok(playlist_dets.clone()))
                 .expect("channel will be there waiting for the pool");
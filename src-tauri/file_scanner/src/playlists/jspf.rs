@@ -0,0 +1,95 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use types::errors::Result;
+
+use super::{split_artists, ParsedPlaylist, PlaylistEntry};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Jspf {
+    playlist: JspfPlaylist,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct JspfPlaylist {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default)]
+    track: Vec<JspfTrack>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct JspfTrack {
+    #[serde(default)]
+    location: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    creator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    album: Option<String>,
+    /// In milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+}
+
+#[tracing::instrument(level = "trace", skip(data))]
+pub fn parse(data: &str) -> Result<ParsedPlaylist> {
+    let jspf: Jspf = serde_json::from_str(data)?;
+    Ok(ParsedPlaylist {
+        title: jspf.playlist.title,
+        entries: jspf
+            .playlist
+            .track
+            .into_iter()
+            .filter_map(|track| {
+                Some(PlaylistEntry {
+                    location: track.location.into_iter().next()?,
+                    title: track.title,
+                    artists: track.creator.map(|c| split_artists(&c)).unwrap_or_default(),
+                    album: track.album,
+                    duration: track.duration.map(|ms| ms as f64 / 1000f64),
+                    cover: track.image,
+                    ..Default::default()
+                })
+            })
+            .collect(),
+    })
+}
+
+#[tracing::instrument(level = "trace", skip(entries))]
+pub fn write(title: &str, entries: &[PlaylistEntry]) -> Result<String> {
+    let jspf = Jspf {
+        playlist: JspfPlaylist {
+            title: Some(title.to_string()),
+            track: entries
+                .iter()
+                .map(|entry| JspfTrack {
+                    location: vec![entry.location.clone()],
+                    title: entry.title.clone(),
+                    creator: (!entry.artists.is_empty()).then(|| entry.artists.join("; ")),
+                    album: entry.album.clone(),
+                    duration: entry.duration.map(|d| (d * 1000f64).round() as u64),
+                    image: entry.cover.clone(),
+                })
+                .collect(),
+        },
+    };
+    Ok(serde_json::to_string_pretty(&jspf)?)
+}
@@ -0,0 +1,132 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fmt::Write, str::FromStr};
+
+use types::{errors::Result, songs::SongType};
+
+use super::{split_artists, ParsedPlaylist, PlaylistEntry};
+
+/// Parses an M3U or extended M3U playlist. Unknown or malformed directives
+/// are skipped instead of failing the whole playlist.
+#[tracing::instrument(level = "trace", skip(data))]
+pub fn parse(data: &str) -> ParsedPlaylist {
+    let mut ret = ParsedPlaylist::default();
+    let mut entry = PlaylistEntry::default();
+
+    for line in data.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some(directive) = line.strip_prefix('#') else {
+            entry.location = line.to_string();
+            ret.entries.push(std::mem::take(&mut entry));
+            continue;
+        };
+
+        let (name, value) = directive.split_once(':').unwrap_or((directive, ""));
+        let value = value.trim();
+        match name.trim().to_ascii_uppercase().as_str() {
+            "EXTINF" => parse_extinf(value, &mut entry),
+            // Takes priority over the artists guessed from #EXTINF
+            "EXTART" => entry.artists = split_artists(value),
+            "EXTALB" if !value.is_empty() => entry.album = Some(value.to_string()),
+            "EXTGENRE" => {
+                entry.genres = value
+                    .split(',')
+                    .map(|g| g.trim().to_string())
+                    .filter(|g| !g.is_empty())
+                    .collect()
+            }
+            "EXTIMG" if !value.is_empty() => entry.cover = Some(value.to_string()),
+            "MOOSINF" => entry.song_type = SongType::from_str(value).ok(),
+            "PLAYLIST" if !value.is_empty() => ret.title = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    ret
+}
+
+/// Parses `#EXTINF:<duration> [attributes],[<artists> - ]<title>`
+#[tracing::instrument(level = "trace", skip(entry))]
+fn parse_extinf(value: &str, entry: &mut PlaylistEntry) {
+    // Attribute values such as group-title="a,b" may contain commas
+    let mut in_quotes = false;
+    let split = value.find(|c: char| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ',' && !in_quotes
+    });
+    let (info, name) = match split {
+        Some(i) => (&value[..i], &value[i + 1..]),
+        None => (value, ""),
+    };
+
+    // -1 means the duration is unknown
+    entry.duration = info
+        .split_whitespace()
+        .next()
+        .and_then(|d| d.parse::<f64>().ok())
+        .filter(|d| d.is_finite() && *d >= 0f64);
+
+    let name = name.trim();
+    let (artists, title) = match name.split_once(" - ") {
+        Some((artists, title)) => (Some(artists), title),
+        None => (None, name),
+    };
+    if entry.artists.is_empty() {
+        entry.artists = artists.map(split_artists).unwrap_or_default();
+    }
+    entry.title = Some(title.trim().to_string()).filter(|t| !t.is_empty());
+}
+
+#[tracing::instrument(level = "trace", skip(entries))]
+pub fn write(title: &str, entries: &[PlaylistEntry]) -> Result<String> {
+    let mut ret = format!("#EXTM3U\n#PLAYLIST:{}\n", title);
+    for entry in entries {
+        write!(
+            ret,
+            "#EXTINF:{},",
+            entry.duration.unwrap_or(-1f64).round() as i64
+        )?;
+        if !entry.artists.is_empty() {
+            write!(ret, "{} - ", entry.artists.join("; "))?;
+        }
+        writeln!(ret, "{}", entry.title.clone().unwrap_or_default())?;
+
+        if !entry.artists.is_empty() {
+            writeln!(ret, "#EXTART:{}", entry.artists.join("; "))?;
+        }
+        if let Some(album) = &entry.album {
+            writeln!(ret, "#EXTALB:{}", album)?;
+        }
+        if !entry.genres.is_empty() {
+            writeln!(ret, "#EXTGENRE:{}", entry.genres.join(","))?;
+        }
+        if let Some(cover) = &entry.cover {
+            writeln!(ret, "#EXTIMG:{}", cover)?;
+        }
+        if let Some(song_type) = entry.song_type {
+            writeln!(ret, "#MOOSINF:{}", song_type)?;
+        }
+        writeln!(ret, "{}", entry.location)?;
+    }
+    Ok(ret)
+}
@@ -0,0 +1,367 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Reading and writing playlist files. Every format is parsed into
+//! [PlaylistEntry] first so resolving songs works the same for all of them.
+
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use types::{
+    entities::{QueryableAlbum, QueryableArtist, QueryablePlaylist},
+    errors::Result,
+    songs::{QueryableSong, Song, SongType},
+};
+use url::Url;
+use uuid::Uuid;
+
+mod jspf;
+mod m3u;
+mod pls;
+mod xspf;
+
+// Characters that can't appear as is in the path of a relative URI
+const PATH_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+pub use types::entities::PlaylistFormat;
+
+/// Whether locations in `format` are URIs rather than file paths
+#[tracing::instrument(level = "trace", skip())]
+fn uses_uris(format: PlaylistFormat) -> bool {
+    matches!(format, PlaylistFormat::Xspf | PlaylistFormat::Jspf)
+}
+
+/// A song as listed in a playlist file, before it is matched to a file on disk
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlaylistEntry {
+    /// A path, file URI or URL
+    pub location: String,
+    pub title: Option<String>,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub genres: Vec<String>,
+    /// In seconds
+    pub duration: Option<f64>,
+    pub cover: Option<String>,
+    pub song_type: Option<SongType>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedPlaylist {
+    pub title: Option<String>,
+    pub entries: Vec<PlaylistEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedPlaylist {
+    pub playlist: QueryablePlaylist,
+    pub songs: Vec<Song>,
+    /// Locations of entries that don't point to a readable file
+    pub unresolved: Vec<String>,
+}
+
+#[tracing::instrument(level = "trace", skip(data))]
+pub fn parse_playlist(format: PlaylistFormat, data: &str) -> Result<ParsedPlaylist> {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    match format {
+        PlaylistFormat::M3u => Ok(m3u::parse(data)),
+        PlaylistFormat::Pls => pls::parse(data),
+        PlaylistFormat::Xspf => xspf::parse(data),
+        PlaylistFormat::Jspf => jspf::parse(data),
+    }
+}
+
+/// Reads a playlist file and matches its entries to songs. Local files are
+/// resolved relative to the playlist, entries whose file is missing are
+/// reported in [ImportedPlaylist::unresolved].
+#[tracing::instrument(level = "trace", skip())]
+pub fn import_playlist(path: &Path) -> Result<ImportedPlaylist> {
+    let format = PlaylistFormat::from_path(path)
+        .ok_or_else(|| format!("Unsupported playlist format: {}", path.display()))?;
+    // Older .m3u files are usually Latin-1 rather than UTF-8
+    let data = String::from_utf8(fs::read(path)?)
+        .unwrap_or_else(|e| e.into_bytes().into_iter().map(char::from).collect());
+    let parsed = parse_playlist(format, &data)?;
+
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    let mut songs = vec![];
+    let mut unresolved = vec![];
+    for entry in parsed.entries {
+        let location = entry.location.clone();
+        match resolve_entry(base_dir, format, entry) {
+            Some(song) => songs.push(song),
+            None => unresolved.push(location),
+        }
+    }
+
+    let playlist_name = parsed.title.unwrap_or_else(|| {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+
+    Ok(ImportedPlaylist {
+        playlist: QueryablePlaylist {
+            playlist_id: Some(Uuid::new_v4().to_string()),
+            playlist_name,
+            playlist_path: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        },
+        songs,
+        unresolved,
+    })
+}
+
+/// Writes `songs` as a playlist. When `relative_to` is set, local paths are
+/// written relative to that directory so the playlist can be moved along
+/// with the music.
+#[tracing::instrument(level = "trace", skip(songs))]
+pub fn write_playlist(
+    format: PlaylistFormat,
+    title: &str,
+    songs: &[Song],
+    relative_to: Option<&Path>,
+) -> Result<String> {
+    let entries = songs
+        .iter()
+        .filter_map(|song| entry_from_song(format, song, relative_to))
+        .collect::<Vec<_>>();
+    match format {
+        PlaylistFormat::M3u => m3u::write(title, &entries),
+        PlaylistFormat::Pls => pls::write(&entries),
+        PlaylistFormat::Xspf => xspf::write(title, &entries),
+        PlaylistFormat::Jspf => jspf::write(title, &entries),
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(song))]
+fn entry_from_song(
+    format: PlaylistFormat,
+    song: &Song,
+    relative_to: Option<&Path>,
+) -> Option<PlaylistEntry> {
+    let location = if let Some(path) = &song.song.path {
+        local_location(format, Path::new(path), relative_to)
+    } else {
+        song.song
+            .url
+            .clone()
+            .or_else(|| song.song.playback_url.clone())?
+    };
+
+    Some(PlaylistEntry {
+        location,
+        title: song.song.title.clone(),
+        artists: song
+            .artists
+            .iter()
+            .flatten()
+            .filter_map(|a| a.artist_name.clone())
+            .collect(),
+        album: song.album.as_ref().and_then(|a| a.album_name.clone()),
+        genres: song
+            .genre
+            .iter()
+            .flatten()
+            .filter_map(|g| g.genre_name.clone())
+            .collect(),
+        duration: song.song.duration,
+        cover: song.song.song_cover_path_high.clone(),
+        song_type: Some(song.song.type_),
+    })
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn local_location(format: PlaylistFormat, path: &Path, relative_to: Option<&Path>) -> String {
+    if let Some(relative) = relative_to.and_then(|base| relative_path(path, base)) {
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if uses_uris(format) {
+            return utf8_percent_encode(&relative, PATH_SET).to_string();
+        }
+        return relative;
+    }
+
+    match format {
+        PlaylistFormat::M3u | PlaylistFormat::Xspf | PlaylistFormat::Jspf => {
+            Url::from_file_path(path)
+                .map(|u| u.to_string())
+                .unwrap_or_else(|_| path.to_string_lossy().to_string())
+        }
+        PlaylistFormat::Pls => path.to_string_lossy().to_string(),
+    }
+}
+
+/// Path of `path` relative to the directory `base`. None if they are on
+/// different roots, e.g. different drives on Windows.
+#[tracing::instrument(level = "trace", skip())]
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    if !path.is_absolute() || !base.is_absolute() {
+        return None;
+    }
+
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    match (path_components.peek(), base_components.peek()) {
+        (Some(Component::Prefix(a)), Some(Component::Prefix(b))) if a != b => return None,
+        _ => {}
+    }
+
+    while let (Some(a), Some(b)) = (path_components.peek(), base_components.peek()) {
+        if a != b {
+            break;
+        }
+        path_components.next();
+        base_components.next();
+    }
+
+    let mut ret = PathBuf::new();
+    for component in base_components {
+        if let Component::Normal(_) = component {
+            ret.push("..");
+        }
+    }
+    ret.extend(path_components);
+    Some(ret)
+}
+
+#[tracing::instrument(level = "trace", skip(entry))]
+fn resolve_entry(base_dir: &Path, format: PlaylistFormat, entry: PlaylistEntry) -> Option<Song> {
+    let location = entry.location.trim();
+    if location.is_empty() {
+        return None;
+    }
+
+    let song_type = entry.song_type.unwrap_or(if is_remote(location) {
+        SongType::URL
+    } else {
+        SongType::LOCAL
+    });
+
+    let mut song = QueryableSong::empty();
+    song.type_ = song_type;
+    song.title = entry.title;
+    song.duration = entry.duration;
+    song.song_cover_path_high = entry.cover;
+
+    if song_type == SongType::LOCAL {
+        let path = local_path(base_dir, format, location)?;
+        let metadata = fs::metadata(&path).ok()?;
+        if !metadata.is_file() {
+            return None;
+        }
+        if song.title.is_none() {
+            song.title = path.file_name().map(|f| f.to_string_lossy().to_string());
+        }
+        song.size = Some(metadata.len() as f64);
+        song.path = Some(path.to_string_lossy().to_string());
+    } else {
+        song._id = Some(format!("{}:{}", song_type, location));
+        song.url = Some(location.to_string());
+        song.playback_url = Some(location.to_string());
+    }
+
+    Some(Song {
+        song,
+        album: entry.album.map(|album_name| QueryableAlbum {
+            album_id: Some(Uuid::new_v4().to_string()),
+            album_name: Some(album_name),
+            ..Default::default()
+        }),
+        artists: Some(
+            entry
+                .artists
+                .into_iter()
+                .map(|artist_name| QueryableArtist {
+                    artist_id: Some(Uuid::new_v4().to_string()),
+                    artist_name: Some(artist_name),
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+        genre: Some(vec![]),
+    })
+}
+
+/// Whether `location` is a URL like an HTTP stream rather than a file
+#[tracing::instrument(level = "trace", skip())]
+fn is_remote(location: &str) -> bool {
+    // Windows drive letters parse as single letter schemes
+    Url::parse(location).is_ok_and(|url| url.scheme() != "file" && url.scheme().len() > 1)
+}
+
+/// Turns a location into an absolute path. Relative locations are relative
+/// to the playlist file.
+#[tracing::instrument(level = "trace", skip())]
+fn local_path(base_dir: &Path, format: PlaylistFormat, location: &str) -> Option<PathBuf> {
+    if location
+        .get(..5)
+        .is_some_and(|s| s.eq_ignore_ascii_case("file:"))
+    {
+        // Some players write file URIs without escaping the path
+        let unescaped = location[5..].trim_start_matches('/');
+        return Url::parse(location)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .and_then(|path| dunce::canonicalize(path).ok())
+            .or_else(|| resolve_path(base_dir, &format!("/{}", unescaped)))
+            .or_else(|| resolve_path(base_dir, unescaped));
+    }
+
+    let decoded = percent_decode_str(location).decode_utf8_lossy();
+    if uses_uris(format) {
+        return resolve_path(base_dir, &decoded);
+    }
+    resolve_path(base_dir, location).or_else(|| resolve_path(base_dir, &decoded))
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn resolve_path(base_dir: &Path, location: &str) -> Option<PathBuf> {
+    // Playlists written on Windows separate directories with backslashes
+    [location.to_string(), location.replace('\\', "/")]
+        .into_iter()
+        .find_map(|location| {
+            let path = PathBuf::from(location);
+            let path = if path.is_relative() {
+                base_dir.join(path)
+            } else {
+                path
+            };
+            dunce::canonicalize(path).ok()
+        })
+}
+
+/// Splits a list of artists the same way the library does
+#[tracing::instrument(level = "trace", skip())]
+fn split_artists(artists: &str) -> Vec<String> {
+    artists
+        .split(';')
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
@@ -0,0 +1,99 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use types::errors::Result;
+
+use super::{ParsedPlaylist, PlaylistEntry};
+
+#[tracing::instrument(level = "trace", skip(data))]
+pub fn parse(data: &str) -> Result<ParsedPlaylist> {
+    let mut lines = data
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with(';'));
+    if !lines
+        .next()
+        .is_some_and(|l| l.eq_ignore_ascii_case("[playlist]"))
+    {
+        return Err("Missing [playlist] header".into());
+    }
+
+    // Keys are numbered, e.g. File1, Title1, Length1 and may come in any order
+    let mut entries: BTreeMap<u32, PlaylistEntry> = BTreeMap::new();
+    for line in lines {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+
+        let split = key.find(|c: char| c.is_ascii_digit()).unwrap_or(key.len());
+        let (name, index) = key.split_at(split);
+        let Ok(index) = index.parse::<u32>() else {
+            continue;
+        };
+
+        let entry = entries.entry(index).or_default();
+        match name {
+            "file" => entry.location = value.to_string(),
+            "title" => entry.title = Some(value.to_string()),
+            "length" => entry.duration = value.parse::<f64>().ok().filter(|d| *d >= 0f64),
+            _ => {}
+        }
+    }
+
+    Ok(ParsedPlaylist {
+        title: None,
+        entries: entries
+            .into_values()
+            .filter(|e| !e.location.is_empty())
+            .collect(),
+    })
+}
+
+#[tracing::instrument(level = "trace", skip(entries))]
+pub fn write(entries: &[PlaylistEntry]) -> Result<String> {
+    let mut ret = "[playlist]\n".to_string();
+    for (i, entry) in entries.iter().enumerate() {
+        let index = i + 1;
+        writeln!(ret, "File{}={}", index, entry.location)?;
+        if let Some(title) = &entry.title {
+            // PLS has no field for artists, players show them as part of the title
+            if entry.artists.is_empty() {
+                writeln!(ret, "Title{}={}", index, title)?;
+            } else {
+                writeln!(
+                    ret,
+                    "Title{}={} - {}",
+                    index,
+                    entry.artists.join("; "),
+                    title
+                )?;
+            }
+        }
+        writeln!(
+            ret,
+            "Length{}={}",
+            index,
+            entry.duration.unwrap_or(-1f64).round() as i64
+        )?;
+    }
+    writeln!(ret, "NumberOfEntries={}", entries.len())?;
+    writeln!(ret, "Version=2")?;
+    Ok(ret)
+}
@@ -0,0 +1,127 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Write;
+
+use quick_xml::{escape::escape, events::Event, Reader};
+use types::errors::Result;
+
+use super::{split_artists, ParsedPlaylist, PlaylistEntry};
+
+#[tracing::instrument(level = "trace", skip(data))]
+pub fn parse(data: &str) -> Result<ParsedPlaylist> {
+    let mut reader = Reader::from_str(data);
+    reader.config_mut().trim_text(true);
+
+    let mut ret = ParsedPlaylist::default();
+    let mut path: Vec<String> = vec![];
+    let mut track: Option<PlaylistEntry> = None;
+
+    loop {
+        let text = match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                if name == "track" {
+                    track = Some(PlaylistEntry::default());
+                }
+                path.push(name);
+                continue;
+            }
+            Event::End(_) => {
+                if path.pop().as_deref() == Some("track") {
+                    if let Some(track) = track.take() {
+                        ret.entries.push(track);
+                    }
+                }
+                continue;
+            }
+            Event::Text(e) => e.unescape().map_err(|e| e.to_string())?.to_string(),
+            Event::CData(e) => String::from_utf8_lossy(&e).to_string(),
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        let Some(tag) = path.last() else {
+            continue;
+        };
+        match track.as_mut() {
+            Some(track) => match tag.as_str() {
+                // Only the first location is used, the rest are alternatives
+                "location" if track.location.is_empty() => track.location = text,
+                "title" => track.title = Some(text),
+                "creator" => track.artists = split_artists(&text),
+                "album" => track.album = Some(text),
+                "duration" => {
+                    track.duration = text.parse::<f64>().ok().map(|ms| ms / 1000f64);
+                }
+                "image" => track.cover = Some(text),
+                _ => {}
+            },
+            None if tag == "title" && path.len() == 2 => ret.title = Some(text),
+            None => {}
+        }
+    }
+
+    ret.entries.retain(|e| !e.location.is_empty());
+    Ok(ret)
+}
+
+#[tracing::instrument(level = "trace", skip(entries))]
+pub fn write(title: &str, entries: &[PlaylistEntry]) -> Result<String> {
+    let mut ret = String::new();
+    writeln!(ret, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        ret,
+        r#"<playlist version="1" xmlns="http://xspf.org/ns/0/">"#
+    )?;
+    writeln!(ret, "  <title>{}</title>", escape(title))?;
+    writeln!(ret, "  <trackList>")?;
+    for entry in entries {
+        writeln!(ret, "    <track>")?;
+        writeln!(
+            ret,
+            "      <location>{}</location>",
+            escape(&entry.location)
+        )?;
+        if let Some(title) = &entry.title {
+            writeln!(ret, "      <title>{}</title>", escape(title))?;
+        }
+        if !entry.artists.is_empty() {
+            writeln!(
+                ret,
+                "      <creator>{}</creator>",
+                escape(&entry.artists.join("; "))
+            )?;
+        }
+        if let Some(album) = &entry.album {
+            writeln!(ret, "      <album>{}</album>", escape(album))?;
+        }
+        if let Some(duration) = entry.duration {
+            writeln!(
+                ret,
+                "      <duration>{}</duration>",
+                (duration * 1000f64).round() as u64
+            )?;
+        }
+        if let Some(cover) = &entry.cover {
+            writeln!(ret, "      <image>{}</image>", escape(cover))?;
+        }
+        writeln!(ret, "    </track>")?;
+    }
+    writeln!(ret, "  </trackList>")?;
+    writeln!(ret, "</playlist>")?;
+    Ok(ret)
+}
@@ -19,7 +19,7 @@ use std::{
     str::FromStr,
     sync::{
         mpsc::{self, Sender},
-        Mutex,
+        Arc, Mutex,
     },
 };
 
@@ -27,7 +27,7 @@ use threadpool::ThreadPool;
 use types::errors::Result;
 use types::{entities::QueryablePlaylist, songs::Song};
 
-use crate::{playlist_scanner::PlaylistScanner, song_scanner::SongScanner};
+use crate::{playlist_scanner::PlaylistScanner, song_scanner::SongScanner, types::KnownFiles};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ScanState {
@@ -65,9 +65,11 @@ impl ScannerHolder {
             artist_split,
             scan_threads,
             song_tx,
-            playlist_tx
+            playlist_tx,
+            known
         )
     )]
+    #[allow(clippy::too_many_arguments)]
     pub fn start_scan(
         &self,
         dir: String,
@@ -76,6 +78,7 @@ impl ScannerHolder {
         scan_threads: f64,
         song_tx: Sender<(Option<String>, Vec<Song>)>,
         playlist_tx: Sender<Vec<QueryablePlaylist>>,
+        known: KnownFiles,
     ) -> Result<()> {
         let mut state = self.state.lock().unwrap();
         if *state != ScanState::UNDEFINED {
@@ -105,6 +108,7 @@ impl ScannerHolder {
             &mut song_pool,
             thumbnail_dir.clone(),
             artist_split,
+            Arc::new(known),
         );
 
         let (tx_song, rx_song) = mpsc::channel::<(Option<String>, Result<Song>)>();
@@ -19,6 +19,8 @@ use std::sync::{mpsc::Sender, Mutex};
 use types::errors::Result;
 use types::{entities::QueryablePlaylist, songs::Song};
 
+use crate::types::KnownFiles;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum ScanState {
     UNDEFINED,
@@ -56,9 +58,11 @@ impl ScannerHolder {
             artist_split,
             scan_threads,
             song_tx,
-            playlist_tx
+            playlist_tx,
+            known
         )
     )]
+    #[allow(clippy::too_many_arguments)]
     pub fn start_scan(
         &self,
         dir: String,
@@ -67,6 +71,7 @@ impl ScannerHolder {
         scan_threads: f64,
         song_tx: Sender<(Option<String>, Vec<Song>)>,
         playlist_tx: Sender<Vec<QueryablePlaylist>>,
+        known: KnownFiles,
     ) -> Result<()> {
         Ok(())
     }
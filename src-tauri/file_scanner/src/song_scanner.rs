@@ -14,33 +14,72 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{path::PathBuf, sync::mpsc::Sender};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{mpsc::Sender, Arc},
+};
 
-use crate::utils::{check_directory, get_files_recursively, scan_file};
+use crate::{
+    types::KnownFiles,
+    utils::{check_directory, get_files_recursively, scan_file},
+};
 use threadpool::ThreadPool;
 use types::errors::Result;
 use types::songs::Song;
 
+#[tracing::instrument(level = "trace", skip(path, thumbnail_dir, size, artist_split, known))]
+fn scan_with_fallback(
+    path: &PathBuf,
+    thumbnail_dir: &Path,
+    size: f64,
+    artist_split: &str,
+    known: &KnownFiles,
+) -> Result<Song> {
+    let metadata = scan_file(path, thumbnail_dir, size, false, artist_split, known);
+    if metadata.is_err() {
+        return scan_file(path, thumbnail_dir, size, true, artist_split, known);
+    }
+    metadata
+}
+
+/// Reads the metadata of a single file without adding it to the library
+#[tracing::instrument(level = "trace", skip(thumbnail_dir, artist_split))]
+pub fn scan_song(path: &Path, thumbnail_dir: &Path, artist_split: &str) -> Result<Song> {
+    check_directory(thumbnail_dir.to_path_buf())?;
+    let size = fs::metadata(path)?.len() as f64;
+    scan_with_fallback(
+        &path.to_path_buf(),
+        thumbnail_dir,
+        size,
+        artist_split,
+        &KnownFiles::new(),
+    )
+}
+
 pub struct SongScanner<'a> {
     dir: PathBuf,
     pool: &'a mut ThreadPool,
     thumbnail_dir: PathBuf,
     artist_split: String,
+    known: Arc<KnownFiles>,
 }
 
 impl<'a> SongScanner<'a> {
-    #[tracing::instrument(level = "trace", skip(dir, pool, thumbnail_dir, artist_split))]
+    #[tracing::instrument(level = "trace", skip(dir, pool, thumbnail_dir, artist_split, known))]
     pub fn new(
         dir: PathBuf,
         pool: &'a mut ThreadPool,
         thumbnail_dir: PathBuf,
         artist_split: String,
+        known: Arc<KnownFiles>,
     ) -> Self {
         Self {
             dir,
             pool,
             thumbnail_dir,
             artist_split,
+            known,
         }
     }
 
@@ -61,11 +100,9 @@ impl<'a> SongScanner<'a> {
     ) {
         let thumbnail_dir = self.thumbnail_dir.clone();
         let artist_split = self.artist_split.clone();
+        let known = self.known.clone();
         self.pool.execute(move || {
-            let mut metadata = scan_file(&path, &thumbnail_dir, size, false, &artist_split);
-            if metadata.is_err() {
-                metadata = scan_file(&path, &thumbnail_dir, size, true, &artist_split);
-            }
+            let metadata = scan_with_fallback(&path, &thumbnail_dir, size, &artist_split, &known);
 
             tx.send((playlist_id, metadata))
                 .expect("channel will be there waiting for the pool");
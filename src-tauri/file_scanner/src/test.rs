@@ -0,0 +1,376 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fs, path::PathBuf};
+
+use test_utils::TempDir;
+use types::songs::{QueryableSong, Song, SongType};
+use url::Url;
+
+use crate::{
+    fingerprint::fingerprint_file,
+    playlists::{import_playlist, parse_playlist, write_playlist, PlaylistEntry, PlaylistFormat},
+    types::KnownFiles,
+    utils::{content_hash, modified_time, scan_file},
+};
+
+/// Creates an empty directory under the system temp dir, returns its guard
+/// and canonical path
+fn test_dir(name: &str) -> (TempDir, PathBuf) {
+    let dir = TempDir::new(&format!("playlists_{}", name));
+    let path = dunce::canonicalize(&*dir).unwrap();
+    (dir, path)
+}
+
+fn local_song(path: &PathBuf, title: &str) -> Song {
+    Song {
+        song: QueryableSong {
+            title: Some(title.to_string()),
+            path: Some(path.to_string_lossy().to_string()),
+            duration: Some(61.5),
+            type_: SongType::LOCAL,
+            ..QueryableSong::empty()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_pls() {
+    let data = "[playlist]\r\n\
+        File2=https://example.com/stream\r\n\
+        Title1=Artist - Song\r\n\
+        File1=music/song.mp3\r\n\
+        Length1=200\r\n\
+        Length2=-1\r\n\
+        NumberOfEntries=2\r\n\
+        Version=2\r\n";
+    let parsed = parse_playlist(PlaylistFormat::Pls, data).unwrap();
+    assert_eq!(
+        parsed.entries,
+        vec![
+            PlaylistEntry {
+                location: "music/song.mp3".into(),
+                title: Some("Artist - Song".into()),
+                duration: Some(200f64),
+                ..Default::default()
+            },
+            PlaylistEntry {
+                location: "https://example.com/stream".into(),
+                ..Default::default()
+            },
+        ]
+    );
+
+    assert!(parse_playlist(PlaylistFormat::Pls, "File1=song.mp3").is_err());
+}
+
+#[test]
+fn test_parse_xspf() {
+    let data = r#"<?xml version="1.0" encoding="UTF-8"?>
+<playlist version="1" xmlns="http://xspf.org/ns/0/">
+  <title>Road &amp; trip</title>
+  <trackList>
+    <track>
+      <location>file:///music/a%20b.flac</location>
+      <location>https://example.com/fallback.flac</location>
+      <title>Song</title>
+      <creator>First; Second</creator>
+      <album>Album</album>
+      <duration>123000</duration>
+    </track>
+    <track>
+      <title>No location</title>
+    </track>
+  </trackList>
+</playlist>"#;
+    let parsed = parse_playlist(PlaylistFormat::Xspf, data).unwrap();
+    assert_eq!(parsed.title.as_deref(), Some("Road & trip"));
+    assert_eq!(
+        parsed.entries,
+        vec![PlaylistEntry {
+            location: "file:///music/a%20b.flac".into(),
+            title: Some("Song".into()),
+            artists: vec!["First".into(), "Second".into()],
+            album: Some("Album".into()),
+            duration: Some(123f64),
+            ..Default::default()
+        }]
+    );
+}
+
+#[test]
+fn test_relative_round_trip() {
+    let (_dir, dir) = test_dir("round_trip");
+    let music = dir.join("music dir");
+    fs::create_dir_all(&music).unwrap();
+    let song_path = music.join("song #1.mp3");
+    fs::write(&song_path, b"").unwrap();
+
+    let playlist_dir = dir.join("playlists");
+    fs::create_dir_all(&playlist_dir).unwrap();
+
+    let songs = vec![local_song(&song_path, "Song")];
+    for format in PlaylistFormat::ALL {
+        let file = playlist_dir.join(format!("test.{}", format.extensions()[0]));
+        let data = write_playlist(format, "Test", &songs, Some(&playlist_dir)).unwrap();
+        assert!(
+            !data.contains(&dir.to_string_lossy().to_string()),
+            "{:?} wrote an absolute path",
+            format
+        );
+        fs::write(&file, data).unwrap();
+
+        let imported = import_playlist(&file).unwrap();
+        assert!(imported.unresolved.is_empty(), "{:?}", format);
+        assert_eq!(imported.songs.len(), 1, "{:?}", format);
+        assert_eq!(
+            imported.songs[0].song.path,
+            Some(song_path.to_string_lossy().to_string()),
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn test_import_reports_missing() {
+    let (_dir, dir) = test_dir("missing");
+    let file = dir.join("test.jspf");
+    fs::write(
+        &file,
+        r#"{"playlist": {"title": "Mixed", "track": [
+            {"location": ["missing.mp3"]},
+            {"location": ["https://example.com/song.mp3"], "title": "Remote"}
+        ]}}"#,
+    )
+    .unwrap();
+
+    let imported = import_playlist(&file).unwrap();
+    assert_eq!(imported.playlist.playlist_name, "Mixed");
+    assert_eq!(imported.unresolved, vec!["missing.mp3".to_string()]);
+    assert_eq!(imported.songs.len(), 1);
+    assert_eq!(imported.songs[0].song.type_, SongType::URL);
+}
+
+#[test]
+fn test_parse_extended_m3u() {
+    let parsed =
+        parse_playlist(PlaylistFormat::M3u, include_str!("fixtures/extended.m3u")).unwrap();
+    assert_eq!(parsed.title.as_deref(), Some("Road trip"));
+    assert_eq!(
+        parsed.entries,
+        vec![
+            PlaylistEntry {
+                location: "music/one_more_time.mp3".into(),
+                title: Some("One More Time".into()),
+                artists: vec!["Daft Punk".into()],
+                album: Some("Discovery".into()),
+                genres: vec!["House".into(), "Electronic".into()],
+                duration: Some(215f64),
+                cover: Some("covers/discovery.jpg".into()),
+                song_type: None,
+            },
+            PlaylistEntry {
+                location: "music/two.flac".into(),
+                title: Some("Unknown duration".into()),
+                artists: vec!["First".into(), "Second".into()],
+                ..Default::default()
+            },
+            PlaylistEntry {
+                location: "https://radio.example.com/stream".into(),
+                title: Some("Live Radio".into()),
+                ..Default::default()
+            },
+            PlaylistEntry {
+                location: "https://www.youtube.com/watch?v=dQw4w9WgXcQ".into(),
+                title: Some("Title-With-Dashes".into()),
+                duration: Some(300f64),
+                song_type: Some(SongType::YOUTUBE),
+                ..Default::default()
+            },
+            PlaylistEntry {
+                location: "music/no_title.ogg".into(),
+                duration: Some(10f64),
+                ..Default::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_m3u_paths() {
+    let (_dir, dir) = test_dir("m3u_paths");
+    for file in [
+        "music/plain.mp3",
+        "music dir/encoded name.mp3",
+        "music/windows.mp3",
+        "music/dots.mp3",
+        "music/absolute.mp3",
+        "music dir/uri file.mp3",
+        "music dir/unescaped #1.mp3",
+    ] {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"").unwrap();
+    }
+
+    let root = dir.to_string_lossy().to_string();
+    let root_uri = Url::from_file_path(&dir).unwrap().to_string();
+    let data = include_str!("fixtures/paths.m3u8")
+        .replace("{root_uri}", &root_uri)
+        .replace("{root}", &root);
+    let file = dir.join("paths.m3u8");
+    fs::write(&file, data).unwrap();
+
+    let imported = import_playlist(&file).unwrap();
+    assert_eq!(
+        imported.unresolved,
+        vec![
+            "C:\\Music\\missing.mp3".to_string(),
+            "\\\\server\\share\\missing.mp3".to_string(),
+            "music/missing.mp3".to_string(),
+        ]
+    );
+
+    let paths = imported
+        .songs
+        .iter()
+        .map(|s| s.song.path.clone())
+        .collect::<Vec<_>>();
+    let expected = [
+        "music/plain.mp3",
+        "music dir/encoded name.mp3",
+        "music/windows.mp3",
+        "music/dots.mp3",
+        "music/absolute.mp3",
+        "music dir/uri file.mp3",
+        "music dir/unescaped #1.mp3",
+    ]
+    .iter()
+    .map(|f| {
+        Some(
+            dunce::canonicalize(dir.join(f))
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+        )
+    })
+    .chain([None])
+    .collect::<Vec<_>>();
+    assert_eq!(paths, expected);
+    assert_eq!(imported.songs[7].song.type_, SongType::URL);
+}
+
+#[test]
+fn test_content_hash_follows_moves() {
+    let (_dir, dir) = test_dir("content_hash");
+    let large = (0..300_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let mut edited = large.clone();
+    edited[150_000] ^= 1;
+
+    fs::write(dir.join("a.mp3"), &large).unwrap();
+    fs::create_dir_all(dir.join("moved")).unwrap();
+    fs::write(dir.join("moved").join("renamed.mp3"), &large).unwrap();
+    fs::write(dir.join("edited.mp3"), &edited).unwrap();
+    fs::write(dir.join("small.mp3"), b"small").unwrap();
+
+    let hash = |name: &str| content_hash(&dir.join(name)).unwrap();
+    assert_eq!(hash("a.mp3"), hash("moved/renamed.mp3"));
+    assert_ne!(hash("a.mp3"), hash("edited.mp3"));
+    assert_ne!(hash("a.mp3"), hash("small.mp3"));
+}
+
+#[test]
+fn test_scan_hashes_only_changed_files() {
+    let (_dir, dir) = test_dir("scan_hash");
+    let path = dir.join("a.wav");
+    write_wav(&path, &[440f64]);
+    let size = fs::metadata(&path).unwrap().len() as f64;
+    let modified = modified_time(&path).unwrap();
+    let key = path.to_string_lossy().to_string();
+    let thumbnails = dir.join("thumbnails");
+
+    let scan = |known: KnownFiles| scan_file(&path, &thumbnails, size, false, "", &known).unwrap();
+
+    // New files are hashed
+    let song = scan(KnownFiles::new());
+    assert_eq!(song.song.hash, Some(content_hash(&path).unwrap()));
+    assert_eq!(song.song.modified, Some(modified));
+
+    // Unchanged files keep the hash already in the library
+    let song = scan(KnownFiles::from([(key.clone(), (size, modified))]));
+    assert_eq!(song.song.hash, None);
+    assert_eq!(song.song.modified, Some(modified));
+
+    // Changed size or modification time
+    let song = scan(KnownFiles::from([(key.clone(), (size + 1f64, modified))]));
+    assert!(song.song.hash.is_some());
+    let song = scan(KnownFiles::from([(key, (size, modified - 10f64))]));
+    assert!(song.song.hash.is_some());
+}
+
+/// Mono 16 bit PCM WAV file of a few tones
+fn write_wav(path: &std::path::Path, freqs: &[f64]) {
+    let rate = 11025u32;
+    let mut samples = vec![];
+    for freq in freqs {
+        for i in 0..rate * 3 {
+            let t = i as f64 / rate as f64;
+            samples.push(((t * freq * std::f64::consts::TAU).sin() * 12000f64) as i16);
+        }
+    }
+
+    let data_len = samples.len() as u32 * 2;
+    let mut wav = vec![];
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&rate.to_le_bytes());
+    wav.extend_from_slice(&(rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    fs::write(path, wav).unwrap();
+}
+
+#[test]
+fn test_fingerprint_file() {
+    let (_dir, dir) = test_dir("fingerprint");
+    write_wav(&dir.join("a.wav"), &[440f64, 660f64, 880f64, 550f64]);
+    fs::copy(dir.join("a.wav"), dir.join("copy.wav")).unwrap();
+    write_wav(&dir.join("b.wav"), &[300f64, 1200f64, 700f64, 250f64]);
+
+    let a = fingerprint_file(&dir.join("a.wav")).unwrap();
+    assert!(!a.fingerprint.is_empty());
+    assert!((a.duration - 12f64).abs() < 0.1);
+    assert_eq!(
+        a.fingerprint,
+        fingerprint_file(&dir.join("copy.wav")).unwrap().fingerprint
+    );
+    assert_ne!(
+        a.fingerprint,
+        fingerprint_file(&dir.join("b.wav")).unwrap().fingerprint
+    );
+    assert!(fingerprint_file(&dir.join("missing.wav")).is_err());
+}
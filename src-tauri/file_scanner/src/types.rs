@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Size and modification time of files already in the library, by path
+pub type KnownFiles = HashMap<String, (f64, f64)>;
 
 #[derive(Debug)]
 pub struct FileList {
@@ -21,9 +21,12 @@ use lofty::{
 };
 use regex::Regex;
 use std::{
-    f64, fs,
+    f64,
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
     num::NonZeroU32,
     path::{Path, PathBuf},
+    time::UNIX_EPOCH,
 };
 use types::{
     entities::{QueryableAlbum, QueryableArtist, QueryableGenre},
@@ -36,7 +39,43 @@ use types::errors::Result;
 
 use fast_image_resize::{self as fr, ResizeOptions};
 
-use crate::types::FileList;
+use crate::types::{FileList, KnownFiles};
+
+/// Bytes read from each sampled region by [content_hash]
+const HASH_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Identifies a file by its contents so it can be recognised after being
+/// moved or renamed. Large files are sampled at the start, middle and end
+/// to keep scans of big libraries fast.
+#[tracing::instrument(level = "trace", skip())]
+pub fn content_hash(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&len.to_le_bytes());
+    if len <= HASH_CHUNK_SIZE * 3 {
+        let mut data = vec![];
+        file.read_to_end(&mut data)?;
+        hasher.update(&data);
+    } else {
+        let mut chunk = vec![0u8; HASH_CHUNK_SIZE as usize];
+        for offset in [0, (len - HASH_CHUNK_SIZE) / 2, len - HASH_CHUNK_SIZE] {
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut chunk)?;
+            hasher.update(&chunk);
+        }
+    }
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// When the file was last modified, in seconds since the unix epoch
+#[tracing::instrument(level = "trace", skip())]
+pub fn modified_time(path: &Path) -> Option<f64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs_f64())
+}
 
 #[tracing::instrument(level = "trace", skip(dir))]
 pub fn check_directory(dir: PathBuf) -> Result<()> {
@@ -54,7 +93,7 @@ pub fn get_files_recursively(dir: PathBuf) -> Result<FileList> {
 
     lazy_static! {
         static ref SONG_RE: Regex = Regex::new("flac|mp3|ogg|m4a|webm|wav|wv|aac|opus").unwrap();
-        static ref PLAYLIST_RE: Regex = Regex::new("m3u|m3u8").unwrap();
+        static ref PLAYLIST_RE: Regex = Regex::new("m3u|m3u8|pls|xspf|jspf").unwrap();
     }
 
     if !dir.exists() {
@@ -150,7 +189,13 @@ fn generate_image(data: &[u8], path: PathBuf, dimensions: u32) -> Result<()> {
 
 #[tracing::instrument(level = "trace", skip(thumbnail_dir, picture))]
 fn store_picture(thumbnail_dir: &Path, picture: &Picture) -> Result<(PathBuf, PathBuf)> {
-    let data = picture.data();
+    store_cover(thumbnail_dir, picture.data())
+}
+
+/// Writes the high and low resolution thumbnails of an image to
+/// `thumbnail_dir`, named after its hash, and returns their paths
+#[tracing::instrument(level = "trace", skip(thumbnail_dir, data))]
+pub fn store_cover(thumbnail_dir: &Path, data: &[u8]) -> Result<(PathBuf, PathBuf)> {
     let hash = blake3::hash(data).to_hex();
     let hash_str = hash.as_str();
 
@@ -199,13 +244,17 @@ fn scan_lrc(mut path: PathBuf) -> Option<String> {
     None
 }
 
-#[tracing::instrument(level = "trace", skip(path, thumbnail_dir, size, guess, artist_split))]
+#[tracing::instrument(
+    level = "trace",
+    skip(path, thumbnail_dir, size, guess, artist_split, known)
+)]
 pub fn scan_file(
     path: &PathBuf,
     thumbnail_dir: &Path,
     size: f64,
     guess: bool,
     artist_split: &str,
+    known: &KnownFiles,
 ) -> Result<Song> {
     let mut song: Song = Song {
         song: QueryableSong::empty(),
@@ -217,6 +266,20 @@ pub fn scan_file(
     song.song.title = Some(path.file_name().unwrap().to_string_lossy().to_string());
     song.song.path = Some(dunce::canonicalize(path)?.to_string_lossy().to_string());
     song.song.size = Some(size);
+    song.song.modified = modified_time(path);
+    // Hashing reads the file, so files the library already has a hash of are
+    // only hashed again when they change
+    let unchanged = song
+        .song
+        .path
+        .as_ref()
+        .and_then(|p| known.get(p))
+        .is_some_and(|&(known_size, known_modified)| {
+            known_size == size && Some(known_modified) == song.song.modified
+        });
+    if !unchanged {
+        song.song.hash = content_hash(path).ok();
+    }
     song.song.duration = Some(0f64);
     song.song.type_ = SongType::LOCAL;
 
@@ -0,0 +1,81 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use types::{errors::Result, lyrics::Lyrics};
+
+use crate::{scraper_client, search_url, LyricsQuery, LyricsSource};
+
+/// Scrapes lyrics from azlyrics.com using its search suggestions
+#[derive(Debug, Default)]
+pub struct AzLyricsSource;
+
+impl AzLyricsSource {
+    #[tracing::instrument(level = "trace", skip(page))]
+    fn parse_page(page: &str) -> Option<String> {
+        let lyrics = page
+            .split("<div class=\"ringtone\">")
+            .nth(1)
+            .unwrap_or("")
+            .split("<div class=\"noprint\"")
+            .next()
+            .unwrap_or("");
+
+        let index = lyrics.find("-->").unwrap_or(0) + 3;
+        let lyrics = lyrics.get(index..)?;
+        Some(
+            lyrics
+                .split("</div>")
+                .next()
+                .unwrap_or("")
+                .replace("<br>", "\n"),
+        )
+    }
+}
+
+#[async_trait]
+impl LyricsSource for AzLyricsSource {
+    fn key(&self) -> &'static str {
+        "azlyrics"
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, query))]
+    async fn fetch(&self, query: &LyricsQuery<'_>) -> Result<Lyrics> {
+        let url = search_url(
+            "https://search.azlyrics.com/suggest.php?q=",
+            &query.artists,
+            &query.title,
+        );
+
+        let client = scraper_client()?;
+        let resp = client.get(url).send().await?.text().await?;
+        let suggestions: Value = serde_json::from_str(&resp)?;
+
+        let song_url = suggestions.pointer("/songs/0/url").and_then(|u| u.as_str());
+
+        let mut lyrics = Lyrics {
+            source: self.key().into(),
+            ..Default::default()
+        };
+        if let Some(song_url) = song_url {
+            let page = client.get(song_url).send().await?.text().await?;
+            lyrics.plain = Self::parse_page(&page).unwrap_or_default();
+        }
+
+        Ok(lyrics)
+    }
+}
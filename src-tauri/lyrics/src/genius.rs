@@ -0,0 +1,82 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::Value;
+use types::{errors::Result, lyrics::Lyrics};
+
+use crate::{scraper_client, search_url, LyricsQuery, LyricsSource};
+
+/// Scrapes lyrics from the song pages of genius.com
+#[derive(Debug, Default)]
+pub struct GeniusSource;
+
+impl GeniusSource {
+    #[tracing::instrument(level = "trace", skip(page))]
+    fn parse_page(page: &str) -> Option<String> {
+        let split = page.split("window.__PRELOADED_STATE__ = ").nth(1)?;
+        let split = split.split("');").next()?;
+        let parsed = split.replace("JSON.parse(", "");
+        let split = parsed.split("\"lyricsData").nth(1)?;
+        let split = split.split("html\\\"").nth(1)?;
+        let split = split.split("\",").next()?;
+
+        let res = split
+            .replace("<br>", "\n")
+            .replace("\\\\n", "")
+            .replace('\\', "");
+
+        // Remove HTML tags using regex
+        let re = Regex::new(r#"<([^>]+)>"#).unwrap();
+        Some(re.replace_all(&res, "").to_string())
+    }
+}
+
+#[async_trait]
+impl LyricsSource for GeniusSource {
+    fn key(&self) -> &'static str {
+        "genius"
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, query))]
+    async fn fetch(&self, query: &LyricsQuery<'_>) -> Result<Lyrics> {
+        let url = search_url(
+            "https://genius.com/api/search/song?q=",
+            &query.artists,
+            &query.title,
+        );
+
+        let client = scraper_client()?;
+        let resp = client.get(url).send().await?.text().await?;
+        let json: Value = serde_json::from_str(resp.as_str())?;
+
+        let song_url = json
+            .pointer("/response/sections/0/hits/0/result/url")
+            .and_then(|u| u.as_str());
+
+        let mut lyrics = Lyrics {
+            source: self.key().into(),
+            ..Default::default()
+        };
+        if let Some(song_url) = song_url {
+            let page = client.get(song_url).send().await?.text().await?;
+            lyrics.plain = Self::parse_page(&page).unwrap_or_default();
+        }
+
+        Ok(lyrics)
+    }
+}
@@ -14,14 +14,80 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::{
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use database::cache::CacheHolder;
 use librespot::LibrespotHolder;
 use regex::Regex;
+use types::{errors::Result, lyrics::Lyrics, preferences::CheckboxPreference};
+
+mod azlyrics;
+mod genius;
+mod lrclib;
+mod spotify;
+
+#[cfg(test)]
+mod test;
+
+pub use azlyrics::AzLyricsSource;
+pub use genius::GeniusSource;
+pub use lrclib::{LrclibSource, LRCLIB_BASE_URL};
+pub use spotify::SpotifySource;
+
+// Found lyrics rarely change, misses are retried sooner in case the song gets added
+const CACHE_TTL: i32 = 7 * 24 * 60 * 60;
+const MISS_CACHE_TTL: i32 = 6 * 60 * 60;
+
+const USER_AGENT: &str =
+    "Mozilla/5.0 (Windows; U; Windows NT 5.1; it; rv:1.8.1.11) Gecko/20071127 Firefox/2.0.0.11";
+
+/// Song to fetch lyrics for
+#[derive(Debug, Default, Clone)]
+pub struct LyricsQuery<'a> {
+    pub id: String,
+    pub url: String,
+    pub artists: Vec<String>,
+    pub title: String,
+    pub album: Option<String>,
+    /// Duration in seconds
+    pub duration: Option<f64>,
+    pub librespot: Option<&'a LibrespotHolder>,
+}
 
-use serde_json::Value;
-use types::errors::Result;
+impl LyricsQuery<'_> {
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn cache_key(&self) -> String {
+        format!(
+            "{}_{}_{:?}_{}_{:?}",
+            self.id, self.url, self.artists, self.title, self.album
+        )
+    }
+}
+
+#[async_trait]
+pub trait LyricsSource: Debug + Send + Sync {
+    /// Identifies the source in preferences and cache keys
+    fn key(&self) -> &'static str;
+
+    /// Separates cached results of differently configured instances of a source
+    fn cache_id(&self) -> String {
+        self.key().to_string()
+    }
 
+    /// Fetches lyrics for `query`. Empty lyrics mean the source has nothing
+    /// for this song and the next source should be tried.
+    async fn fetch(&self, query: &LyricsQuery<'_>) -> Result<Lyrics>;
+}
+
+/// Asks the configured [LyricsSource]s in order until one of them has lyrics.
 #[derive(Debug)]
-pub struct LyricsFetcher {}
+pub struct LyricsFetcher {
+    sources: RwLock<Vec<Arc<dyn LyricsSource>>>,
+}
 
 impl Default for LyricsFetcher {
     #[tracing::instrument(level = "trace", skip())]
@@ -33,250 +99,123 @@ impl Default for LyricsFetcher {
 impl LyricsFetcher {
     #[tracing::instrument(level = "trace", skip())]
     pub fn new() -> LyricsFetcher {
-        LyricsFetcher {}
+        Self::with_sources(Self::available_sources(LRCLIB_BASE_URL))
     }
 
-    #[tracing::instrument(level = "trace", skip(self, title))]
-    fn sanitize_title(&self, title: &str) -> String {
-        let re1 = Regex::new(r"\((.*?)\)|\[(.*?)\]").unwrap();
-        let re2 =
-            Regex::new(r"[\u{1F300}-\u{1F3FF}\u{1F400}-\u{1F64F}\u{1F680}-\u{1F6FF}\u{274C}]")
-                .unwrap();
-        let re3 = Regex::new(r"//([^/]+)").unwrap();
-
-        let result = re1.replace_all(title, "").to_string();
-
-        let result = re2.replace_all(&result, "").to_string();
-
-        let result = re3.replace_all(&result, "").to_string();
-
-        result
-            .to_lowercase()
-            .replace("official", "")
-            .replace("music", "")
-            .replace("video", "")
-    }
-
-    #[tracing::instrument(level = "trace", skip(self, base, artists, title, append_lyrics))]
-    fn get_url(&self, base: &str, artists: &[String], title: &str, append_lyrics: bool) -> String {
-        let mut parsed_title = self.sanitize_title(title);
-
-        for a in artists {
-            parsed_title = parsed_title.replace(a.as_str(), "");
-        }
-
-        if append_lyrics {
-            parsed_title.push_str(" lyrics");
+    #[tracing::instrument(level = "trace", skip(sources))]
+    pub fn with_sources(sources: Vec<Arc<dyn LyricsSource>>) -> LyricsFetcher {
+        LyricsFetcher {
+            sources: RwLock::new(sources),
         }
-
-        format!("{}{} - {}", base, artists.join(", ").as_str(), parsed_title)
     }
 
-    #[tracing::instrument(level = "trace", skip(self, artists, title))]
-    async fn get_genius_lyrics(&self, artists: &[String], title: &str) -> Result<String> {
-        let url = self.get_url(
-            "https://genius.com/api/search/song?q=",
-            artists,
-            title,
-            false,
-        );
+    #[tracing::instrument(level = "trace", skip())]
+    fn available_sources(lrclib_url: &str) -> Vec<Arc<dyn LyricsSource>> {
+        vec![
+            Arc::new(SpotifySource),
+            Arc::new(LrclibSource::new(lrclib_url)),
+            Arc::new(AzLyricsSource),
+            Arc::new(GeniusSource),
+        ]
+    }
 
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows; U; Windows NT 5.1; it; rv:1.8.1.11) Gecko/20071127 Firefox/2.0.0.11")
-            .build()?;
-
-        let resp = client.get(url).send().await?.text().await?;
-        let json: Value = serde_json::from_str(resp.as_str())?;
-
-        // tracing::info!("{}", resp);
-
-        if let Some(resp) = json.get("response") {
-            if let Some(result) = resp.get("sections") {
-                if let Some(result) = result.get(0) {
-                    if let Some(result) = result.get("hits") {
-                        if let Some(result) = result.get(0) {
-                            if let Some(result) = result.get("result") {
-                                if let Some(result) = result.get("url") {
-                                    let url = result.as_str().unwrap();
-                                    let lyrics_resp = client.get(url).send().await?.text().await?;
-
-                                    let split =
-                                        lyrics_resp.split("window.__PRELOADED_STATE__ = ").nth(1);
-
-                                    if let Some(split) = split {
-                                        let split = split.split("');").next();
-                                        if let Some(split) = split {
-                                            let parsed = split.replace("JSON.parse(", "");
-                                            let split = parsed.split("\"lyricsData").nth(1);
-
-                                            if let Some(split) = split {
-                                                let split = split.split("html\\\"").nth(1);
-                                                if let Some(split) = split {
-                                                    let split = split.split("\",").nth(0);
-                                                    if let Some(split) = split {
-                                                        let res = split
-                                                            .replace("<br>", "\n")
-                                                            .replace("\\\\n", "")
-                                                            .replace('\\', "");
-
-                                                        // Remove HTML tags using regex
-                                                        let re =
-                                                            Regex::new(r#"<([^>]+)>"#).unwrap();
-                                                        let data =
-                                                            re.replace_all(&res, "").to_string();
-
-                                                        return Ok(data);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+    /// Orders sources as listed in `order`, dropping the disabled ones.
+    /// Sources missing from `order` keep their default position after the listed ones.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn configure(&self, order: &[CheckboxPreference], lrclib_url: Option<String>) {
+        let mut available =
+            Self::available_sources(lrclib_url.as_deref().unwrap_or(LRCLIB_BASE_URL));
+
+        let mut sources = vec![];
+        for pref in order {
+            if let Some(pos) = available.iter().position(|s| s.key() == pref.key) {
+                let source = available.remove(pos);
+                if pref.enabled {
+                    sources.push(source);
                 }
             }
         }
+        sources.extend(available);
 
-        Ok(String::new())
-    }
-
-    #[tracing::instrument(level = "trace", skip(self, artists, title))]
-    async fn get_az_lyrics(&self, artists: &[String], title: &str) -> Result<String> {
-        let url = self.get_url(
-            "https://search.azlyrics.com/suggest.php?q=",
-            artists,
-            title,
-            false,
+        tracing::debug!(
+            "Lyrics sources: {:?}",
+            sources.iter().map(|s| s.key()).collect::<Vec<_>>()
         );
+        *self.sources.write().unwrap() = sources;
+    }
 
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows; U; Windows NT 5.1; it; rv:1.8.1.11) Gecko/20071127 Firefox/2.0.0.11")
-            .build()?;
-
-        let request = client.get(url).send().await?;
-        let lyrics_resp = request.text().await?;
-
-        let suggestions: Value = serde_json::from_str(&lyrics_resp)?;
-        if let Some(suggestions) = suggestions.get("songs") {
-            if let Some(suggestion) = suggestions.as_array() {
-                if let Some(suggestion) = suggestion.first() {
-                    if let Some(suggestion) = suggestion.as_object() {
-                        if let Some(suggestion) = suggestion.get("url") {
-                            let lyrics_resp = client
-                                .get(suggestion.as_str().unwrap())
-                                .send()
-                                .await?
-                                .text()
-                                .await?;
-
-                            let lyrics = lyrics_resp
-                                .split("<div class=\"ringtone\">")
-                                .nth(1)
-                                .unwrap_or("")
-                                .split("<div class=\"noprint\"")
-                                .next()
-                                .unwrap_or("");
-
-                            let index = lyrics.find("-->").unwrap_or(0) + 3;
-                            let lyrics = &lyrics.get(index..);
-                            if let Some(lyrics) = lyrics {
-                                let lyrics = lyrics
-                                    .split("</div>")
-                                    .next()
-                                    .unwrap_or("")
-                                    .replace("<br>", "\n");
-                                return Ok(lyrics.to_string());
-                            }
-                        }
-                    }
+    #[tracing::instrument(level = "trace", skip(self, cache, query))]
+    pub async fn get_lyrics(
+        &self,
+        cache: Option<&CacheHolder>,
+        query: &LyricsQuery<'_>,
+    ) -> Result<Lyrics> {
+        let sources = self.sources.read().unwrap().clone();
+        for source in sources {
+            let cache_key = format!("get_lyrics_{}_{}", source.cache_id(), query.cache_key());
+            if let Some(cached) = cache.and_then(|c| c.get::<Lyrics>(&cache_key).ok()) {
+                if !cached.is_empty() {
+                    return Ok(cached);
                 }
+                continue;
             }
-        }
 
-        // let lyrics_resp = reqwest::get(url).await?.text().await?;
-
-        Ok(String::new())
-    }
-
-    #[tracing::instrument(level = "trace", skip(self, _artists, _title))]
-    async fn get_google_lyrics(&self, _artists: Vec<String>, _title: String) -> Result<String> {
-        // let url = self.get_url("https://www.google.com/search?q=", artists, title, true);
-
-        // let client = reqwest::Client::builder()
-        //     .user_agent("Mozilla/5.0 (Windows; U; Windows NT 5.1; it; rv:1.8.1.11) Gecko/20071127 Firefox/2.0.0.11")
-        //     .build()?;
-
-        // let request = client.get(url).send().await?;
-        // let body = request.text().await?;
-        // tracing::info!("{}", body);
-
-        // let scraped = scraper::html::Html::parse_document(body.as_str());
-        // let selector = &Selector::parse("div.BNeawe.s3v9rd.AP7Wnd").unwrap();
-        // let lyrics_box = scraped.select(selector);
-
-        // let mut res = String::new();
-        // for i in lyrics_box {
-        //     let lyrics = i.text().collect::<Vec<_>>();
-        //     if let Some(lyrics) = lyrics.first() {
-        //         let re = Regex::new(r"<(.*?)>").unwrap();
-        //         let final_result = re.replace_all(lyrics, "");
-        //         res.push_str(&final_result);
-        //         res.push_str("\n")
-        //     }
-        // }
-        // return Ok(res);
-
-        Ok(String::new())
-    }
+            match source.fetch(query).await {
+                Ok(lyrics) => {
+                    if let Some(cache) = cache {
+                        let ttl = if lyrics.is_empty() {
+                            MISS_CACHE_TTL
+                        } else {
+                            CACHE_TTL
+                        };
+                        let _ = cache.set(&cache_key, &lyrics, ttl);
+                    }
 
-    #[tracing::instrument(level = "trace", skip(self, librespot, uri))]
-    fn get_spotify_lyrics(&self, librespot: &LibrespotHolder, uri: String) -> Result<String> {
-        let res = librespot.get_lyrics(format!("spotify:track:{}", uri))?;
-        let parsed: Value = serde_json::from_str(&res)?;
-        if let Some(lyrics_obj) = parsed.get("lyrics") {
-            if let Some(lines) = lyrics_obj.get("lines") {
-                if let Some(lines_arr) = lines.as_array() {
-                    let mut res = String::new();
-                    for line in lines_arr {
-                        if let Some(words) = line.get("words") {
-                            res.push_str(words.as_str().unwrap())
-                        }
+                    if !lyrics.is_empty() {
+                        return Ok(lyrics);
                     }
-                    return Ok(res);
                 }
+                Err(e) => tracing::warn!("Lyrics source {} failed: {:?}", source.key(), e),
             }
         }
 
-        Ok(String::new())
+        Err("No lyrics found".into())
     }
+}
 
-    #[tracing::instrument(level = "trace", skip(self, librespot, id, url, artists, title))]
-    pub async fn get_lyrics(
-        &self,
-        librespot: &LibrespotHolder,
-        id: String,
-        url: String,
-        artists: Vec<String>,
-        title: String,
-    ) -> Result<String> {
-        // self.get_google_lyrics(artists, title).await
-        if id.starts_with("spotify:") {
-            let res = self.get_spotify_lyrics(librespot, format!("spotify:track:{}", url));
-            if res.is_ok() {
-                return res;
-            }
-        }
+#[tracing::instrument(level = "trace", skip(title))]
+fn sanitize_title(title: &str) -> String {
+    let re1 = Regex::new(r"\((.*?)\)|\[(.*?)\]").unwrap();
+    let re2 =
+        Regex::new(r"[\u{1F300}-\u{1F3FF}\u{1F400}-\u{1F64F}\u{1F680}-\u{1F6FF}\u{274C}]").unwrap();
+    let re3 = Regex::new(r"//([^/]+)").unwrap();
 
-        let res = self.get_az_lyrics(&artists, &title).await;
-        if res.is_ok() {
-            return res;
-        }
+    let result = re1.replace_all(title, "").to_string();
+
+    let result = re2.replace_all(&result, "").to_string();
 
-        self.get_genius_lyrics(&artists, &title).await
+    let result = re3.replace_all(&result, "").to_string();
+
+    result
+        .to_lowercase()
+        .replace("official", "")
+        .replace("music", "")
+        .replace("video", "")
+}
+
+/// Search url for scraped sources, `base` followed by "artists - title"
+#[tracing::instrument(level = "trace", skip(base, artists, title))]
+fn search_url(base: &str, artists: &[String], title: &str) -> String {
+    let mut parsed_title = sanitize_title(title);
+
+    for a in artists {
+        parsed_title = parsed_title.replace(a.as_str(), "");
     }
+
+    format!("{}{} - {}", base, artists.join(", ").as_str(), parsed_title)
 }
 
 #[tracing::instrument(level = "trace", skip())]
-pub fn main() {}
+fn scraper_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder().user_agent(USER_AGENT).build()?)
+}
@@ -0,0 +1,151 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use types::{errors::Result, lyrics::Lyrics};
+
+use crate::{LyricsQuery, LyricsSource};
+
+pub const LRCLIB_BASE_URL: &str = "https://lrclib.net";
+
+const USER_AGENT: &str = "Moosync (https://github.com/Moosync/Moosync)";
+
+// Search results whose duration differs by more seconds than this belong to another edit
+const DURATION_TOLERANCE: f64 = 2f64;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LrclibTrack {
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default)]
+    instrumental: bool,
+    #[serde(default)]
+    plain_lyrics: Option<String>,
+    #[serde(default)]
+    synced_lyrics: Option<String>,
+}
+
+/// Synced lyrics from an LRCLIB compatible JSON API
+#[derive(Debug)]
+pub struct LrclibSource {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl LrclibSource {
+    #[tracing::instrument(level = "trace", skip())]
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::builder()
+                .user_agent(USER_AGENT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Exact lookup, only possible when the duration of the song is known
+    #[tracing::instrument(level = "trace", skip(self, query))]
+    async fn get(&self, query: &LyricsQuery<'_>, duration: f64) -> Result<Option<LrclibTrack>> {
+        let resp = self
+            .client
+            .get(format!("{}/api/get", self.base_url))
+            .query(&[
+                ("track_name", query.title.clone()),
+                ("artist_name", query.artists.join(", ")),
+                ("album_name", query.album.clone().unwrap_or_default()),
+                ("duration", (duration.round() as u64).to_string()),
+            ])
+            .send()
+            .await?;
+
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = resp.error_for_status()?.text().await?;
+        Ok(Some(serde_json::from_str(&body)?))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, query))]
+    async fn search(&self, query: &LyricsQuery<'_>) -> Result<Option<LrclibTrack>> {
+        let mut params = vec![("track_name", query.title.clone())];
+        if let Some(artist) = query.artists.first() {
+            params.push(("artist_name", artist.clone()));
+        }
+
+        let body = self
+            .client
+            .get(format!("{}/api/search", self.base_url))
+            .query(&params)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let tracks: Vec<LrclibTrack> = serde_json::from_str(&body)?;
+
+        let matches_duration = |track: &LrclibTrack| match (query.duration, track.duration) {
+            (Some(a), Some(b)) => (a - b).abs() <= DURATION_TOLERANCE,
+            _ => true,
+        };
+        let candidates = tracks
+            .into_iter()
+            .filter(matches_duration)
+            .collect::<Vec<_>>();
+        // Prefer synced lyrics over the best ranked plain ones
+        let pos = candidates
+            .iter()
+            .position(|t| t.synced_lyrics.is_some())
+            .unwrap_or(0);
+        Ok(candidates.into_iter().nth(pos))
+    }
+}
+
+#[async_trait]
+impl LyricsSource for LrclibSource {
+    fn key(&self) -> &'static str {
+        "lrclib"
+    }
+
+    // A miss on one server says nothing about another
+    fn cache_id(&self) -> String {
+        format!("{}_{}", self.key(), self.base_url)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, query))]
+    async fn fetch(&self, query: &LyricsQuery<'_>) -> Result<Lyrics> {
+        let mut track = None;
+        if let Some(duration) = query.duration.filter(|d| *d > 0f64) {
+            track = self.get(query, duration).await?;
+        }
+        if track.is_none() {
+            track = self.search(query).await?;
+        }
+
+        let mut lyrics = Lyrics {
+            source: self.key().into(),
+            ..Default::default()
+        };
+        if let Some(track) = track.filter(|t| !t.instrumental) {
+            lyrics.plain = track.plain_lyrics.unwrap_or_default();
+            lyrics.synced = track.synced_lyrics.filter(|s| !s.trim().is_empty());
+        }
+        Ok(lyrics)
+    }
+}
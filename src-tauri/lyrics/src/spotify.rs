@@ -0,0 +1,92 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use async_trait::async_trait;
+use serde_json::Value;
+use types::{errors::Result, lyrics::Lyrics};
+
+use crate::{LyricsQuery, LyricsSource};
+
+/// Lyrics served by Spotify for its own tracks, needs librespot to be signed in
+#[derive(Debug, Default)]
+pub struct SpotifySource;
+
+impl SpotifySource {
+    #[tracing::instrument(level = "trace", skip(resp))]
+    fn parse_response(resp: &str) -> Result<(String, Option<String>)> {
+        let parsed: Value = serde_json::from_str(resp)?;
+        let Some(lines) = parsed.pointer("/lyrics/lines").and_then(|l| l.as_array()) else {
+            return Ok((String::new(), None));
+        };
+
+        let mut plain = vec![];
+        let mut synced = vec![];
+        for line in lines {
+            let words = line.get("words").and_then(|w| w.as_str()).unwrap_or("");
+            plain.push(words.to_string());
+
+            let start_ms = line
+                .get("startTimeMs")
+                .and_then(|s| s.as_str())
+                .and_then(|s| s.parse::<u64>().ok());
+            if let Some(start_ms) = start_ms {
+                synced.push(format!(
+                    "[{:02}:{:02}.{:02}]{}",
+                    start_ms / 60_000,
+                    (start_ms / 1000) % 60,
+                    (start_ms % 1000) / 10,
+                    words
+                ));
+            }
+        }
+
+        let is_synced =
+            parsed.pointer("/lyrics/syncType").and_then(|s| s.as_str()) == Some("LINE_SYNCED");
+        let synced = if is_synced && synced.len() == plain.len() {
+            Some(synced.join("\n"))
+        } else {
+            None
+        };
+
+        Ok((plain.join("\n"), synced))
+    }
+}
+
+#[async_trait]
+impl LyricsSource for SpotifySource {
+    fn key(&self) -> &'static str {
+        "spotify"
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, query))]
+    async fn fetch(&self, query: &LyricsQuery<'_>) -> Result<Lyrics> {
+        let mut lyrics = Lyrics {
+            source: self.key().into(),
+            ..Default::default()
+        };
+
+        let Some(librespot) = query.librespot else {
+            return Ok(lyrics);
+        };
+        if !query.id.starts_with("spotify:") {
+            return Ok(lyrics);
+        }
+
+        let res = librespot.get_lyrics(format!("spotify:track:{}", query.url))?;
+        (lyrics.plain, lyrics.synced) = Self::parse_response(&res)?;
+        Ok(lyrics)
+    }
+}
@@ -0,0 +1,216 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use database::cache::CacheHolder;
+use test_utils::{mock_server, TempDb};
+use types::{errors::Result, lyrics::Lyrics, preferences::CheckboxPreference};
+
+use crate::{LrclibSource, LyricsFetcher, LyricsQuery, LyricsSource};
+
+fn query() -> LyricsQuery<'static> {
+    LyricsQuery {
+        id: "local-song".into(),
+        artists: vec!["Artist".into()],
+        title: "Song".into(),
+        album: Some("Album".into()),
+        duration: Some(200f64),
+        ..Default::default()
+    }
+}
+
+#[derive(Debug)]
+// Fails when no lyrics are given
+struct StaticSource(&'static str, Option<&'static str>);
+
+#[async_trait]
+impl LyricsSource for StaticSource {
+    fn key(&self) -> &'static str {
+        self.0
+    }
+
+    async fn fetch(&self, _: &LyricsQuery<'_>) -> Result<Lyrics> {
+        match self.1 {
+            Some(plain) => Ok(Lyrics {
+                source: self.0.into(),
+                plain: plain.to_string(),
+                synced: None,
+            }),
+            None => Err("source failed".into()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_lrclib_get() {
+    let base_url = mock_server(vec![(
+        "/api/get",
+        200,
+        r#"{"id":1,"trackName":"Song","artistName":"Artist","albumName":"Album","duration":200,"instrumental":false,"plainLyrics":"First line\nSecond line","syncedLyrics":"[00:01.00] First line\n[00:05.50] Second line"}"#,
+    )]);
+
+    let lyrics = LrclibSource::new(&base_url).fetch(&query()).await.unwrap();
+    assert_eq!(lyrics.source, "lrclib");
+    assert_eq!(lyrics.plain, "First line\nSecond line");
+    assert_eq!(
+        lyrics.synced.as_deref(),
+        Some("[00:01.00] First line\n[00:05.50] Second line")
+    );
+}
+
+#[tokio::test]
+async fn test_lrclib_search_fallback() {
+    let base_url = mock_server(vec![(
+        "/api/search",
+        200,
+        r#"[
+            {"id":1,"duration":320,"instrumental":false,"plainLyrics":"Extended mix","syncedLyrics":"[00:01.00] Extended mix"},
+            {"id":2,"duration":201,"instrumental":false,"plainLyrics":"Plain only","syncedLyrics":null},
+            {"id":3,"duration":199,"instrumental":false,"plainLyrics":"Synced","syncedLyrics":"[00:01.00] Synced"}
+        ]"#,
+    )]);
+
+    let lyrics = LrclibSource::new(&base_url).fetch(&query()).await.unwrap();
+    assert_eq!(lyrics.plain, "Synced");
+    assert_eq!(lyrics.synced.as_deref(), Some("[00:01.00] Synced"));
+
+    let base_url = mock_server(vec![("/api/search", 200, "[]")]);
+    let lyrics = LrclibSource::new(&base_url).fetch(&query()).await.unwrap();
+    assert!(lyrics.is_empty());
+}
+
+#[tokio::test]
+async fn test_empty_source_tries_next() {
+    let fetcher = LyricsFetcher::with_sources(vec![
+        Arc::new(StaticSource("empty", Some("  "))),
+        Arc::new(StaticSource("failing", None)),
+        Arc::new(StaticSource("found", Some("Lyrics"))),
+        Arc::new(StaticSource("unused", Some("Other lyrics"))),
+    ]);
+
+    let lyrics = fetcher.get_lyrics(None, &query()).await.unwrap();
+    assert_eq!(lyrics.source, "found");
+    assert_eq!(lyrics.plain, "Lyrics");
+
+    let fetcher = LyricsFetcher::with_sources(vec![Arc::new(StaticSource("empty", Some("")))]);
+    assert!(fetcher.get_lyrics(None, &query()).await.is_err());
+}
+
+#[derive(Debug)]
+// Counts how often it is asked, returns `lyrics` or nothing
+struct CountingSource(&'static str, Option<&'static str>, Arc<AtomicUsize>);
+
+#[async_trait]
+impl LyricsSource for CountingSource {
+    fn key(&self) -> &'static str {
+        self.0
+    }
+
+    async fn fetch(&self, _: &LyricsQuery<'_>) -> Result<Lyrics> {
+        self.2.fetch_add(1, Ordering::SeqCst);
+        Ok(Lyrics {
+            source: self.0.into(),
+            plain: self.1.unwrap_or_default().to_string(),
+            synced: None,
+        })
+    }
+}
+
+/// Cache in the temp dir, removed when the returned guard drops
+fn temp_cache(name: &str) -> (TempDb, CacheHolder) {
+    let db = TempDb::new(&format!("lyrics_{}", name));
+    let cache = CacheHolder::new(db.path());
+    (db, cache)
+}
+
+#[tokio::test]
+async fn test_cache_per_source() {
+    let missing_calls = Arc::new(AtomicUsize::new(0));
+    let found_calls = Arc::new(AtomicUsize::new(0));
+    let fetcher = LyricsFetcher::with_sources(vec![
+        Arc::new(CountingSource("missing", None, missing_calls.clone())),
+        Arc::new(CountingSource("found", Some("Lyrics"), found_calls.clone())),
+    ]);
+
+    let (_db, cache) = temp_cache("per_source");
+    for _ in 0..2 {
+        let lyrics = fetcher.get_lyrics(Some(&cache), &query()).await.unwrap();
+        assert_eq!(lyrics.plain, "Lyrics");
+    }
+    // Both the miss and the hit were cached under their own source
+    assert_eq!(missing_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(found_calls.load(Ordering::SeqCst), 1);
+
+    // A cached miss of one source doesn't hide lyrics of a source added later
+    let other_calls = Arc::new(AtomicUsize::new(0));
+    let fetcher = LyricsFetcher::with_sources(vec![
+        Arc::new(CountingSource("missing", None, missing_calls.clone())),
+        Arc::new(CountingSource(
+            "other",
+            Some("Other lyrics"),
+            other_calls.clone(),
+        )),
+    ]);
+    let lyrics = fetcher.get_lyrics(Some(&cache), &query()).await.unwrap();
+    assert_eq!(lyrics.plain, "Other lyrics");
+    assert_eq!(missing_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(other_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_cached_miss_per_lrclib_url() {
+    let empty_url = mock_server(vec![("/api/get", 404, "{}"), ("/api/search", 200, "[]")]);
+    let found_url = mock_server(vec![(
+        "/api/get",
+        200,
+        r#"{"id":1,"duration":200,"instrumental":false,"plainLyrics":"From the mirror","syncedLyrics":null}"#,
+    )]);
+    let order = ["lrclib", "spotify", "azlyrics", "genius"]
+        .iter()
+        .map(|key| CheckboxPreference {
+            key: key.to_string(),
+            enabled: *key == "lrclib",
+        })
+        .collect::<Vec<_>>();
+
+    let (_db, cache) = temp_cache("lrclib_url");
+    let fetcher = LyricsFetcher::new();
+
+    fetcher.configure(&order, Some(empty_url));
+    assert!(fetcher.get_lyrics(Some(&cache), &query()).await.is_err());
+
+    // The miss cached for the first server must not stick to the second
+    fetcher.configure(&order, Some(found_url));
+    let lyrics = fetcher.get_lyrics(Some(&cache), &query()).await.unwrap();
+    assert_eq!(lyrics.plain, "From the mirror");
+}
+
+#[test]
+fn test_lrclib_cache_id() {
+    assert_ne!(
+        LrclibSource::new("http://a.example").cache_id(),
+        LrclibSource::new("http://b.example").cache_id()
+    );
+    assert_eq!(
+        LrclibSource::new("http://a.example/").cache_id(),
+        LrclibSource::new("http://a.example").cache_id()
+    );
+}
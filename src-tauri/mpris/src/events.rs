@@ -0,0 +1,69 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use types::ui::player_details::RepeatModes;
+
+/// Events sent by the OS media controls.
+#[derive(Clone, PartialEq, Debug)]
+pub enum MediaControlEvent {
+    Play,
+    Pause,
+    Toggle,
+    Next,
+    Previous,
+    Stop,
+
+    /// Seek forward or backward by an undetermined amount.
+    Seek(SeekDirection),
+    /// Seek forward or backward by a certain amount.
+    SeekBy(SeekDirection, Duration),
+    /// Set the position/progress of the currently playing media item.
+    SetPosition(MediaPosition),
+    /// Sets the volume. The value is intended to be from 0.0 to 1.0.
+    /// But other values are also accepted. **It is up to the user to
+    /// set constraints on this value.**
+    /// **NOTE**: If the volume event was received and correctly handled,
+    /// the user must call `MprisHolder::set_volume`. Note that
+    /// this must be done only with the MPRIS backend.
+    SetVolume(f64),
+    /// Open the URI in the media player.
+    OpenUri(String),
+
+    /// Bring the media player's user interface to the front using any appropriate mechanism available.
+    Raise,
+    /// Shut down the media player.
+    Quit,
+
+    /// Enable or disable shuffling of the queue.
+    SetShuffle(bool),
+    /// Change the repeat mode of the player.
+    SetLoopStatus(RepeatModes),
+    /// Skip to the queue entry at the given index.
+    GoTo(usize),
+}
+
+/// An instant in a media item.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MediaPosition(pub Duration);
+
+/// The direction to seek in.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SeekDirection {
+    Forward,
+    Backward,
+}
@@ -14,7 +14,6 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-pub use souvlaki::MediaControlEvent;
 use std::{
     sync::{
         mpsc::{self, Receiver},
@@ -27,9 +26,11 @@ use souvlaki::{MediaControls, MediaMetadata, MediaPlayback, MediaPosition, Platf
 use types::{
     errors::{MoosyncError, Result},
     mpris::MprisPlayerDetails,
-    ui::player_details::PlayerState,
+    ui::player_details::{PlayerState, RepeatModes},
 };
 
+use crate::events::{self, MediaControlEvent};
+
 pub struct MprisHolder {
     controls: Mutex<MediaControls>,
     pub event_rx: Arc<Mutex<Receiver<MediaControlEvent>>>,
@@ -64,7 +65,7 @@ impl MprisHolder {
         let (event_tx, event_rx) = mpsc::channel();
         controls
             .attach(move |event| {
-                event_tx.send(event).unwrap();
+                event_tx.send(event.into()).unwrap();
             })
             .map_err(|e| MoosyncError::String(e.to_string()))?;
 
@@ -147,6 +148,59 @@ impl MprisHolder {
         self.set_playback_state(last_state)?;
         Ok(())
     }
+
+    // Volume, repeat, shuffle and the queue are only exposed through MPRIS on Linux
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_volume(&self, _volume: f64) -> Result<()> {
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_loop_status(&self, _mode: RepeatModes) -> Result<()> {
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_shuffle(&self, _shuffle: bool) -> Result<()> {
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, _tracks))]
+    pub fn set_tracklist(&self, _tracks: Vec<MprisPlayerDetails>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_current_index(&self, _index: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl From<souvlaki::MediaControlEvent> for MediaControlEvent {
+    fn from(value: souvlaki::MediaControlEvent) -> Self {
+        let direction = |dir: souvlaki::SeekDirection| match dir {
+            souvlaki::SeekDirection::Forward => events::SeekDirection::Forward,
+            souvlaki::SeekDirection::Backward => events::SeekDirection::Backward,
+        };
+
+        match value {
+            souvlaki::MediaControlEvent::Play => Self::Play,
+            souvlaki::MediaControlEvent::Pause => Self::Pause,
+            souvlaki::MediaControlEvent::Toggle => Self::Toggle,
+            souvlaki::MediaControlEvent::Next => Self::Next,
+            souvlaki::MediaControlEvent::Previous => Self::Previous,
+            souvlaki::MediaControlEvent::Stop => Self::Stop,
+            souvlaki::MediaControlEvent::Seek(dir) => Self::Seek(direction(dir)),
+            souvlaki::MediaControlEvent::SeekBy(dir, by) => Self::SeekBy(direction(dir), by),
+            souvlaki::MediaControlEvent::SetPosition(pos) => {
+                Self::SetPosition(events::MediaPosition(pos.0))
+            }
+            souvlaki::MediaControlEvent::SetVolume(volume) => Self::SetVolume(volume),
+            souvlaki::MediaControlEvent::OpenUri(uri) => Self::OpenUri(uri),
+            souvlaki::MediaControlEvent::Raise => Self::Raise,
+            souvlaki::MediaControlEvent::Quit => Self::Quit,
+        }
+    }
 }
 
 #[cfg(target_os = "windows")]
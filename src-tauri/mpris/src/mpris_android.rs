@@ -26,7 +26,13 @@ use std::{
 use serde_json::Value;
 use tauri::{AppHandle, Listener};
 use tauri_plugin_audioplayer::AudioplayerExt;
-use types::{errors::Result, mpris::MprisPlayerDetails, ui::player_details::PlayerState};
+use types::{
+    errors::Result,
+    mpris::MprisPlayerDetails,
+    ui::player_details::{PlayerState, RepeatModes},
+};
+
+use crate::events::{MediaControlEvent, MediaPosition};
 
 pub struct MprisHolder {
     last_duration: Mutex<u64>,
@@ -125,47 +131,28 @@ impl MprisHolder {
         }
         Ok(())
     }
-}
 
-/// Events sent by the OS media controls.
-#[derive(Clone, PartialEq, Debug)]
-pub enum MediaControlEvent {
-    Play,
-    Pause,
-    Toggle,
-    Next,
-    Previous,
-    Stop,
-
-    /// Seek forward or backward by an undetermined amount.
-    Seek(SeekDirection),
-    /// Seek forward or backward by a certain amount.
-    SeekBy(SeekDirection, Duration),
-    /// Set the position/progress of the currently playing media item.
-    SetPosition(MediaPosition),
-    /// Sets the volume. The value is intended to be from 0.0 to 1.0.
-    /// But other values are also accepted. **It is up to the user to
-    /// set constraints on this value.**
-    /// **NOTE**: If the volume event was received and correctly handled,
-    /// the user must call [`MediaControls::set_volume`]. Note that
-    /// this must be done only with the MPRIS backend.
-    SetVolume(f64),
-    /// Open the URI in the media player.
-    OpenUri(String),
-
-    /// Bring the media player's user interface to the front using any appropriate mechanism available.
-    Raise,
-    /// Shut down the media player.
-    Quit,
-}
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_volume(&self, _volume: f64) -> Result<()> {
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_loop_status(&self, _mode: RepeatModes) -> Result<()> {
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_shuffle(&self, _shuffle: bool) -> Result<()> {
+        Ok(())
+    }
 
-/// An instant in a media item.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct MediaPosition(pub Duration);
+    #[tracing::instrument(level = "trace", skip(self, _tracks))]
+    pub fn set_tracklist(&self, _tracks: Vec<MprisPlayerDetails>) -> Result<()> {
+        Ok(())
+    }
 
-/// The direction to seek in.
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-pub enum SeekDirection {
-    Forward,
-    Backward,
+    pub fn set_current_index(&self, _index: usize) -> Result<()> {
+        Ok(())
+    }
 }
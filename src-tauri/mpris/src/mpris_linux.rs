@@ -0,0 +1,718 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use types::{
+    errors::{MoosyncError, Result},
+    mpris::MprisPlayerDetails,
+    ui::player_details::{PlayerState, RepeatModes},
+};
+use zbus::{
+    connection, fdo, interface,
+    object_server::SignalEmitter,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value},
+    Connection,
+};
+
+use crate::events::{MediaControlEvent, MediaPosition, SeekDirection};
+
+const BUS_NAME: &str = "org.mpris.MediaPlayer2.moosync";
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+// Position reports that differ from the extrapolated position by more than this are seeks
+const SEEK_THRESHOLD: Duration = Duration::from_millis(1500);
+
+#[derive(Debug)]
+struct PlayerData {
+    metadata: Option<MprisPlayerDetails>,
+    state: PlayerState,
+    position: Duration,
+    position_updated: Instant,
+    volume: f64,
+    repeat: RepeatModes,
+    shuffle: bool,
+    tracks: Vec<MprisPlayerDetails>,
+    current_index: usize,
+}
+
+impl Default for PlayerData {
+    fn default() -> Self {
+        Self {
+            metadata: None,
+            state: PlayerState::Stopped,
+            position: Duration::ZERO,
+            position_updated: Instant::now(),
+            volume: 1f64,
+            repeat: RepeatModes::None,
+            shuffle: false,
+            tracks: vec![],
+            current_index: 0,
+        }
+    }
+}
+
+impl PlayerData {
+    fn current_position(&self) -> Duration {
+        if self.state == PlayerState::Playing {
+            self.position + self.position_updated.elapsed()
+        } else {
+            self.position
+        }
+    }
+
+    fn current_track(&self) -> OwnedObjectPath {
+        let id = self.metadata.as_ref().and_then(|m| m.id.as_deref());
+        let index = self
+            .tracks
+            .get(self.current_index)
+            .filter(|t| t.id.as_deref() == id)
+            .map(|_| self.current_index);
+        track_id(index, id)
+    }
+
+    fn track_ids(&self) -> Vec<OwnedObjectPath> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| track_id(Some(i), t.id.as_deref()))
+            .collect()
+    }
+
+    fn track_position(&self, path: &str) -> Option<usize> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .position(|(i, t)| track_id(Some(i), t.id.as_deref()).as_str() == path)
+    }
+}
+
+enum Signal {
+    Metadata,
+    PlaybackStatus,
+    Seeked(i64),
+    Volume,
+    LoopStatus,
+    Shuffle,
+    TrackList,
+}
+
+// Song ids may contain characters which aren't allowed in object paths.
+// The queue may hold the same song more than once, so the queue index is part of the path
+fn track_id(index: Option<usize>, id: Option<&str>) -> OwnedObjectPath {
+    let path = match id {
+        Some(id) if !id.is_empty() => {
+            let id = id.bytes().map(|b| format!("{:02x}", b)).collect::<String>();
+            match index {
+                Some(index) => format!("/app/moosync/track/{}_{}", index, id),
+                None => format!("/app/moosync/track/{}", id),
+            }
+        }
+        _ => NO_TRACK.to_string(),
+    };
+    OwnedObjectPath::try_from(path).unwrap()
+}
+
+fn art_url(thumbnail: &str) -> Option<String> {
+    if thumbnail.contains("://") {
+        return Some(thumbnail.to_string());
+    }
+    // Paths may contain spaces and other characters which need escaping in a URL
+    url::Url::from_file_path(thumbnail)
+        .ok()
+        .map(|u| u.to_string())
+}
+
+fn owned<'a>(value: impl Into<Value<'a>>) -> OwnedValue {
+    value.into().try_to_owned().unwrap()
+}
+
+fn metadata_map(
+    track: OwnedObjectPath,
+    metadata: Option<&MprisPlayerDetails>,
+) -> HashMap<String, OwnedValue> {
+    let mut map = HashMap::new();
+    map.insert("mpris:trackid".into(), owned(track.into_inner()));
+    let Some(metadata) = metadata else {
+        return map;
+    };
+
+    if let Some(duration) = metadata.duration {
+        map.insert(
+            "mpris:length".into(),
+            owned((duration * 1_000_000f64) as i64),
+        );
+    }
+    if let Some(art_url) = metadata.thumbnail.as_deref().and_then(art_url) {
+        map.insert("mpris:artUrl".into(), owned(art_url));
+    }
+    if let Some(title) = &metadata.title {
+        map.insert("xesam:title".into(), owned(title.clone()));
+    }
+    if let Some(album) = &metadata.album_name {
+        map.insert("xesam:album".into(), owned(album.clone()));
+    }
+    if let Some(artist) = &metadata.artist_name {
+        map.insert("xesam:artist".into(), owned(vec![artist.clone()]));
+    }
+    if let Some(album_artist) = &metadata.album_artist {
+        map.insert(
+            "xesam:albumArtist".into(),
+            owned(vec![album_artist.clone()]),
+        );
+    }
+    if let Some(genres) = &metadata.genres {
+        map.insert("xesam:genre".into(), owned(genres.clone()));
+    }
+    map
+}
+
+fn send_event(events: &Sender<MediaControlEvent>, event: MediaControlEvent) {
+    if let Err(e) = events.send(event) {
+        tracing::error!("Failed to send media control event: {:?}", e);
+    }
+}
+
+struct RootInterface {
+    events: Sender<MediaControlEvent>,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl RootInterface {
+    fn raise(&self) {
+        send_event(&self.events, MediaControlEvent::Raise);
+    }
+
+    fn quit(&self) {
+        send_event(&self.events, MediaControlEvent::Quit);
+    }
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> String {
+        "Moosync".into()
+    }
+
+    #[zbus(property)]
+    fn desktop_entry(&self) -> String {
+        "moosync".into()
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        vec!["file".into(), "http".into(), "https".into()]
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        vec![
+            "audio/mpeg".into(),
+            "audio/flac".into(),
+            "audio/ogg".into(),
+            "audio/wav".into(),
+            "audio/mp4".into(),
+            "audio/webm".into(),
+        ]
+    }
+}
+
+struct PlayerInterface {
+    data: Arc<Mutex<PlayerData>>,
+    events: Sender<MediaControlEvent>,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl PlayerInterface {
+    fn next(&self) {
+        send_event(&self.events, MediaControlEvent::Next);
+    }
+
+    fn previous(&self) {
+        send_event(&self.events, MediaControlEvent::Previous);
+    }
+
+    fn pause(&self) {
+        send_event(&self.events, MediaControlEvent::Pause);
+    }
+
+    fn play_pause(&self) {
+        send_event(&self.events, MediaControlEvent::Toggle);
+    }
+
+    fn stop(&self) {
+        send_event(&self.events, MediaControlEvent::Stop);
+    }
+
+    fn play(&self) {
+        send_event(&self.events, MediaControlEvent::Play);
+    }
+
+    fn seek(&self, offset: i64) {
+        let direction = if offset < 0 {
+            SeekDirection::Backward
+        } else {
+            SeekDirection::Forward
+        };
+        send_event(
+            &self.events,
+            MediaControlEvent::SeekBy(direction, Duration::from_micros(offset.unsigned_abs())),
+        );
+    }
+
+    fn set_position(&self, track_id: ObjectPath<'_>, position: i64) {
+        if position < 0 {
+            return;
+        }
+
+        let data = self.data.lock().unwrap();
+        if data.current_track().as_str() != track_id.as_str() {
+            tracing::debug!("Ignoring SetPosition for stale track {}", track_id);
+            return;
+        }
+
+        let position = Duration::from_micros(position as u64);
+        let length = data
+            .metadata
+            .as_ref()
+            .and_then(|m| m.duration)
+            .map(Duration::from_secs_f64);
+        if length.map(|l| position > l).unwrap_or(false) {
+            return;
+        }
+        drop(data);
+
+        send_event(
+            &self.events,
+            MediaControlEvent::SetPosition(MediaPosition(position)),
+        );
+    }
+
+    fn open_uri(&self, uri: String) {
+        send_event(&self.events, MediaControlEvent::OpenUri(uri));
+    }
+
+    #[zbus(signal)]
+    async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> String {
+        match self.data.lock().unwrap().state {
+            PlayerState::Playing => "Playing",
+            PlayerState::Paused | PlayerState::Loading => "Paused",
+            PlayerState::Stopped => "Stopped",
+        }
+        .into()
+    }
+
+    // The queue always wraps around, so "None" is reported as "Playlist"
+    #[zbus(property)]
+    fn loop_status(&self) -> String {
+        match self.data.lock().unwrap().repeat {
+            RepeatModes::None => "Playlist",
+            RepeatModes::Once | RepeatModes::Loop => "Track",
+        }
+        .into()
+    }
+
+    #[zbus(property)]
+    fn set_loop_status(&mut self, value: String) -> fdo::Result<()> {
+        let mode = match value.as_str() {
+            "None" | "Playlist" => RepeatModes::None,
+            "Track" => RepeatModes::Loop,
+            _ => {
+                return Err(fdo::Error::InvalidArgs(format!(
+                    "Invalid loop status {}",
+                    value
+                )))
+            }
+        };
+
+        self.data.lock().unwrap().repeat = mode;
+        send_event(&self.events, MediaControlEvent::SetLoopStatus(mode));
+        Ok(())
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        1f64
+    }
+
+    #[zbus(property)]
+    fn set_rate(&mut self, _value: f64) {}
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        1f64
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        1f64
+    }
+
+    #[zbus(property)]
+    fn shuffle(&self) -> bool {
+        self.data.lock().unwrap().shuffle
+    }
+
+    #[zbus(property)]
+    fn set_shuffle(&mut self, value: bool) {
+        self.data.lock().unwrap().shuffle = value;
+        send_event(&self.events, MediaControlEvent::SetShuffle(value));
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        let data = self.data.lock().unwrap();
+        metadata_map(data.current_track(), data.metadata.as_ref())
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        self.data.lock().unwrap().volume
+    }
+
+    #[zbus(property)]
+    fn set_volume(&mut self, value: f64) {
+        let volume = value.clamp(0f64, 1f64);
+        self.data.lock().unwrap().volume = volume;
+        send_event(&self.events, MediaControlEvent::SetVolume(volume));
+    }
+
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> i64 {
+        self.data.lock().unwrap().current_position().as_micros() as i64
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        self.data.lock().unwrap().metadata.is_some()
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        self.data.lock().unwrap().metadata.is_some()
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        self.data.lock().unwrap().metadata.is_some()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
+
+struct TrackListInterface {
+    data: Arc<Mutex<PlayerData>>,
+    events: Sender<MediaControlEvent>,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.TrackList")]
+impl TrackListInterface {
+    fn get_tracks_metadata(
+        &self,
+        track_ids: Vec<OwnedObjectPath>,
+    ) -> Vec<HashMap<String, OwnedValue>> {
+        let data = self.data.lock().unwrap();
+        track_ids
+            .into_iter()
+            .filter_map(|id| {
+                data.track_position(id.as_str())
+                    .map(|i| metadata_map(id, data.tracks.get(i)))
+            })
+            .collect()
+    }
+
+    // The queue can't be edited over MPRIS (CanEditTracks is false)
+    fn add_track(&self, _uri: String, _after_track: ObjectPath<'_>, _set_as_current: bool) {}
+
+    fn remove_track(&self, _track_id: ObjectPath<'_>) {}
+
+    fn go_to(&self, track_id: ObjectPath<'_>) {
+        let index = self.data.lock().unwrap().track_position(track_id.as_str());
+
+        if let Some(index) = index {
+            send_event(&self.events, MediaControlEvent::GoTo(index));
+        }
+    }
+
+    #[zbus(signal)]
+    async fn track_list_replaced(
+        emitter: &SignalEmitter<'_>,
+        tracks: Vec<OwnedObjectPath>,
+        current_track: OwnedObjectPath,
+    ) -> zbus::Result<()>;
+
+    #[zbus(property(emits_changed_signal = "invalidates"))]
+    fn tracks(&self) -> Vec<OwnedObjectPath> {
+        self.data.lock().unwrap().track_ids()
+    }
+
+    #[zbus(property)]
+    fn can_edit_tracks(&self) -> bool {
+        false
+    }
+}
+
+pub struct MprisHolder {
+    data: Arc<Mutex<PlayerData>>,
+    signal_tx: UnboundedSender<Signal>,
+    pub event_rx: Arc<Mutex<Receiver<MediaControlEvent>>>,
+}
+
+impl MprisHolder {
+    #[tracing::instrument(level = "trace", skip())]
+    pub fn new() -> Result<MprisHolder> {
+        let data = Arc::new(Mutex::new(PlayerData::default()));
+        let (event_tx, event_rx) = mpsc::channel();
+        let (signal_tx, signal_rx) = unbounded_channel();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        let server_data = data.clone();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+
+            runtime.block_on(async move {
+                let connection = match Self::connect(server_data.clone(), event_tx).await {
+                    Ok(connection) => {
+                        ready_tx.send(Ok(())).unwrap();
+                        connection
+                    }
+                    Err(e) => {
+                        ready_tx.send(Err(e)).unwrap();
+                        return;
+                    }
+                };
+
+                Self::emit_signals(connection, server_data, signal_rx).await;
+            });
+        });
+
+        ready_rx
+            .recv()
+            .map_err(|e| MoosyncError::String(e.to_string()))??;
+
+        Ok(MprisHolder {
+            data,
+            signal_tx,
+            event_rx: Arc::new(Mutex::new(event_rx)),
+        })
+    }
+
+    async fn connect(
+        data: Arc<Mutex<PlayerData>>,
+        events: Sender<MediaControlEvent>,
+    ) -> Result<Connection> {
+        let build = || -> zbus::Result<connection::Builder<'static>> {
+            connection::Builder::session()?
+                .name(BUS_NAME)?
+                .serve_at(
+                    OBJECT_PATH,
+                    RootInterface {
+                        events: events.clone(),
+                    },
+                )?
+                .serve_at(
+                    OBJECT_PATH,
+                    PlayerInterface {
+                        data: data.clone(),
+                        events: events.clone(),
+                    },
+                )?
+                .serve_at(
+                    OBJECT_PATH,
+                    TrackListInterface {
+                        data: data.clone(),
+                        events: events.clone(),
+                    },
+                )
+        };
+
+        let connection = build()
+            .map_err(|e| MoosyncError::String(e.to_string()))?
+            .build()
+            .await
+            .map_err(|e| MoosyncError::String(e.to_string()))?;
+        Ok(connection)
+    }
+
+    async fn emit_signals(
+        connection: Connection,
+        data: Arc<Mutex<PlayerData>>,
+        mut signal_rx: UnboundedReceiver<Signal>,
+    ) {
+        let object_server = connection.object_server();
+        let (player, tracklist) = match (
+            object_server
+                .interface::<_, PlayerInterface>(OBJECT_PATH)
+                .await,
+            object_server
+                .interface::<_, TrackListInterface>(OBJECT_PATH)
+                .await,
+        ) {
+            (Ok(player), Ok(tracklist)) => (player, tracklist),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::error!("Failed to get mpris interfaces: {:?}", e);
+                return;
+            }
+        };
+
+        while let Some(signal) = signal_rx.recv().await {
+            let emitter = player.signal_emitter();
+            let iface = player.get().await;
+            let res = match signal {
+                Signal::Metadata => iface.metadata_changed(emitter).await,
+                Signal::PlaybackStatus => iface.playback_status_changed(emitter).await,
+                Signal::Seeked(position) => PlayerInterface::seeked(emitter, position).await,
+                Signal::Volume => iface.volume_changed(emitter).await,
+                Signal::LoopStatus => iface.loop_status_changed(emitter).await,
+                Signal::Shuffle => iface.shuffle_changed(emitter).await,
+                Signal::TrackList => {
+                    let (tracks, current) = {
+                        let data = data.lock().unwrap();
+                        (data.track_ids(), data.current_track())
+                    };
+                    TrackListInterface::track_list_replaced(
+                        tracklist.signal_emitter(),
+                        tracks,
+                        current,
+                    )
+                    .await
+                }
+            };
+
+            if let Err(e) = res {
+                tracing::error!("Failed to emit mpris signal: {:?}", e);
+            }
+        }
+    }
+
+    fn emit(&self, signal: Signal) -> Result<()> {
+        self.signal_tx
+            .send(signal)
+            .map_err(|e| MoosyncError::String(e.to_string()))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, metadata))]
+    pub fn set_metadata(&self, metadata: MprisPlayerDetails) -> Result<()> {
+        {
+            let mut data = self.data.lock().unwrap();
+            data.metadata = Some(metadata);
+            data.position = Duration::ZERO;
+            data.position_updated = Instant::now();
+        }
+        self.emit(Signal::Metadata)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, state))]
+    pub fn set_playback_state(&self, state: PlayerState) -> Result<()> {
+        {
+            let mut data = self.data.lock().unwrap();
+            data.position = data.current_position();
+            data.position_updated = Instant::now();
+            data.state = state;
+        }
+        self.emit(Signal::PlaybackStatus)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, duration))]
+    pub fn set_position(&self, duration: f64) -> Result<()> {
+        let position = Duration::from_secs_f64(duration.max(0f64));
+        let seeked = {
+            let mut data = self.data.lock().unwrap();
+            let expected = data.current_position();
+            data.position = position;
+            data.position_updated = Instant::now();
+
+            expected.abs_diff(position) > SEEK_THRESHOLD
+        };
+
+        if seeked {
+            self.emit(Signal::Seeked(position.as_micros() as i64))?;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_volume(&self, volume: f64) -> Result<()> {
+        self.data.lock().unwrap().volume = volume.clamp(0f64, 1f64);
+        self.emit(Signal::Volume)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_loop_status(&self, mode: RepeatModes) -> Result<()> {
+        self.data.lock().unwrap().repeat = mode;
+        self.emit(Signal::LoopStatus)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_shuffle(&self, shuffle: bool) -> Result<()> {
+        self.data.lock().unwrap().shuffle = shuffle;
+        self.emit(Signal::Shuffle)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, tracks))]
+    pub fn set_tracklist(&self, tracks: Vec<MprisPlayerDetails>) -> Result<()> {
+        self.data.lock().unwrap().tracks = tracks;
+        self.emit(Signal::TrackList)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_current_index(&self, index: usize) -> Result<()> {
+        self.data.lock().unwrap().current_index = index;
+        Ok(())
+    }
+}
@@ -15,3 +15,6 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod preferences;
+
+#[cfg(test)]
+mod test;
@@ -18,8 +18,10 @@ use std::{
     fmt::Debug,
     fs::{self, File},
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::OnceLock,
     thread,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use std::sync::Mutex;
@@ -41,6 +43,73 @@ use whoami;
 use types::errors::{MoosyncError, Result};
 
 const SCHEMA: &str = include_str!("./schema.json");
+const CONFIG_VERSION: usize = MIGRATIONS.len();
+
+type Migration = fn(&mut Value);
+
+// Index n migrates a config at version n to version n + 1
+const MIGRATIONS: &[Migration] = &[
+    // The spotify toggle was described as "enabled" while everything reads "enable"
+    |prefs| rename_key(prefs, "prefs.spotify.enabled", "prefs.spotify.enable"),
+];
+
+#[tracing::instrument(level = "trace", skip(prefs))]
+fn rename_key(prefs: &mut Value, from: &str, to: &str) {
+    if let Ok(Some(value)) = prefs.dot_take::<Value>(from) {
+        let existing: Option<Value> = prefs.dot_get(to).unwrap_or_default();
+        if existing.is_none() {
+            let _ = prefs.dot_set(to, value);
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn validator() -> &'static Validator {
+    static VALIDATOR: OnceLock<Validator> = OnceLock::new();
+    VALIDATOR.get_or_init(|| {
+        let schema = serde_json::from_str(SCHEMA).unwrap();
+        match jsonschema::validator_for(&schema) {
+            Ok(s) => s,
+            Err(e) => panic!("{}: {}", e, e.instance_path),
+        }
+    })
+}
+
+/// Removes the value at the JSON pointer `path` from its parent object or array.
+/// Top level values like "prefs" itself are never removed.
+#[tracing::instrument(level = "trace", skip(prefs))]
+fn remove_pointer(prefs: &mut Value, path: &str) -> bool {
+    let Some((parent, leaf)) = path.rsplit_once('/') else {
+        return false;
+    };
+    if parent.is_empty() {
+        return false;
+    }
+
+    let leaf = leaf.replace("~1", "/").replace("~0", "~");
+    match prefs.pointer_mut(parent) {
+        Some(Value::Object(map)) => map.remove(&leaf).is_some(),
+        Some(Value::Array(items)) => match leaf.parse::<usize>() {
+            Ok(index) if index < items.len() => {
+                items.remove(index);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(path))]
+fn backup_config(path: &Path) -> Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let backup_path = path.with_extension(format!("json.{}.bak", timestamp));
+    fs::copy(path, &backup_path)?;
+    Ok(backup_path)
+}
 
 #[derive(Debug)]
 pub struct PreferenceConfig {
@@ -92,18 +161,7 @@ impl PreferenceConfig {
         #[cfg(target_os = "android")]
         let secret = ChaCha20Poly1305::generate_key(&mut OsRng);
 
-        let mut config_file = File::open(config_file_path.clone())?;
-        let mut prefs = String::new();
-        config_file.read_to_string(&mut prefs)?;
-
-        let prefs = serde_json::from_str(&prefs).unwrap_or_default();
-
-        // let schema = serde_json::from_str(SCHEMA).unwrap();
-        // let schema = match jsonschema::validator_for(&schema) {
-        //     Ok(s) => s,
-        //     Err(e) => panic!("{}: {}", e, e.instance_path),
-        // };
-        // schema.validate(&prefs)?;
+        let prefs = Self::read_config(&config_file_path)?;
 
         let (sender, receiver) = bounded(1);
 
@@ -116,6 +174,81 @@ impl PreferenceConfig {
         })
     }
 
+    /// Reads the config at `path`, migrating it to the current version and dropping
+    /// any values which don't match the schema. The original file is backed up
+    /// before anything in it is discarded.
+    #[tracing::instrument(level = "trace", skip(path))]
+    pub(crate) fn read_config(path: &Path) -> Result<Value> {
+        let mut config_file = File::open(path)?;
+        let mut contents = String::new();
+        config_file.read_to_string(&mut contents)?;
+
+        let mut prefs: Value = match serde_json::from_str(&contents) {
+            Ok(prefs) => prefs,
+            Err(e) => {
+                let backup_path = backup_config(path)?;
+                tracing::error!(
+                    "Failed to parse config, backed up to {:?}: {:?}",
+                    backup_path,
+                    e
+                );
+                serde_json::json!({ "prefs": {} })
+            }
+        };
+
+        if !prefs.get("prefs").map(|p| p.is_object()).unwrap_or(false) {
+            prefs["prefs"] = serde_json::json!({});
+        }
+
+        let version = prefs
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default() as usize;
+        for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            tracing::info!("Migrating config from version {} to {}", i, i + 1);
+            migration(&mut prefs);
+        }
+        prefs["version"] = CONFIG_VERSION.into();
+
+        let mut invalid_paths = validator()
+            .iter_errors(&prefs)
+            .map(|e| {
+                tracing::warn!("Invalid preference: {} ({})", e, e.instance_path);
+                e.instance_path.to_string()
+            })
+            .collect::<Vec<_>>();
+
+        if !invalid_paths.is_empty() {
+            let backup_path = backup_config(path)?;
+            tracing::warn!(
+                "Dropping invalid preferences, backed up to {:?}",
+                backup_path
+            );
+
+            // Remove later array items first so earlier indices stay valid
+            invalid_paths.sort_by(|a, b| {
+                let segments = |p: &str| {
+                    p.split('/')
+                        .map(|s| (s.parse::<usize>().ok(), s.to_string()))
+                        .collect::<Vec<_>>()
+                };
+                segments(b).cmp(&segments(a))
+            });
+            invalid_paths.dedup();
+            for path in invalid_paths {
+                if !remove_pointer(&mut prefs, &path) {
+                    tracing::warn!("Keeping invalid preference at {:?}", path);
+                }
+            }
+        }
+
+        let mut config_file = File::create(path)?;
+        config_file.write_all(&serde_json::to_vec(&prefs)?)?;
+        config_file.flush()?;
+
+        Ok(prefs)
+    }
+
     #[tracing::instrument(level = "trace", skip(self, key))]
     pub fn load_selective<T>(&self, key: String) -> Result<T>
     where
@@ -159,12 +292,7 @@ impl PreferenceConfig {
             {
                 let mut prefs_clone = prefs.clone();
                 prefs_clone.dot_set(key.as_str(), &value).unwrap();
-                // let schema = serde_json::from_str(SCHEMA).unwrap();
-                // let schema = match jsonschema::validator_for(&schema) {
-                //     Ok(s) => s,
-                //     Err(e) => panic!("{}: {}", e, e.instance_path),
-                // };
-                // schema.validate(&prefs_clone)?;
+                validator().validate(&prefs_clone)?;
             }
             prefs.dot_set(key.as_str(), &value).unwrap();
         }
@@ -218,10 +346,17 @@ impl PreferenceConfig {
         #[cfg(not(any(target_os = "android", target_os = "ios")))]
         {
             let data: String = self.load_selective(key.clone())?;
-            let mut split = data.split(':');
-            let nonce = split.next().unwrap();
-            let nonce = GenericArray::clone_from_slice(&hex::decode(nonce).unwrap()[0..12]);
-            let ciphertext = hex::decode(split.next().unwrap()).unwrap();
+            // Values stored in plain text are rejected instead of decrypted
+            let Some((nonce, ciphertext)) = data.split_once(':') else {
+                return Err(format!("{} is not stored encrypted", key).into());
+            };
+            let nonce = hex::decode(nonce).map_err(|e| MoosyncError::String(e.to_string()))?;
+            if nonce.len() < 12 {
+                return Err(format!("{} is not stored encrypted", key).into());
+            }
+            let nonce = GenericArray::clone_from_slice(&nonce[0..12]);
+            let ciphertext =
+                hex::decode(ciphertext).map_err(|e| MoosyncError::String(e.to_string()))?;
 
             let secret = self.secret.lock().unwrap();
             let cipher = ChaCha20Poly1305::new(&secret);
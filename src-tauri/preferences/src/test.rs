@@ -0,0 +1,128 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fs, path::PathBuf};
+
+use serde_json::{json, Value};
+use test_utils::TempDir;
+
+use crate::preferences::PreferenceConfig;
+
+/// Writes `config` into a fresh directory under the system temp dir, which
+/// is removed when the returned guard drops
+fn write_config(name: &str, config: &str) -> (TempDir, PathBuf) {
+    let dir = TempDir::new(&format!("preferences_{}", name));
+    let path = dir.join("config.json");
+    fs::write(&path, config).unwrap();
+    (dir, path)
+}
+
+fn backups(path: &PathBuf) -> usize {
+    fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".bak"))
+        .count()
+}
+
+#[test]
+fn test_migrates_unversioned_config() {
+    let (_dir, path) = write_config(
+        "migrate",
+        r#"{"prefs": {"spotify": {"enabled": [{"key": "enable", "enabled": true}]}}}"#,
+    );
+
+    let prefs = PreferenceConfig::read_config(&path).unwrap();
+    assert_eq!(prefs["version"], json!(1));
+    assert_eq!(
+        prefs["prefs"]["spotify"],
+        json!({"enable": [{"key": "enable", "enabled": true}]})
+    );
+
+    // The migrated config is written back
+    let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written, prefs);
+    assert_eq!(backups(&path), 0);
+}
+
+#[test]
+fn test_migration_keeps_existing_value() {
+    let (_dir, path) = write_config(
+        "migrate_existing",
+        r#"{"prefs": {"spotify": {
+            "enabled": [{"key": "enable", "enabled": true}],
+            "enable": [{"key": "enable", "enabled": false}]
+        }}}"#,
+    );
+
+    let prefs = PreferenceConfig::read_config(&path).unwrap();
+    assert_eq!(
+        prefs["prefs"]["spotify"],
+        json!({"enable": [{"key": "enable", "enabled": false}]})
+    );
+}
+
+#[test]
+fn test_skips_applied_migrations() {
+    let config = r#"{"version": 1, "prefs": {"spotify": {"enabled": "kept"}}}"#;
+    let (_dir, path) = write_config("migrate_skip", config);
+
+    let prefs = PreferenceConfig::read_config(&path).unwrap();
+    assert_eq!(prefs["prefs"]["spotify"], json!({"enabled": "kept"}));
+}
+
+#[test]
+fn test_strips_only_invalid_leaves() {
+    let (_dir, path) = write_config(
+        "invalid",
+        r#"{"version": 1, "prefs": {
+            "scan_threads": "many",
+            "artist_splitter": ";",
+            "spotify": {"username": 1, "password": "secret"},
+            "music_paths": ["/a", 2, "/b", 3],
+            "system_settings": [{"key": "auto_startup"}, {"key": "jukebox_mode", "enabled": true}]
+        }}"#,
+    );
+
+    let prefs = PreferenceConfig::read_config(&path).unwrap();
+    assert_eq!(
+        prefs["prefs"],
+        json!({
+            "artist_splitter": ";",
+            "spotify": {"password": "secret"},
+            "music_paths": ["/a", "/b"],
+            "system_settings": [{"key": "jukebox_mode", "enabled": true}]
+        })
+    );
+    assert_eq!(backups(&path), 1);
+}
+
+#[test]
+fn test_never_clears_root() {
+    let (_dir, path) = write_config("root", r#"{"version": "one", "prefs": []}"#);
+
+    let prefs = PreferenceConfig::read_config(&path).unwrap();
+    assert_eq!(prefs, json!({"version": 1, "prefs": {}}));
+}
+
+#[test]
+fn test_recovers_unparsable_config() {
+    let (_dir, path) = write_config("unparsable", "{\"prefs\": ");
+
+    let prefs = PreferenceConfig::read_config(&path).unwrap();
+    assert_eq!(prefs, json!({"version": 1, "prefs": {}}));
+    assert_eq!(backups(&path), 1);
+}
@@ -0,0 +1,253 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    fs::{self, File},
+    io::{self, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+use stream_download::storage::StorageProvider;
+use tracing::{debug, error, trace};
+use types::errors::Result;
+
+const PARTIAL_EXTENSION: &str = "part";
+
+/// Content-addressed on-disk cache for remote audio streams.
+/// Entries are keyed by the hash of a caller supplied key (the song id, or the source url
+/// when there is none) and evicted in least-recently-played order
+/// once the total size exceeds the configured limit.
+#[derive(Debug)]
+pub struct AudioCache {
+    dir: PathBuf,
+    max_size: AtomicU64,
+}
+
+impl AudioCache {
+    #[tracing::instrument(level = "trace", skip())]
+    pub fn new(dir: PathBuf, max_size: u64) -> Self {
+        if !dir.exists() {
+            fs::create_dir_all(dir.clone()).unwrap();
+        }
+
+        // Partial downloads from a previous session can never be completed
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path
+                    .extension()
+                    .map(|e| e == PARTIAL_EXTENSION)
+                    .unwrap_or(false)
+                {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
+
+        Self {
+            dir,
+            max_size: AtomicU64::new(max_size),
+        }
+    }
+
+    fn hash(key: &str) -> String {
+        blake3::hash(key.as_bytes()).to_hex().to_string()
+    }
+
+    /// Returns the cached file for `key` if it has been fully downloaded before,
+    /// marking it as recently used.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get(&self, key: &str) -> Option<PathBuf> {
+        let path = self.dir.join(Self::hash(key));
+        if !path.exists() {
+            return None;
+        }
+
+        if let Ok(file) = File::options().write(true).open(&path) {
+            if let Err(e) = file.set_modified(SystemTime::now()) {
+                error!("Failed to update cache entry access time: {:?}", e);
+            }
+        }
+        Some(path)
+    }
+
+    /// Storage provider which persists a stream under `key` once it completes.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn storage(self: &Arc<Self>, key: &str) -> CacheStorageProvider {
+        let key = Self::hash(key);
+        CacheStorageProvider {
+            cache: self.clone(),
+            partial_path: self.dir.join(format!("{}.{}", key, PARTIAL_EXTENSION)),
+            final_path: self.dir.join(key),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_max_size(&self, max_size: u64) {
+        self.max_size.store(max_size, Ordering::Relaxed);
+        if let Err(e) = self.evict() {
+            error!("Failed to evict audio cache: {:?}", e);
+        }
+    }
+
+    /// Removes the least recently used entries until the cache fits in its size limit.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn evict(&self) -> Result<()> {
+        let max_size = self.max_size.load(Ordering::Relaxed);
+
+        let mut entries = vec![];
+        let mut total_size = 0u64;
+        for entry in fs::read_dir(&self.dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().is_some() {
+                continue;
+            }
+
+            let metadata = entry.metadata()?;
+            total_size += metadata.len();
+            entries.push((
+                path,
+                metadata.len(),
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            ));
+        }
+
+        entries.sort_by_key(|(_, _, modified)| *modified);
+
+        for (path, size, _) in entries {
+            if total_size <= max_size {
+                break;
+            }
+
+            debug!("Evicting {:?} from audio cache", path);
+            if let Err(e) = fs::remove_file(&path) {
+                error!("Failed to evict {:?}: {:?}", path, e);
+                continue;
+            }
+            total_size -= size;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn clear(&self) -> Result<()> {
+        for entry in fs::read_dir(&self.dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().is_none() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct CacheStorageProvider {
+    cache: Arc<AudioCache>,
+    partial_path: PathBuf,
+    final_path: PathBuf,
+}
+
+impl StorageProvider for CacheStorageProvider {
+    type Reader = File;
+    type Writer = CacheWriter;
+
+    fn into_reader_writer(
+        self,
+        content_length: Option<u64>,
+    ) -> io::Result<(Self::Reader, Self::Writer)> {
+        let file = File::create(&self.partial_path)?;
+        let reader = File::open(&self.partial_path)?;
+        Ok((
+            reader,
+            CacheWriter {
+                file,
+                cache: self.cache,
+                partial_path: self.partial_path,
+                final_path: self.final_path,
+                content_length,
+                position: 0,
+                contiguous: true,
+            },
+        ))
+    }
+}
+
+/// Writes the stream into a partial file and promotes it to a cache entry when dropped,
+/// but only if the whole content was written sequentially.
+#[derive(Debug)]
+pub struct CacheWriter {
+    file: File,
+    cache: Arc<AudioCache>,
+    partial_path: PathBuf,
+    final_path: PathBuf,
+    content_length: Option<u64>,
+    position: u64,
+    contiguous: bool,
+}
+
+impl Write for CacheWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.position += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Seek for CacheWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = self.file.seek(pos)?;
+        if position != self.position {
+            // A range request after seeking leaves gaps in the file
+            self.contiguous = false;
+        }
+        self.position = position;
+        Ok(position)
+    }
+}
+
+impl Drop for CacheWriter {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+
+        let complete = self.contiguous && self.content_length == Some(self.position);
+        if !complete {
+            trace!("Discarding partial stream {:?}", self.partial_path);
+            let _ = fs::remove_file(&self.partial_path);
+            return;
+        }
+
+        if let Err(e) = fs::rename(&self.partial_path, &self.final_path) {
+            error!("Failed to persist stream to cache: {:?}", e);
+            return;
+        }
+
+        debug!("Cached stream at {:?}", self.final_path);
+        if let Err(e) = self.cache.evict() {
+            error!("Failed to evict audio cache: {:?}", e);
+        }
+    }
+}
@@ -20,53 +20,89 @@ use std::{
     path::PathBuf,
     str::FromStr,
     sync::{
-        mpsc::{channel, Receiver, Sender},
+        atomic::{AtomicU64, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
         Arc, Mutex,
     },
     thread,
     time::Duration,
 };
 
-use rodio::{Decoder, OutputStream, Sink};
-use stream_download::{storage::temp::TempStorageProvider, Settings, StreamDownload};
-use tracing::{debug, error, info, trace};
-use types::{errors::Result, ui::player_details::PlayerEvents};
+use cache::AudioCache;
+use rodio::{
+    cpal::{self, traits::HostTrait},
+    Decoder, Device, DeviceTrait, OutputStream, OutputStreamHandle, Sink,
+};
+use stream_download::{Settings, StreamDownload};
+use tracing::{debug, error, info, trace, warn};
+use types::{
+    errors::{MoosyncError, Result},
+    ui::player_details::PlayerEvents,
+};
+
+mod cache;
+#[cfg(test)]
+mod test;
+
+// How often the player thread checks whether the selected output device is still present.
+// Devices are only polled while something is playing, an idle player just waits for commands.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 pub struct RodioPlayer {
     tx: Sender<RodioCommand>,
     events_rx: Arc<Mutex<Receiver<PlayerEvents>>>,
+    cache: Arc<AudioCache>,
 }
 
 enum RodioCommand {
-    SetSrc(String),
+    SetSrc(String, Option<String>),
     Play,
     Pause,
     Stop,
     SetVolume(f32),
     Seek(u64),
+    SetDevice(Option<String>),
 }
 
 impl RodioPlayer {
     #[tracing::instrument(level = "trace", skip())]
-    pub fn new(cache_dir: PathBuf) -> Self {
+    pub fn new(cache_dir: PathBuf, output_device: Option<String>, cache_size: u64) -> Self {
         let (events_tx, events_rx) = channel::<PlayerEvents>();
-        let cache_dir = cache_dir.join("rodio");
-        if !cache_dir.exists() {
-            fs::create_dir(cache_dir.clone()).unwrap();
-        }
-        let tx = Self::initialize(events_tx, cache_dir);
+        let cache = Arc::new(AudioCache::new(cache_dir.join("rodio"), cache_size));
+        let tx = Self::initialize(events_tx, cache.clone(), output_device);
         Self {
             tx,
             events_rx: Arc::new(Mutex::new(events_rx)),
+            cache,
         }
     }
 
-    async fn set_src(cache_dir: PathBuf, src: String, sink: &Arc<Sink>) -> Result<()> {
+    fn append_file(path: PathBuf, sink: &Arc<Sink>) -> Result<()> {
+        let file = fs::File::open(path)?;
+        let reader = BufReader::new(file);
+        let decoder = Decoder::new(reader)?;
+        sink.append(decoder);
+        Ok(())
+    }
+
+    async fn set_src(
+        cache: Arc<AudioCache>,
+        src: String,
+        id: Option<String>,
+        sink: &Arc<Sink>,
+    ) -> Result<()> {
         if src.starts_with("http") {
+            // Stream urls usually expire, the song id is the stable identity
+            let key = id.unwrap_or_else(|| src.clone());
+            if let Some(path) = cache.get(&key) {
+                trace!("Playing from cache {:?}", path);
+                return Self::append_file(path, sink);
+            }
+
             trace!("Creating stream");
             match StreamDownload::new_http(
                 src.parse().unwrap(),
-                TempStorageProvider::new_in(cache_dir),
+                cache.storage(&key),
                 Settings::default(),
             )
             .await
@@ -86,11 +122,7 @@ impl RodioPlayer {
         } else {
             let path = PathBuf::from_str(src.as_str()).unwrap();
             if path.exists() {
-                let file = fs::File::open(path)?;
-                let reader = BufReader::new(file);
-                let decoder = Decoder::new(reader)?;
-                sink.append(decoder);
-                return Ok(());
+                return Self::append_file(path, sink);
             }
 
             Err("Failed to read src".into())
@@ -105,13 +137,69 @@ impl RodioPlayer {
         events_tx.send(event).unwrap();
     }
 
-    fn initialize(events_tx: Sender<PlayerEvents>, cache_dir: PathBuf) -> Sender<RodioCommand> {
+    fn find_output_device(name: &str) -> Option<Device> {
+        let host = cpal::default_host();
+        match host.output_devices() {
+            Ok(mut devices) => devices.find(|d| d.name().map(|n| n == name).unwrap_or(false)),
+            Err(e) => {
+                error!("Failed to list output devices: {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Opens the output stream on the device named `name`, falling back to the default device
+    /// if it is missing. Returns the name of the device that was actually opened (None for the default).
+    fn open_output(
+        name: Option<&str>,
+    ) -> Result<(Option<String>, OutputStream, OutputStreamHandle)> {
+        if let Some(name) = name {
+            if let Some(device) = Self::find_output_device(name) {
+                match OutputStream::try_from_device(&device) {
+                    Ok((stream, handle)) => return Ok((Some(name.to_string()), stream, handle)),
+                    Err(e) => error!("Failed to open output device {}: {:?}", name, e),
+                }
+            } else {
+                warn!("Output device {} not found, falling back to default", name);
+            }
+        }
+
+        let (stream, handle) = OutputStream::try_default()?;
+        Ok((None, stream, handle))
+    }
+
+    fn new_sink(handle: &OutputStreamHandle) -> Result<Arc<Sink>> {
+        Ok(Arc::new(
+            Sink::try_new(handle).map_err(|e| MoosyncError::String(e.to_string()))?,
+        ))
+    }
+
+    // Send ended event only if song (or output device) hasn't changed yet
+    fn watch_end(sink: Arc<Sink>, generation: Arc<AtomicU64>, events_tx: Sender<PlayerEvents>) {
+        let current = generation.load(Ordering::SeqCst);
+        thread::spawn(move || {
+            sink.sleep_until_end();
+            let latest = generation.load(Ordering::SeqCst);
+            info!("last generation={}, current generation={}", latest, current);
+            if latest == current {
+                Self::send_event(events_tx, PlayerEvents::Ended);
+            }
+        });
+    }
+
+    fn initialize(
+        events_tx: Sender<PlayerEvents>,
+        cache: Arc<AudioCache>,
+        output_device: Option<String>,
+    ) -> Sender<RodioCommand> {
         let (tx, rx) = channel::<RodioCommand>();
         let ret = tx.clone();
 
         thread::spawn(move || {
-            let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-            let sink = Arc::new(rodio::Sink::try_new(&stream_handle).unwrap());
+            let mut preferred_device = output_device;
+            let (mut active_device, mut _stream, stream_handle) =
+                Self::open_output(preferred_device.as_deref()).unwrap();
+            let mut sink = Self::new_sink(&stream_handle).unwrap();
 
             let runtime = tokio::runtime::Builder::new_multi_thread()
                 .enable_all()
@@ -121,48 +209,60 @@ impl RodioPlayer {
             let events_tx = events_tx.clone();
             runtime.block_on(async move {
                 let last_src = Arc::new(Mutex::new(None));
-                while let Ok(command) = rx.recv() {
-                    let sink = sink.clone();
+                let generation = Arc::new(AtomicU64::new(0));
+                loop {
+                    let playing = !sink.empty() && !sink.is_paused();
+                    let received = if playing {
+                        rx.recv_timeout(DEVICE_POLL_INTERVAL)
+                    } else {
+                        rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                    };
+                    let command = match received {
+                        Ok(command) => command,
+                        Err(RecvTimeoutError::Timeout) => {
+                            let preferred_available = preferred_device
+                                .as_deref()
+                                .map(|name| Self::find_output_device(name).is_some());
+                            let active_available = active_device
+                                .as_deref()
+                                .map(|name| Self::find_output_device(name).is_some());
+
+                            if active_available == Some(false) {
+                                warn!("Output device {:?} disappeared", active_device);
+                                RodioCommand::SetDevice(preferred_device.clone())
+                            } else if active_device != preferred_device
+                                && preferred_available == Some(true)
+                            {
+                                info!("Preferred output device {:?} is back", preferred_device);
+                                RodioCommand::SetDevice(preferred_device.clone())
+                            } else {
+                                continue;
+                            }
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
 
                     match command {
-                        RodioCommand::SetSrc(src) => {
-                            let last_src = last_src.clone();
+                        RodioCommand::SetSrc(src, id) => {
+                            let sink = sink.clone();
                             {
                                 let mut last_src = last_src.lock().unwrap();
-                                *last_src = Some(src.clone());
+                                *last_src = Some((src.clone(), id.clone()));
                             }
 
+                            generation.fetch_add(1, Ordering::SeqCst);
                             sink.clear();
                             Self::send_event(events_tx.clone(), PlayerEvents::TimeUpdate(0f64));
                             Self::send_event(events_tx.clone(), PlayerEvents::Loading);
 
                             if let Err(err) =
-                                Self::set_src(cache_dir.clone(), src.clone(), &sink).await
+                                Self::set_src(cache.clone(), src.clone(), id, &sink).await
                             {
                                 error!("Failed to set src: {:?}", err);
                                 Self::send_event(events_tx.clone(), PlayerEvents::Error(err))
                             } else {
                                 debug!("Set src");
-                                let src_clone = src.clone();
-
-                                let events_tx = events_tx.clone();
-                                let sink = sink.clone();
-
-                                // Send ended event only if song hasn't changed yet
-                                thread::spawn(move || {
-                                    sink.sleep_until_end();
-                                    let last_src = last_src.clone();
-                                    let last_src = last_src.lock().unwrap();
-                                    if let Some(last_src) = last_src.clone() {
-                                        info!("last src={}, current src={}", last_src, src_clone);
-                                        if last_src == src_clone {
-                                            Self::send_event(
-                                                events_tx.clone(),
-                                                PlayerEvents::Ended,
-                                            );
-                                        }
-                                    }
-                                });
+                                Self::watch_end(sink, generation.clone(), events_tx.clone());
                             }
                         }
                         RodioCommand::Play => {
@@ -202,13 +302,68 @@ impl RodioPlayer {
                             } else {
                                 let last_src = last_src.clone();
                                 let last_src = last_src.lock().unwrap();
-                                if let Some(last_src) = last_src.clone() {
-                                    tx.send(RodioCommand::SetSrc(last_src.clone())).unwrap();
+                                if let Some((src, id)) = last_src.clone() {
+                                    tx.send(RodioCommand::SetSrc(src, id)).unwrap();
                                     tx.send(RodioCommand::Seek(pos)).unwrap();
                                     tx.send(RodioCommand::Play).unwrap();
                                 }
                             }
                         }
+                        RodioCommand::SetDevice(device) => {
+                            preferred_device = device;
+
+                            let (new_device, new_stream, new_handle) =
+                                match Self::open_output(preferred_device.as_deref()) {
+                                    Ok(output) => output,
+                                    Err(e) => {
+                                        error!("Failed to open output stream: {:?}", e);
+                                        continue;
+                                    }
+                                };
+                            let new_sink = match Self::new_sink(&new_handle) {
+                                Ok(new_sink) => new_sink,
+                                Err(e) => {
+                                    error!("Failed to create sink: {:?}", e);
+                                    continue;
+                                }
+                            };
+
+                            info!("Switching output device to {:?}", new_device);
+                            let pos = sink.get_pos();
+                            let paused = sink.is_paused();
+                            let was_empty = sink.empty();
+                            new_sink.set_volume(sink.volume());
+
+                            generation.fetch_add(1, Ordering::SeqCst);
+                            sink.stop();
+
+                            active_device = new_device;
+                            _stream = new_stream;
+                            sink = new_sink;
+
+                            let src = last_src.lock().unwrap().clone();
+                            if let (false, Some((src, id))) = (was_empty, src) {
+                                sink.pause();
+                                if let Err(err) = Self::set_src(cache.clone(), src, id, &sink).await
+                                {
+                                    error!("Failed to restore src on new device: {:?}", err);
+                                    Self::send_event(events_tx.clone(), PlayerEvents::Error(err));
+                                    continue;
+                                }
+
+                                if let Err(err) = sink.try_seek(pos) {
+                                    error!("Failed to restore position: {:?}", err)
+                                }
+                                if !paused {
+                                    sink.play();
+                                }
+                                Self::watch_end(
+                                    sink.clone(),
+                                    generation.clone(),
+                                    events_tx.clone(),
+                                );
+                            }
+                        }
                     }
                 }
             });
@@ -217,10 +372,19 @@ impl RodioPlayer {
         ret
     }
 
+    #[tracing::instrument(level = "trace", skip())]
+    pub fn get_output_devices() -> Result<Vec<String>> {
+        let host = cpal::default_host();
+        let devices = host
+            .output_devices()
+            .map_err(|e| MoosyncError::String(e.to_string()))?;
+        Ok(devices.filter_map(|d| d.name().ok()).collect())
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
-    pub async fn rodio_load(&self, src: String) -> Result<()> {
-        info!("Loading src={}", src);
-        self.tx.send(RodioCommand::SetSrc(src.clone())).unwrap();
+    pub async fn rodio_load(&self, src: String, id: Option<String>) -> Result<()> {
+        info!("Loading src={} id={:?}", src, id);
+        self.tx.send(RodioCommand::SetSrc(src, id)).unwrap();
         Ok(())
     }
 
@@ -260,4 +424,25 @@ impl RodioPlayer {
     pub async fn rodio_get_volume(&self) -> Result<f32> {
         Ok(0f32)
     }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn rodio_get_output_devices(&self) -> Result<Vec<String>> {
+        Self::get_output_devices()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn rodio_set_output_device(&self, device: Option<String>) -> Result<()> {
+        self.tx.send(RodioCommand::SetDevice(device)).unwrap();
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_cache_size(&self, cache_size: u64) {
+        self.cache.set_max_size(cache_size);
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn rodio_clear_cache(&self) -> Result<()> {
+        self.cache.clear()
+    }
 }
@@ -0,0 +1,146 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    fs::{self, File},
+    io::{Seek, SeekFrom, Write},
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use stream_download::storage::StorageProvider;
+use test_utils::TempDir;
+
+use crate::cache::AudioCache;
+
+fn write_entry(cache: &Arc<AudioCache>, key: &str, len: usize) {
+    let (_reader, mut writer) = cache
+        .storage(key)
+        .into_reader_writer(Some(len as u64))
+        .unwrap();
+    writer.write_all(&vec![0u8; len]).unwrap();
+}
+
+fn set_age(path: &Path, secs: u64) {
+    let file = File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(secs))
+        .unwrap();
+}
+
+fn partial_files(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .unwrap()
+        .flatten()
+        .filter(|e| e.path().extension().is_some())
+        .count()
+}
+
+#[test]
+fn test_evicts_least_recently_used() {
+    let dir = TempDir::new("audio_cache_lru");
+    let cache = Arc::new(AudioCache::new(dir.to_path_buf(), 25));
+
+    write_entry(&cache, "a", 10);
+    write_entry(&cache, "b", 10);
+    set_age(&cache.get("a").unwrap(), 100);
+    set_age(&cache.get("b").unwrap(), 50);
+
+    // Playing "a" again makes "b" the least recently used entry
+    assert!(cache.get("a").is_some());
+    write_entry(&cache, "c", 10);
+
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("c").is_some());
+}
+
+#[test]
+fn test_shrinking_limit_evicts() {
+    let dir = TempDir::new("audio_cache_shrink");
+    let cache = Arc::new(AudioCache::new(dir.to_path_buf(), 100));
+
+    write_entry(&cache, "a", 10);
+    write_entry(&cache, "b", 10);
+    set_age(&cache.get("a").unwrap(), 100);
+
+    cache.set_max_size(15);
+    assert!(cache.get("a").is_none());
+    assert!(cache.get("b").is_some());
+
+    cache.set_max_size(0);
+    assert!(cache.get("b").is_none());
+}
+
+#[test]
+fn test_writer_persists_complete_stream() {
+    let dir = TempDir::new("audio_cache_complete");
+    let cache = Arc::new(AudioCache::new(dir.to_path_buf(), 100));
+
+    let (_reader, mut writer) = cache.storage("song").into_reader_writer(Some(6)).unwrap();
+    writer.write_all(b"abc").unwrap();
+    writer.write_all(b"def").unwrap();
+    drop(writer);
+
+    let path = cache.get("song").unwrap();
+    assert_eq!(fs::read(path).unwrap(), b"abcdef");
+    assert_eq!(partial_files(&dir), 0);
+}
+
+#[test]
+fn test_writer_discards_incomplete_stream() {
+    let dir = TempDir::new("audio_cache_incomplete");
+    let cache = Arc::new(AudioCache::new(dir.to_path_buf(), 100));
+
+    let (_reader, mut writer) = cache.storage("song").into_reader_writer(Some(6)).unwrap();
+    writer.write_all(b"abc").unwrap();
+    drop(writer);
+
+    assert!(cache.get("song").is_none());
+    assert_eq!(partial_files(&dir), 0);
+
+    // Without a content length there is no way to tell whether the stream completed
+    let (_reader, mut writer) = cache.storage("song").into_reader_writer(None).unwrap();
+    writer.write_all(b"abcdef").unwrap();
+    drop(writer);
+
+    assert!(cache.get("song").is_none());
+}
+
+#[test]
+fn test_writer_discards_seeked_stream() {
+    let dir = TempDir::new("audio_cache_seeked");
+    let cache = Arc::new(AudioCache::new(dir.to_path_buf(), 100));
+
+    let (_reader, mut writer) = cache.storage("song").into_reader_writer(Some(6)).unwrap();
+    writer.write_all(b"ab").unwrap();
+    writer.seek(SeekFrom::Start(4)).unwrap();
+    writer.write_all(b"ef").unwrap();
+    drop(writer);
+
+    assert!(cache.get("song").is_none());
+    assert_eq!(partial_files(&dir), 0);
+}
+
+#[test]
+fn test_entries_keyed_independently() {
+    let dir = TempDir::new("audio_cache_keys");
+    let cache = Arc::new(AudioCache::new(dir.to_path_buf(), 100));
+
+    write_entry(&cache, "song_id", 4);
+    assert!(cache.get("song_id").is_some());
+    assert!(cache.get("https://example.com/stream?expires=1").is_none());
+}
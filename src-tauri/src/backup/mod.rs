@@ -0,0 +1,500 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, TryLockError},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use database::{database::Database, migrations::latest_schema_version};
+use fs_extra::dir::CopyOptions;
+use preferences::preferences::PreferenceConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+use tracing::{info, warn};
+use types::{
+    entities::BackupManifest,
+    errors::{MoosyncError, Result},
+    window::DialogFilter,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::window::handler::WindowHandler;
+
+#[cfg(test)]
+mod test;
+
+/// Bumped whenever the layout of the archive changes
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const DATABASE: &str = "songs.db";
+const CONFIG: &str = "config.json";
+const THEMES: &str = "themes";
+const EXTENSIONS: &str = "extensions";
+const THUMBNAILS: &str = "thumbnails";
+
+/// Directory inside the app data dir where a restore waits for the next start
+const RESTORE_DIR: &str = "restore_pending";
+const PENDING: &str = "pending.json";
+
+/// Locked by the running app, see [lock_instance]
+const INSTANCE_LOCK: &str = "instance.lock";
+
+/// Held for as long as the app runs. Released when dropped
+pub struct InstanceLock {
+    _file: File,
+}
+
+/// Locks the app data in `data_dir` so that restoring from the command line
+/// can't swap the library out from under a running app
+#[tracing::instrument(level = "trace", skip())]
+pub fn lock_instance(data_dir: &Path) -> Result<InstanceLock> {
+    fs::create_dir_all(data_dir)?;
+    let file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(data_dir.join(INSTANCE_LOCK))?;
+    match file.try_lock() {
+        Ok(()) => Ok(InstanceLock { _file: file }),
+        Err(TryLockError::WouldBlock) => Err(MoosyncError::String(
+            "Moosync is running, close it and try again".into(),
+        )),
+        Err(TryLockError::Error(e)) => Err(e.into()),
+    }
+}
+
+/// Locations of everything that goes into a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupPaths {
+    pub database: PathBuf,
+    pub config: PathBuf,
+    pub themes: PathBuf,
+    pub extensions: PathBuf,
+    pub thumbnails: PathBuf,
+}
+
+impl BackupPaths {
+    #[tracing::instrument(level = "trace", skip(app, preferences))]
+    pub fn from_app(app: &AppHandle, preferences: &PreferenceConfig) -> Result<Self> {
+        let data_dir = app.path().app_data_dir()?;
+        let local_data_dir = app.path().app_local_data_dir()?;
+        Ok(Self {
+            database: data_dir.join(DATABASE),
+            config: app.path().app_config_dir()?.join(CONFIG),
+            themes: local_data_dir.join(THEMES),
+            extensions: data_dir.join(EXTENSIONS),
+            thumbnails: preferences
+                .load_selective::<PathBuf>("thumbnail_path".to_string())
+                .unwrap_or(local_data_dir.join(THUMBNAILS)),
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn staging_dir(&self) -> PathBuf {
+        restore_dir(self.database.parent().unwrap_or(Path::new(".")))
+    }
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn restore_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(RESTORE_DIR)
+}
+
+/// Writes the database, config, themes, extensions and thumbnails to a single
+/// zip archive at `dest`. The database is copied with SQLite's online backup
+/// so this is safe to run while the app is in use.
+#[tracing::instrument(level = "trace", skip(db, paths))]
+pub fn create_backup(db: &Database, paths: &BackupPaths, dest: &Path) -> Result<BackupManifest> {
+    let config = read_config(&paths.config)?;
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: db.schema_version()?,
+        created_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs(),
+        platform: std::env::consts::OS.to_string(),
+        music_roots: config
+            .pointer("/prefs/music_paths")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default(),
+        thumbnail_dir: Some(paths.thumbnails.to_string_lossy().to_string()),
+    };
+
+    let snapshot = std::env::temp_dir().join(format!("moosync-backup-{}.db", std::process::id()));
+    db.backup_to(&snapshot)?;
+
+    let res = write_archive(&manifest, &snapshot, paths, dest);
+    let _ = fs::remove_file(&snapshot);
+    res?;
+
+    info!("Created backup at {:?}", dest);
+    Ok(manifest)
+}
+
+#[tracing::instrument(level = "trace", skip(manifest, snapshot, paths))]
+fn write_archive(
+    manifest: &BackupManifest,
+    snapshot: &Path,
+    paths: &BackupPaths,
+    dest: &Path,
+) -> Result<()> {
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    let mut zip = ZipWriter::new(File::create(dest)?);
+
+    zip.start_file(MANIFEST, options)?;
+    serde_json::to_writer_pretty(&mut zip, manifest)?;
+
+    zip.start_file(DATABASE, options)?;
+    io::copy(&mut File::open(snapshot)?, &mut zip)?;
+
+    if paths.config.exists() {
+        zip.start_file(CONFIG, options)?;
+        io::copy(&mut File::open(&paths.config)?, &mut zip)?;
+    }
+
+    for (name, dir) in [
+        (THEMES, &paths.themes),
+        (EXTENSIONS, &paths.extensions),
+        (THUMBNAILS, &paths.thumbnails),
+    ] {
+        if dir.is_dir() {
+            add_dir(&mut zip, name, dir, options)?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(zip, options))]
+fn add_dir(
+    zip: &mut ZipWriter<File>,
+    prefix: &str,
+    dir: &Path,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    zip.add_directory(prefix, options)?;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            add_dir(zip, &name, &entry.path(), options)?;
+        } else if file_type.is_file() {
+            zip.start_file(name, options)?;
+            io::copy(&mut File::open(entry.path())?, zip)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the manifest of a backup and checks that this build can restore it
+#[tracing::instrument(level = "trace", skip())]
+pub fn read_backup_manifest(archive: &Path) -> Result<BackupManifest> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    let manifest: BackupManifest = match zip.by_name(MANIFEST) {
+        Ok(file) => serde_json::from_reader(file)?,
+        Err(_) => return Err("Not a Moosync backup: manifest is missing".into()),
+    };
+
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "Backup format {} is newer than supported ({}), please update Moosync",
+            manifest.format_version, BACKUP_FORMAT_VERSION
+        )
+        .into());
+    }
+
+    if let (Some(schema), Some(latest)) = (&manifest.schema_version, latest_schema_version()) {
+        if *schema > latest {
+            return Err(format!(
+                "Backup was made by Moosync {} with a newer library, please update Moosync",
+                manifest.app_version
+            )
+            .into());
+        }
+    }
+
+    if zip.by_name(DATABASE).is_err() {
+        return Err("Not a Moosync backup: database is missing".into());
+    }
+
+    Ok(manifest)
+}
+
+/// Unpacks a backup next to the current library so it can replace it on the
+/// next start, see [apply_pending_restore]. The database is migrated and
+/// checked here, and paths under the old music folders are moved to their
+/// new location using `music_roots` (old root to new root).
+#[tracing::instrument(level = "trace", skip(paths))]
+pub fn prepare_restore(
+    archive: &Path,
+    paths: &BackupPaths,
+    music_roots: &HashMap<String, String>,
+) -> Result<BackupManifest> {
+    let manifest = read_backup_manifest(archive)?;
+
+    let staging = paths.staging_dir();
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+
+    let res = stage_restore(archive, &manifest, &staging, paths, music_roots);
+    if res.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    res?;
+
+    info!("Staged backup from {:?} for restore", archive);
+    Ok(manifest)
+}
+
+#[tracing::instrument(level = "trace", skip(manifest, paths))]
+fn stage_restore(
+    archive: &Path,
+    manifest: &BackupManifest,
+    staging: &Path,
+    paths: &BackupPaths,
+    music_roots: &HashMap<String, String>,
+) -> Result<()> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i)?;
+        let Some(name) = file.enclosed_name() else {
+            warn!("Skipping unsafe path in backup: {}", file.name());
+            continue;
+        };
+
+        let target = staging.join(name);
+        if file.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut file, &mut File::create(&target)?)?;
+        }
+    }
+
+    let mut remaps: Vec<(String, String)> = music_roots
+        .iter()
+        .map(|(from, to)| (from.clone(), to.clone()))
+        .collect();
+    if let Some(thumbnail_dir) = &manifest.thumbnail_dir {
+        remaps.push((
+            thumbnail_dir.clone(),
+            paths.thumbnails.to_string_lossy().to_string(),
+        ));
+    }
+
+    // Opening runs any migrations the backup is missing
+    let db = Database::open(staging.join(DATABASE))?;
+    let problems = db.check_integrity()?;
+    if problems.len() != 1 || problems[0] != "ok" {
+        return Err(format!("Backup database is corrupt: {}", problems.join(", ")).into());
+    }
+    for (from, to) in &remaps {
+        db.rewrite_path_prefix(from, to)?;
+    }
+    drop(db);
+
+    let config_path = staging.join(CONFIG);
+    if config_path.exists() {
+        let mut config = read_config(&config_path)?;
+        if let Some(prefs) = config.get_mut("prefs").and_then(|p| p.as_object_mut()) {
+            for value in prefs.values_mut() {
+                for (from, to) in music_roots {
+                    rewrite_path_prefixes(value, from, to);
+                }
+            }
+            prefs.insert(
+                "thumbnail_path".to_string(),
+                paths.thumbnails.to_string_lossy().into(),
+            );
+        }
+        fs::write(&config_path, serde_json::to_vec(&config)?)?;
+    }
+
+    fs::write(staging.join(PENDING), serde_json::to_vec(paths)?)?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn read_config(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Null);
+    }
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Moves every string in `value` that is a path under `from` to `to`
+#[tracing::instrument(level = "trace", skip(value))]
+pub(crate) fn rewrite_path_prefixes(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::String(s) => {
+            let from = from.trim_end_matches(['/', '\\']);
+            if let Some(rest) = s.strip_prefix(from) {
+                if rest.is_empty() || rest.starts_with(['/', '\\']) {
+                    *s = format!("{}{}", to.trim_end_matches(['/', '\\']), rest);
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|v| rewrite_path_prefixes(v, from, to)),
+        Value::Object(values) => values
+            .values_mut()
+            .for_each(|v| rewrite_path_prefixes(v, from, to)),
+        _ => {}
+    }
+}
+
+/// Swaps in a restore staged by [prepare_restore]. Must run before the
+/// database is opened. Returns whether anything was restored.
+///
+/// Only what the backup contains is replaced. Themes, extensions and
+/// thumbnails that aren't in it are left alone, since the thumbnail folder
+/// may be shared with other files.
+#[tracing::instrument(level = "trace", skip())]
+pub fn apply_pending_restore(data_dir: &Path) -> Result<bool> {
+    let staging = restore_dir(data_dir);
+    let pending = staging.join(PENDING);
+    if !pending.exists() {
+        return Ok(false);
+    }
+
+    let paths: BackupPaths = serde_json::from_slice(&fs::read(&pending)?)?;
+
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = paths.database.clone().into_os_string();
+        sidecar.push(suffix);
+        let sidecar = PathBuf::from(sidecar);
+        if sidecar.exists() {
+            fs::remove_file(sidecar)?;
+        }
+    }
+
+    for (name, dest) in [(DATABASE, &paths.database), (CONFIG, &paths.config)] {
+        let src = staging.join(name);
+        if src.exists() {
+            replace_path(&src, dest)?;
+        }
+    }
+
+    for (name, dest) in [
+        (THEMES, &paths.themes),
+        (EXTENSIONS, &paths.extensions),
+        (THUMBNAILS, &paths.thumbnails),
+    ] {
+        let src = staging.join(name);
+        if src.is_dir() {
+            fs::create_dir_all(dest)?;
+            for entry in fs::read_dir(&src)? {
+                let entry = entry?;
+                replace_path(&entry.path(), &dest.join(entry.file_name()))?;
+            }
+        }
+    }
+
+    fs::remove_dir_all(&staging)?;
+    info!("Restored backup");
+    Ok(true)
+}
+
+/// Moves `src` over `dest`, copying when they are on different filesystems
+#[tracing::instrument(level = "trace", skip())]
+fn replace_path(src: &Path, dest: &Path) -> Result<()> {
+    if dest.is_dir() {
+        fs::remove_dir_all(dest)?;
+    } else if dest.exists() {
+        fs::remove_file(dest)?;
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+
+    if src.is_dir() {
+        fs::create_dir_all(dest)?;
+        fs_extra::dir::copy(src, dest, &CopyOptions::new().content_only(true))?;
+    } else {
+        fs::copy(src, dest)?;
+    }
+    Ok(())
+}
+
+/// Backs up the library to a zip file picked by the user
+#[tracing::instrument(level = "trace", skip(app, db, preferences, window_handler))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn create_library_backup(
+    app: AppHandle,
+    db: State<Database>,
+    preferences: State<PreferenceConfig>,
+    window_handler: State<WindowHandler>,
+) -> Result<BackupManifest> {
+    let paths = BackupPaths::from_app(&app, &preferences)?;
+    let mut dest = window_handler.open_save_file(
+        app,
+        vec![DialogFilter {
+            name: "Moosync backup".to_string(),
+            extensions: vec!["zip".to_string()],
+        }],
+        Some("moosync-backup.zip".to_string()),
+    )?;
+    if dest.extension().is_none() {
+        dest.set_extension("zip");
+    }
+    create_backup(&db, &paths, &dest)
+}
+
+#[tracing::instrument(level = "trace", skip())]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn get_backup_manifest(path: String) -> Result<BackupManifest> {
+    read_backup_manifest(Path::new(&path))
+}
+
+/// Restores the backup at `path` and restarts the app to load it.
+/// `music_roots` maps music folders in the backup to folders on this machine.
+#[tracing::instrument(level = "trace", skip(app, preferences))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn restore_library_backup(
+    app: AppHandle,
+    preferences: State<PreferenceConfig>,
+    path: String,
+    music_roots: HashMap<String, String>,
+) -> Result<()> {
+    let paths = BackupPaths::from_app(&app, &preferences)?;
+    prepare_restore(Path::new(&path), &paths, &music_roots)?;
+    app.restart();
+}
@@ -0,0 +1,264 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+use database::database::Database;
+use serde_json::Value;
+use test_utils::TempDir;
+use types::{
+    entities::BackupManifest,
+    songs::{GetSongOptions, QueryableSong, SearchableSong, Song},
+};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+use super::{
+    apply_pending_restore, create_backup, lock_instance, prepare_restore, read_backup_manifest,
+    BackupPaths, BACKUP_FORMAT_VERSION,
+};
+
+fn backup_paths(root: &Path) -> BackupPaths {
+    BackupPaths {
+        database: root.join("data").join("songs.db"),
+        config: root.join("config").join("config.json"),
+        themes: root.join("local").join("themes"),
+        extensions: root.join("data").join("extensions"),
+        thumbnails: root.join("thumbnails"),
+    }
+}
+
+fn write(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+    let mut zip = ZipWriter::new(File::create(path).unwrap());
+    for (name, data) in entries {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(data).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn manifest_bytes(manifest: BackupManifest) -> Vec<u8> {
+    serde_json::to_vec(&manifest).unwrap()
+}
+
+fn song_paths(db: &Database) -> Vec<String> {
+    db.get_songs_by_options(GetSongOptions {
+        song: Some(SearchableSong::default()),
+        ..Default::default()
+    })
+    .unwrap()
+    .into_iter()
+    .filter_map(|s| s.song.path)
+    .collect()
+}
+
+#[test]
+fn test_archive_round_trip() {
+    let root = TempDir::new("backup_round_trip");
+    let paths = backup_paths(&root);
+    let data_dir = paths.database.parent().unwrap().to_path_buf();
+
+    let db = Database::open(paths.database.clone()).unwrap();
+    db.insert_songs(vec![Song {
+        song: QueryableSong {
+            _id: Some("a".into()),
+            path: Some("/old/music/a.mp3".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }])
+    .unwrap();
+    write(
+        &paths.config,
+        r#"{"prefs": {"music_paths": ["/old/music"], "thumbnail_path": "/elsewhere"}}"#,
+    );
+    write(&paths.themes.join("dark").join("config.json"), "dark");
+    write(&paths.extensions.join("ext").join("package.json"), "ext");
+    write(&paths.thumbnails.join("a.png"), "cover");
+
+    let archive = root.join("backup.zip");
+    let manifest = create_backup(&db, &paths, &archive).unwrap();
+    drop(db);
+    assert_eq!(manifest.format_version, BACKUP_FORMAT_VERSION);
+    assert_eq!(manifest.music_roots, ["/old/music"]);
+    assert_eq!(read_backup_manifest(&archive).unwrap(), manifest);
+
+    // Changes made after the backup
+    fs::remove_dir_all(&paths.themes).unwrap();
+    write(&paths.thumbnails.join("a.png"), "changed");
+    write(&paths.thumbnails.join("unrelated.txt"), "not ours");
+
+    let remaps = HashMap::from([("/old/music".to_string(), "/new/music".to_string())]);
+    prepare_restore(&archive, &paths, &remaps).unwrap();
+    // Nothing is replaced until the next start
+    assert_eq!(read(&paths.thumbnails.join("a.png")), "changed");
+
+    assert!(apply_pending_restore(&data_dir).unwrap());
+    assert!(!apply_pending_restore(&data_dir).unwrap());
+
+    assert_eq!(read(&paths.themes.join("dark").join("config.json")), "dark");
+    assert_eq!(
+        read(&paths.extensions.join("ext").join("package.json")),
+        "ext"
+    );
+    assert_eq!(read(&paths.thumbnails.join("a.png")), "cover");
+    // Files the backup doesn't own survive the restore
+    assert_eq!(read(&paths.thumbnails.join("unrelated.txt")), "not ours");
+
+    let config: Value = serde_json::from_str(&read(&paths.config)).unwrap();
+    assert_eq!(
+        config.pointer("/prefs/music_paths"),
+        Some(&serde_json::json!(["/new/music"]))
+    );
+    assert_eq!(
+        config
+            .pointer("/prefs/thumbnail_path")
+            .and_then(|v| v.as_str()),
+        Some(paths.thumbnails.to_string_lossy().as_ref())
+    );
+
+    let db = Database::open(paths.database.clone()).unwrap();
+    assert_eq!(song_paths(&db), ["/new/music/a.mp3"]);
+    drop(db);
+}
+
+#[test]
+fn test_manifest_validation() {
+    let root = TempDir::new("backup_manifest");
+    let archive = root.join("backup.zip");
+    let valid = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: "1.0.0".into(),
+        ..Default::default()
+    };
+
+    write_zip(
+        &archive,
+        &[
+            ("manifest.json", &manifest_bytes(valid.clone())),
+            ("songs.db", b""),
+        ],
+    );
+    assert_eq!(read_backup_manifest(&archive).unwrap(), valid);
+
+    write_zip(&archive, &[("songs.db", b"")]);
+    let err = read_backup_manifest(&archive).unwrap_err().to_string();
+    assert!(err.contains("manifest is missing"), "{}", err);
+
+    write_zip(
+        &archive,
+        &[("manifest.json", &manifest_bytes(valid.clone()))],
+    );
+    let err = read_backup_manifest(&archive).unwrap_err().to_string();
+    assert!(err.contains("database is missing"), "{}", err);
+
+    let newer_format = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION + 1,
+        ..valid.clone()
+    };
+    write_zip(
+        &archive,
+        &[
+            ("manifest.json", &manifest_bytes(newer_format)),
+            ("songs.db", b""),
+        ],
+    );
+    let err = read_backup_manifest(&archive).unwrap_err().to_string();
+    assert!(err.contains("newer than supported"), "{}", err);
+
+    let newer_schema = BackupManifest {
+        schema_version: Some("99999999999999".into()),
+        ..valid
+    };
+    write_zip(
+        &archive,
+        &[
+            ("manifest.json", &manifest_bytes(newer_schema)),
+            ("songs.db", b""),
+        ],
+    );
+    let err = read_backup_manifest(&archive).unwrap_err().to_string();
+    assert!(err.contains("newer library"), "{}", err);
+
+    write(&archive, "not a zip");
+    assert!(read_backup_manifest(&archive).is_err());
+}
+
+#[test]
+fn test_restore_skips_unsafe_paths() {
+    let root = TempDir::new("backup_unsafe_paths");
+    let paths = backup_paths(&root);
+    let data_dir = paths.database.parent().unwrap().to_path_buf();
+
+    let db = Database::open(paths.database.clone()).unwrap();
+    let archive = root.join("backup.zip");
+    let manifest = create_backup(&db, &paths, &archive).unwrap();
+    drop(db);
+
+    let mut database = vec![];
+    ZipArchive::new(File::open(&archive).unwrap())
+        .unwrap()
+        .by_name("songs.db")
+        .unwrap()
+        .read_to_end(&mut database)
+        .unwrap();
+
+    let crafted = root.join("crafted.zip");
+    write_zip(
+        &crafted,
+        &[
+            ("manifest.json", &manifest_bytes(manifest)),
+            ("songs.db", &database),
+            ("../escape.txt", b"escaped"),
+            ("themes/../../escape.txt", b"escaped"),
+            ("themes/dark/config.json", b"dark"),
+        ],
+    );
+
+    prepare_restore(&crafted, &paths, &HashMap::new()).unwrap();
+    assert!(!data_dir.join("escape.txt").exists());
+    assert!(!root.join("escape.txt").exists());
+
+    apply_pending_restore(&data_dir).unwrap();
+    assert_eq!(read(&paths.themes.join("dark").join("config.json")), "dark");
+    assert!(!data_dir.join("escape.txt").exists());
+    assert!(!root.join("escape.txt").exists());
+}
+
+#[test]
+fn test_instance_lock() {
+    let root = TempDir::new("backup_instance_lock");
+
+    let lock = lock_instance(&root).unwrap();
+    let err = lock_instance(&root).err().unwrap().to_string();
+    assert!(err.contains("Moosync is running"), "{}", err);
+
+    drop(lock);
+    assert!(lock_instance(&root).is_ok());
+}
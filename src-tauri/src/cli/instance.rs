@@ -0,0 +1,227 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::{Path, PathBuf};
+
+use database::database::Database;
+use file_scanner::{
+    playlists::{import_playlist, PlaylistFormat},
+    scan_song,
+};
+use mpris::MediaControlEvent;
+use preferences::preferences::PreferenceConfig;
+use tauri::{AppHandle, Manager, State};
+use types::{
+    errors::Result,
+    songs::{QueryableSong, Song, SongType},
+};
+use url::Url;
+
+use super::Args;
+use crate::{
+    mpris::send_media_event,
+    oauth::handler::OAuthHandler,
+    providers::handler::ProviderHandler,
+    queue::QueueManager,
+    scanner::{find_song_by_path, ScanSettings},
+};
+
+/// Player controls accepted on the command line. When another instance is
+/// already running these are forwarded to it instead.
+#[derive(clap::Args, Debug, Default)]
+pub struct PlayerArgs {
+    /// Toggle between play and pause
+    #[arg(long)]
+    pub play_pause: bool,
+    /// Skip to the next song
+    #[arg(long)]
+    pub next: bool,
+    /// Add a file, playlist or URL to the end of the queue
+    #[arg(long, value_name = "FILE_OR_URL")]
+    pub enqueue: Vec<String>,
+    /// Play a file, playlist or URL right away
+    #[arg(long, value_name = "FILE_OR_URL")]
+    pub play: Option<String>,
+    /// Files to play, e.g. from "Open with" in a file manager
+    #[arg(value_name = "FILE_OR_URL")]
+    pub inputs: Vec<String>,
+}
+
+/// Parses the arguments another instance was started with. Launchers may add
+/// flags of their own next to a deep link, so when clap rejects them the
+/// first argument is still handled if it is one.
+#[tracing::instrument(level = "trace", skip())]
+pub fn parse_instance_args(argv: &[String]) -> Option<PlayerArgs> {
+    match Args::try_parse_from(argv) {
+        Ok(args) => Some(args.player),
+        Err(e) => match argv.get(1) {
+            Some(link) if link.starts_with("moosync:") => Some(PlayerArgs {
+                inputs: vec![link.clone()],
+                ..Default::default()
+            }),
+            _ => {
+                tracing::warn!("Ignoring args from another instance: {}", e);
+                None
+            }
+        },
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn handle_player_args(app: &AppHandle, args: PlayerArgs, cwd: PathBuf) {
+    if args.play_pause {
+        if let Err(e) = send_media_event(app, MediaControlEvent::Toggle) {
+            tracing::error!("Failed to toggle playback: {:?}", e);
+        }
+    }
+    if args.next {
+        if let Err(e) = send_media_event(app, MediaControlEvent::Next) {
+            tracing::error!("Failed to skip song: {:?}", e);
+        }
+    }
+
+    let mut play = vec![];
+    for input in args.inputs {
+        // OAuth redirects open the app through the same deep link
+        if input.starts_with("moosync:") {
+            let oauth: State<OAuthHandler> = app.state();
+            if let Err(e) = oauth.handle_oauth(app.clone(), input) {
+                tracing::error!("Failed to handle oauth url: {:?}", e);
+            }
+            continue;
+        }
+        play.push(input);
+    }
+    play.extend(args.play);
+
+    let enqueue = args.enqueue;
+    if play.is_empty() && enqueue.is_empty() {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = open_inputs(&app, play, enqueue, &cwd).await {
+            tracing::error!("Failed to open inputs: {:?}", e);
+        }
+    });
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+async fn open_inputs(
+    app: &AppHandle,
+    play: Vec<String>,
+    enqueue: Vec<String>,
+    cwd: &Path,
+) -> Result<()> {
+    let queue: State<QueueManager> = app.state();
+
+    let songs = resolve_inputs(app, enqueue, cwd).await;
+    if !songs.is_empty() {
+        queue.queue_add(songs)?;
+    }
+
+    let songs = resolve_inputs(app, play, cwd).await;
+    if !songs.is_empty() {
+        queue.queue_play_now(songs)?;
+        // Held back until the UI listens when the app was just started
+        send_media_event(app, MediaControlEvent::Play)?;
+    }
+
+    Ok(())
+}
+
+/// Turns each input into songs, skipping the ones that can't be read
+#[tracing::instrument(level = "trace", skip(app))]
+async fn resolve_inputs(app: &AppHandle, inputs: Vec<String>, cwd: &Path) -> Vec<Song> {
+    let mut ret = vec![];
+    for input in inputs {
+        match resolve_input(app, &input, cwd).await {
+            Ok(songs) => ret.extend(songs),
+            Err(e) => tracing::warn!("Could not open {}: {:?}", input, e),
+        }
+    }
+    ret
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+async fn resolve_input(app: &AppHandle, input: &str, cwd: &Path) -> Result<Vec<Song>> {
+    if input.starts_with("http://") || input.starts_with("https://") {
+        return Ok(vec![song_from_url(app, input).await?]);
+    }
+
+    // File managers pass file:// URIs when the desktop entry uses %U
+    let path = if input.starts_with("file://") {
+        Url::parse(input)
+            .map_err(|e| e.to_string())?
+            .to_file_path()
+            .map_err(|_| format!("Not a local file: {}", input))?
+    } else {
+        cwd.join(input)
+    };
+    let path = dunce::canonicalize(path)?;
+    if PlaylistFormat::from_path(&path).is_some() {
+        return Ok(import_playlist(&path)?.songs);
+    }
+
+    Ok(vec![song_from_path(app, &path)?])
+}
+
+/// Prefers the library entry for the file so play counts and covers are
+/// shared. Otherwise reads the file without adding it to the library.
+#[tracing::instrument(level = "trace", skip(app))]
+fn song_from_path(app: &AppHandle, path: &Path) -> Result<Song> {
+    let path_str = path.to_string_lossy().to_string();
+
+    let database: State<Database> = app.state();
+    if let Some(song) = find_song_by_path(&database, &path_str)? {
+        return Ok(song);
+    }
+
+    let preferences: State<PreferenceConfig> = app.state();
+    let settings = ScanSettings::load(&preferences)?;
+    scan_song(
+        path,
+        Path::new(&settings.thumbnail_dir),
+        &settings.artist_split,
+    )
+}
+
+/// Asks the providers for the song behind `url`, falling back to streaming it as is
+#[tracing::instrument(level = "trace", skip(app))]
+async fn song_from_url(app: &AppHandle, url: &str) -> Result<Song> {
+    let provider_handler: State<ProviderHandler> = app.state();
+    for key in provider_handler.get_provider_keys().await? {
+        if provider_handler
+            .match_url(key.clone(), url.to_string())
+            .await
+            .unwrap_or_default()
+        {
+            return provider_handler.song_from_url(key, url.to_string()).await;
+        }
+    }
+
+    Ok(Song {
+        song: QueryableSong {
+            title: Some(url.to_string()),
+            url: Some(url.to_string()),
+            playback_url: Some(url.to_string()),
+            type_: SongType::URL,
+            ..QueryableSong::empty()
+        },
+        ..Default::default()
+    })
+}
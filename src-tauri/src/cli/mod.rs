@@ -0,0 +1,396 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use database::database::Database;
+use file_scanner::{
+    playlists::{write_playlist, PlaylistFormat},
+    ScannerHolder,
+};
+use preferences::preferences::PreferenceConfig;
+use serde::Serialize;
+use serde_json::{json, Value};
+use themes::themes::ThemeHolder;
+use types::{entities::TagSuggestion, errors::Result};
+
+use crate::backup::{
+    apply_pending_restore, create_backup, lock_instance, prepare_restore, read_backup_manifest,
+    BackupPaths,
+};
+use crate::scanner::{import_playlist_file, relocate_library, scan_into_database, ScanSettings};
+use crate::tagger::{apply_suggestion, build_lookup, fingerprint_library, lookup_tags};
+
+mod instance;
+pub use instance::{handle_player_args, parse_instance_args, PlayerArgs};
+
+#[cfg(test)]
+mod test;
+
+// Must match the identifier in tauri.conf.json
+const IDENTIFIER: &str = "app.moosync.moosync";
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[arg(short, long)]
+    pub mobile: bool,
+    #[command(flatten)]
+    pub player: PlayerArgs,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Scan a directory into the library
+    Scan { path: PathBuf },
+    /// Search songs, albums, artists and playlists in the library
+    Search { term: String },
+    /// Import or export playlists
+    #[command(subcommand)]
+    Playlist(PlaylistCommand),
+    /// Show library statistics
+    Stats,
+    /// Maintain the library database
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// Manage themes
+    #[command(subcommand)]
+    Theme(ThemeCommand),
+    /// Back up or restore the library
+    #[command(subcommand)]
+    Backup(BackupCommand),
+    /// Find tags of songs through AcoustID and MusicBrainz
+    #[command(subcommand)]
+    Tags(TagsCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PlaylistCommand {
+    /// Write a playlist to a file. The format (M3U, PLS, XSPF or JSPF)
+    /// follows the extension of OUTPUT
+    Export {
+        playlist_id: String,
+        output: PathBuf,
+        /// Write local songs relative to OUTPUT so the playlist stays portable
+        #[arg(long)]
+        relative: bool,
+    },
+    /// Add a playlist file and its songs to the library, listing entries
+    /// that could not be found
+    Import { path: PathBuf },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommand {
+    /// Reclaim unused space
+    Vacuum,
+    /// Check the database for corruption
+    Check,
+    /// Point the library at a music folder that moved, keeping play counts
+    /// and playlists of the songs in it
+    Relocate { from: String, to: PathBuf },
+    /// List songs that are probably the same recording
+    Duplicates,
+    /// Fold duplicate songs into KEEP, carrying over their playlists, play
+    /// counts and lyrics
+    Merge {
+        keep: String,
+        #[arg(required = true)]
+        duplicates: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ThemeCommand {
+    /// Install a theme from a .mstx file or URL
+    Install { source: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupCommand {
+    /// Write the library, settings, themes, extensions and thumbnails to a
+    /// zip archive
+    Create { output: PathBuf },
+    /// Replace the library with a backup. Moosync must not be running
+    Restore {
+        archive: PathBuf,
+        /// Move songs from a music folder in the backup to one on this
+        /// machine. NEW alone is enough if the backup has one music folder
+        #[arg(long = "music-root", value_name = "OLD=NEW")]
+        music_roots: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagsCommand {
+    /// Fingerprint the local songs which don't have a fingerprint yet
+    Fingerprint,
+    /// List suggested tags for a song without changing it
+    Lookup { song_id: String },
+    /// Tag a song with a MusicBrainz recording, e.g. one listed by lookup
+    Apply {
+        song_id: String,
+        recording_mbid: String,
+    },
+}
+
+/// The directories Tauri would resolve for the app, which isn't built for CLI commands
+#[derive(Debug)]
+struct AppDirs {
+    data: PathBuf,
+    local_data: PathBuf,
+    config: PathBuf,
+}
+
+impl AppDirs {
+    #[tracing::instrument(level = "trace", skip())]
+    fn resolve() -> Result<Self> {
+        let app_dir = |dir: Option<PathBuf>| {
+            dir.map(|dir| dir.join(IDENTIFIER))
+                .ok_or("Could not resolve app directories")
+        };
+        Ok(Self {
+            data: app_dir(dirs::data_dir())?,
+            local_data: app_dir(dirs::data_local_dir())?,
+            config: app_dir(dirs::config_dir())?,
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn database(&self) -> Result<Database> {
+        fs::create_dir_all(&self.data)?;
+        Database::open(self.data.join("songs.db"))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn backup_paths(&self) -> Result<BackupPaths> {
+        let thumbnails: PathBuf = self
+            .preferences()?
+            .load_selective("thumbnail_path".to_string())?;
+        Ok(BackupPaths {
+            database: self.data.join("songs.db"),
+            config: self.config.join("config.json"),
+            themes: self.local_data.join("themes"),
+            extensions: self.data.join("extensions"),
+            thumbnails,
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn preferences(&self) -> Result<PreferenceConfig> {
+        let preferences = PreferenceConfig::new(self.config.clone())?;
+        if !preferences.has_key("thumbnail_path") {
+            let path = self.local_data.join("thumbnails");
+            preferences.save_selective("thumbnail_path".to_string(), Some(path))?;
+        }
+        Ok(preferences)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn themes(&self) -> Result<ThemeHolder> {
+        let path = self.local_data.join("themes");
+        fs::create_dir_all(&path)?;
+        // Nothing listens for theme changes outside the app
+        let (tx, _) = std::sync::mpsc::channel();
+        Ok(ThemeHolder::new(path, std::env::temp_dir(), tx))
+    }
+}
+
+/// Runs `command` without starting the app. Prints the result as JSON and
+/// returns the process exit code.
+#[tracing::instrument(level = "trace", skip())]
+pub fn run(command: Command) -> i32 {
+    let result = AppDirs::resolve().and_then(|dirs| execute(&dirs, command));
+    match &result {
+        Ok((_, output)) => println!(
+            "{}",
+            serde_json::to_string_pretty(output).unwrap_or_default()
+        ),
+        Err(e) => eprintln!("{}", json!({ "error": e.to_string() })),
+    }
+    exit_code(&result)
+}
+
+#[tracing::instrument(level = "trace", skip(result))]
+fn exit_code(result: &Result<(bool, Value)>) -> i32 {
+    match result {
+        Ok((true, _)) => 0,
+        _ => 1,
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(value))]
+fn output<T: Serialize>(value: T) -> Result<(bool, Value)> {
+    Ok((true, serde_json::to_value(value)?))
+}
+
+/// Returns whether the command succeeded along with its output
+#[tracing::instrument(level = "trace", skip(dirs))]
+fn execute(dirs: &AppDirs, command: Command) -> Result<(bool, Value)> {
+    match command {
+        Command::Scan { path } => {
+            let path = fs::canonicalize(path)?.to_string_lossy().to_string();
+            let settings = ScanSettings::load(&dirs.preferences()?)?;
+            let added = scan_into_database(
+                dirs.database()?,
+                &ScannerHolder::new(),
+                path.clone(),
+                &settings,
+            )?;
+            output(json!({ "path": path, "songs": added }))
+        }
+        Command::Search { term } => output(dirs.database()?.search_all(term)?),
+        Command::Playlist(PlaylistCommand::Export {
+            playlist_id,
+            output: path,
+            relative,
+        }) => {
+            let format = PlaylistFormat::from_path(&path)
+                .ok_or_else(|| format!("Unknown playlist format: {}", path.display()))?;
+            let (playlist, songs) = dirs
+                .database()?
+                .get_playlist_with_songs(playlist_id.clone())?;
+
+            let path = std::env::current_dir()?.join(path);
+            // Song paths are canonical so the directory has to be too
+            let relative_to = match path.parent() {
+                Some(parent) if relative => Some(dunce::canonicalize(parent)?),
+                _ => None,
+            };
+            let data = write_playlist(
+                format,
+                &playlist.playlist_name,
+                &songs,
+                relative_to.as_deref(),
+            )?;
+            fs::write(&path, data)?;
+            output(json!({ "playlist_id": playlist_id, "path": path }))
+        }
+        Command::Playlist(PlaylistCommand::Import { path }) => {
+            let settings = ScanSettings::load(&dirs.preferences()?)?;
+            let result =
+                import_playlist_file(&dirs.database()?, &fs::canonicalize(path)?, &settings)?;
+            output(result)
+        }
+        Command::Stats => output(dirs.database()?.get_library_stats()?),
+        Command::Db(DbCommand::Vacuum) => {
+            dirs.database()?.vacuum()?;
+            output(json!({ "vacuumed": true }))
+        }
+        Command::Db(DbCommand::Check) => {
+            let problems = dirs.database()?.check_integrity()?;
+            let ok = problems.len() == 1 && problems[0] == "ok";
+            Ok((ok, json!({ "ok": ok, "problems": problems })))
+        }
+        Command::Db(DbCommand::Relocate { from, to }) => {
+            let to = dunce::canonicalize(to)?.to_string_lossy().to_string();
+            let changed = relocate_library(&dirs.database()?, &dirs.preferences()?, &from, &to)?;
+            output(json!({ "from": from, "to": to, "paths": changed }))
+        }
+        Command::Db(DbCommand::Duplicates) => output(dirs.database()?.find_duplicates()?),
+        Command::Db(DbCommand::Merge { keep, duplicates }) => {
+            dirs.database()?
+                .merge_songs(keep.clone(), duplicates.clone())?;
+            output(json!({ "kept": keep, "merged": duplicates }))
+        }
+        Command::Theme(ThemeCommand::Install { source }) => {
+            let themes = dirs.themes()?;
+            if source.starts_with("http://") || source.starts_with("https://") {
+                tauri::async_runtime::block_on(themes.download_theme(source.clone()))?;
+            } else {
+                let path = fs::canonicalize(&source)?;
+                themes.import_theme(path.to_string_lossy().to_string())?;
+            }
+            output(json!({ "installed": source }))
+        }
+        Command::Backup(BackupCommand::Create { output: path }) => {
+            let path = std::env::current_dir()?.join(path);
+            let manifest = create_backup(&dirs.database()?, &dirs.backup_paths()?, &path)?;
+            output(json!({ "path": path, "manifest": manifest }))
+        }
+        Command::Backup(BackupCommand::Restore {
+            archive,
+            music_roots,
+        }) => {
+            // Held until the restore is applied so the app can't start in between
+            let _lock = lock_instance(&dirs.data)?;
+
+            let manifest = read_backup_manifest(&archive)?;
+            let mut remaps = HashMap::new();
+            for root in music_roots {
+                match root.split_once('=') {
+                    Some((from, to)) => remaps.insert(from.to_string(), to.to_string()),
+                    None if manifest.music_roots.len() == 1 => {
+                        remaps.insert(manifest.music_roots[0].clone(), root)
+                    }
+                    None => {
+                        return Err(format!(
+                            "Backup has {} music folders, use --music-root OLD=NEW",
+                            manifest.music_roots.len()
+                        )
+                        .into())
+                    }
+                };
+            }
+
+            prepare_restore(&archive, &dirs.backup_paths()?, &remaps)?;
+            apply_pending_restore(&dirs.data)?;
+            output(json!({ "restored": archive, "manifest": manifest }))
+        }
+        Command::Tags(TagsCommand::Fingerprint) => {
+            let settings = ScanSettings::load(&dirs.preferences()?)?;
+            let songs = fingerprint_library(&dirs.database()?, settings.scan_threads)?;
+            output(json!({ "fingerprinted": songs }))
+        }
+        Command::Tags(TagsCommand::Lookup { song_id }) => {
+            let lookup = build_lookup(&dirs.preferences()?);
+            output(tauri::async_runtime::block_on(lookup_tags(
+                &dirs.database()?,
+                &lookup,
+                &song_id,
+            ))?)
+        }
+        Command::Tags(TagsCommand::Apply {
+            song_id,
+            recording_mbid,
+        }) => {
+            let preferences = dirs.preferences()?;
+            let thumbnail_dir: PathBuf =
+                preferences.load_selective("thumbnail_path".to_string())?;
+            let lookup = build_lookup(&preferences);
+            let song = tauri::async_runtime::block_on(async {
+                let suggestion = lookup
+                    .recording(&recording_mbid)
+                    .await?
+                    .ok_or_else(|| format!("Recording {} not found", recording_mbid))?;
+                apply_suggestion(
+                    &dirs.database()?,
+                    &lookup,
+                    &thumbnail_dir,
+                    TagSuggestion {
+                        song_id,
+                        ..suggestion
+                    },
+                )
+                .await
+            })?;
+            output(song)
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use serde_json::json;
+use test_utils::TempDir;
+
+use super::{
+    execute, exit_code, parse_instance_args, AppDirs, Args, BackupCommand, Command, DbCommand,
+    PlaylistCommand, TagsCommand,
+};
+
+/// App directories under a fresh temp dir, so commands never touch the real
+/// library. The directories are removed when the returned guard drops.
+fn temp_dirs(name: &str) -> (TempDir, AppDirs) {
+    let root = TempDir::new(&format!("cli_{}", name));
+    let dirs = AppDirs {
+        data: root.join("data"),
+        local_data: root.join("local_data"),
+        config: root.join("config"),
+    };
+    (root, dirs)
+}
+
+#[test]
+fn test_parse_no_args() {
+    let args = Args::try_parse_from(["moosync"]).unwrap();
+    assert!(!args.mobile);
+    assert!(args.command.is_none());
+    assert!(args.player.inputs.is_empty());
+}
+
+#[test]
+fn test_parse_subcommands() {
+    let args = Args::try_parse_from(["moosync", "scan", "/music"]).unwrap();
+    assert!(
+        matches!(args.command, Some(Command::Scan { path }) if path == PathBuf::from("/music"))
+    );
+
+    let args = Args::try_parse_from(["moosync", "search", "term"]).unwrap();
+    assert!(matches!(args.command, Some(Command::Search { term }) if term == "term"));
+
+    let args = Args::try_parse_from([
+        "moosync",
+        "playlist",
+        "export",
+        "id",
+        "out.m3u",
+        "--relative",
+    ])
+    .unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Playlist(PlaylistCommand::Export { playlist_id, output, relative: true }))
+            if playlist_id == "id" && output == PathBuf::from("out.m3u")
+    ));
+
+    let args = Args::try_parse_from(["moosync", "db", "merge", "keep", "a", "b"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Db(DbCommand::Merge { keep, duplicates }))
+            if keep == "keep" && duplicates == ["a", "b"]
+    ));
+
+    let args = Args::try_parse_from([
+        "moosync",
+        "backup",
+        "restore",
+        "backup.zip",
+        "--music-root",
+        "/old=/new",
+        "--music-root",
+        "/other",
+    ])
+    .unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Backup(BackupCommand::Restore { archive, music_roots }))
+            if archive == PathBuf::from("backup.zip") && music_roots == ["/old=/new", "/other"]
+    ));
+
+    let args = Args::try_parse_from(["moosync", "tags", "apply", "song", "mbid"]).unwrap();
+    assert!(matches!(
+        args.command,
+        Some(Command::Tags(TagsCommand::Apply { song_id, recording_mbid }))
+            if song_id == "song" && recording_mbid == "mbid"
+    ));
+}
+
+#[test]
+fn test_parse_errors() {
+    // Missing arguments
+    assert!(Args::try_parse_from(["moosync", "scan"]).is_err());
+    assert!(Args::try_parse_from(["moosync", "db", "merge", "keep"]).is_err());
+    assert!(Args::try_parse_from(["moosync", "playlist"]).is_err());
+    // Unknown flag
+    assert!(Args::try_parse_from(["moosync", "stats", "--verbose"]).is_err());
+    // Player controls don't mix with subcommands
+    assert!(Args::try_parse_from(["moosync", "--next", "stats"]).is_err());
+}
+
+#[test]
+fn test_execute_output() {
+    let (_root, dirs) = temp_dirs("output");
+
+    let (ok, output) = execute(&dirs, Command::Stats).unwrap();
+    assert!(ok);
+    assert_eq!(output["songs"], json!(0));
+    assert_eq!(output["playlists"], json!(0));
+
+    let (ok, output) = execute(
+        &dirs,
+        Command::Search {
+            term: "nothing".into(),
+        },
+    )
+    .unwrap();
+    assert!(ok);
+    assert_eq!(output["songs"], json!([]));
+
+    let (ok, output) = execute(&dirs, Command::Db(DbCommand::Vacuum)).unwrap();
+    assert!(ok);
+    assert_eq!(output, json!({ "vacuumed": true }));
+
+    let (ok, output) = execute(&dirs, Command::Db(DbCommand::Check)).unwrap();
+    assert!(ok);
+    assert_eq!(output, json!({ "ok": true, "problems": ["ok"] }));
+
+    let (ok, output) = execute(&dirs, Command::Db(DbCommand::Duplicates)).unwrap();
+    assert!(ok);
+    assert_eq!(output["exact"], json!([]));
+}
+
+#[test]
+fn test_execute_errors() {
+    let (_root, dirs) = temp_dirs("errors");
+
+    let result = execute(
+        &dirs,
+        Command::Playlist(PlaylistCommand::Export {
+            playlist_id: "id".into(),
+            output: "playlist.txt".into(),
+            relative: false,
+        }),
+    );
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Unknown playlist format"));
+
+    let result = execute(
+        &dirs,
+        Command::Playlist(PlaylistCommand::Export {
+            playlist_id: "missing".into(),
+            output: dirs.data.join("playlist.m3u"),
+            relative: false,
+        }),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_exit_code() {
+    assert_eq!(exit_code(&Ok((true, json!({})))), 0);
+    // Commands which ran but found problems, e.g. a failed integrity check
+    assert_eq!(exit_code(&Ok((false, json!({ "ok": false })))), 1);
+    assert_eq!(exit_code(&Err("failed".into())), 1);
+
+    let (_root, dirs) = temp_dirs("exit_code");
+    assert_eq!(exit_code(&execute(&dirs, Command::Stats)), 0);
+    assert_eq!(
+        exit_code(&execute(
+            &dirs,
+            Command::Playlist(PlaylistCommand::Export {
+                playlist_id: "id".into(),
+                output: "playlist.txt".into(),
+                relative: false,
+            }),
+        )),
+        1
+    );
+}
+
+fn argv(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn test_parse_player_args() {
+    let args = Args::try_parse_from([
+        "moosync",
+        "--play-pause",
+        "--next",
+        "--enqueue",
+        "a.mp3",
+        "--enqueue",
+        "b.m3u",
+        "--play",
+        "https://example.com/song",
+        "c.flac",
+        "d.flac",
+    ])
+    .unwrap();
+    assert!(args.command.is_none());
+    assert!(args.player.play_pause);
+    assert!(args.player.next);
+    assert_eq!(args.player.enqueue, ["a.mp3", "b.m3u"]);
+    assert_eq!(
+        args.player.play.as_deref(),
+        Some("https://example.com/song")
+    );
+    assert_eq!(args.player.inputs, ["c.flac", "d.flac"]);
+
+    // Only one song can be played right away
+    assert!(Args::try_parse_from(["moosync", "--play", "a.mp3", "--play", "b.mp3"]).is_err());
+}
+
+#[test]
+fn test_parse_instance_args() {
+    let args = parse_instance_args(&argv(&["moosync", "--next", "a.mp3"])).unwrap();
+    assert!(args.next);
+    assert_eq!(args.inputs, ["a.mp3"]);
+
+    let args =
+        parse_instance_args(&argv(&["moosync", "moosync://spotify/login?code=abc"])).unwrap();
+    assert_eq!(args.inputs, ["moosync://spotify/login?code=abc"]);
+}
+
+#[test]
+fn test_parse_instance_args_fallback() {
+    // Flags added by the launcher next to a deep link
+    let args = parse_instance_args(&argv(&[
+        "moosync",
+        "moosync://spotify/login?code=abc",
+        "--launcher-flag",
+    ]))
+    .unwrap();
+    assert_eq!(args.inputs, ["moosync://spotify/login?code=abc"]);
+    assert!(!args.next);
+    assert!(args.play.is_none());
+
+    assert!(parse_instance_args(&argv(&["moosync", "--launcher-flag"])).is_none());
+    assert!(parse_instance_args(&argv(&["moosync", "a.mp3", "--launcher-flag"])).is_none());
+}
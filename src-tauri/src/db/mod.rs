@@ -15,6 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use database::{cache::CacheHolder, database::Database};
+use file_scanner::playlists::{write_playlist, PlaylistFormat};
 use macros::generate_command;
 use serde_json::Value;
 use std::fs;
@@ -22,14 +23,20 @@ use tauri::{App, AppHandle, Manager, State};
 use tracing::{info, trace};
 use types::errors::Result;
 use types::{
+    cache::CacheStats,
     entities::{
-        GetEntityOptions, QueryableAlbum, QueryableArtist, QueryablePlaylist, SearchResult,
+        DuplicateReport, GetEntityOptions, QueryableAlbum, QueryableArtist, QueryablePlaylist,
+        SearchResult, TrackMapping,
     },
     songs::{GetSongOptions, QueryableSong, Song},
+    window::DialogFilter,
 };
 
 use crate::window::handler::WindowHandler;
 
+/// Writes a playlist to a file picked by the user. The format follows the
+/// extension of the file. With `relative`, local songs are written relative
+/// to the playlist file.
 #[tracing::instrument(level = "trace", skip(app, db, window_handler))]
 #[tauri_invoke_proc::parse_tauri_command]
 #[tauri::command(async)]
@@ -38,14 +45,92 @@ pub fn export_playlist(
     db: State<Database>,
     window_handler: State<WindowHandler>,
     id: String,
+    relative: bool,
 ) -> Result<()> {
     info!("Exporting playlist");
-    let exported = db.export_playlist(id)?;
-    let selected_file = window_handler.open_save_file(app)?;
+    let (playlist, songs) = db.get_playlist_with_songs(id)?;
+
+    let filters = PlaylistFormat::ALL
+        .iter()
+        .map(|format| DialogFilter {
+            name: format.name().to_string(),
+            extensions: format.extensions().iter().map(|e| e.to_string()).collect(),
+        })
+        .collect();
+    let mut selected_file = window_handler.open_save_file(
+        app,
+        filters,
+        Some(format!("{}.m3u8", playlist.playlist_name)),
+    )?;
+
+    let format = match PlaylistFormat::from_path(&selected_file) {
+        Some(format) => format,
+        None => {
+            selected_file.set_extension("m3u8");
+            PlaylistFormat::M3u
+        }
+    };
+    let relative_to = if relative {
+        selected_file.parent()
+    } else {
+        None
+    };
+    let exported = write_playlist(format, &playlist.playlist_name, &songs, relative_to)?;
     trace!("Exported playlist");
     Ok(fs::write(selected_file, exported)?)
 }
 
+/// Prefix of the cached `fetch_playback_url` results of a song on `provider`.
+/// Cached commands key their results by the Debug output of their arguments,
+/// which for a song starts with its id.
+#[tracing::instrument(level = "trace", skip())]
+fn playback_url_cache_prefix(provider: &str, song_id: &str) -> String {
+    let song = format!(
+        "{:?}",
+        Song {
+            song: QueryableSong {
+                _id: Some(song_id.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    );
+    let id = format!("_id: {:?}", Some(song_id));
+    let end = song.find(&id).map(|i| i + id.len()).unwrap_or(song.len());
+    format!("fetch_playback_url_{:?}_{}", provider, &song[..end])
+}
+
+/// Stores a track mapping and drops the playback url cached for the old one,
+/// so a changed match is used on the next play.
+#[tracing::instrument(level = "trace", skip(db, cache))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn set_track_mapping(
+    db: State<Database>,
+    cache: State<CacheHolder>,
+    mapping: TrackMapping,
+) -> Result<()> {
+    let prefix = playback_url_cache_prefix(&mapping.provider, &mapping.song_id);
+    db.set_track_mapping(mapping)?;
+    cache.clear_cache(Some(prefix))?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(db, cache))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn remove_track_mapping(
+    db: State<Database>,
+    cache: State<CacheHolder>,
+    song_id: String,
+    provider: String,
+) -> Result<()> {
+    let prefix = playback_url_cache_prefix(&provider, &song_id);
+    db.remove_track_mapping(song_id, provider)?;
+    cache.clear_cache(Some(prefix))?;
+    Ok(())
+}
+
 generate_command!(insert_songs, Database, Vec<Song>, songs: Vec<Song>);
 generate_command!(remove_songs, Database, (), songs: Vec<String>);
 generate_command!(update_song, Database, (), a: QueryableSong);
@@ -64,6 +149,11 @@ generate_command!(update_songs, Database, (), songs: Vec<Song>);
 generate_command!(update_lyrics, Database, (), id: String, lyrics: String);
 generate_command!(increment_play_count, Database, (), id: String);
 generate_command!(increment_play_time, Database, (), id: String, duration: f64);
+generate_command!(get_track_mapping, Database, Option<TrackMapping>, song_id: String, provider: String);
+generate_command!(find_duplicates, Database, DuplicateReport,);
+generate_command!(merge_songs, Database, (), keep: String, duplicates: Vec<String>);
+generate_command!(get_cache_stats, CacheHolder, CacheStats, prefix: Option<String>);
+generate_command!(clear_cache, CacheHolder, usize, prefix: Option<String>);
 
 #[tracing::instrument(level = "trace", skip(app))]
 pub fn get_cache_state(app: &mut App) -> CacheHolder {
@@ -74,7 +164,9 @@ pub fn get_cache_state(app: &mut App) -> CacheHolder {
         }
     }
 
-    CacheHolder::new(path)
+    let cache = CacheHolder::new(path);
+    cache.spawn_maintenance_task();
+    cache
 }
 
 #[tracing::instrument(level = "trace", skip(app))]
@@ -31,9 +31,9 @@ use types::ui::extensions::ExtensionExtraEventArgs;
 use types::ui::extensions::FetchedExtensionManifest;
 use types::ui::extensions::PackageNameArgs;
 
-use crate::providers::handler::ProviderHandler;
+use crate::{providers::handler::ProviderHandler, remote::RemoteControl};
 
-mod request_handler;
+pub mod request_handler;
 
 #[tracing::instrument(level = "trace", skip(app_handle))]
 async fn extension_runner_connected(app_handle: AppHandle) {
@@ -102,10 +102,18 @@ generate_command_async!(
     ExtensionHandler,
     Vec<ExtensionDetail>,
 );
-generate_command_async!(
-    send_extra_event,
-    ExtensionHandler,
-    Value,
-    args: ExtensionExtraEventArgs
-);
 generate_command_async_cached!(get_extension_icon, ExtensionHandler, String, args: PackageNameArgs);
+
+/// Player events reach extensions through here, so the remote control event
+/// stream taps into them as well
+#[tracing::instrument(level = "trace", skip(extension_handler, remote))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub async fn send_extra_event(
+    extension_handler: State<'_, ExtensionHandler>,
+    remote: State<'_, RemoteControl>,
+    args: ExtensionExtraEventArgs,
+) -> Result<Value> {
+    remote.publish(&args.data);
+    extension_handler.send_extra_event(args).await
+}
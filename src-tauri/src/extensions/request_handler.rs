@@ -24,10 +24,13 @@ use types::{
     errors::Result,
     extensions::{MainCommand, MainCommandResponse},
     songs::{GetSongOptions, SearchableSong, Song},
-    ui::extensions::PreferenceData,
+    ui::extensions::{ExtensionOAuthClient, PreferenceData},
 };
 
-use crate::{providers::handler::ProviderHandler, window::handler::WindowHandler};
+use crate::{
+    oauth::handler::OAuthHandler, providers::handler::ProviderHandler, queue::QueueManager,
+    window::handler::WindowHandler,
+};
 
 #[derive(Clone)]
 pub struct ReplyHandler {
@@ -151,10 +154,70 @@ impl ReplyHandler {
         Ok(MainCommandResponse::SetSecure(true))
     }
 
-    #[tracing::instrument(level = "trace", skip(self, _data))]
-    pub fn register_oauth(&self, _data: String) -> Result<MainCommandResponse> {
-        // TODO: Implement oauth registration
-        Ok(MainCommandResponse::RegisterOAuth(false))
+    #[tracing::instrument(level = "trace", skip(self, data))]
+    pub fn register_oauth(&self, data: String) -> Result<MainCommandResponse> {
+        // Tagged as "{package_name}:{redirect}" by the extension runner
+        let Some((package_name, redirect)) = data.split_once(':') else {
+            return Ok(MainCommandResponse::RegisterOAuth(false));
+        };
+
+        let oauth: State<'_, OAuthHandler> = self.app_handle.state();
+        if let Err(e) = oauth.register_extension_redirect(
+            self.app_handle.clone(),
+            package_name.to_string(),
+            redirect.to_string(),
+        ) {
+            tracing::error!(
+                "Failed to register oauth redirect for {}: {:?}",
+                package_name,
+                e
+            );
+            return Ok(MainCommandResponse::RegisterOAuth(false));
+        }
+        Ok(MainCommandResponse::RegisterOAuth(true))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, client))]
+    pub fn register_oauth_client(
+        &self,
+        client: ExtensionOAuthClient,
+    ) -> Result<MainCommandResponse> {
+        let oauth: State<'_, OAuthHandler> = self.app_handle.state();
+        let package_name = client.package_name.clone();
+        if let Err(e) = oauth.register_extension_client(client) {
+            tracing::error!(
+                "Failed to register oauth client for {}: {:?}",
+                package_name,
+                e
+            );
+            return Ok(MainCommandResponse::RegisterOAuthClient(false));
+        }
+        Ok(MainCommandResponse::RegisterOAuthClient(true))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn oauth_login(&self, package_name: String) -> Result<MainCommandResponse> {
+        let oauth: State<'_, OAuthHandler> = self.app_handle.state();
+        let url = oauth.extension_login(&self.app_handle, &package_name)?;
+        Ok(MainCommandResponse::OAuthLogin(url))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn get_oauth_token(&self, package_name: String) -> Result<MainCommandResponse> {
+        let oauth: State<'_, OAuthHandler> = self.app_handle.state();
+        let token = oauth
+            .extension_token(&self.app_handle, &package_name)
+            .await?;
+        Ok(MainCommandResponse::GetOAuthToken(token))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn oauth_signout(&self, package_name: String) -> Result<MainCommandResponse> {
+        let oauth: State<'_, OAuthHandler> = self.app_handle.state();
+        oauth
+            .extension_signout(&self.app_handle, &package_name)
+            .await?;
+        Ok(MainCommandResponse::OAuthSignout(true))
     }
 
     #[tracing::instrument(level = "trace", skip(self, data))]
@@ -208,9 +271,6 @@ impl ReplyHandler {
                 MainCommand::GetTime() => {
                     Ok(MainCommandResponse::GetTime(serde_json::from_str(&data)?))
                 }
-                MainCommand::GetQueue() => {
-                    Ok(MainCommandResponse::GetQueue(serde_json::from_str(&data)?))
-                }
                 _ => Err("Not a ui request".into()),
             },
             Err(_) => Err("Failed to get response from UI".into()),
@@ -236,8 +296,11 @@ impl ReplyHandler {
             MainCommand::GetCurrentSong()
             | MainCommand::GetPlayerState()
             | MainCommand::GetVolume()
-            | MainCommand::GetTime()
-            | MainCommand::GetQueue() => self.send_ui_request(command).await?,
+            | MainCommand::GetTime() => self.send_ui_request(command).await?,
+            MainCommand::GetQueue() => {
+                let queue: State<QueueManager> = self.app_handle.state();
+                MainCommandResponse::GetQueue(serde_json::to_value(queue.get_queue()?)?)
+            }
             MainCommand::GetPreference(preference_data) => self.get_preferences(preference_data)?,
             MainCommand::SetPreference(preference_data) => self.set_preferences(preference_data)?,
             MainCommand::GetSecure(preference_data) => self.get_secure(preference_data)?,
@@ -253,6 +316,10 @@ impl ReplyHandler {
                 add_to_playlist_request.songs,
             )?,
             MainCommand::RegisterOAuth(url) => self.register_oauth(url)?,
+            MainCommand::RegisterOAuthClient(client) => self.register_oauth_client(client)?,
+            MainCommand::OAuthLogin(package_name) => self.oauth_login(package_name)?,
+            MainCommand::GetOAuthToken(package_name) => self.get_oauth_token(package_name).await?,
+            MainCommand::OAuthSignout(package_name) => self.oauth_signout(package_name).await?,
             MainCommand::OpenExternalUrl(url) => self.open_external(url)?,
             MainCommand::UpdateAccounts(key) => self.update_accounts(key)?,
             MainCommand::ExtensionsUpdated() => self.extension_updated().await?,
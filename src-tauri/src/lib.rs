@@ -17,8 +17,17 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 use std::fs;
+#[cfg(desktop)]
+use std::path::PathBuf;
 
+use backup::{
+    apply_pending_restore, create_library_backup, get_backup_manifest, lock_instance,
+    restore_library_backup,
+};
+#[cfg(desktop)]
 use clap::Parser;
+#[cfg(desktop)]
+use cli::{handle_player_args, parse_instance_args, Args};
 use db::is_song_in_playlist;
 use extensions::get_extension_state;
 use librespot::{
@@ -31,15 +40,18 @@ use lyrics::{get_lyrics, get_lyrics_state};
 use mobile_player::{
     mobile_load, mobile_pause, mobile_play, mobile_seek, mobile_stop, MobilePlayer,
 };
-use mpris::{get_mpris_state, set_metadata, set_playback_state, set_position};
+use mpris::{
+    get_mpris_state, media_listener_ready, set_loop_status, set_metadata, set_playback_state,
+    set_position, set_shuffle, set_tracklist, set_volume, PendingMediaEvents,
+};
 use preference_holder::{
     get_preference_state, get_secure, handle_pref_changes, initial, load_selective,
     load_selective_array, save_selective, set_secure,
 };
 use providers::handler::get_provider_handler_state;
 use rodio::{
-    get_rodio_state, rodio_get_volume, rodio_load, rodio_pause, rodio_play, rodio_seek,
-    rodio_set_volume, rodio_stop,
+    get_rodio_state, rodio_clear_cache, rodio_get_output_devices, rodio_get_volume, rodio_load,
+    rodio_pause, rodio_play, rodio_seek, rodio_set_output_device, rodio_set_volume, rodio_stop,
 };
 use themes::{
     download_theme, export_theme, get_css, get_theme_handler_state, get_themes_manifest,
@@ -51,20 +63,29 @@ use extensions::{
     install_extension, remove_extension, send_extra_event,
 };
 use providers::handler::{
-    fetch_playback_url, fetch_playlist_content, fetch_user_playlists, get_album_content,
-    get_all_status, get_artist_content, get_provider_key_by_id, get_provider_keys, get_suggestions,
-    initialize_all_providers, match_url, playlist_from_url, provider_authorize, provider_login,
-    provider_search, provider_signout, song_from_url,
+    cancel_provider_requests, fetch_playback_url, fetch_playlist_content, fetch_user_playlists,
+    get_album_content, get_all_status, get_artist_content, get_provider_key_by_id,
+    get_provider_keys, get_suggestions, initialize_all_providers, match_url, playlist_from_url,
+    provider_authorize, provider_login, provider_search, provider_signout, search_everywhere,
+    song_from_url, switch_account,
+};
+use queue::{
+    get_queue, get_queue_state, queue_add, queue_clear, queue_clear_except_current, queue_move,
+    queue_next, queue_play_next, queue_play_now, queue_prev, queue_remove, queue_replace,
+    queue_set_index, queue_set_repeat, queue_set_shuffle,
 };
-use scanner::{get_scanner_state, start_scan, ScanTask};
-use tauri::{Listener, Manager, State};
+use remote::{configure_remote_control, get_remote_state};
+use scanner::{get_scanner_state, import_playlist, relocate_music_folder, start_scan, ScanTask};
+use tagger::{apply_tag_suggestion, get_tagger_state, lookup_song_tags};
+use tauri::{Listener, Manager};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
     filter::EnvFilter,
     fmt::{self},
     layer::SubscriberExt,
 };
-use window::handler::{build_tray_menu, handle_window_close};
+use window::handler::handle_window_close;
+use window::tray::{add_tray_recent_playlist, build_tray_menu, get_tray_state, set_tray_labels};
 
 #[cfg(mobile)]
 use tauri_plugin_audioplayer::AudioplayerExt;
@@ -73,13 +94,15 @@ use {
     db::{
         get_cache_state,
         {
-            add_to_playlist, create_playlist, export_playlist, get_db_state, get_entity_by_options,
-            get_songs_by_options, increment_play_count, increment_play_time, insert_songs,
-            remove_from_playlist, remove_playlist, remove_songs, search_all, update_album,
-            update_artist, update_lyrics, update_playlist, update_song, update_songs,
+            add_to_playlist, clear_cache, create_playlist, export_playlist, find_duplicates,
+            get_cache_stats, get_db_state, get_entity_by_options, get_songs_by_options,
+            get_track_mapping, increment_play_count, increment_play_time, insert_songs,
+            merge_songs, remove_from_playlist, remove_playlist, remove_songs, remove_track_mapping,
+            search_all, set_track_mapping, update_album, update_artist, update_lyrics,
+            update_playlist, update_song, update_songs,
         },
     },
-    oauth::handler::{get_oauth_state, OAuthHandler},
+    oauth::handler::get_oauth_state,
     window::handler::{
         close_window, disable_fullscreen, enable_fullscreen, get_platform, get_window_state,
         has_frame, is_maximized, maximize_window, minimize_window, open_external,
@@ -89,6 +112,9 @@ use {
     youtube::get_youtube_scraper_state,
 };
 
+mod backup;
+#[cfg(desktop)]
+mod cli;
 mod db;
 mod extensions;
 mod librespot;
@@ -99,20 +125,15 @@ mod mpris;
 mod oauth;
 mod preference_holder;
 mod providers;
+mod queue;
+mod remote;
 mod rodio;
 mod scanner;
+mod tagger;
 mod themes;
 mod window;
 mod youtube;
 
-#[cfg(desktop)]
-#[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
-struct Args {
-    #[arg(short, long)]
-    mobile: bool,
-}
-
 #[tracing::instrument(level = "trace", skip())]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -129,6 +150,15 @@ pub fn run() {
     #[cfg(desktop)]
     let args = Args::parse();
 
+    // Subcommands run headless and never open a window
+    #[cfg(desktop)]
+    if let Some(command) = args.command {
+        std::process::exit(cli::run(command));
+    }
+
+    #[cfg(desktop)]
+    let player_args = args.player;
+
     let mut builder = tauri::Builder::default();
 
     #[cfg(desktop)]
@@ -140,11 +170,10 @@ pub fn run() {
 
         builder = builder
             .plugin(tauri_plugin_updater::Builder::new().build())
-            .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
-                if let Some(url) = argv.get(1) {
-                    tracing::info!("Got url {}", url);
-                    let state: State<OAuthHandler> = app.state();
-                    state.handle_oauth(app.clone(), url.to_string()).unwrap();
+            .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+                tracing::info!("Got args from another instance {:?}", argv);
+                if let Some(args) = parse_instance_args(&argv) {
+                    handle_player_args(app, args, PathBuf::from(cwd));
                 }
             }))
             .plugin(tauri_plugin_dialog::init())
@@ -223,7 +252,19 @@ pub fn run() {
             update_lyrics,
             increment_play_count,
             increment_play_time,
+            get_track_mapping,
+            set_track_mapping,
+            remove_track_mapping,
+            find_duplicates,
+            merge_songs,
             export_playlist,
+            // Backup
+            create_library_backup,
+            get_backup_manifest,
+            restore_library_backup,
+            // Cache
+            get_cache_stats,
+            clear_cache,
             // Window
             is_maximized,
             has_frame,
@@ -240,8 +281,27 @@ pub fn run() {
             toggle_dev_tools,
             restart_app,
             open_file_browser,
+            add_tray_recent_playlist,
+            set_tray_labels,
+            // Queue
+            get_queue,
+            queue_add,
+            queue_play_now,
+            queue_play_next,
+            queue_replace,
+            queue_remove,
+            queue_move,
+            queue_set_index,
+            queue_next,
+            queue_prev,
+            queue_clear,
+            queue_clear_except_current,
+            queue_set_repeat,
+            queue_set_shuffle,
             // Scanner
             start_scan,
+            import_playlist,
+            relocate_music_folder,
             // Librespot
             is_initialized,
             librespot_play,
@@ -264,11 +324,19 @@ pub fn run() {
             get_themes_manifest,
             download_theme,
             // MPRIS
+            media_listener_ready,
             set_metadata,
             set_playback_state,
             set_position,
+            set_volume,
+            set_loop_status,
+            set_shuffle,
+            set_tracklist,
             // Lyrics
             get_lyrics,
+            // Tagger
+            lookup_song_tags,
+            apply_tag_suggestion,
             // Extensions
             install_extension,
             remove_extension,
@@ -283,7 +351,10 @@ pub fn run() {
             provider_login,
             provider_signout,
             provider_authorize,
+            switch_account,
             get_provider_key_by_id,
+            cancel_provider_requests,
+            search_everywhere,
             fetch_user_playlists,
             fetch_playlist_content,
             fetch_playback_url,
@@ -303,6 +374,9 @@ pub fn run() {
             rodio_seek,
             rodio_set_volume,
             rodio_stop,
+            rodio_get_output_devices,
+            rodio_set_output_device,
+            rodio_clear_cache,
             // Logger
             renderer_write,
             // Mobile player
@@ -334,6 +408,19 @@ pub fn run() {
 
             // tracing::subscriber::set_global_default(subscriber).unwrap();
 
+            let data_dir = app.path().app_data_dir()?;
+            match lock_instance(&data_dir) {
+                Ok(lock) => {
+                    app.manage(lock);
+                }
+                Err(e) => tracing::warn!("Failed to lock app data: {}", e),
+            }
+
+            // A restore is staged while the old library is open and swapped in here
+            if let Err(e) = apply_pending_restore(&data_dir) {
+                tracing::error!("Failed to restore backup: {}", e);
+            }
+
             let db = get_db_state(app);
             app.manage(db);
 
@@ -364,12 +451,25 @@ pub fn run() {
             let theme_handler_state = get_theme_handler_state(app);
             app.manage(theme_handler_state);
 
+            let tray_state = get_tray_state(app.app_handle().clone());
+            app.manage(tray_state);
+
+            app.manage(PendingMediaEvents::default());
             let mpris_state = get_mpris_state(app.app_handle().clone())?;
             app.manage(mpris_state);
 
-            let lyrics_state = get_lyrics_state();
+            let lyrics_state = get_lyrics_state(app);
             app.manage(lyrics_state);
 
+            let tagger_state = get_tagger_state(app);
+            app.manage(tagger_state);
+
+            let queue_state = get_queue_state(app.app_handle().clone());
+            app.manage(queue_state);
+
+            let remote_state = get_remote_state(app.app_handle().clone());
+            app.manage(remote_state);
+
             let provider_handler_state = get_provider_handler_state(app.app_handle().clone());
             app.manage(provider_handler_state);
 
@@ -400,6 +500,14 @@ pub fn run() {
 
             initial(app);
             handle_pref_changes(app.handle().clone());
+            configure_remote_control(app.handle());
+
+            #[cfg(desktop)]
+            handle_player_args(
+                app.handle(),
+                player_args,
+                std::env::current_dir().unwrap_or_default(),
+            );
 
             app.listen("deep-link://new-url", |url| {
                 tracing::info!("got url {:?}", url);
@@ -16,18 +16,35 @@
 
 use database::cache::CacheHolder;
 use librespot::LibrespotHolder;
-use lyrics::LyricsFetcher;
-use tauri::State;
-use types::errors::Result;
+use lyrics::{LyricsFetcher, LyricsQuery};
+use preferences::preferences::PreferenceConfig;
+use tauri::{App, Manager, State};
+use types::{errors::Result, lyrics::Lyrics, preferences::CheckboxPreference};
 
-#[tracing::instrument(level = "trace", skip())]
-pub fn get_lyrics_state() -> LyricsFetcher {
-    LyricsFetcher::new()
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn get_lyrics_state(app: &App) -> LyricsFetcher {
+    let lyrics = LyricsFetcher::new();
+    configure_lyrics_sources(&lyrics, app.state::<PreferenceConfig>().inner());
+    lyrics
 }
 
+/// Applies the source order and LRCLIB url from preferences
+#[tracing::instrument(level = "trace", skip(lyrics, preferences))]
+pub fn configure_lyrics_sources(lyrics: &LyricsFetcher, preferences: &PreferenceConfig) {
+    let order = preferences
+        .load_selective::<Vec<CheckboxPreference>>("lyrics_sources".into())
+        .unwrap_or_default();
+    let lrclib_url = preferences
+        .load_selective::<String>("lrclib_url".into())
+        .ok()
+        .filter(|url| !url.trim().is_empty());
+    lyrics.configure(&order, lrclib_url);
+}
+
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(
     level = "trace",
-    skip(lyrics, librespot, cache, id, url, artists, title)
+    skip(lyrics, librespot, cache, id, url, artists, title, album, duration)
 )]
 #[tauri_invoke_proc::parse_tauri_command]
 #[tauri::command()]
@@ -39,18 +56,17 @@ pub async fn get_lyrics(
     url: String,
     artists: Vec<String>,
     title: String,
-) -> Result<String> {
-    let cache_string = format!("get_lyrics_{}_{}_{:?}_{}", id, url, artists, title);
-
-    let cached = cache.get(&cache_string);
-    if cached.is_ok() {
-        return cached;
-    }
-
-    let res = lyrics
-        .get_lyrics(librespot.inner(), id, url, artists, title)
-        .await?;
-
-    let _ = cache.set(cache_string.as_str(), &res, 7200);
-    Ok(res)
+    album: Option<String>,
+    duration: Option<f64>,
+) -> Result<Lyrics> {
+    let query = LyricsQuery {
+        id,
+        url,
+        artists,
+        title,
+        album,
+        duration,
+        librespot: Some(librespot.inner()),
+    };
+    lyrics.get_lyrics(Some(cache.inner()), &query).await
 }
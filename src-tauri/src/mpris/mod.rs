@@ -14,13 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::thread;
+use std::{sync::Mutex, thread};
 
+use crate::window::tray::TrayHandler;
 use macros::generate_command;
-use mpris::MprisHolder;
+use mpris::{MediaControlEvent, MprisHolder, SeekDirection};
 use serde_json::{json, Value};
-use tauri::{AppHandle, Emitter, State};
-use types::{errors::Result, mpris::MprisPlayerDetails, ui::player_details::PlayerState};
+use tauri::{AppHandle, Emitter, Manager, State};
+use types::{
+    errors::Result,
+    mpris::MprisPlayerDetails,
+    ui::player_details::{PlayerState, RepeatModes},
+};
+
+// Amount to seek by when the OS doesn't specify one
+const DEFAULT_SEEK_SECS: f64 = 5f64;
 
 #[tracing::instrument(level = "trace", skip(app))]
 pub fn get_mpris_state(app: AppHandle) -> Result<MprisHolder> {
@@ -35,37 +43,135 @@ pub fn get_mpris_state(app: AppHandle) -> Result<MprisHolder> {
         loop {
             tracing::trace!("Waiting for mpris events");
             let event = receiver.recv().unwrap();
-            let data = match event {
-                mpris::MediaControlEvent::Play => (0, Value::Null),
-                mpris::MediaControlEvent::Pause => (1, Value::Null),
-                mpris::MediaControlEvent::Toggle => (13, Value::Null),
-                mpris::MediaControlEvent::Next => (6, Value::Null),
-                mpris::MediaControlEvent::Previous => (7, Value::Null),
-                mpris::MediaControlEvent::Stop => (2, Value::Null),
-                mpris::MediaControlEvent::Seek(_) => (12, json!(0)),
-                mpris::MediaControlEvent::SeekBy(_dir, pos) => {
-                    // match dir {
-                    //     mpris::SeekDirection::Forward => (12, json!(pos.as_secs())),
-                    //     mpris::SeekDirection::Backward => {
-                    //         (12, json!((pos.as_secs() as i128).neg()))
-                    //     }
-                    // }
-                    (12, json!(pos.as_secs()))
-                }
-                mpris::MediaControlEvent::SetPosition(pos) => (12, json!(pos.0.as_secs())),
-
-                mpris::MediaControlEvent::SetVolume(vol) => (15, json!(vol)),
-                mpris::MediaControlEvent::OpenUri(uri) => (16, Value::String(uri)),
-                mpris::MediaControlEvent::Raise => (17, Value::Null),
-                mpris::MediaControlEvent::Quit => (18, Value::Null),
-            };
-            let _ = app.emit("media_button_press", data);
+            let _ = send_media_event(&app, event);
         }
     });
 
     Ok(mpris_holder)
 }
 
-generate_command!(set_metadata, MprisHolder, (), metadata: MprisPlayerDetails);
-generate_command!(set_playback_state, MprisHolder, (), state: PlayerState);
+/// Events sent before the UI listens for them, e.g. playing files the app
+/// was started with. `None` once the UI is listening.
+pub struct PendingMediaEvents(Mutex<Option<Vec<(i32, Value)>>>);
+
+impl Default for PendingMediaEvents {
+    fn default() -> Self {
+        Self(Mutex::new(Some(vec![])))
+    }
+}
+
+/// Sends the events held back so far. Called by the UI once it listens
+#[tracing::instrument(level = "trace", skip(app, pending))]
+#[tauri::command]
+#[tauri_invoke_proc::parse_tauri_command]
+pub fn media_listener_ready(app: AppHandle, pending: State<PendingMediaEvents>) -> Result<()> {
+    // Held while emitting so newer events can't overtake the pending ones
+    let mut pending = pending.0.lock().unwrap();
+    for data in pending.take().unwrap_or_default() {
+        app.emit("media_button_press", data)?;
+    }
+    Ok(())
+}
+
+/// Forwards a media control event to the UI. Used for the OS media controls
+/// and anything else that drives the player from outside
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn send_media_event(app: &AppHandle, event: MediaControlEvent) -> Result<()> {
+    let data = match event {
+        MediaControlEvent::Play => (0, Value::Null),
+        MediaControlEvent::Pause => (1, Value::Null),
+        MediaControlEvent::Toggle => (13, Value::Null),
+        MediaControlEvent::Next => (6, Value::Null),
+        MediaControlEvent::Previous => (7, Value::Null),
+        MediaControlEvent::Stop => (2, Value::Null),
+        MediaControlEvent::Seek(dir) => match dir {
+            SeekDirection::Forward => (19, json!(DEFAULT_SEEK_SECS)),
+            SeekDirection::Backward => (19, json!(-DEFAULT_SEEK_SECS)),
+        },
+        MediaControlEvent::SeekBy(dir, pos) => match dir {
+            SeekDirection::Forward => (19, json!(pos.as_secs_f64())),
+            SeekDirection::Backward => (19, json!(-pos.as_secs_f64())),
+        },
+        MediaControlEvent::SetPosition(pos) => (12, json!(pos.0.as_secs_f64())),
+
+        // The UI works with volume in the range 0-100
+        MediaControlEvent::SetVolume(vol) => (15, json!(vol * 100f64)),
+        MediaControlEvent::OpenUri(uri) => (16, Value::String(uri)),
+        MediaControlEvent::Raise => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            (17, Value::Null)
+        }
+        MediaControlEvent::Quit => {
+            app.exit(0);
+            (18, Value::Null)
+        }
+        MediaControlEvent::SetShuffle(shuffle) => (20, json!(shuffle)),
+        MediaControlEvent::SetLoopStatus(mode) => (21, json!(mode)),
+        MediaControlEvent::GoTo(index) => (22, json!(index)),
+    };
+
+    if let Some(pending) = app.try_state::<PendingMediaEvents>() {
+        let mut pending = pending.0.lock().unwrap();
+        if let Some(events) = pending.as_mut() {
+            events.push(data);
+            return Ok(());
+        }
+    }
+    app.emit("media_button_press", data)?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(mpris, tray))]
+#[tauri::command]
+#[tauri_invoke_proc::parse_tauri_command]
+pub fn set_metadata(
+    mpris: State<MprisHolder>,
+    tray: State<TrayHandler>,
+    metadata: MprisPlayerDetails,
+) -> Result<()> {
+    tray.set_metadata(metadata.clone())?;
+    mpris.set_metadata(metadata)
+}
+
+#[tracing::instrument(level = "trace", skip(mpris, tray))]
+#[tauri::command]
+#[tauri_invoke_proc::parse_tauri_command]
+pub fn set_playback_state(
+    mpris: State<MprisHolder>,
+    tray: State<TrayHandler>,
+    state: PlayerState,
+) -> Result<()> {
+    tray.set_playback_state(state)?;
+    mpris.set_playback_state(state)
+}
+
+#[tracing::instrument(level = "trace", skip(mpris, tray))]
+#[tauri::command]
+#[tauri_invoke_proc::parse_tauri_command]
+pub fn set_loop_status(
+    mpris: State<MprisHolder>,
+    tray: State<TrayHandler>,
+    mode: RepeatModes,
+) -> Result<()> {
+    tray.set_loop_status(mode)?;
+    mpris.set_loop_status(mode)
+}
+
+#[tracing::instrument(level = "trace", skip(mpris, tray))]
+#[tauri::command]
+#[tauri_invoke_proc::parse_tauri_command]
+pub fn set_shuffle(
+    mpris: State<MprisHolder>,
+    tray: State<TrayHandler>,
+    shuffle: bool,
+) -> Result<()> {
+    tray.set_shuffle(shuffle)?;
+    mpris.set_shuffle(shuffle)
+}
+
 generate_command!(set_position, MprisHolder, (), duration: f64);
+generate_command!(set_volume, MprisHolder, (), volume: f64);
+generate_command!(set_tracklist, MprisHolder, (), tracks: Vec<MprisPlayerDetails>);
@@ -14,25 +14,128 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use tauri::{AppHandle, Manager, State};
+use oauth2::{
+    basic::BasicClient, reqwest::async_http_client, AuthUrl, AuthorizationCode, ClientId,
+    ClientSecret, CsrfToken, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken, Scope,
+    TokenResponse, TokenUrl,
+};
+use preferences::preferences::PreferenceConfig;
+use serde::{Deserialize, Serialize};
+use tauri::{async_runtime::JoinHandle, AppHandle, Manager, State};
 use url::Url;
 
-use types::errors::Result;
+use types::{
+    errors::{MoosyncError, Result},
+    oauth::{OAuth2Client, OAuthTokenResponse},
+    ui::extensions::{ExtensionOAuthClient, ExtensionOAuthToken},
+};
 
-use crate::providers::handler::ProviderHandler;
+use crate::{providers::handler::ProviderHandler, window::handler::WindowHandler};
 
+use super::loopback;
+
+// Access tokens are renewed this long before they expire
+const REFRESH_MARGIN: Duration = Duration::from_secs(120);
+// Lower bound between two refreshes, guards against tokens without an expiry
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolder {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: u64,
+    pub expires_at: i64,
+}
+
+/// Describes the authorization code flow of a provider
+#[derive(Debug, Clone, Default)]
+pub struct OAuthConfig {
+    pub auth_url: String,
+    pub token_url: String,
+    pub redirect_uri: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub scopes: Vec<String>,
+    pub extra_params: Vec<(String, String)>,
+    /// Host of the moosync:// deep link the redirect ends up at
+    pub callback_path: String,
+    /// Secure preference holding the signed in accounts
+    pub token_key: String,
+    /// Secure preference holding the refresh token of the single account older versions kept
+    pub legacy_token_key: Option<String>,
+    /// Refresh only when a token is asked for instead of ahead of expiry.
+    /// Used for extensions, which fetch tokens through [OAuthHandler::extension_token]
+    pub on_demand_refresh: bool,
+}
+
+/// Id given to the account migrated from [OAuthConfig::legacy_token_key]
+/// until the provider learns its actual id
+pub const LEGACY_ACCOUNT_ID: &str = "default";
+
+/// Extensions get a single account, stored under this id
+pub const EXTENSION_ACCOUNT_ID: &str = "default";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredAccount {
+    pub id: String,
+    pub user_name: Option<String>,
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredAccounts {
+    pub active: Option<String>,
+    pub accounts: Vec<StoredAccount>,
+}
+
+impl OAuthConfig {
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn client(&self) -> Result<OAuth2Client> {
+        let client_secret = self
+            .client_secret
+            .clone()
+            .filter(|s| !s.is_empty())
+            .map(ClientSecret::new);
+
+        Ok(BasicClient::new(
+            ClientId::new(self.client_id.clone()),
+            client_secret,
+            AuthUrl::new(self.auth_url.clone()).map_err(|e| e.to_string())?,
+            Some(TokenUrl::new(self.token_url.clone()).map_err(|e| e.to_string())?),
+        )
+        .set_redirect_uri(RedirectUrl::new(self.redirect_uri.clone()).map_err(|e| e.to_string())?))
+    }
+}
+
+struct PendingLogin {
+    verifier: PkceCodeVerifier,
+    csrf: CsrfToken,
+}
+
+/// Runs OAuth flows on behalf of providers and extensions.
+///
+/// Deep links (or loopback redirects) are routed by their host to the
+/// provider which registered it, which then calls [OAuthHandler::authorize].
+#[derive(Default)]
 pub struct OAuthHandler {
     pub oauth_map: Mutex<HashMap<String, String>>,
+    configs: Mutex<HashMap<String, OAuthConfig>>,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+    refresh_tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+    // Last access tokens handed out to extensions
+    tokens: Mutex<HashMap<String, TokenHolder>>,
 }
 
 impl OAuthHandler {
     #[tracing::instrument(level = "trace", skip())]
     pub fn new() -> OAuthHandler {
-        OAuthHandler {
-            oauth_map: Mutex::new(HashMap::new()),
-        }
+        OAuthHandler::default()
     }
 
     #[tracing::instrument(level = "trace", skip(self, path, key))]
@@ -47,26 +150,514 @@ impl OAuthHandler {
         oauth_map.remove(&path);
     }
 
+    /// Routes redirects for an extension which runs its own flow.
+    /// `redirect` is either a deep link host or a loopback URL to listen on.
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn register_extension_redirect(
+        &self,
+        app: AppHandle,
+        package_name: String,
+        redirect: String,
+    ) -> Result<()> {
+        let key = extension_key(&package_name);
+        // The extension takes over the flow, redirects are forwarded to it again
+        self.configs.lock().unwrap().remove(&key);
+        self.pending.lock().unwrap().remove(&key);
+        if loopback::is_loopback(&redirect) {
+            let callback_path = format!("{}oauthcallback", package_name);
+            loopback::listen(app, &redirect, callback_path.clone())?;
+            self.register_oauth_path(callback_path, key);
+        } else {
+            self.register_oauth_path(redirect, key);
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "trace", skip(self, app, url))]
     pub fn handle_oauth(&self, app: AppHandle, url: String) -> Result<()> {
         let oauth_map = self.oauth_map.lock().unwrap();
-        let url_parsed = Url::parse(url.as_str()).unwrap();
-        let path = url_parsed.host_str().unwrap();
-        if let Some(key) = oauth_map.get(path) {
-            let app = app.clone();
-            let key = key.clone();
+        let url_parsed = Url::parse(url.as_str()).map_err(|e| e.to_string())?;
+        let Some(path) = url_parsed.host_str() else {
+            return Err(format!("No oauth path in {}", url).into());
+        };
+        let Some(key) = oauth_map.get(path) else {
+            return Err(format!("No pending oauth login for {}", path).into());
+        };
+        let key = key.clone();
+        if key.starts_with("extension:") && self.configs.lock().unwrap().contains_key(&key) {
             tauri::async_runtime::spawn(async move {
-                tracing::info!("Authorizing {}", key);
-                let provider_handler: State<ProviderHandler> = app.state();
-                let err = provider_handler.provider_authorize(key.clone(), url).await;
-                if let Err(err) = err {
+                tracing::info!("Completing oauth login of {}", key);
+                let oauth: State<OAuthHandler> = app.state();
+                if let Err(err) = oauth.complete_extension_login(&app, &key, url).await {
                     tracing::error!("Error authorizing {}: {:?}", key, err);
                 }
             });
+            return Ok(());
         }
+        tauri::async_runtime::spawn(async move {
+            tracing::info!("Authorizing {}", key);
+            let provider_handler: State<ProviderHandler> = app.state();
+            let err = provider_handler.provider_authorize(key.clone(), url).await;
+            if let Err(err) = err {
+                tracing::error!("Error authorizing {}: {:?}", key, err);
+            }
+        });
 
         Ok(())
     }
+
+    /// Sets the flow used by `key`. Any login in progress is dropped.
+    #[tracing::instrument(level = "trace", skip(self, config))]
+    pub fn register_client(&self, key: String, config: OAuthConfig) {
+        self.pending.lock().unwrap().remove(&key);
+        self.configs.lock().unwrap().insert(key, config);
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn config(&self, key: &str) -> Result<OAuthConfig> {
+        self.configs
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or(format!("No oauth client registered for {}", key).into())
+    }
+
+    /// Starts a PKCE authorization code flow and opens the consent page
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn login(&self, app: &AppHandle, key: &str) -> Result<String> {
+        let (auth_url, config) = self.begin_login(key)?;
+
+        if loopback::is_loopback(&config.redirect_uri) {
+            // A listener from an earlier attempt may still hold the port, it forwards just the same
+            if let Err(e) =
+                loopback::listen(app.clone(), &config.redirect_uri, config.callback_path)
+            {
+                tracing::warn!("Could not listen for oauth redirect: {:?}", e);
+            }
+        }
+
+        tracing::info!("Opening url {:?}", auth_url);
+        let window: State<WindowHandler> = app.state();
+        if let Err(e) = window.inner().open_external(app.clone(), auth_url.clone()) {
+            tracing::error!("Error opening URL: {:?}", e);
+        }
+        Ok(auth_url)
+    }
+
+    /// Builds the consent page url and remembers the PKCE verifier and state
+    /// the redirect has to match
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn begin_login(&self, key: &str) -> Result<(String, OAuthConfig)> {
+        let config = self.config(key)?;
+        if config.client_id.is_empty() {
+            return Err("Client ID not set".into());
+        }
+
+        let (pkce_challenge, verifier) = PkceCodeChallenge::new_random_sha256();
+        let client = config.client()?;
+        let mut builder = client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(config.scopes.iter().map(|s| Scope::new(s.clone())))
+            .set_pkce_challenge(pkce_challenge);
+        for (param, value) in config.extra_params.iter() {
+            builder = builder.add_extra_param(param, value);
+        }
+        let (auth_url, csrf) = builder.url();
+
+        self.pending
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), PendingLogin { verifier, csrf });
+        self.register_oauth_path(config.callback_path.clone(), key.to_string());
+
+        Ok((auth_url.to_string(), config))
+    }
+
+    /// Completes a login started by [OAuthHandler::login] with the redirect it received.
+    /// The caller decides which account the tokens belong to, see [OAuthHandler::add_account].
+    #[tracing::instrument(level = "trace", skip(self, redirect))]
+    pub async fn authorize(&self, key: &str, redirect: String) -> Result<TokenHolder> {
+        let config = self.config(key)?;
+
+        let parsed = if redirect.starts_with("?") {
+            Url::parse(format!("https://moosync.app/login{}", redirect).as_str())
+        } else {
+            Url::parse(redirect.as_str())
+        }
+        .map_err(|e| e.to_string())?;
+        let query: HashMap<_, _> = parsed.query_pairs().into_owned().collect();
+
+        // A redirect with the wrong state must not cancel the login it doesn't belong to
+        let pending = {
+            let mut pending = self.pending.lock().unwrap();
+            let Some(login) = pending.get(key) else {
+                return Err("OAuth not initiated".into());
+            };
+            if query.get("state") != Some(login.csrf.secret()) {
+                return Err("OAuth state mismatch, ignoring redirect".into());
+            }
+            pending.remove(key).unwrap()
+        };
+        self.unregister_oauth_path(config.callback_path.clone());
+
+        if let Some(error) = query.get("error") {
+            return Err(format!(
+                "Authorization denied: {} {}",
+                error,
+                query.get("error_description").cloned().unwrap_or_default()
+            )
+            .into());
+        }
+
+        let Some(code) = query.get("code") else {
+            return Err("No authorization code in redirect".into());
+        };
+
+        let res = config
+            .client()?
+            .exchange_code(AuthorizationCode::new(code.clone()))
+            .set_pkce_verifier(pending.verifier)
+            .request_async(async_http_client)
+            .await
+            .map_err(map_token_error)?;
+
+        token_holder(res, None)
+    }
+
+    /// Accounts of `key` kept in secure storage
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn accounts(&self, app: &AppHandle, key: &str) -> Result<StoredAccounts> {
+        let config = self.config(key)?;
+        let preferences: State<PreferenceConfig> = app.state();
+
+        let stored: Result<String> = preferences.inner().get_secure(config.token_key.clone());
+        match stored {
+            Ok(stored) => return Ok(serde_json::from_str(&stored)?),
+            Err(e) => tracing::debug!("No accounts stored for {}: {:?}", key, e),
+        }
+
+        // Older versions kept the refresh token of a single account
+        if let Some(legacy_key) = config.legacy_token_key {
+            let refresh_token: Result<String> = preferences.inner().get_secure(legacy_key);
+            if let Ok(refresh_token) = refresh_token {
+                if !refresh_token.is_empty() {
+                    return Ok(StoredAccounts {
+                        active: Some(LEGACY_ACCOUNT_ID.into()),
+                        accounts: vec![StoredAccount {
+                            id: LEGACY_ACCOUNT_ID.into(),
+                            user_name: None,
+                            refresh_token,
+                        }],
+                    });
+                }
+            }
+        }
+
+        Ok(StoredAccounts::default())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, app, accounts))]
+    fn save_accounts(&self, app: &AppHandle, key: &str, accounts: &StoredAccounts) -> Result<()> {
+        let config = self.config(key)?;
+        let preferences: State<PreferenceConfig> = app.state();
+        preferences.set_secure(config.token_key, Some(serde_json::to_string(accounts)?))?;
+
+        if let Some(legacy_key) = config.legacy_token_key {
+            let legacy: Result<String> = preferences.inner().get_secure(legacy_key.clone());
+            if legacy.is_ok() {
+                preferences.set_secure(legacy_key, None::<String>)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stores a freshly authorized account of `key` and makes it the active one
+    #[tracing::instrument(level = "trace", skip(self, app, tokens))]
+    pub fn add_account(
+        &self,
+        app: &AppHandle,
+        key: &str,
+        account_id: &str,
+        user_name: Option<String>,
+        tokens: &TokenHolder,
+    ) -> Result<()> {
+        let mut accounts = self.accounts(app, key)?;
+        accounts.accounts.retain(|a| a.id != account_id);
+        accounts.accounts.push(StoredAccount {
+            id: account_id.to_string(),
+            user_name,
+            refresh_token: tokens.refresh_token.clone(),
+        });
+        accounts.active = Some(account_id.to_string());
+        self.save_accounts(app, key, &accounts)?;
+
+        if tokens.expires_in > 0 && !self.config(key)?.on_demand_refresh {
+            self.schedule_refresh(app, key, account_id, Duration::from_secs(tokens.expires_in));
+        }
+        Ok(())
+    }
+
+    /// Forgets an account of `key` along with its refresh token
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn remove_account(&self, app: &AppHandle, key: &str, account_id: &str) -> Result<()> {
+        if let Some(task) = self
+            .refresh_tasks
+            .lock()
+            .unwrap()
+            .remove(&format!("{}:{}", key, account_id))
+        {
+            task.abort();
+        }
+
+        let mut accounts = self.accounts(app, key)?;
+        accounts.accounts.retain(|a| a.id != account_id);
+        if accounts.active.as_deref() == Some(account_id) {
+            accounts.active = accounts.accounts.first().map(|a| a.id.clone());
+        }
+        self.save_accounts(app, key, &accounts)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn set_active_account(&self, app: &AppHandle, key: &str, account_id: &str) -> Result<()> {
+        let mut accounts = self.accounts(app, key)?;
+        if !accounts.accounts.iter().any(|a| a.id == account_id) {
+            return Err(format!("No account {} for {}", account_id, key).into());
+        }
+        accounts.active = Some(account_id.to_string());
+        self.save_accounts(app, key, &accounts)
+    }
+
+    /// Exchanges the stored refresh token of an account for a new access token
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub async fn refresh(
+        &self,
+        app: &AppHandle,
+        key: &str,
+        account_id: &str,
+    ) -> Result<TokenHolder> {
+        let config = self.config(key)?;
+        let Some(account) = self
+            .accounts(app, key)?
+            .accounts
+            .into_iter()
+            .find(|a| a.id == account_id)
+        else {
+            return Err(format!("Refresh token not found for {}", account_id).into());
+        };
+
+        let res = config
+            .client()?
+            .exchange_refresh_token(&RefreshToken::new(account.refresh_token.clone()))
+            .request_async(async_http_client)
+            .await
+            .map_err(map_token_error)?;
+        let tokens = token_holder(res, Some(account.refresh_token.clone()))?;
+
+        // Some providers rotate refresh tokens on every use
+        if tokens.refresh_token != account.refresh_token {
+            let mut accounts = self.accounts(app, key)?;
+            for stored in accounts.accounts.iter_mut().filter(|a| a.id == account_id) {
+                stored.refresh_token = tokens.refresh_token.clone();
+            }
+            self.save_accounts(app, key, &accounts)?;
+        }
+
+        if tokens.expires_in > 0 && !config.on_demand_refresh {
+            self.schedule_refresh(app, key, account_id, Duration::from_secs(tokens.expires_in));
+        }
+        Ok(tokens)
+    }
+
+    /// Lets the app run the login of an extension. Replaces any redirect the
+    /// extension registered with [OAuthHandler::register_extension_redirect].
+    #[tracing::instrument(level = "trace", skip(self, client))]
+    pub fn register_extension_client(&self, client: ExtensionOAuthClient) -> Result<()> {
+        if client.package_name.is_empty() {
+            return Err("Extension oauth client without a package name".into());
+        }
+
+        let key = extension_key(&client.package_name);
+        let callback_path = if loopback::is_loopback(&client.redirect_uri) {
+            format!("{}oauthcallback", client.package_name)
+        } else {
+            Url::parse(&client.redirect_uri)
+                .map_err(|e| e.to_string())?
+                .host_str()
+                .ok_or(format!("No oauth path in {}", client.redirect_uri))?
+                .to_string()
+        };
+
+        self.tokens.lock().unwrap().remove(&key);
+        self.register_client(
+            key,
+            OAuthConfig {
+                auth_url: client.auth_url,
+                token_url: client.token_url,
+                redirect_uri: client.redirect_uri,
+                client_id: client.client_id,
+                client_secret: client.client_secret,
+                scopes: client.scopes,
+                extra_params: client.extra_params,
+                callback_path,
+                token_key: format!("extensions.{}.oauth_accounts", client.package_name),
+                legacy_token_key: None,
+                on_demand_refresh: true,
+            },
+        );
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn extension_login(&self, app: &AppHandle, package_name: &str) -> Result<String> {
+        self.login(app, &extension_key(package_name))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, app, redirect))]
+    async fn complete_extension_login(
+        &self,
+        app: &AppHandle,
+        key: &str,
+        redirect: String,
+    ) -> Result<()> {
+        let tokens = self.authorize(key, redirect).await?;
+        self.add_account(app, key, EXTENSION_ACCOUNT_ID, None, &tokens)?;
+        self.tokens.lock().unwrap().insert(key.to_string(), tokens);
+
+        let provider_handler: State<ProviderHandler> = app.state();
+        provider_handler
+            .request_account_status(key.to_string())
+            .await
+    }
+
+    /// Access token of the signed in account of an extension, refreshed if it's about to expire
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub async fn extension_token(
+        &self,
+        app: &AppHandle,
+        package_name: &str,
+    ) -> Result<Option<ExtensionOAuthToken>> {
+        let key = extension_key(package_name);
+        let cached = self.tokens.lock().unwrap().get(&key).cloned();
+        let tokens = match cached.filter(token_valid) {
+            Some(tokens) => tokens,
+            None => {
+                let Some(account_id) = self.accounts(app, &key)?.active else {
+                    return Ok(None);
+                };
+                let tokens = self.refresh(app, &key, &account_id).await?;
+                self.tokens.lock().unwrap().insert(key, tokens.clone());
+                tokens
+            }
+        };
+
+        Ok(Some(ExtensionOAuthToken {
+            access_token: tokens.access_token,
+            expires_at: if tokens.expires_in > 0 {
+                tokens.expires_at
+            } else {
+                0
+            },
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub async fn extension_signout(&self, app: &AppHandle, package_name: &str) -> Result<()> {
+        let key = extension_key(package_name);
+        self.tokens.lock().unwrap().remove(&key);
+        self.remove_account(app, &key, EXTENSION_ACCOUNT_ID)?;
+
+        let provider_handler: State<ProviderHandler> = app.state();
+        provider_handler.request_account_status(key).await
+    }
+
+    /// Asks the provider to refresh an account shortly before its access token expires
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    fn schedule_refresh(&self, app: &AppHandle, key: &str, account_id: &str, expires_in: Duration) {
+        let delay = expires_in
+            .saturating_sub(REFRESH_MARGIN)
+            .max(MIN_REFRESH_INTERVAL);
+
+        let app = app.clone();
+        let provider_key = key.to_string();
+        let account_id = account_id.to_string();
+        let task_key = format!("{}:{}", key, account_id);
+        let task = {
+            let task_key = task_key.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(delay).await;
+                tracing::info!("Refreshing oauth tokens for {}", task_key);
+
+                // Refreshing schedules the next run, which must not abort this one
+                let oauth: State<OAuthHandler> = app.state();
+                oauth.refresh_tasks.lock().unwrap().remove(&task_key);
+
+                let provider_handler: State<ProviderHandler> = app.state();
+                if let Err(e) = provider_handler
+                    .provider_refresh_login(provider_key, account_id)
+                    .await
+                {
+                    tracing::error!("Error refreshing {}: {:?}", task_key, e);
+                }
+            })
+        };
+
+        if let Some(old) = self.refresh_tasks.lock().unwrap().insert(task_key, task) {
+            old.abort();
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn extension_key(package_name: &str) -> String {
+    format!("extension:{}", package_name)
+}
+
+#[tracing::instrument(level = "trace", skip(tokens))]
+fn token_valid(tokens: &TokenHolder) -> bool {
+    if tokens.expires_in == 0 {
+        return true;
+    }
+    let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+        return false;
+    };
+    tokens.expires_at > (now + REFRESH_MARGIN).as_millis() as i64
+}
+
+#[tracing::instrument(level = "trace", skip(res, default_refresh))]
+fn token_holder(res: OAuthTokenResponse, default_refresh: Option<String>) -> Result<TokenHolder> {
+    let refresh_token = res
+        .refresh_token()
+        .map(|r| r.secret().clone())
+        .unwrap_or(default_refresh.unwrap_or_default());
+    let expires_in = res.expires_in().unwrap_or_default();
+    Ok(TokenHolder {
+        access_token: res.access_token().secret().clone(),
+        refresh_token,
+        expires_in: expires_in.as_secs(),
+        expires_at: (SystemTime::now().duration_since(UNIX_EPOCH)? + expires_in).as_millis() as i64,
+    })
+}
+
+#[tracing::instrument(level = "trace", skip(err))]
+fn map_token_error<RE: std::error::Error + 'static>(
+    err: oauth2::RequestTokenError<
+        RE,
+        oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
+    >,
+) -> MoosyncError {
+    match err {
+        oauth2::RequestTokenError::ServerResponse(e) => {
+            MoosyncError::String(format!("{:?}: {:?}", e.error(), e.error_description()))
+        }
+        oauth2::RequestTokenError::Request(e) => {
+            MoosyncError::String(format!("Token request failed: {}", e))
+        }
+        oauth2::RequestTokenError::Parse(e, _) => {
+            MoosyncError::String(format!("Invalid token response: {}", e))
+        }
+        oauth2::RequestTokenError::Other(e) => MoosyncError::String(e),
+    }
 }
 
 #[tracing::instrument(level = "trace", skip())]
@@ -0,0 +1,136 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    io::ErrorKind,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Manager, State};
+use types::errors::Result;
+use url::Url;
+
+use super::handler::OAuthHandler;
+
+// Frees the port if the login is abandoned
+const LISTEN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+// A client that connects but never sends its request must not block the listener
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+const RESPONSE_BODY: &str = "<html><body><h3>Login complete, you may close this window and return to Moosync.</h3></body></html>";
+
+/// Whether `redirect_uri` points at a local HTTP server instead of a deep link
+#[tracing::instrument(level = "trace", skip())]
+pub fn is_loopback(redirect_uri: &str) -> bool {
+    Url::parse(redirect_uri).is_ok_and(|url| {
+        url.scheme() == "http"
+            && matches!(
+                url.host_str(),
+                Some("127.0.0.1") | Some("localhost") | Some("[::1]")
+            )
+    })
+}
+
+/// Serves `redirect_uri` until [OAuthHandler] accepts a request forwarded
+/// as `moosync://{callback_path}?...`, other requests are refused.
+/// For platforms where the deep link can't be registered.
+/// Gives up after [LISTEN_TIMEOUT].
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn listen(app: AppHandle, redirect_uri: &str, callback_path: String) -> Result<()> {
+    let redirect = Url::parse(redirect_uri).map_err(|e| e.to_string())?;
+    let socket_addr = redirect
+        .socket_addrs(|| None)?
+        .pop()
+        .ok_or(format!("Could not resolve {}", redirect_uri))?;
+    let listener = TcpListener::bind(socket_addr)?;
+    listener.set_nonblocking(true)?;
+    let expected_path = redirect.path().to_string();
+
+    tracing::info!("Listening for oauth redirect on {:?}", socket_addr);
+    thread::spawn(move || {
+        let deadline = Instant::now() + LISTEN_TIMEOUT;
+        loop {
+            if Instant::now() >= deadline {
+                tracing::warn!("No oauth redirect on {:?}, giving up", socket_addr);
+                break;
+            }
+            let mut stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    if e.kind() != ErrorKind::WouldBlock {
+                        tracing::warn!("Failed to accept oauth redirect: {:?}", e);
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+            };
+            if stream.set_nonblocking(false).is_err()
+                || stream.set_read_timeout(Some(READ_TIMEOUT)).is_err()
+            {
+                continue;
+            }
+
+            let mut request_line = String::new();
+            if BufReader::new(&stream)
+                .read_line(&mut request_line)
+                .is_err()
+            {
+                continue;
+            }
+
+            let Some(target) = request_line.split_whitespace().nth(1) else {
+                continue;
+            };
+            let Some((path, query)) = target.split_once('?') else {
+                // Browsers ask for the favicon before the actual redirect
+                let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+                continue;
+            };
+            if path != expected_path {
+                let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+                continue;
+            }
+
+            tracing::info!("Got oauth redirect on {}", path);
+            let oauth_handler: State<OAuthHandler> = app.state();
+            if let Err(e) = oauth_handler.handle_oauth(
+                app.clone(),
+                format!("moosync://{}?{}", callback_path, query),
+            ) {
+                // Keeps listening, the actual redirect may still follow
+                tracing::error!("Failed to handle oauth redirect: {:?}", e);
+                let _ = stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n");
+                continue;
+            }
+
+            let _ = stream.write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    RESPONSE_BODY.len(),
+                    RESPONSE_BODY
+                )
+                .as_bytes(),
+            );
+            break;
+        }
+    });
+
+    Ok(())
+}
@@ -15,3 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod handler;
+pub mod loopback;
+
+#[cfg(test)]
+mod test;
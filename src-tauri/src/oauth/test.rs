@@ -0,0 +1,247 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+};
+
+use url::Url;
+
+use crate::oauth::handler::{OAuthConfig, OAuthHandler};
+
+const KEY: &str = "test";
+
+fn config(token_url: &str) -> OAuthConfig {
+    OAuthConfig {
+        auth_url: "https://auth.example.com/authorize".into(),
+        token_url: token_url.into(),
+        redirect_uri: "moosync://testoauthcallback".into(),
+        client_id: "client".into(),
+        client_secret: None,
+        scopes: vec!["read".into(), "write".into()],
+        extra_params: vec![("prompt".into(), "consent".into())],
+        callback_path: "testoauthcallback".into(),
+        token_key: "TestAccounts".into(),
+        legacy_token_key: None,
+        on_demand_refresh: false,
+    }
+}
+
+fn handler(token_url: &str) -> OAuthHandler {
+    let handler = OAuthHandler::new();
+    handler.register_client(KEY.into(), config(token_url));
+    handler
+}
+
+fn query(url: &str) -> HashMap<String, String> {
+    Url::parse(url)
+        .unwrap()
+        .query_pairs()
+        .into_owned()
+        .collect()
+}
+
+/// Begins a login and returns the state it expects back
+fn begin(handler: &OAuthHandler) -> String {
+    let (url, _) = handler.begin_login(KEY).unwrap();
+    query(&url).remove("state").unwrap()
+}
+
+/// Answers a single token request and sends its body back
+fn mock_token_endpoint(response: &'static str) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/token", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        tx.send(String::from_utf8(body).unwrap()).unwrap();
+
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+    });
+
+    (url, rx)
+}
+
+#[test]
+fn test_begin_login_unknown_client() {
+    let handler = OAuthHandler::new();
+    assert!(handler.begin_login(KEY).is_err());
+}
+
+#[test]
+fn test_begin_login_without_client_id() {
+    let handler = OAuthHandler::new();
+    handler.register_client(
+        KEY.into(),
+        OAuthConfig {
+            client_id: String::new(),
+            ..config("https://auth.example.com/token")
+        },
+    );
+    assert!(handler.begin_login(KEY).is_err());
+}
+
+#[test]
+fn test_begin_login_url() {
+    let handler = handler("https://auth.example.com/token");
+    let (url, config) = handler.begin_login(KEY).unwrap();
+    assert_eq!(config.callback_path, "testoauthcallback");
+    assert!(url.starts_with("https://auth.example.com/authorize?"));
+
+    let query = query(&url);
+    assert_eq!(query.get("client_id").unwrap(), "client");
+    assert_eq!(query.get("response_type").unwrap(), "code");
+    assert_eq!(query.get("code_challenge_method").unwrap(), "S256");
+    assert!(!query.get("code_challenge").unwrap().is_empty());
+    assert!(!query.get("state").unwrap().is_empty());
+    assert_eq!(query.get("scope").unwrap(), "read write");
+    assert_eq!(query.get("prompt").unwrap(), "consent");
+    assert_eq!(
+        query.get("redirect_uri").unwrap(),
+        "moosync://testoauthcallback"
+    );
+
+    let oauth_map = handler.oauth_map.lock().unwrap();
+    assert_eq!(oauth_map.get("testoauthcallback").unwrap(), KEY);
+}
+
+#[test]
+fn test_authorize_without_login() {
+    let handler = handler("https://auth.example.com/token");
+    let res = tauri::async_runtime::block_on(
+        handler.authorize(KEY, "moosync://testoauthcallback?code=abc&state=xyz".into()),
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_authorize_state_mismatch_keeps_login() {
+    let handler = handler("https://auth.example.com/token");
+    let state = begin(&handler);
+
+    let res = tauri::async_runtime::block_on(handler.authorize(
+        KEY,
+        "moosync://testoauthcallback?code=abc&state=forged".into(),
+    ));
+    assert!(res.is_err());
+    let res = tauri::async_runtime::block_on(
+        handler.authorize(KEY, "moosync://testoauthcallback?code=abc".into()),
+    );
+    assert!(res.is_err());
+
+    // Still pending, so the genuine redirect is denied for its error and not for a missing login
+    let res = tauri::async_runtime::block_on(
+        handler.authorize(KEY, format!("?error=access_denied&state={}", state)),
+    );
+    let err = format!("{:?}", res.unwrap_err());
+    assert!(err.contains("access_denied"), "{}", err);
+}
+
+#[test]
+fn test_authorize_error_ends_login() {
+    let handler = handler("https://auth.example.com/token");
+    let state = begin(&handler);
+
+    let res = tauri::async_runtime::block_on(handler.authorize(
+        KEY,
+        format!(
+            "moosync://testoauthcallback?error=access_denied&state={}",
+            state
+        ),
+    ));
+    assert!(res.is_err());
+    assert!(handler
+        .oauth_map
+        .lock()
+        .unwrap()
+        .get("testoauthcallback")
+        .is_none());
+
+    let res = tauri::async_runtime::block_on(handler.authorize(
+        KEY,
+        format!("moosync://testoauthcallback?code=abc&state={}", state),
+    ));
+    let err = format!("{:?}", res.unwrap_err());
+    assert!(err.contains("not initiated"), "{}", err);
+}
+
+#[test]
+fn test_authorize_without_code() {
+    let handler = handler("https://auth.example.com/token");
+    let state = begin(&handler);
+
+    let res = tauri::async_runtime::block_on(
+        handler.authorize(KEY, format!("moosync://testoauthcallback?state={}", state)),
+    );
+    let err = format!("{:?}", res.unwrap_err());
+    assert!(err.contains("No authorization code"), "{}", err);
+}
+
+#[test]
+fn test_authorize_exchanges_code() {
+    let (token_url, requests) = mock_token_endpoint(
+        r#"{"access_token":"access","token_type":"bearer","expires_in":3600,"refresh_token":"refresh"}"#,
+    );
+    let handler = handler(&token_url);
+    let state = begin(&handler);
+
+    let tokens = tauri::async_runtime::block_on(handler.authorize(
+        KEY,
+        format!("moosync://testoauthcallback?code=abc&state={}", state),
+    ))
+    .unwrap();
+    assert_eq!(tokens.access_token, "access");
+    assert_eq!(tokens.refresh_token, "refresh");
+    assert_eq!(tokens.expires_in, 3600);
+
+    let body: HashMap<String, String> =
+        url::form_urlencoded::parse(requests.recv().unwrap().as_bytes())
+            .into_owned()
+            .collect();
+    assert_eq!(body.get("grant_type").unwrap(), "authorization_code");
+    assert_eq!(body.get("code").unwrap(), "abc");
+    assert!(!body.get("code_verifier").unwrap().is_empty());
+}
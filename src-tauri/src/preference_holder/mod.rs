@@ -16,15 +16,23 @@
 
 use std::thread;
 
+use database::cache::CacheHolder;
+use lyrics::LyricsFetcher;
 use macros::generate_command;
 use preferences::preferences::PreferenceConfig;
+use rand::{distributions::Alphanumeric, Rng};
 use serde_json::Value;
 use tauri::{async_runtime, App, AppHandle, Emitter, Manager, State};
 use types::{errors::Result, preferences::CheckboxPreference};
 
+use rodio_player::RodioPlayer;
+
 use crate::{
+    lyrics::configure_lyrics_sources,
     providers::handler::ProviderHandler,
+    remote::{configure_remote_control, DEFAULT_PORT},
     scanner::{start_scan, ScanTask},
+    tagger::TagLookupHolder,
 };
 
 const UI_KEYS: &[&str] = &[
@@ -99,6 +107,35 @@ pub fn handle_pref_changes(app: AppHandle) {
                 }
             }
 
+            if key == "prefs.http_cache_size" {
+                if let Some(cache_size) = value.as_u64() {
+                    let cache: State<CacheHolder> = app.state();
+                    cache.set_max_size(cache_size * 1024 * 1024);
+                }
+            }
+
+            if key == "prefs.audio_cache_size" {
+                if let Some(cache_size) = value.as_u64() {
+                    let rodio: State<RodioPlayer> = app.state();
+                    rodio.set_cache_size(cache_size * 1024 * 1024);
+                }
+            }
+
+            if key == "prefs.lyrics_sources" || key == "prefs.lrclib_url" {
+                configure_lyrics_sources(app.state::<LyricsFetcher>().inner(), &pref_config);
+            }
+
+            if key == "prefs.acoustid_url"
+                || key == "prefs.acoustid_api_key"
+                || key == "prefs.musicbrainz_url"
+            {
+                app.state::<TagLookupHolder>().configure(&pref_config);
+            }
+
+            if key.starts_with("prefs.remote_control") {
+                configure_remote_control(&app);
+            }
+
             if key.starts_with("prefs.scan_interval") {
                 let scan_task: State<ScanTask> = app.state();
                 scan_task.spawn_scan_task(app.clone(), value.as_u64().unwrap().max(30));
@@ -146,6 +183,47 @@ pub fn initial(app: &mut App) {
         );
     }
 
+    if !pref_config.has_key("lyrics_sources") {
+        let _ = pref_config.save_selective(
+            "lyrics_sources".to_string(),
+            Some(
+                ["spotify", "lrclib", "azlyrics", "genius"]
+                    .into_iter()
+                    .map(|source| CheckboxPreference {
+                        key: source.to_string(),
+                        enabled: true,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+        );
+    }
+
+    if !pref_config.has_key("remote_control_token") {
+        let token = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect::<String>();
+        let _ = pref_config.set_secure("remote_control_token".to_string(), Some(token));
+    } else if pref_config
+        .get_secure::<String>("remote_control_token".into())
+        .is_err()
+    {
+        // Earlier versions kept the token in plain text
+        if let Ok(token) = pref_config.load_selective::<String>("remote_control_token".into()) {
+            let _ = pref_config.set_secure("remote_control_token".to_string(), Some(token));
+        }
+    }
+
+    if !pref_config.has_key("remote_control_port") {
+        let _ = pref_config.save_selective("remote_control_port".to_string(), Some(DEFAULT_PORT));
+    }
+
+    if let Ok(cache_size) = pref_config.load_selective::<u64>("http_cache_size".into()) {
+        let cache: State<CacheHolder> = app.state();
+        cache.set_max_size(cache_size * 1024 * 1024);
+    }
+
     // Spawn scan task
     let scan_task: State<ScanTask> = app.state();
     let scan_duration = pref_config.load_selective::<u64>("scan_interval".into());
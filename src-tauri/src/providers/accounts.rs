@@ -0,0 +1,138 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use types::{errors::Result, providers::generic::ProviderStatus};
+
+use crate::oauth::handler::TokenHolder;
+
+/// A signed in account of a built-in provider along with its API client
+#[derive(Debug)]
+pub struct Account<C> {
+    pub id: String,
+    pub user_name: Option<String>,
+    pub tokens: TokenHolder,
+    pub client: C,
+}
+
+/// Signed in accounts of a built-in provider, one of which is active.
+/// Requests not tied to a specific account go through the active one.
+#[derive(Debug)]
+pub struct AccountStore<C> {
+    accounts: Vec<Account<C>>,
+    active: Option<String>,
+}
+
+impl<C> Default for AccountStore<C> {
+    fn default() -> Self {
+        Self {
+            accounts: vec![],
+            active: None,
+        }
+    }
+}
+
+impl<C> AccountStore<C> {
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn active(&self) -> Option<&Account<C>> {
+        self.active
+            .as_ref()
+            .and_then(|id| self.accounts.iter().find(|a| &a.id == id))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn active_id(&self) -> Option<String> {
+        self.active().map(|a| a.id.clone())
+    }
+
+    /// The account with `id`, falling back to the active one
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_or_active(&self, id: Option<&str>) -> Option<&Account<C>> {
+        id.and_then(|id| self.accounts.iter().find(|a| a.id == id))
+            .or_else(|| self.active())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn contains(&self, id: &str) -> bool {
+        self.accounts.iter().any(|a| a.id == id)
+    }
+
+    /// Adds or replaces an account. The first account becomes the active one.
+    #[tracing::instrument(level = "trace", skip(self, account))]
+    pub fn insert(&mut self, account: Account<C>) {
+        if self.active.is_none() {
+            self.active = Some(account.id.clone());
+        }
+
+        if let Some(existing) = self.accounts.iter_mut().find(|a| a.id == account.id) {
+            *existing = account;
+        } else {
+            self.accounts.push(account);
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn remove(&mut self, id: &str) -> Option<Account<C>> {
+        let index = self.accounts.iter().position(|a| a.id == id)?;
+        let account = self.accounts.remove(index);
+        if self.active.as_deref() == Some(id) {
+            self.active = self.accounts.first().map(|a| a.id.clone());
+        }
+        Some(account)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_active(&mut self, id: &str) -> Result<()> {
+        if !self.contains(id) {
+            return Err(format!("Account {} is not signed in", id).into());
+        }
+        self.active = Some(id.to_string());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn clear(&mut self) {
+        self.accounts.clear();
+        self.active = None;
+    }
+
+    /// Statuses of all accounts, preceded by a logged out entry used to sign in another account.
+    /// The entry uses the provider key as account id.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn statuses(&self, key: &str, name: &str, bg_color: &str) -> Vec<ProviderStatus> {
+        let mut statuses = vec![ProviderStatus {
+            key: key.to_string(),
+            name: name.to_string(),
+            user_name: None,
+            logged_in: false,
+            bg_color: bg_color.to_string(),
+            account_id: key.to_string(),
+            active: false,
+        }];
+
+        for account in &self.accounts {
+            statuses.push(ProviderStatus {
+                key: key.to_string(),
+                name: name.to_string(),
+                user_name: account.user_name.clone(),
+                logged_in: true,
+                bg_color: bg_color.to_string(),
+                account_id: account.id.clone(),
+                active: self.active.as_ref() == Some(&account.id),
+            });
+        }
+        statuses
+    }
+}
@@ -94,6 +94,7 @@ impl ExtensionProvider {
                     logged_in: account.logged_in,
                     bg_color: account.bg_color,
                     account_id: account.id,
+                    active: false,
                 })
                 .await;
         }
@@ -129,6 +130,11 @@ impl GenericProvider for ExtensionProvider {
         id.starts_with(&format!("{}:", self.extension.package_name))
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn can_search(&self) -> bool {
+        self.provides.contains(&ExtensionProviderScope::Search)
+    }
+
     async fn requested_account_status(&mut self) -> Result<()> {
         self.get_accounts().await
     }
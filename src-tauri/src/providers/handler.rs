@@ -16,21 +16,35 @@
 
 use futures::{
     channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
-    future::join_all,
+    future::{join_all, AbortHandle, Abortable, BoxFuture},
     lock::Mutex,
-    StreamExt,
+    stream::FuturesUnordered,
+    Future, FutureExt, StreamExt,
 };
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    sync::{RwLock, Semaphore, SemaphorePermit},
+    time::timeout,
+};
 
-use database::cache::CacheHolder;
+use database::{cache::CacheHolder, database::Database};
 use macros::{generate_command_async, generate_command_async_cached};
-use tauri::{
-    async_runtime::{self, block_on},
-    AppHandle, Emitter, State,
-};
+use tauri::{async_runtime, AppHandle, Emitter, Manager, State};
 use types::{
-    entities::{QueryableAlbum, QueryableArtist, QueryablePlaylist, SearchResult},
+    entities::{
+        ProviderDataUpdate, ProviderSearchResult, QueryableAlbum, QueryableArtist,
+        QueryablePlaylist, SearchResult,
+    },
     errors::{MoosyncError, Result},
     providers::generic::{GenericProvider, Pagination, ProviderStatus},
     songs::Song,
@@ -40,6 +54,119 @@ use crate::{extensions::get_extension_handler, providers::extension::ExtensionPr
 
 use super::{spotify::SpotifyProvider, youtube::YoutubeProvider};
 
+// Maximum number of requests a single provider may serve at once
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(10);
+const LOCAL_SEARCH_KEY: &str = "Local";
+// Cached provider responses younger than this are served without refreshing
+const RESPONSE_FRESH_FOR: Duration = Duration::from_secs(10 * 60);
+// Cached provider responses are kept this long (in seconds) so they can be served offline
+const RESPONSE_KEEP_FOR: i32 = 30 * 24 * 60 * 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    fetched_at: u64,
+    data: Value,
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn unix_time() -> Result<u64> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs())
+}
+
+#[tracing::instrument(level = "trace", skip(cache, data))]
+fn store_response(cache: &CacheHolder, cache_key: &str, data: Value) -> Result<()> {
+    cache.set(
+        cache_key,
+        &CachedResponse {
+            fetched_at: unix_time()?,
+            data,
+        },
+        RESPONSE_KEEP_FOR,
+    )
+}
+
+/// Merges per-source search results, keeping the first copy of each track.
+/// Local results always win, remaining sources are taken in key order so the
+/// merged view doesn't depend on which provider answered first.
+fn merge_search_results(mut results: BTreeMap<String, SearchResult>) -> SearchResult {
+    let mut ordered = Vec::with_capacity(results.len());
+    if let Some(local) = results.remove(LOCAL_SEARCH_KEY) {
+        ordered.push(local);
+    }
+    ordered.extend(results.into_values());
+
+    let mut merged = SearchResult::default();
+    let mut seen_songs = HashSet::new();
+    for mut result in ordered {
+        result
+            .songs
+            .retain(|song| seen_songs.insert(song_match_key(song)));
+        merged.songs.extend(result.songs);
+        merged.artists.extend(result.artists);
+        merged.albums.extend(result.albums);
+        merged.playlists.extend(result.playlists);
+        merged.genres.extend(result.genres);
+    }
+    merged
+}
+
+/// Identity of a track across sources, used to drop duplicate search results
+#[tracing::instrument(level = "trace", skip(song))]
+fn song_match_key(song: &Song) -> String {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect::<String>()
+    };
+
+    let title = song
+        .song
+        .title
+        .as_deref()
+        .map(normalize)
+        .unwrap_or_default();
+    let mut artists = song
+        .artists
+        .iter()
+        .flatten()
+        .filter_map(|a| a.artist_name.as_deref().map(normalize))
+        .collect::<Vec<_>>();
+    artists.sort();
+
+    if title.is_empty() {
+        // Nothing to match on, keep the track
+        return song.song._id.clone().unwrap_or_default();
+    }
+    format!("{}:{}", title, artists.join(","))
+}
+
+macro_rules! provider_call {
+    ($self:ident, $key:ident, $method_name:ident, $($param_name:ident),*) => {
+        async move {
+            let mut provider_key = $key;
+            loop {
+                let Some(entry) = $self.get_provider(&provider_key).await else {
+                    return Err(format!("Provider ({}) not found for method {}", provider_key, stringify!($method_name)).into());
+                };
+
+                let _permit = entry.acquire().await?;
+                let provider = entry.provider.read().await;
+                tracing::debug!("calling provider {} - {}", provider_key, stringify!($method_name));
+                let res = provider.$method_name($($param_name.clone()),*).await;
+                match res {
+                    Ok(result) => return Ok(result),
+                    Err(MoosyncError::SwitchProviders(e)) => {provider_key = e; continue;},
+                    Err(err) => return Err(format!("{} - {}", provider_key, err).into()),
+                }
+            }
+        }
+    };
+}
+
 macro_rules! generate_wrapper {
     ($($func_name:ident {
         args: { $($param_name:ident: $param_type:ty),* $(,)? },
@@ -49,23 +176,25 @@ macro_rules! generate_wrapper {
         $(
             #[tracing::instrument(level = "trace", skip(self))]
             pub async fn $func_name(&self, key: String, $($param_name: $param_type),*) -> Result<$result_type> {
-                let mut provider_key = key;
-                let provider_store = self.provider_store.lock().await;
-                loop {
-                    let provider = provider_store.get(&provider_key);
-                    if let Some(provider) = provider {
-                        let provider = provider.lock().await;
-                        tracing::debug!("calling provider {} - {}", provider_key, stringify!($method_name));
-                        let res = provider.$method_name($($param_name.clone()),*).await;
-                        match res {
-                            Ok(result) => return Ok(result),
-                            Err(MoosyncError::SwitchProviders(e)) => {provider_key = e; continue;},
-                            Err(err) => return Err(format!("{} - {}", provider_key, err).into()),
-                        }
-                    }
+                provider_call!(self, key, $method_name, $($param_name),*).await
+            }
+        )*
+    }
+}
 
-                    return Err(format!("Provider ({}) not found for method {}", provider_key, stringify!($method_name)).into());
-                }
+/// Like [generate_wrapper] but the generated methods take the scope the request belongs to,
+/// so that it can be cancelled through [ProviderHandler::cancel_provider_requests]
+macro_rules! generate_scoped_wrapper {
+    ($($func_name:ident {
+        args: { $($param_name:ident: $param_type:ty),* $(,)? },
+        result_type: $result_type:ty,
+        method_name: $method_name:ident,
+    }),* $(,)?) => {
+        $(
+            #[tracing::instrument(level = "trace", skip(self))]
+            pub async fn $func_name(&self, key: String, $($param_name: $param_type,)* scope: Option<String>) -> Result<$result_type> {
+                let fut = provider_call!(self, key, $method_name, $($param_name),*);
+                self.run_request(fut, scope).await
             }
         )*
     }
@@ -81,69 +210,167 @@ macro_rules! generate_wrapper_mut {
             #[tracing::instrument(level = "trace", skip(self))]
             pub async fn $func_name(&self, key: String, $($param_name: $param_type),*) -> Result<$result_type> {
                 let mut provider_key = key;
-                let provider_store = self.provider_store.lock().await;
                 loop {
-                    let provider = provider_store.get(&provider_key);
-                    if let Some(provider) = provider {
-                        let mut provider = provider.lock().await;
-                        let res = provider.$method_name($($param_name.clone()),*).await;
-                        match res {
-                            Ok(result) => return Ok(result),
-                            Err(MoosyncError::SwitchProviders(e)) => {provider_key = e; continue;},
-                            Err(err) => return Err(err),
-                        }
+                    let Some(entry) = self.get_provider(&provider_key).await else {
+                        return Err(format!("Provider ({}) not found for method {}", provider_key, stringify!($method_name)).into());
+                    };
+
+                    let mut provider = entry.provider.write().await;
+                    let res = provider.$method_name($($param_name.clone()),*).await;
+                    match res {
+                        Ok(result) => return Ok(result),
+                        Err(MoosyncError::SwitchProviders(e)) => {provider_key = e; continue;},
+                        Err(err) => return Err(err),
                     }
-
-                    return Err(format!("Provider ({}) not found for method {}", provider_key, stringify!($method_name)).into());
                 }
             }
         )*
     }
 }
 
+macro_rules! generate_cached_wrapper {
+    ($($func_name:ident => $remote_name:ident {
+        args: { $($param_name:ident: $param_type:ty),* $(,)? },
+        result_type: $result_type:ty,
+    }),* $(,)?) => {
+        $(
+            #[tracing::instrument(level = "trace", skip(self))]
+            pub async fn $func_name(&self, key: String, $($param_name: $param_type,)* scope: Option<String>) -> Result<$result_type> {
+                let request = format!("{:?}", ($(&$param_name,)*));
+                let app = self.app_handle.clone();
+                let provider_key = key.clone();
+                let fetch = move |scope| async move {
+                    let handler: State<ProviderHandler> = app.state();
+                    handler.$remote_name(provider_key, $($param_name,)* scope).await
+                };
+                self.cached_request(key, stringify!($func_name), request, scope, fetch).await
+            }
+        )*
+    }
+}
+
+/// Handle to a registered provider. Cloning it is cheap, so calls never
+/// need to hold the registry lock while a provider is working.
+#[derive(Debug, Clone)]
+struct ProviderEntry {
+    provider: Arc<RwLock<dyn GenericProvider>>,
+    limiter: Arc<Semaphore>,
+}
+
+impl ProviderEntry {
+    #[tracing::instrument(level = "trace", skip(provider))]
+    fn new<T: GenericProvider + 'static>(provider: T) -> Self {
+        Self {
+            provider: Arc::new(RwLock::new(provider)),
+            limiter: Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS)),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn acquire(&self) -> Result<SemaphorePermit<'_>> {
+        self.limiter
+            .acquire()
+            .await
+            .map_err(|e| MoosyncError::String(e.to_string()))
+    }
+}
+
 #[derive(Debug)]
 pub struct ProviderHandler {
-    provider_store: Mutex<HashMap<String, Arc<Mutex<dyn GenericProvider>>>>,
+    provider_store: RwLock<HashMap<String, ProviderEntry>>,
     app_handle: AppHandle,
     status_tx: UnboundedSender<ProviderStatus>,
     provider_status: Arc<Mutex<HashMap<String, ProviderStatus>>>,
+    request_id: AtomicU64,
+    /// Cancellable requests by the scope (usually a page of the UI) they were made for
+    in_flight: std::sync::Mutex<HashMap<String, HashMap<u64, AbortHandle>>>,
 }
 
 impl ProviderHandler {
     #[tracing::instrument(level = "trace", skip(app))]
     pub fn new(app: AppHandle) -> Self {
         let (status_tx, status_rx) = unbounded();
+
+        let mut provider_store = HashMap::new();
+
+        let spotify_provider = SpotifyProvider::new(app.clone(), status_tx.clone());
+        provider_store.insert(spotify_provider.key(), ProviderEntry::new(spotify_provider));
+
+        let youtube_provider = YoutubeProvider::new(app.clone(), status_tx.clone());
+        provider_store.insert(youtube_provider.key(), ProviderEntry::new(youtube_provider));
+
         let store = Self {
-            app_handle: app.clone(),
-            provider_store: Default::default(),
+            app_handle: app,
+            provider_store: RwLock::new(provider_store),
             status_tx,
             provider_status: Default::default(),
+            request_id: AtomicU64::new(0),
+            in_flight: Default::default(),
         };
         store.listen_status_changes(status_rx);
 
-        let mut provider_store = block_on(store.provider_store.lock());
+        store
+    }
 
-        let spotify_provider = SpotifyProvider::new(app.clone(), store.status_tx.clone());
-        provider_store.insert(
-            spotify_provider.key(),
-            Arc::new(Mutex::new(spotify_provider)),
-        );
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn get_provider(&self, key: &str) -> Option<ProviderEntry> {
+        self.provider_store.read().await.get(key).cloned()
+    }
 
-        let youtube_provider: YoutubeProvider = YoutubeProvider::new(app, store.status_tx.clone());
-        provider_store.insert(
-            youtube_provider.key(),
-            Arc::new(Mutex::new(youtube_provider)),
-        );
-        drop(provider_store);
+    /// Runs a provider request. Requests made with a `scope` are registered so that
+    /// they may be aborted through [ProviderHandler::cancel_provider_requests].
+    #[tracing::instrument(level = "trace", skip(self, fut))]
+    async fn run_request<T>(
+        &self,
+        fut: impl Future<Output = Result<T>>,
+        scope: Option<String>,
+    ) -> Result<T> {
+        let Some(scope) = scope else {
+            return fut.await;
+        };
 
-        store
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed);
+        self.in_flight
+            .lock()
+            .unwrap()
+            .entry(scope.clone())
+            .or_default()
+            .insert(id, abort_handle);
+
+        let res = Abortable::new(fut, abort_registration).await;
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(requests) = in_flight.get_mut(&scope) {
+            requests.remove(&id);
+            if requests.is_empty() {
+                in_flight.remove(&scope);
+            }
+        }
+        res.unwrap_or_else(|_| Err(MoosyncError::Cancelled))
+    }
+
+    /// Aborts the requests made for `scope`, requests of other scopes keep running
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn cancel_provider_requests(&self, scope: String) -> Result<()> {
+        let requests = self.in_flight.lock().unwrap().remove(&scope);
+        if let Some(requests) = requests {
+            tracing::debug!(
+                "Cancelling {} provider requests of {}",
+                requests.len(),
+                scope
+            );
+            for handle in requests.into_values() {
+                handle.abort();
+            }
+        }
+        Ok(())
     }
 
     pub async fn request_account_status(&self, key: String) -> Result<()> {
-        let provider_store = self.provider_store.lock().await;
-        if let Some(provider) = provider_store.get(&key) {
-            let mut provider = provider.lock().await;
-            provider.requested_account_status().await?;
+        if let Some(entry) = self.get_provider(&key).await {
+            let mut provider = entry.provider.write().await;
+            return provider.requested_account_status().await;
         }
 
         Err("Provider not found".into())
@@ -163,7 +390,7 @@ impl ProviderHandler {
             while let Some(status) = status_rx.next().await {
                 tracing::debug!("Got provider status update {:?}", status);
                 let mut provider_status = provider_status.lock().await;
-                provider_status.insert(status.key.clone(), status);
+                provider_status.insert(format!("{}:{}", status.key, status.account_id), status);
                 let res = app_handle.emit("provider-status-update", provider_status.clone());
                 if let Err(e) = res {
                     tracing::error!("Error emitting status update: {:?}", e);
@@ -203,10 +430,11 @@ impl ProviderHandler {
                     self.app_handle.clone(),
                     self.status_tx.clone(),
                 );
-                let mut provider_store = self.provider_store.lock().await;
-                provider_store.insert(provider.key(), Arc::new(Mutex::new(provider.clone())));
+                let mut provider_store = self.provider_store.write().await;
+                provider_store.insert(provider.key(), ProviderEntry::new(provider.clone()));
 
                 tracing::info!("provider_store: {:?}", provider_store);
+                drop(provider_store);
                 async_runtime::spawn(async move {
                     let res = provider.initialize().await;
                     if let Err(err) = res {
@@ -226,10 +454,8 @@ impl ProviderHandler {
 
     #[tracing::instrument(level = "trace", skip(self, key))]
     pub async fn initialize_provider(&self, key: String) {
-        let provider_store = self.provider_store.lock().await;
-        let provider = provider_store.get(&key);
-        if let Some(provider) = provider {
-            let mut provider = provider.lock().await;
+        if let Some(entry) = self.get_provider(&key).await {
+            let mut provider = entry.provider.write().await;
             if let Err(e) = provider.initialize().await {
                 tracing::error!("Error initializing provider {}: {:?}", provider.key(), e);
             }
@@ -239,11 +465,10 @@ impl ProviderHandler {
     #[tracing::instrument(level = "trace", skip(self))]
     pub async fn initialize_all_providers(&self) -> Result<()> {
         let mut fut = vec![];
-        let provider_store = self.provider_store.lock().await;
-        for (_, provider) in provider_store.iter() {
-            let provider = provider.clone();
+        let provider_store = self.provider_store.read().await.clone();
+        for (_, entry) in provider_store {
             fut.push(Box::pin(async move {
-                let mut provider = provider.lock().await;
+                let mut provider = entry.provider.write().await;
                 tracing::info!("Initializing {}", provider.key());
                 let err = provider.initialize().await;
                 if let Err(err) = err {
@@ -259,11 +484,11 @@ impl ProviderHandler {
 
     #[tracing::instrument(level = "trace", skip(self, id))]
     pub async fn get_provider_key_by_id(&self, id: String) -> Result<String> {
-        let provider_store = self.provider_store.lock().await;
-        for (key, provider) in provider_store.iter() {
-            let provider = provider.lock().await;
+        let provider_store = self.provider_store.read().await.clone();
+        for (key, entry) in provider_store {
+            let provider = entry.provider.read().await;
             if provider.match_id(id.clone()) {
-                return Ok(key.clone());
+                return Ok(key);
             }
         }
         Err(format!("Provider for id {} not found", id).into())
@@ -271,7 +496,7 @@ impl ProviderHandler {
 
     #[tracing::instrument(level = "trace", skip(self))]
     pub async fn get_provider_keys(&self) -> Result<Vec<String>> {
-        let provider_store = self.provider_store.lock().await;
+        let provider_store = self.provider_store.read().await;
         Ok(provider_store.keys().cloned().collect())
     }
 
@@ -280,6 +505,181 @@ impl ProviderHandler {
         Ok(self.provider_status.lock().await.clone())
     }
 
+    /// Cached responses belong to the account they were fetched as, identified by its
+    /// id since display names can change or be shared between accounts.
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn response_cache_key(&self, key: &str, method: &str, request: &str) -> String {
+        let account = self
+            .provider_status
+            .lock()
+            .await
+            .values()
+            .filter(|s| s.key == key && s.logged_in)
+            .max_by_key(|s| s.active)
+            .map(|s| s.account_id.clone())
+            .unwrap_or("guest".into());
+        format!("provider_{}_{}_{}_{}", key, account, method, request)
+    }
+
+    /// Serves the cached response of a provider call if there is one, even if it is stale.
+    /// Stale responses are refreshed in the background and a "provider-data-updated"
+    /// event is emitted if the fresh data differs.
+    /// Only the request made for a cache miss belongs to `scope`, background refreshes
+    /// outlive the page which triggered them.
+    #[tracing::instrument(level = "trace", skip(self, fetch))]
+    async fn cached_request<T, F, Fut>(
+        &self,
+        key: String,
+        method: &'static str,
+        request: String,
+        scope: Option<String>,
+        fetch: F,
+    ) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+        F: FnOnce(Option<String>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        let cache_key = self.response_cache_key(&key, method, &request).await;
+        let cache: State<CacheHolder> = self.app_handle.state();
+
+        if let Ok(cached) = cache.get::<CachedResponse>(&cache_key) {
+            match serde_json::from_value::<T>(cached.data.clone()) {
+                Ok(data) => {
+                    let age = unix_time()?.saturating_sub(cached.fetched_at);
+                    if age >= RESPONSE_FRESH_FOR.as_secs() {
+                        tracing::debug!("Refreshing stale response {}", cache_key);
+                        let app = self.app_handle.clone();
+                        async_runtime::spawn(async move {
+                            let fresh = match fetch(None)
+                                .await
+                                .and_then(|d| Ok(serde_json::to_value(d)?))
+                            {
+                                Ok(fresh) => fresh,
+                                Err(e) => {
+                                    tracing::warn!("Failed to refresh {}: {:?}", cache_key, e);
+                                    return;
+                                }
+                            };
+
+                            let cache: State<CacheHolder> = app.state();
+                            if let Err(e) = store_response(&cache, &cache_key, fresh.clone()) {
+                                tracing::error!("Failed to cache {}: {:?}", cache_key, e);
+                            }
+
+                            if fresh != cached.data {
+                                let res = app.emit(
+                                    "provider-data-updated",
+                                    ProviderDataUpdate {
+                                        key,
+                                        method: method.to_string(),
+                                        request,
+                                        data: fresh,
+                                    },
+                                );
+                                if let Err(e) = res {
+                                    tracing::error!("Error emitting data update: {:?}", e);
+                                }
+                            }
+                        });
+                    }
+                    return Ok(data);
+                }
+                Err(e) => tracing::warn!("Discarding unreadable cache {}: {:?}", cache_key, e),
+            }
+        }
+
+        let data = fetch(scope).await?;
+        if let Err(e) = store_response(&cache, &cache_key, serde_json::to_value(&data)?) {
+            tracing::error!("Failed to cache {}: {:?}", cache_key, e);
+        }
+        Ok(data)
+    }
+
+    /// Searches the local library and every searchable provider in parallel.
+    /// Each source's results are emitted unchanged as a "search-result" event as
+    /// soon as it completes. Resolves to the merged results of all sources, where
+    /// duplicate tracks are dropped in a fixed priority: the local library first,
+    /// then providers in key order.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn search_everywhere(
+        &self,
+        term: String,
+        scope: Option<String>,
+    ) -> Result<SearchResult> {
+        let fut = async move {
+            let mut searches: FuturesUnordered<BoxFuture<'_, (String, Result<SearchResult>)>> =
+                FuturesUnordered::new();
+
+            let local_term = term.clone();
+            searches.push(
+                async move {
+                    let database: State<Database> = self.app_handle.state();
+                    (
+                        LOCAL_SEARCH_KEY.to_string(),
+                        database.search_all(local_term),
+                    )
+                }
+                .boxed(),
+            );
+
+            let provider_store = self.provider_store.read().await.clone();
+            for (key, entry) in provider_store {
+                if !entry.provider.read().await.can_search() {
+                    tracing::debug!("Skipping search for {}", key);
+                    continue;
+                }
+
+                let term = term.clone();
+                searches.push(
+                    async move {
+                        let search = async {
+                            let _permit = entry.acquire().await?;
+                            let provider = entry.provider.read().await;
+                            provider.search(term).await
+                        };
+                        let res = match timeout(SEARCH_TIMEOUT, search).await {
+                            Ok(res) => res,
+                            Err(_) => {
+                                Err(format!("Search timed out after {:?}", SEARCH_TIMEOUT).into())
+                            }
+                        };
+                        (key, res)
+                    }
+                    .boxed(),
+                );
+            }
+
+            let mut results = BTreeMap::new();
+            while let Some((key, res)) = searches.next().await {
+                let result = match res {
+                    Ok(result) => result,
+                    Err(e) => {
+                        tracing::warn!("Search failed for {}: {:?}", key, e);
+                        continue;
+                    }
+                };
+
+                let res = self.app_handle.emit(
+                    "search-result",
+                    ProviderSearchResult {
+                        term: term.clone(),
+                        key: key.clone(),
+                        result: result.clone(),
+                    },
+                );
+                if let Err(e) = res {
+                    tracing::error!("Error emitting search result: {:?}", e);
+                }
+
+                results.insert(key, result);
+            }
+
+            Ok(merge_search_results(results))
+        };
+        self.run_request(fut, scope).await
+    }
+
     generate_wrapper_mut!(
         provider_login {
             args: {
@@ -288,7 +688,7 @@ impl ProviderHandler {
             result_type: String,
             method_name: login,
         },
-        provider_signout {
+        provider_signout_remote {
             args: {
                 account_id: String
             },
@@ -299,25 +699,47 @@ impl ProviderHandler {
             args: { code: String },
             result_type: (),
             method_name: authorize,
+        },
+        provider_refresh_login {
+            args: { account_id: String },
+            result_type: (),
+            method_name: refresh_login,
+        },
+        provider_set_active_account {
+            args: { account_id: String },
+            result_type: (),
+            method_name: set_active_account,
         }
     );
 
+    /// Signs `account_id` out and drops its status if the provider didn't replace it
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn provider_signout(&self, key: String, account_id: String) -> Result<()> {
+        self.provider_signout_remote(key.clone(), account_id.clone())
+            .await?;
+
+        let mut provider_status = self.provider_status.lock().await;
+        let status_key = format!("{}:{}", key, account_id);
+        if provider_status
+            .get(&status_key)
+            .is_some_and(|s| s.logged_in && s.account_id != s.key)
+        {
+            provider_status.remove(&status_key);
+            self.app_handle
+                .emit("provider-status-update", provider_status.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Makes `account_id` the account requests of the provider are made as
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn switch_account(&self, key: String, account_id: String) -> Result<()> {
+        self.provider_set_active_account(key, account_id).await?;
+        self.app_handle.emit("providers-updated", Value::Null)?;
+        Ok(())
+    }
+
     generate_wrapper!(
-        fetch_user_playlists {
-            args: {
-                pagination: Pagination
-            },
-            result_type: (Vec<QueryablePlaylist>, Pagination),
-            method_name: fetch_user_playlists,
-        },
-        fetch_playlist_content {
-            args: {
-                playlist: QueryablePlaylist,
-                pagination: Pagination
-            },
-            result_type: (Vec<Song>, Pagination),
-            method_name: get_playlist_content,
-        },
         fetch_playback_url {
             args: {
                 song: Song,
@@ -326,13 +748,6 @@ impl ProviderHandler {
             result_type: String,
             method_name: get_playback_url,
         },
-        provider_search {
-            args: {
-                term: String
-            },
-            result_type: SearchResult,
-            method_name: search,
-        },
         playlist_from_url {
             args: {
                 url: String
@@ -354,6 +769,13 @@ impl ProviderHandler {
             result_type: bool,
             method_name: match_url,
         },
+        provider_search {
+            args: {
+                term: String
+            },
+            result_type: SearchResult,
+            method_name: search,
+        },
         get_suggestions {
             args: {
 
@@ -361,7 +783,25 @@ impl ProviderHandler {
             result_type: Vec<Song>,
             method_name: get_suggestions,
         },
-        get_album_content {
+    );
+
+    generate_scoped_wrapper!(
+        fetch_user_playlists_remote {
+            args: {
+                pagination: Pagination
+            },
+            result_type: (Vec<QueryablePlaylist>, Pagination),
+            method_name: fetch_user_playlists,
+        },
+        fetch_playlist_content_remote {
+            args: {
+                playlist: QueryablePlaylist,
+                pagination: Pagination
+            },
+            result_type: (Vec<Song>, Pagination),
+            method_name: get_playlist_content,
+        },
+        get_album_content_remote {
             args: {
                 album: QueryableAlbum,
                 pagination: Pagination
@@ -369,7 +809,7 @@ impl ProviderHandler {
             result_type: (Vec<Song>, Pagination),
             method_name: get_album_content,
         },
-        get_artist_content {
+        get_artist_content_remote {
             args: {
                 artist: QueryableArtist,
                 pagination: Pagination
@@ -378,6 +818,36 @@ impl ProviderHandler {
             method_name: get_artist_content,
         },
     );
+
+    generate_cached_wrapper!(
+        fetch_user_playlists => fetch_user_playlists_remote {
+            args: {
+                pagination: Pagination
+            },
+            result_type: (Vec<QueryablePlaylist>, Pagination),
+        },
+        fetch_playlist_content => fetch_playlist_content_remote {
+            args: {
+                playlist: QueryablePlaylist,
+                pagination: Pagination
+            },
+            result_type: (Vec<Song>, Pagination),
+        },
+        get_album_content => get_album_content_remote {
+            args: {
+                album: QueryableAlbum,
+                pagination: Pagination
+            },
+            result_type: (Vec<Song>, Pagination),
+        },
+        get_artist_content => get_artist_content_remote {
+            args: {
+                artist: QueryableArtist,
+                pagination: Pagination
+            },
+            result_type: (Vec<Song>, Pagination),
+        },
+    );
 }
 
 #[tracing::instrument(level = "trace", skip(app))]
@@ -390,9 +860,12 @@ generate_command_async!(initialize_all_providers, ProviderHandler, (),);
 generate_command_async!(provider_login, ProviderHandler, String, key: String, account_id: String);
 generate_command_async!(provider_signout, ProviderHandler, (), key: String, account_id: String);
 generate_command_async!(provider_authorize, ProviderHandler, (), key: String, code: String);
+generate_command_async!(switch_account, ProviderHandler, (), key: String, account_id: String);
 generate_command_async!(get_provider_key_by_id, ProviderHandler, String, id: String);
-generate_command_async_cached!(fetch_user_playlists, ProviderHandler, (Vec<QueryablePlaylist>, Pagination), key: String, pagination: Pagination);
-generate_command_async_cached!(fetch_playlist_content, ProviderHandler, (Vec<Song>, Pagination), key: String, playlist: QueryablePlaylist, pagination: Pagination);
+generate_command_async!(cancel_provider_requests, ProviderHandler, (), scope: String);
+generate_command_async!(search_everywhere, ProviderHandler, SearchResult, term: String, scope: Option<String>);
+generate_command_async!(fetch_user_playlists, ProviderHandler, (Vec<QueryablePlaylist>, Pagination), key: String, pagination: Pagination, scope: Option<String>);
+generate_command_async!(fetch_playlist_content, ProviderHandler, (Vec<Song>, Pagination), key: String, playlist: QueryablePlaylist, pagination: Pagination, scope: Option<String>);
 generate_command_async_cached!(fetch_playback_url, ProviderHandler, String, key: String, song: Song, player: String);
 generate_command_async_cached!(provider_search, ProviderHandler, SearchResult, key: String, term: String);
 generate_command_async!(get_all_status, ProviderHandler, HashMap<String, ProviderStatus>, );
@@ -400,5 +873,5 @@ generate_command_async_cached!(playlist_from_url, ProviderHandler, QueryablePlay
 generate_command_async_cached!(song_from_url, ProviderHandler, Song, key: String, url: String);
 generate_command_async_cached!(match_url, ProviderHandler, bool, key: String, url: String);
 generate_command_async_cached!(get_suggestions, ProviderHandler, Vec<Song>, key: String);
-generate_command_async_cached!(get_artist_content, ProviderHandler, (Vec<Song>, Pagination), key: String, artist: QueryableArtist, pagination: Pagination);
-generate_command_async_cached!(get_album_content, ProviderHandler, (Vec<Song>, Pagination), key: String, album: QueryableAlbum, pagination: Pagination);
+generate_command_async!(get_artist_content, ProviderHandler, (Vec<Song>, Pagination), key: String, artist: QueryableArtist, pagination: Pagination, scope: Option<String>);
+generate_command_async!(get_album_content, ProviderHandler, (Vec<Song>, Pagination), key: String, album: QueryableAlbum, pagination: Pagination, scope: Option<String>);
@@ -0,0 +1,165 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use types::songs::Song;
+
+/// Candidates scoring below this are rejected, better ones are played and remembered
+pub const MIN_ACCEPTED_SCORE: f64 = 0.6;
+
+const TITLE_WEIGHT: f64 = 0.4;
+const ARTIST_WEIGHT: f64 = 0.25;
+const DURATION_WEIGHT: f64 = 0.25;
+const OFFICIAL_WEIGHT: f64 = 0.1;
+const UNWANTED_PENALTY: f64 = 0.25;
+
+// Durations within this many seconds are considered equal
+const DURATION_TOLERANCE: f64 = 3f64;
+// Durations differing by this many seconds or more get no duration score
+const DURATION_CUTOFF: f64 = 30f64;
+
+// Markers of a different rendition of a song, ignored if the song itself has them
+const UNWANTED_KEYWORDS: &[&str] = &[
+    "live",
+    "cover",
+    "karaoke",
+    "remix",
+    "instrumental",
+    "acoustic",
+    "nightcore",
+    "slowed",
+    "sped up",
+    "reverb",
+    "8d",
+    "hour",
+    "hours",
+    "loop",
+    "reaction",
+    "tutorial",
+    "mashup",
+];
+
+const OFFICIAL_KEYWORDS: &[&str] = &["official audio", "official video", "official music video"];
+
+#[tracing::instrument(level = "trace", skip(s))]
+fn normalize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[tracing::instrument(level = "trace", skip(haystack, phrase))]
+fn contains_phrase(haystack: &str, phrase: &str) -> bool {
+    format!(" {} ", haystack).contains(&format!(" {} ", phrase))
+}
+
+#[tracing::instrument(level = "trace", skip(song))]
+fn artist_names(song: &Song) -> Vec<String> {
+    song.artists
+        .iter()
+        .flatten()
+        .filter_map(|a| a.artist_name.as_deref().map(normalize))
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// Scores how likely `candidate` is the same recording as `song`, between 0 and 1.
+#[tracing::instrument(level = "trace", skip(song, candidate))]
+pub fn score_candidate(song: &Song, candidate: &Song) -> f64 {
+    let title = normalize(song.song.title.as_deref().unwrap_or_default());
+    let candidate_title = normalize(candidate.song.title.as_deref().unwrap_or_default());
+    let candidate_artists = artist_names(candidate);
+
+    let title_tokens = title
+        .split(' ')
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>();
+    let title_score = if title_tokens.is_empty() {
+        0f64
+    } else {
+        title_tokens
+            .iter()
+            .filter(|t| contains_phrase(&candidate_title, t))
+            .count() as f64
+            / title_tokens.len() as f64
+    };
+
+    let artists = artist_names(song);
+    let artist_score = if artists.is_empty() {
+        0.5
+    } else {
+        artists
+            .iter()
+            .filter(|a| {
+                contains_phrase(&candidate_title, a)
+                    || candidate_artists.iter().any(|c| c.contains(a.as_str()))
+            })
+            .count() as f64
+            / artists.len() as f64
+    };
+
+    let duration_score = match (song.song.duration, candidate.song.duration) {
+        (Some(a), Some(b)) if a > 0f64 && b > 0f64 => {
+            let delta = (a - b).abs();
+            if delta <= DURATION_TOLERANCE {
+                1f64
+            } else {
+                (1f64 - (delta - DURATION_TOLERANCE) / (DURATION_CUTOFF - DURATION_TOLERANCE))
+                    .max(0f64)
+            }
+        }
+        _ => 0.5,
+    };
+
+    // Auto-generated "Artist - Topic" channels only carry official audio
+    let is_official = candidate_artists.iter().any(|a| a.ends_with(" topic"))
+        || OFFICIAL_KEYWORDS
+            .iter()
+            .any(|k| contains_phrase(&candidate_title, k));
+    let official_score = if is_official { 1f64 } else { 0f64 };
+
+    let unwanted = UNWANTED_KEYWORDS
+        .iter()
+        .filter(|k| contains_phrase(&candidate_title, k) && !contains_phrase(&title, k))
+        .count() as f64;
+
+    let score = title_score * TITLE_WEIGHT
+        + artist_score * ARTIST_WEIGHT
+        + duration_score * DURATION_WEIGHT
+        + official_score * OFFICIAL_WEIGHT
+        - unwanted * UNWANTED_PENALTY;
+    score.clamp(0f64, 1f64)
+}
+
+/// Picks the highest scoring candidate for `song` along with its score.
+/// Candidates without a url can't be played and are skipped.
+#[tracing::instrument(level = "trace", skip(song, candidates))]
+pub fn best_match<'a>(song: &Song, candidates: &'a [Song]) -> Option<(&'a Song, f64)> {
+    candidates
+        .iter()
+        .filter(|c| c.song.url.is_some())
+        .map(|c| (c, score_candidate(song, c)))
+        .inspect(|(c, score)| tracing::trace!("Match candidate {:?}: {}", c.song.title, score))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+}
@@ -14,8 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-pub mod common;
+pub mod accounts;
 pub mod extension;
 pub mod handler;
+pub mod matcher;
 pub mod spotify;
 pub mod youtube;
+
+#[cfg(test)]
+mod test;
@@ -14,18 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{
-    collections::{HashMap, HashSet},
-    io::{BufRead, BufReader},
-    net::TcpListener,
-    thread,
-};
+use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 
 use chrono::{DateTime, TimeDelta};
 use futures::{channel::mpsc::UnboundedSender, SinkExt, StreamExt};
-use oauth2::{CsrfToken, PkceCodeVerifier};
 use preferences::preferences::PreferenceConfig;
 use regex::Regex;
 use rspotify::{
@@ -43,19 +37,19 @@ use tauri::{AppHandle, Manager, State};
 use types::{
     entities::{EntityInfo, QueryableAlbum, QueryableArtist, QueryablePlaylist, SearchResult},
     errors::Result,
-    oauth::OAuth2Client,
     providers::generic::{Pagination, ProviderStatus},
     songs::{QueryableSong, Song, SongType},
 };
 use types::{errors::MoosyncError, providers::generic::GenericProvider};
 use url::Url;
 
-use crate::{librespot::initialize_librespot, oauth::handler::OAuthHandler};
-
-use super::common::{
-    authorize, get_oauth_client, login, refresh_login, LoginArgs, OAuthClientArgs, TokenHolder,
+use crate::{
+    librespot::initialize_librespot,
+    oauth::handler::{OAuthConfig, OAuthHandler, TokenHolder},
 };
 
+use super::accounts::{Account, AccountStore};
+
 macro_rules! search_and_parse_all {
     ($client:expr, $term:expr, [$(($type:expr, $variant:path, $parse_fn:expr, $result_vec:expr)),*]) => {{
         $(
@@ -74,15 +68,13 @@ struct SpotifyConfig {
     client_id: Option<String>,
     redirect_uri: &'static str,
     scopes: Vec<&'static str>,
-    tokens: Option<TokenHolder>,
 }
 
 #[derive(Debug)]
 pub struct SpotifyProvider {
     app: AppHandle,
     config: SpotifyConfig,
-    verifier: Option<(OAuth2Client, PkceCodeVerifier, CsrfToken)>,
-    api_client: Option<AuthCodePkceSpotify>,
+    accounts: AccountStore<AuthCodePkceSpotify>,
     status_tx: UnboundedSender<ProviderStatus>,
 }
 
@@ -102,8 +94,7 @@ impl SpotifyProvider {
         Self {
             app,
             config: SpotifyConfig::default(),
-            verifier: None,
-            api_client: None,
+            accounts: AccountStore::default(),
             status_tx,
         }
     }
@@ -111,71 +102,120 @@ impl SpotifyProvider {
 
 impl SpotifyProvider {
     #[tracing::instrument(level = "trace", skip(self))]
-    fn get_oauth_client(&self) -> OAuth2Client {
-        get_oauth_client(OAuthClientArgs {
+    fn oauth_config(&self) -> OAuthConfig {
+        OAuthConfig {
             auth_url: "https://accounts.spotify.com/authorize".to_string(),
             token_url: "https://accounts.spotify.com/api/token".to_string(),
-            redirect_url: self.config.redirect_uri.to_string(),
-            client_id: self.config.client_id.clone().unwrap(),
-            client_secret: self.config.client_secret.clone().unwrap_or_default(),
-        })
+            redirect_uri: self.config.redirect_uri.to_string(),
+            client_id: self.config.client_id.clone().unwrap_or_default(),
+            client_secret: self.config.client_secret.clone(),
+            scopes: self.config.scopes.iter().map(|s| s.to_string()).collect(),
+            extra_params: vec![],
+            callback_path: "spotifyoauthcallback".into(),
+            token_key: "MoosyncSpotifyAccounts".into(),
+            legacy_token_key: Some("MoosyncSpotifyRefreshToken".into()),
+            on_demand_refresh: false,
+        }
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    async fn create_api_client(&mut self) {
-        tracing::debug!("Creating spotify api client");
-        if let Some(token) = &self.config.tokens {
-            self.api_client = Some(AuthCodePkceSpotify::from_token(Token {
-                access_token: token.access_token.clone(),
-                expires_in: TimeDelta::seconds(token.expires_in.try_into().unwrap()),
-                expires_at: Some(DateTime::from_timestamp_millis(token.expires_at).unwrap()),
-                refresh_token: Some(token.refresh_token.clone()),
-                scopes: HashSet::from_iter(self.config.scopes.iter().map(|v| v.to_string())),
-            }));
-
-            let res = self.fetch_user_details().await;
-            let mut is_spotify_premium = false;
-            if let Ok((res, is_premium)) = res {
-                let _ = self.status_tx.send(res).await;
-                is_spotify_premium = is_premium;
-            } else {
-                let _ = self
-                    .status_tx
-                    .send(ProviderStatus {
-                        key: self.key(),
-                        name: "Spotify".into(),
-                        user_name: None,
-                        logged_in: true,
-                        bg_color: "#07C330".into(),
-                        account_id: "spotify".into(),
-                    })
-                    .await;
-            }
+    fn api_client(&self) -> Option<&AuthCodePkceSpotify> {
+        self.accounts.active().map(|a| &a.client)
+    }
 
-            if is_spotify_premium {
-                tracing::debug!("Initializing librespot");
-                if let Err(err) = initialize_librespot(self.app.clone(), token.access_token.clone())
-                {
-                    tracing::error!("Error initializing librespot {:?}", err);
+    #[tracing::instrument(level = "trace", skip(self, tokens))]
+    fn build_api_client(&self, tokens: &TokenHolder) -> AuthCodePkceSpotify {
+        AuthCodePkceSpotify::from_token(Token {
+            access_token: tokens.access_token.clone(),
+            expires_in: TimeDelta::seconds(tokens.expires_in as i64),
+            expires_at: DateTime::from_timestamp_millis(tokens.expires_at),
+            refresh_token: Some(tokens.refresh_token.clone()),
+            scopes: HashSet::from_iter(self.config.scopes.iter().map(|v| v.to_string())),
+        })
+    }
+
+    /// Signs an account in with fresh tokens. Stored accounts pass their id,
+    /// new logins are identified by the Spotify user id.
+    #[tracing::instrument(level = "trace", skip(self, tokens))]
+    async fn connect_account(
+        &mut self,
+        account_id: Option<String>,
+        tokens: TokenHolder,
+    ) -> Result<()> {
+        tracing::debug!("Creating spotify api client");
+        let client = self.build_api_client(&tokens);
+        let is_new = account_id.is_none();
+
+        let app = self.app.clone();
+        let oauth: State<OAuthHandler> = app.state();
+        let (id, user_name) = match (client.current_user().await, account_id) {
+            (Ok(user), stored_id) => {
+                let id = user.id.id().to_string();
+                // New logins and accounts migrated from a single refresh token
+                if stored_id.as_deref() != Some(id.as_str()) {
+                    oauth.add_account(
+                        &self.app,
+                        &self.key(),
+                        &id,
+                        user.display_name.clone(),
+                        &tokens,
+                    )?;
+                    if let Some(stored_id) = stored_id {
+                        oauth.remove_account(&self.app, &self.key(), &stored_id)?;
+                    }
                 }
+                (id, user.display_name)
             }
+            (Err(e), Some(stored_id)) => {
+                tracing::error!("Error fetching spotify user: {:?}", e);
+                (stored_id, None)
+            }
+            (Err(e), None) => return Err(e.into()),
+        };
+
+        self.accounts.insert(Account {
+            id: id.clone(),
+            user_name,
+            tokens,
+            client,
+        });
+        if is_new {
+            self.accounts.set_active(&id)?;
         }
+
+        if self.accounts.active_id() == Some(id) {
+            self.initialize_librespot().await;
+        }
+        self.send_statuses().await;
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    async fn refresh_login(&mut self) -> Result<()> {
-        tracing::debug!("Refreshing spotify login");
-        self.config.tokens = Some(
-            refresh_login(
-                "MoosyncSpotifyRefreshToken",
-                self.get_oauth_client(),
-                &self.app,
-            )
-            .await?,
-        );
-        self.create_api_client().await;
+    async fn initialize_librespot(&self) {
+        let Some(account) = self.accounts.active() else {
+            return;
+        };
 
-        Ok(())
+        let is_premium = account
+            .client
+            .current_user()
+            .await
+            .is_ok_and(|user| user.product == Some(SubscriptionLevel::Premium));
+        if is_premium {
+            tracing::debug!("Initializing librespot");
+            if let Err(err) =
+                initialize_librespot(self.app.clone(), account.tokens.access_token.clone())
+            {
+                tracing::error!("Error initializing librespot {:?}", err);
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn send_statuses(&mut self) {
+        for status in self.accounts.statuses(&self.key(), "Spotify", "#07C330") {
+            let _ = self.status_tx.send(status).await;
+        }
     }
 
     #[tracing::instrument(level = "trace", skip(self, playlist))]
@@ -255,36 +295,6 @@ impl SpotifyProvider {
         }
     }
 
-    #[tracing::instrument(level = "trace", skip(self))]
-    async fn fetch_user_details(&self) -> Result<(ProviderStatus, bool)> {
-        tracing::info!("Fetching user details for spotify");
-        if let Some(api_client) = &self.api_client {
-            let token = api_client.token.lock().await.unwrap();
-            drop(token);
-
-            let user = api_client.current_user().await?;
-            let mut is_premium = false;
-            if let Some(subscription) = user.product {
-                if subscription == SubscriptionLevel::Premium {
-                    is_premium = true
-                }
-            }
-            return Ok((
-                ProviderStatus {
-                    key: self.key(),
-                    name: "Spotify".into(),
-                    user_name: user.display_name,
-                    logged_in: true,
-                    bg_color: "#07C330".into(),
-                    account_id: "spotify".into(),
-                },
-                is_premium,
-            ));
-        }
-
-        Err("API client not initialized".into())
-    }
-
     fn get_full_track(&self, track: SimplifiedTrack) -> FullTrack {
         FullTrack {
             album: track.album.unwrap_or_default(),
@@ -331,17 +341,8 @@ impl SpotifyProvider {
 impl GenericProvider for SpotifyProvider {
     #[tracing::instrument(level = "trace", skip(self))]
     async fn initialize(&mut self) -> Result<()> {
-        let _ = self
-            .status_tx
-            .send(ProviderStatus {
-                key: self.key(),
-                name: "Spotify".into(),
-                user_name: None,
-                logged_in: false,
-                bg_color: "#07C330".into(),
-                account_id: "spotify".into(),
-            })
-            .await;
+        self.accounts.clear();
+        self.send_statuses().await;
 
         let preferences: State<PreferenceConfig> = self.app.state();
         let spotify_config: Value = preferences
@@ -378,9 +379,26 @@ impl GenericProvider for SpotifyProvider {
             "app-remote-control",
         ];
 
-        let res = self.refresh_login().await;
-        if let Err(err) = res {
-            tracing::error!("spotify refresh login err: {:?}", err);
+        let stored = {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.register_client(self.key(), self.oauth_config());
+            oauth.accounts(&self.app, &self.key()).unwrap_or_default()
+        };
+
+        for account in stored.accounts {
+            let res = self.refresh_login(account.id).await;
+            if let Err(err) = res {
+                tracing::error!("spotify refresh login err: {:?}", err);
+            }
+        }
+        // The first account to connect became active in the meantime
+        if let Some(active) = stored.active {
+            if self.accounts.active_id() != Some(active.clone())
+                && self.accounts.set_active(&active).is_ok()
+            {
+                self.initialize_librespot().await;
+                self.send_statuses().await;
+            }
         }
 
         Ok(())
@@ -391,6 +409,11 @@ impl GenericProvider for SpotifyProvider {
         "spotify".into()
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn can_search(&self) -> bool {
+        self.api_client().is_some()
+    }
+
     #[tracing::instrument(level = "trace", skip(self, id))]
     fn match_id(&self, id: String) -> bool {
         id.starts_with("spotify-playlist:")
@@ -406,95 +429,61 @@ impl GenericProvider for SpotifyProvider {
 
     #[tracing::instrument(level = "trace", skip(self))]
     async fn login(&mut self, _: String) -> Result<String> {
-        let (url, verifier) = login(
-            LoginArgs {
-                client_id: self.config.client_id.clone(),
-                client_secret: self.config.client_secret.clone(),
-                scopes: self.config.scopes.clone(),
-                extra_params: None,
-            },
-            self.get_oauth_client(),
-            &self.app,
-        )?;
-        self.verifier = verifier;
-
-        let redirect_uri = self.config.redirect_uri;
-        if redirect_uri.starts_with("http://127.0.0.1:8898") {
-            let app_handle = self.app.clone();
-            thread::spawn(move || {
-                let socket_addr = Url::parse(redirect_uri)
-                    .unwrap()
-                    .socket_addrs(|| None)
-                    .unwrap()
-                    .pop()
-                    .unwrap();
-
-                tracing::info!("Listening {:?}", socket_addr);
-
-                let listener = TcpListener::bind(socket_addr).unwrap();
-                let stream = listener.incoming().flatten().next().unwrap();
-                let mut reader = BufReader::new(&stream);
-                let mut request_line = String::new();
-                reader.read_line(&mut request_line).unwrap();
-
-                let code = request_line.split_whitespace().nth(1);
-                if let Some(code) = code {
-                    tracing::info!("Got redirect URI {:?}", code);
-                    let parsed_code = code.replace("/login", "");
-                    let oauth_handler: State<OAuthHandler> = app_handle.state();
-                    oauth_handler
-                        .handle_oauth(
-                            app_handle.clone(),
-                            format!("moosync://spotifyoauthcallback{}", parsed_code),
-                        )
-                        .unwrap();
-                }
-            });
-        }
-
-        let oauth_handler: State<OAuthHandler> = self.app.state();
-        oauth_handler.register_oauth_path("spotifyoauthcallback".into(), self.key());
-
-        Ok(url)
+        let oauth: State<OAuthHandler> = self.app.state();
+        oauth.login(&self.app, &self.key())
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    async fn signout(&mut self, _: String) -> Result<()> {
-        self.config.tokens = None;
-        self.api_client = None;
-        self.verifier = None;
+    async fn signout(&mut self, account_id: String) -> Result<()> {
+        let was_active = self.accounts.active_id().as_ref() == Some(&account_id);
+        if self.accounts.remove(&account_id).is_none() {
+            return Ok(());
+        }
 
-        let preferences: State<PreferenceConfig> = self.app.state();
-        preferences.set_secure("MoosyncSpotifyRefreshToken".into(), None::<String>)?;
-
-        let _ = self
-            .status_tx
-            .send(ProviderStatus {
-                key: self.key(),
-                name: "Spotify".into(),
-                user_name: None,
-                logged_in: false,
-                bg_color: "#07C330".into(),
-                account_id: "spotify".into(),
-            })
-            .await;
+        {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.remove_account(&self.app, &self.key(), &account_id)?;
+        }
+
+        if was_active {
+            self.initialize_librespot().await;
+        }
+        self.send_statuses().await;
         Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self, code))]
     async fn authorize(&mut self, code: String) -> Result<()> {
         tracing::info!("Authorizing with code {}", code);
-        self.config.tokens = Some(
-            authorize(
-                "MoosyncSpotifyRefreshToken",
-                code,
-                &mut self.verifier,
-                &self.app,
-            )
-            .await?,
-        );
-
-        self.create_api_client().await;
+        let tokens = {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.authorize(&self.key(), code).await?
+        };
+
+        self.connect_account(None, tokens).await
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn refresh_login(&mut self, account_id: String) -> Result<()> {
+        tracing::debug!("Refreshing spotify login");
+        let tokens = {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.refresh(&self.app, &self.key(), &account_id).await?
+        };
+
+        self.connect_account(Some(account_id), tokens).await
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn set_active_account(&mut self, account_id: String) -> Result<()> {
+        self.accounts.set_active(&account_id)?;
+        {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.set_active_account(&self.app, &self.key(), &account_id)?;
+        }
+
+        self.initialize_librespot().await;
+        self.send_statuses().await;
         Ok(())
     }
 
@@ -504,14 +493,17 @@ impl GenericProvider for SpotifyProvider {
         pagination: Pagination,
     ) -> Result<(Vec<QueryablePlaylist>, Pagination)> {
         let mut ret = vec![];
-        tracing::info!("Fetching spotify playlists {:?}", self.api_client);
-        if let Some(api_client) = &self.api_client {
-            let playlists = api_client
+        tracing::info!("Fetching spotify playlists {:?}", self.accounts.active_id());
+        if let Some(account) = self.accounts.active() {
+            let playlists = account
+                .client
                 .current_user_playlists_manual(Some(pagination.limit), Some(pagination.offset))
                 .await;
             if let Ok(playlists) = playlists {
                 for playlist in playlists.items {
-                    ret.push(self.parse_playlist(playlist))
+                    let mut playlist = self.parse_playlist(playlist);
+                    playlist.account_id = Some(account.id.clone());
+                    ret.push(playlist)
                 }
             }
             tracing::info!("Got user playlists {:?}", ret);
@@ -532,7 +524,9 @@ impl GenericProvider for SpotifyProvider {
             return Err("Playlist ID cannot be none".into());
         }
         let playlist_id = playlist.playlist_id.unwrap();
-        if let Some(api_client) = &self.api_client {
+        // Private playlists are only visible to the account they were listed from
+        let account = self.accounts.get_or_active(playlist.account_id.as_deref());
+        if let Some(api_client) = account.map(|a| &a.client) {
             let playlist_id = playlist_id
                 .strip_prefix("spotify-playlist:")
                 .unwrap_or(&playlist_id);
@@ -581,7 +575,7 @@ impl GenericProvider for SpotifyProvider {
             ..Default::default()
         };
 
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             search_and_parse_all!(
                 api_client,
                 &term,
@@ -657,7 +651,7 @@ impl GenericProvider for SpotifyProvider {
             url
         };
 
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let playlists = api_client
                 .playlist(
                     PlaylistId::from_id_or_uri(playlist_id.as_str())
@@ -695,7 +689,7 @@ impl GenericProvider for SpotifyProvider {
             url
         };
 
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let res = api_client
                 .track(TrackId::from_id_or_uri(track_id.as_str())?, None)
                 .await?;
@@ -708,7 +702,7 @@ impl GenericProvider for SpotifyProvider {
 
     #[tracing::instrument(level = "trace", skip(self))]
     async fn get_suggestions(&self) -> Result<Vec<Song>> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let mut i = 0;
             let mut ret = vec![];
             while i < 5 {
@@ -768,7 +762,7 @@ impl GenericProvider for SpotifyProvider {
         album: QueryableAlbum,
         pagination: Pagination,
     ) -> Result<(Vec<Song>, Pagination)> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let mut raw_id = album.album_id;
             if let Some(id) = &raw_id {
                 if !self.match_id(id.clone()) {
@@ -813,7 +807,7 @@ impl GenericProvider for SpotifyProvider {
         artist: QueryableArtist,
         pagination: Pagination,
     ) -> Result<(Vec<Song>, Pagination)> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             if let Some(next_page_token) = &pagination.token {
                 // TODO: Fetch next pages
                 let _tokens = next_page_token.split(";").collect::<Vec<_>>();
@@ -0,0 +1,280 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use types::{
+    entities::QueryableArtist,
+    songs::{QueryableSong, Song},
+};
+
+use crate::{
+    oauth::handler::TokenHolder,
+    providers::{
+        accounts::{Account, AccountStore},
+        matcher::{best_match, score_candidate, MIN_ACCEPTED_SCORE},
+    },
+};
+
+fn song(title: &str, artists: &[&str], duration: Option<f64>) -> Song {
+    Song {
+        song: QueryableSong {
+            title: Some(title.to_string()),
+            duration,
+            ..Default::default()
+        },
+        artists: Some(
+            artists
+                .iter()
+                .map(|a| QueryableArtist {
+                    artist_name: Some(a.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    }
+}
+
+fn candidate(title: &str, artists: &[&str], duration: Option<f64>) -> Song {
+    let mut candidate = song(title, artists, duration);
+    candidate.song.url = Some(title.to_string());
+    candidate
+}
+
+fn original() -> Song {
+    song("Bohemian Rhapsody", &["Queen"], Some(354f64))
+}
+
+#[test]
+fn test_official_upload_scores_high() {
+    let topic = song("Bohemian Rhapsody", &["Queen - Topic"], Some(354f64));
+    assert!(score_candidate(&original(), &topic) > 0.99);
+
+    let video = song(
+        "Queen - Bohemian Rhapsody (Official Video)",
+        &["Queen Official"],
+        Some(359f64),
+    );
+    assert!(score_candidate(&original(), &video) >= MIN_ACCEPTED_SCORE);
+}
+
+#[test]
+fn test_unrelated_candidate_below_threshold() {
+    let unrelated = song("Never Gonna Give You Up", &["Rick Astley"], Some(213f64));
+    let score = score_candidate(&original(), &unrelated);
+    assert_eq!(score, 0f64);
+    assert!(score < MIN_ACCEPTED_SCORE);
+}
+
+#[test]
+fn test_unwanted_rendition_penalized() {
+    let studio = song("Queen - Bohemian Rhapsody", &["Queen"], Some(354f64));
+    let live = song(
+        "Queen - Bohemian Rhapsody (Live Aid 1985)",
+        &["Queen"],
+        Some(354f64),
+    );
+    let karaoke = song(
+        "Bohemian Rhapsody Karaoke Instrumental",
+        &["Sing King"],
+        Some(354f64),
+    );
+
+    let studio_score = score_candidate(&original(), &studio);
+    assert!(score_candidate(&original(), &live) < studio_score);
+    assert!(score_candidate(&original(), &karaoke) < MIN_ACCEPTED_SCORE);
+}
+
+#[test]
+fn test_keyword_in_own_title_not_penalized() {
+    let live_song = song("Bohemian Rhapsody (Live)", &["Queen"], None);
+    let live = song(
+        "Queen - Bohemian Rhapsody (Live Aid 1985)",
+        &["Queen"],
+        None,
+    );
+    assert!(score_candidate(&live_song, &live) >= MIN_ACCEPTED_SCORE);
+}
+
+#[test]
+fn test_duration_difference_lowers_score() {
+    let exact = song("Bohemian Rhapsody", &["Queen"], Some(356f64));
+    let off = song("Bohemian Rhapsody", &["Queen"], Some(370f64));
+    let far = song("Bohemian Rhapsody", &["Queen"], Some(400f64));
+    let unknown = song("Bohemian Rhapsody", &["Queen"], None);
+
+    let exact_score = score_candidate(&original(), &exact);
+    let off_score = score_candidate(&original(), &off);
+    let far_score = score_candidate(&original(), &far);
+    assert!(exact_score > off_score);
+    assert!(off_score > far_score);
+
+    // Missing durations are neither rewarded nor punished fully
+    let unknown_score = score_candidate(&original(), &unknown);
+    assert!(unknown_score < exact_score);
+    assert!(unknown_score > far_score);
+}
+
+#[test]
+fn test_best_match_picks_highest_score() {
+    let candidates = vec![
+        candidate("Never Gonna Give You Up", &["Rick Astley"], Some(213f64)),
+        candidate(
+            "Queen - Bohemian Rhapsody (Live Aid 1985)",
+            &["Queen"],
+            Some(354f64),
+        ),
+        candidate("Bohemian Rhapsody", &["Queen - Topic"], Some(354f64)),
+    ];
+
+    let (matched, score) = best_match(&original(), &candidates).unwrap();
+    assert!(std::ptr::eq(matched, &candidates[2]));
+    assert!(score > 0.99);
+}
+
+#[test]
+fn test_best_match_without_candidates() {
+    assert!(best_match(&original(), &[]).is_none());
+}
+
+#[test]
+fn test_best_match_skips_candidates_without_url() {
+    let candidates = vec![
+        song("Bohemian Rhapsody", &["Queen - Topic"], Some(354f64)),
+        candidate("Never Gonna Give You Up", &["Rick Astley"], Some(213f64)),
+    ];
+
+    let (matched, _) = best_match(&original(), &candidates).unwrap();
+    assert!(std::ptr::eq(matched, &candidates[1]));
+    assert!(best_match(&original(), &candidates[..1]).is_none());
+}
+
+fn account(id: &str, client: u32) -> Account<u32> {
+    Account {
+        id: id.to_string(),
+        user_name: Some(format!("user {}", id)),
+        tokens: TokenHolder {
+            access_token: String::new(),
+            refresh_token: String::new(),
+            expires_in: 0,
+            expires_at: 0,
+        },
+        client,
+    }
+}
+
+#[test]
+fn test_accounts_first_insert_is_active() {
+    let mut accounts = AccountStore::default();
+    assert!(accounts.active().is_none());
+
+    accounts.insert(account("a", 1));
+    accounts.insert(account("b", 2));
+    assert_eq!(accounts.active_id().as_deref(), Some("a"));
+    assert!(accounts.contains("b"));
+    assert!(!accounts.contains("c"));
+}
+
+#[test]
+fn test_accounts_insert_replaces() {
+    let mut accounts = AccountStore::default();
+    accounts.insert(account("a", 1));
+    accounts.insert(account("a", 2));
+
+    assert_eq!(accounts.active().unwrap().client, 2);
+    assert_eq!(accounts.statuses("key", "Name", "#fff").len(), 2);
+}
+
+#[test]
+fn test_accounts_get_or_active() {
+    let mut accounts = AccountStore::default();
+    accounts.insert(account("a", 1));
+    accounts.insert(account("b", 2));
+
+    assert_eq!(accounts.get_or_active(Some("b")).unwrap().client, 2);
+    assert_eq!(accounts.get_or_active(Some("missing")).unwrap().client, 1);
+    assert_eq!(accounts.get_or_active(None).unwrap().client, 1);
+}
+
+#[test]
+fn test_accounts_set_active() {
+    let mut accounts = AccountStore::default();
+    accounts.insert(account("a", 1));
+    accounts.insert(account("b", 2));
+
+    accounts.set_active("b").unwrap();
+    assert_eq!(accounts.active_id().as_deref(), Some("b"));
+    assert!(accounts.set_active("missing").is_err());
+    assert_eq!(accounts.active_id().as_deref(), Some("b"));
+}
+
+#[test]
+fn test_accounts_remove() {
+    let mut accounts = AccountStore::default();
+    accounts.insert(account("a", 1));
+    accounts.insert(account("b", 2));
+    accounts.insert(account("c", 3));
+    accounts.set_active("b").unwrap();
+
+    // Removing an inactive account keeps the active one
+    assert_eq!(accounts.remove("c").unwrap().client, 3);
+    assert_eq!(accounts.active_id().as_deref(), Some("b"));
+
+    // Removing the active account falls back to the first remaining one
+    accounts.remove("b").unwrap();
+    assert_eq!(accounts.active_id().as_deref(), Some("a"));
+
+    assert!(accounts.remove("missing").is_none());
+    accounts.remove("a").unwrap();
+    assert!(accounts.active().is_none());
+}
+
+#[test]
+fn test_accounts_clear() {
+    let mut accounts = AccountStore::default();
+    accounts.insert(account("a", 1));
+    accounts.clear();
+
+    assert!(accounts.active().is_none());
+    assert!(!accounts.contains("a"));
+}
+
+#[test]
+fn test_accounts_statuses() {
+    let mut accounts = AccountStore::default();
+    assert_eq!(accounts.statuses("key", "Name", "#fff").len(), 1);
+
+    accounts.insert(account("a", 1));
+    accounts.insert(account("b", 2));
+    accounts.set_active("b").unwrap();
+    let statuses = accounts.statuses("key", "Name", "#fff");
+    assert_eq!(statuses.len(), 3);
+
+    // Leading entry is used to sign in another account
+    assert!(!statuses[0].logged_in);
+    assert_eq!(statuses[0].account_id, "key");
+    assert!(!statuses[0].active);
+
+    assert_eq!(statuses[1].account_id, "a");
+    assert!(statuses[1].logged_in);
+    assert!(!statuses[1].active);
+    assert_eq!(statuses[1].user_name.as_deref(), Some("user a"));
+
+    assert_eq!(statuses[2].account_id, "b");
+    assert!(statuses[2].active);
+    assert!(statuses
+        .iter()
+        .all(|s| s.key == "key" && s.name == "Name" && s.bg_color == "#fff"));
+}
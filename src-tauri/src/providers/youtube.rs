@@ -15,33 +15,36 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use async_trait::async_trait;
+use database::database::Database;
 use futures::channel::mpsc::UnboundedSender;
 use futures::SinkExt;
 use google_youtube3::api::{Channel, ChannelSnippet, Playlist, PlaylistSnippet, Video};
 use google_youtube3::hyper_rustls::HttpsConnector;
 use google_youtube3::hyper_util::client::legacy::connect::HttpConnector;
 use google_youtube3::{hyper_rustls, hyper_util, YouTube};
-use oauth2::basic::BasicClient;
-use oauth2::{AuthUrl, ClientId, ClientSecret, CsrfToken, PkceCodeVerifier, RedirectUrl, TokenUrl};
 use preferences::preferences::PreferenceConfig;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
 use tauri::{AppHandle, Manager, State};
 use types::entities::{
-    EntityInfo, QueryableAlbum, QueryableArtist, QueryablePlaylist, SearchResult,
+    EntityInfo, QueryableAlbum, QueryableArtist, QueryablePlaylist, SearchResult, TrackMapping,
 };
 use types::errors::{MoosyncError, Result};
+use types::preferences::CheckboxPreference;
+use types::providers::generic::GenericProvider;
 use types::providers::generic::{Pagination, ProviderStatus};
 use types::songs::{QueryableSong, Song, SongType};
-use types::{oauth::OAuth2Client, providers::generic::GenericProvider};
 use url::Url;
+use youtube::formats::AudioQuality;
 use youtube::youtube::YoutubeScraper;
 
-use crate::oauth::handler::OAuthHandler;
+use crate::oauth::handler::{OAuthConfig, OAuthHandler, TokenHolder};
 
-use super::common::{authorize, login, refresh_login, LoginArgs, TokenHolder};
+use super::{
+    accounts::{Account, AccountStore},
+    matcher::{best_match, MIN_ACCEPTED_SCORE},
+};
 
 macro_rules! search_and_parse {
     ($client:expr, $term:expr, $type:expr, $process_fn:expr) => {{
@@ -76,14 +79,12 @@ struct YoutubeConfig {
     client_id: Option<String>,
     redirect_uri: &'static str,
     scopes: Vec<&'static str>,
-    tokens: Option<TokenHolder>,
 }
 
 pub struct YoutubeProvider {
     app: AppHandle,
     config: YoutubeConfig,
-    verifier: Option<(OAuth2Client, PkceCodeVerifier, CsrfToken)>,
-    api_client: Option<YouTube<HttpsConnector<HttpConnector>>>,
+    accounts: AccountStore<YouTube<HttpsConnector<HttpConnector>>>,
     status_tx: UnboundedSender<ProviderStatus>,
 }
 
@@ -100,77 +101,108 @@ impl YoutubeProvider {
         Self {
             app,
             config: YoutubeConfig::default(),
-            verifier: None,
-            api_client: None,
+            accounts: AccountStore::default(),
             status_tx,
         }
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    fn get_oauth_client(&self) -> Option<OAuth2Client> {
-        if self.config.client_id.is_some() && self.config.client_secret.is_some() {
-            let client = BasicClient::new(
-                ClientId::new(self.config.client_id.clone().unwrap()),
-                Some(ClientSecret::new(
-                    self.config.client_secret.clone().unwrap(),
-                )),
-                AuthUrl::new("https://accounts.google.com/o/oauth2/v2/auth".to_string()).unwrap(),
-                Some(TokenUrl::new("https://oauth2.googleapis.com/token".to_string()).unwrap()),
-            )
-            .set_redirect_uri(RedirectUrl::new(self.config.redirect_uri.to_string()).unwrap());
-            return Some(client);
-        }
-        None
+    fn oauth_config(&self) -> Option<OAuthConfig> {
+        let (Some(client_id), Some(client_secret)) =
+            (&self.config.client_id, &self.config.client_secret)
+        else {
+            return None;
+        };
+
+        Some(OAuthConfig {
+            auth_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
+            token_url: "https://oauth2.googleapis.com/token".to_string(),
+            redirect_uri: self.config.redirect_uri.to_string(),
+            client_id: client_id.clone(),
+            client_secret: Some(client_secret.clone()),
+            scopes: self.config.scopes.iter().map(|s| s.to_string()).collect(),
+            extra_params: vec![
+                ("prompt".into(), "consent".into()),
+                ("access_type".into(), "offline".into()),
+            ],
+            callback_path: "youtubeoauthcallback".into(),
+            token_key: "MoosyncYoutubeAccounts".into(),
+            legacy_token_key: Some("MoosyncYoutubeRefreshToken".into()),
+            on_demand_refresh: false,
+        })
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    async fn create_api_client(&mut self) {
-        if let Some(token) = &self.config.tokens {
-            let client =
-                hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
-                    .build(
-                        hyper_rustls::HttpsConnectorBuilder::new()
-                            .with_native_roots()
-                            .unwrap()
-                            .https_or_http()
-                            .enable_http1()
-                            .build(),
-                    );
+    fn api_client(&self) -> Option<&YouTube<HttpsConnector<HttpConnector>>> {
+        self.accounts.active().map(|a| &a.client)
+    }
 
-            self.api_client = Some(google_youtube3::YouTube::new(
-                client,
-                token.access_token.clone(),
-            ));
+    #[tracing::instrument(level = "trace", skip(tokens))]
+    fn build_api_client(tokens: &TokenHolder) -> YouTube<HttpsConnector<HttpConnector>> {
+        let client =
+            hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+                .build(
+                    hyper_rustls::HttpsConnectorBuilder::new()
+                        .with_native_roots()
+                        .unwrap()
+                        .https_or_http()
+                        .enable_http1()
+                        .build(),
+                );
+
+        google_youtube3::YouTube::new(client, tokens.access_token.clone())
+    }
 
-            let res = self.fetch_user_details().await;
-            if let Ok(res) = res {
-                let _ = self.status_tx.send(res).await;
-            } else {
-                let _ = self
-                    .status_tx
-                    .send(ProviderStatus {
-                        key: self.key(),
-                        name: "Youtube".into(),
-                        user_name: None,
-                        logged_in: true,
-                        bg_color: "#E62017".into(),
-                        account_id: "youtube".into(),
-                    })
-                    .await;
+    /// Signs an account in with fresh tokens. Stored accounts pass their id,
+    /// new logins are identified by the id of the user's channel.
+    #[tracing::instrument(level = "trace", skip(self, tokens))]
+    async fn connect_account(
+        &mut self,
+        account_id: Option<String>,
+        tokens: TokenHolder,
+    ) -> Result<()> {
+        let client = Self::build_api_client(&tokens);
+        let is_new = account_id.is_none();
+
+        let app = self.app.clone();
+        let oauth: State<OAuthHandler> = app.state();
+        let (id, user_name) = match (Self::fetch_user_details(&client).await, account_id) {
+            (Ok((id, user_name)), stored_id) => {
+                // New logins and accounts migrated from a single refresh token
+                if stored_id.as_deref() != Some(id.as_str()) {
+                    oauth.add_account(&self.app, &self.key(), &id, user_name.clone(), &tokens)?;
+                    if let Some(stored_id) = stored_id {
+                        oauth.remove_account(&self.app, &self.key(), &stored_id)?;
+                    }
+                }
+                (id, user_name)
             }
+            (Err(e), Some(stored_id)) => {
+                tracing::error!("Error fetching youtube user: {:?}", e);
+                (stored_id, None)
+            }
+            (Err(e), None) => return Err(e),
+        };
+
+        self.accounts.insert(Account {
+            id: id.clone(),
+            user_name,
+            tokens,
+            client,
+        });
+        if is_new {
+            self.accounts.set_active(&id)?;
         }
+
+        self.send_statuses().await;
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    async fn refresh_login(&mut self) -> Result<()> {
-        let client = self.get_oauth_client();
-        if let Some(client) = client {
-            self.config.tokens =
-                Some(refresh_login("MoosyncYoutubeRefreshToken", client, &self.app).await?);
-            self.create_api_client().await;
+    async fn send_statuses(&mut self) {
+        for status in self.accounts.statuses(&self.key(), "Youtube", "#E62017") {
+            let _ = self.status_tx.send(status).await;
         }
-
-        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self, resp))]
@@ -225,7 +257,7 @@ impl YoutubeProvider {
     #[tracing::instrument(level = "trace", skip(self, ids))]
     async fn fetch_song_details(&self, ids: Vec<String>) -> Result<Vec<Song>> {
         tracing::info!("Fetching song details for {:?}", ids);
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let mut ret = vec![];
 
             for id_chunk in ids.chunks(50) {
@@ -294,39 +326,29 @@ impl YoutubeProvider {
         }
     }
 
-    #[tracing::instrument(level = "trace", skip(self))]
-    async fn fetch_user_details(&self) -> Result<ProviderStatus> {
-        if let Some(api_client) = &self.api_client {
-            let (_, user_info) = api_client
-                .channels()
-                .list(&vec!["snippet".into()])
-                .mine(true)
-                .max_results(1)
-                .doit()
-                .await?;
-
-            let mut username = Some("".to_string());
-            if let Some(items) = user_info.items {
-                let channel = items.first().unwrap();
-                if let Some(snippet) = &channel.snippet {
-                    username = snippet.title.clone();
-                }
-            }
-            return Ok(ProviderStatus {
-                key: self.key(),
-                name: "Youtube".into(),
-                user_name: username,
-                logged_in: true,
-                bg_color: "#E62017".into(),
-                account_id: "youtube".into(),
-            });
-        }
+    /// Id and title of the channel of the signed in user
+    #[tracing::instrument(level = "trace", skip(api_client))]
+    async fn fetch_user_details(
+        api_client: &YouTube<HttpsConnector<HttpConnector>>,
+    ) -> Result<(String, Option<String>)> {
+        let (_, user_info) = api_client
+            .channels()
+            .list(&vec!["snippet".into()])
+            .mine(true)
+            .max_results(1)
+            .doit()
+            .await?;
 
-        Err("API client not initialized".into())
+        let channel = user_info
+            .items
+            .and_then(|items| items.into_iter().next())
+            .ok_or("No channel found for user")?;
+        let id = channel.id.ok_or("Channel has no id")?;
+        Ok((id, channel.snippet.and_then(|s| s.title)))
     }
 
     async fn search_playlists(&self, term: &str) -> Result<Vec<QueryablePlaylist>> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             return Ok(search_and_parse!(api_client, term, "playlist", |item| {
                 item.id.as_ref().and_then(|id| {
                     id.playlist_id.as_ref().map(|playlist_id| {
@@ -354,7 +376,7 @@ impl YoutubeProvider {
     }
 
     async fn search_artists(&self, term: &str) -> Result<Vec<QueryableArtist>> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             return Ok(search_and_parse!(api_client, &term, "channel", |item| {
                 item.id.as_ref().and_then(|id| {
                     id.channel_id.as_ref().map(|channel_id| {
@@ -387,7 +409,7 @@ impl YoutubeProvider {
         pagination: Pagination,
     ) -> Result<(Vec<Song>, Pagination)> {
         let artist_id = artist_id.replace("youtube-artist:", "");
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let mut builder = api_client
                 .channels()
                 .list(&vec!["contentDetails".into()])
@@ -420,10 +442,17 @@ impl YoutubeProvider {
             };
         }
 
+        if !pagination.is_first && pagination.token.is_none() {
+            return Ok((vec![], pagination));
+        }
+
         let youtube_scraper: State<YoutubeScraper> = self.app.state();
-        let search_res = youtube_scraper.search_yt(artist_id).await?;
+        let res = youtube_scraper
+            .get_channel_uploads(artist_id, pagination.clone())
+            .await?;
 
-        Ok((search_res.songs, pagination.next_page()))
+        let next_page_token = res.next_page_token.map(|t| t.encode()).transpose()?;
+        Ok((res.songs, pagination.next_page_wtoken(next_page_token)))
     }
 }
 
@@ -431,17 +460,8 @@ impl YoutubeProvider {
 impl GenericProvider for YoutubeProvider {
     #[tracing::instrument(level = "trace", skip(self))]
     async fn initialize(&mut self) -> Result<()> {
-        let _ = self
-            .status_tx
-            .send(ProviderStatus {
-                key: self.key(),
-                name: "Youtube".into(),
-                user_name: None,
-                logged_in: false,
-                bg_color: "#E62017".into(),
-                account_id: "youtube".into(),
-            })
-            .await;
+        self.accounts.clear();
+        self.send_statuses().await;
 
         let preferences: State<PreferenceConfig> = self.app.state();
         let youtube_config: Value = preferences
@@ -458,9 +478,29 @@ impl GenericProvider for YoutubeProvider {
         self.config.redirect_uri = "https://moosync.app/youtube";
         self.config.scopes = vec!["https://www.googleapis.com/auth/youtube.readonly"];
 
-        let res = self.refresh_login().await;
-        if let Err(err) = res {
-            tracing::error!("youtube refresh login err: {:?}", err);
+        let Some(config) = self.oauth_config() else {
+            return Ok(());
+        };
+
+        let stored = {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.register_client(self.key(), config);
+            oauth.accounts(&self.app, &self.key()).unwrap_or_default()
+        };
+
+        for account in stored.accounts {
+            let res = self.refresh_login(account.id).await;
+            if let Err(err) = res {
+                tracing::error!("youtube refresh login err: {:?}", err);
+            }
+        }
+        // The first account to connect became active in the meantime
+        if let Some(active) = stored.active {
+            if self.accounts.active_id() != Some(active.clone())
+                && self.accounts.set_active(&active).is_ok()
+            {
+                self.send_statuses().await;
+            }
         }
 
         Ok(())
@@ -486,73 +526,60 @@ impl GenericProvider for YoutubeProvider {
 
     #[tracing::instrument(level = "trace", skip(self))]
     async fn login(&mut self, _: String) -> Result<String> {
-        let client = self.get_oauth_client();
-        if let Some(client) = client {
-            let (url, verifier) = login(
-                LoginArgs {
-                    client_id: self.config.client_id.clone(),
-                    client_secret: self.config.client_secret.clone(),
-                    scopes: self.config.scopes.clone(),
-                    extra_params: Some(HashMap::from([
-                        ("prompt", "consent"),
-                        ("access_type", "offline"),
-                    ])),
-                },
-                client,
-                &self.app,
-            )?;
-
-            self.verifier = verifier;
-
-            let oauth_handler: State<OAuthHandler> = self.app.state();
-            oauth_handler.register_oauth_path("youtubeoauthcallback".into(), self.key());
-
-            Ok(url)
-        } else {
-            Err("Could not create OAuth client, client_id and secret likely not set".into())
+        if self.oauth_config().is_none() {
+            return Err(
+                "Could not create OAuth client, client_id and secret likely not set".into(),
+            );
         }
+
+        let oauth: State<OAuthHandler> = self.app.state();
+        oauth.login(&self.app, &self.key())
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    async fn signout(&mut self, _: String) -> Result<()> {
-        self.api_client = None;
-        self.verifier = None;
-        self.config.tokens = None;
+    async fn signout(&mut self, account_id: String) -> Result<()> {
+        if self.accounts.remove(&account_id).is_none() {
+            return Ok(());
+        }
 
-        let preferences: State<PreferenceConfig> = self.app.state();
-        preferences.set_secure("MoosyncYoutubeRefreshToken".into(), None::<String>)?;
-
-        let _ = self
-            .status_tx
-            .send(ProviderStatus {
-                key: self.key(),
-                name: "Youtube".into(),
-                user_name: None,
-                logged_in: false,
-                bg_color: "#E62017".into(),
-                account_id: "youtube".into(),
-            })
-            .await;
+        {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.remove_account(&self.app, &self.key(), &account_id)?;
+        }
 
+        self.send_statuses().await;
         Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self, code))]
     async fn authorize(&mut self, code: String) -> Result<()> {
-        self.config.tokens = Some(
-            authorize(
-                "MoosyncYoutubeRefreshToken",
-                code,
-                &mut self.verifier,
-                &self.app,
-            )
-            .await?,
-        );
-
-        self.create_api_client().await;
-
-        // Remove
-        self.fetch_user_details().await.unwrap();
+        let tokens = {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.authorize(&self.key(), code).await?
+        };
+
+        self.connect_account(None, tokens).await
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn refresh_login(&mut self, account_id: String) -> Result<()> {
+        let tokens = {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.refresh(&self.app, &self.key(), &account_id).await?
+        };
+
+        self.connect_account(Some(account_id), tokens).await
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn set_active_account(&mut self, account_id: String) -> Result<()> {
+        self.accounts.set_active(&account_id)?;
+        {
+            let oauth: State<OAuthHandler> = self.app.state();
+            oauth.set_active_account(&self.app, &self.key(), &account_id)?;
+        }
+
+        self.send_statuses().await;
         Ok(())
     }
 
@@ -561,12 +588,13 @@ impl GenericProvider for YoutubeProvider {
         &self,
         pagination: Pagination,
     ) -> Result<(Vec<QueryablePlaylist>, Pagination)> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(account) = self.accounts.active() {
             if !pagination.is_first && pagination.token.is_none() {
                 return Ok((vec![], pagination));
             }
 
-            let mut builder = api_client
+            let mut builder = account
+                .client
                 .playlists()
                 .list(&vec![
                     "id".into(),
@@ -582,7 +610,13 @@ impl GenericProvider for YoutubeProvider {
 
             let (_, resp) = builder.doit().await?;
             let ret = if let Some(items) = resp.items {
-                items.into_iter().map(|p| self.parse_playlist(p)).collect()
+                items
+                    .into_iter()
+                    .map(|p| QueryablePlaylist {
+                        account_id: Some(account.id.clone()),
+                        ..self.parse_playlist(p)
+                    })
+                    .collect()
             } else {
                 vec![]
             };
@@ -607,7 +641,11 @@ impl GenericProvider for YoutubeProvider {
         let playlist_id = playlist_id
             .strip_prefix("youtube-playlist:")
             .unwrap_or(&playlist_id);
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self
+            .accounts
+            .get_or_active(playlist.account_id.as_deref())
+            .map(|a| &a.client)
+        {
             if !pagination.is_first && pagination.token.is_none() {
                 return Ok((vec![], pagination));
             }
@@ -646,8 +684,8 @@ impl GenericProvider for YoutubeProvider {
             return Ok((ret, pagination.next_page_wtoken(resp.next_page_token)));
         }
 
-        if !pagination.is_first {
-            return Ok((vec![], pagination.next_page()));
+        if !pagination.is_first && pagination.token.is_none() {
+            return Ok((vec![], pagination));
         }
 
         let youtube_scraper: State<YoutubeScraper> = self.app.state();
@@ -655,7 +693,8 @@ impl GenericProvider for YoutubeProvider {
             .get_playlist_content(playlist_id.to_string(), pagination.clone())
             .await?;
 
-        return Ok((res.songs, pagination.next_page()));
+        let next_page_token = res.next_page_token.map(|t| t.encode()).transpose()?;
+        return Ok((res.songs, pagination.next_page_wtoken(next_page_token)));
     }
 
     #[tracing::instrument(level = "trace", skip(self, song, player))]
@@ -664,29 +703,118 @@ impl GenericProvider for YoutubeProvider {
         if song.song.provider_extension.unwrap_or_default() != self.key()
             && player.to_lowercase() == "youtube"
         {
+            let database: State<Database> = self.app.state();
+            if let Some(song_id) = song.song._id.clone() {
+                if let Some(mapping) = database.get_track_mapping(song_id, self.key())? {
+                    tracing::debug!("Using remembered match {}", mapping.mapped_id);
+                    return Ok(mapping.mapped_id);
+                }
+            }
+
+            // The best candidates are on the first page
             let youtube_scraper: State<YoutubeScraper> = self.app.state();
             let res = youtube_scraper
-                .search_yt(format!(
-                    "{} - {}",
-                    song.artists
-                        .unwrap_or_default()
-                        .iter()
-                        .filter_map(|a| a.artist_name.clone())
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                    song.song.title.unwrap_or_default()
-                ))
-                .await?;
-            if let Some(first) = res.songs.first() {
-                return Ok(first.song.url.clone().unwrap());
+                .search_page(
+                    format!(
+                        "{} - {}",
+                        song.artists
+                            .clone()
+                            .unwrap_or_default()
+                            .iter()
+                            .filter_map(|a| a.artist_name.clone())
+                            .collect::<Vec<String>>()
+                            .join(", "),
+                        song.song.title.clone().unwrap_or_default()
+                    ),
+                    Pagination::new_token(None),
+                )
+                .await?
+                .result;
+
+            let Some((candidate, score)) = best_match(&song, &res.songs) else {
+                return Err(format!("No match found for {:?}", song.song.title).into());
+            };
+
+            let mapped_id = candidate.song.url.clone().unwrap_or_default();
+            if score < MIN_ACCEPTED_SCORE {
+                tracing::warn!(
+                    "Rejected match {:?} ({}) for {:?} with score {}",
+                    candidate.song.title,
+                    mapped_id,
+                    song.song.title,
+                    score
+                );
+                return Err(format!(
+                    "No confident match found for {:?}, best candidate scored {:.2}",
+                    song.song.title, score
+                )
+                .into());
+            }
+
+            tracing::info!(
+                "Matched {:?} to {:?} ({}) with score {}",
+                song.song.title,
+                candidate.song.title,
+                mapped_id,
+                score
+            );
+
+            if let Some(song_id) = song.song._id.clone() {
+                database.set_track_mapping(TrackMapping {
+                    song_id,
+                    provider: self.key(),
+                    mapped_id: mapped_id.clone(),
+                    score,
+                    user_override: false,
+                })?;
             }
+            return Ok(mapped_id);
         }
 
         if player == "local" || player == "rodio" {
+            // Symphonia has no opus decoder
+            let codecs: &[&str] = if player == "rodio" {
+                &["mp4a"]
+            } else {
+                &["opus", "mp4a"]
+            };
+
+            let preferences: State<PreferenceConfig> = self.app.state();
+            let quality = match preferences.load_selective_array::<CheckboxPreference>(
+                "youtube_audio_quality.data_saver".into(),
+            ) {
+                Ok(pref) if pref.enabled => AudioQuality::DataSaver,
+                _ => AudioQuality::High,
+            };
+
             let youtube_scraper: State<YoutubeScraper> = self.app.state();
-            return youtube_scraper
-                .get_video_url(song.song.url.clone().unwrap())
-                .await;
+            let format = youtube_scraper
+                .get_video_url(song.song.url.clone().unwrap(), codecs, quality)
+                .await?;
+
+            // Library songs show the stream they are played from like local files do
+            let bitrate = Some(format.bitrate as f64);
+            if song.song.library_item.unwrap_or_default()
+                && (song.song.codec != format.codec
+                    || song.song.container != format.container
+                    || song.song.bitrate != bitrate)
+            {
+                let database: State<Database> = self.app.state();
+                let res = database.update_song(QueryableSong {
+                    codec: format.codec.clone(),
+                    container: format.container.clone(),
+                    bitrate,
+                    ..song.song.clone()
+                });
+                if let Err(e) = res {
+                    tracing::warn!(
+                        "Failed to store stream format of {:?}: {:?}",
+                        song.song._id,
+                        e
+                    );
+                }
+            }
+            return Ok(format.url);
         } else {
             return Ok(song.song.url.clone().unwrap());
         }
@@ -694,7 +822,7 @@ impl GenericProvider for YoutubeProvider {
 
     #[tracing::instrument(level = "trace", skip(self, term))]
     async fn search(&self, term: String) -> Result<SearchResult> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let mut songs = vec![];
 
             let song_details = search_and_parse!(api_client, &term, "video", |item| {
@@ -741,7 +869,7 @@ impl GenericProvider for YoutubeProvider {
 
         let playlist_id = playlist_id.unwrap().1.to_string();
 
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let (_, playlists) = api_client
                 .playlists()
                 .list(&vec![
@@ -763,7 +891,10 @@ impl GenericProvider for YoutubeProvider {
         }
 
         let youtube_scraper: State<YoutubeScraper> = self.app.state();
-        let res = youtube_scraper.search_yt(playlist_id).await?;
+        let res = youtube_scraper
+            .search_page(playlist_id, Pagination::new_token(None))
+            .await?
+            .result;
         if let Some(first) = res.playlists.first() {
             return Ok(first.clone());
         }
@@ -796,7 +927,7 @@ impl GenericProvider for YoutubeProvider {
 
     #[tracing::instrument(level = "trace", skip(self))]
     async fn get_suggestions(&self) -> Result<Vec<Song>> {
-        if let Some(api_client) = &self.api_client {
+        if let Some(api_client) = self.api_client() {
             let (_, resp) = api_client
                 .search()
                 .list(&vec!["snippet".into()])
@@ -0,0 +1,481 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use database::database::Database;
+use macros::generate_command;
+use mpris::MprisHolder;
+use rand::seq::SliceRandom;
+use tauri::{AppHandle, Emitter, Manager, State};
+use types::{
+    entities::QueueStateRow,
+    errors::Result,
+    mpris::MprisPlayerDetails,
+    songs::Song,
+    ui::player_details::{QueueState, RepeatModes},
+};
+
+use crate::window::tray::TrayHandler;
+
+#[cfg(test)]
+mod test;
+
+/// What an operation changed, decides what gets written to the database
+#[derive(Debug, PartialEq)]
+enum QueueChange {
+    /// Only the position or modes
+    State,
+    Insert(usize, Vec<Song>),
+    Remove(usize),
+    Move(usize, usize),
+    /// Order of (nearly) every song, rewrites the whole queue
+    All,
+}
+
+#[derive(Debug, Default)]
+struct QueueData {
+    /// Songs in play order
+    songs: Vec<Song>,
+    current_index: usize,
+    repeat: RepeatModes,
+    /// Song ids in the order before shuffling, set while shuffle is on
+    original_order: Option<Vec<String>>,
+}
+
+impl QueueData {
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn to_state(&self) -> QueueState {
+        QueueState {
+            song_queue: self.songs.iter().map(song_id).collect(),
+            current_index: self.current_index,
+            data: self
+                .songs
+                .iter()
+                .map(|s| (song_id(s), s.clone()))
+                .collect::<HashMap<_, _>>(),
+            repeat: self.repeat,
+            shuffle: self.original_order.is_some(),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn to_row(&self) -> Result<QueueStateRow> {
+        Ok(QueueStateRow {
+            id: 0,
+            current_index: self.current_index as i32,
+            repeat: serde_json::to_string(&self.repeat)?,
+            shuffle: self.original_order.is_some(),
+            original_order: self
+                .original_order
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?,
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(songs, row))]
+    fn from_db(songs: Vec<Song>, row: Option<QueueStateRow>) -> Self {
+        let mut data = Self {
+            songs,
+            ..Default::default()
+        };
+        if let Some(row) = row {
+            data.current_index = row.current_index.max(0) as usize;
+            data.repeat = serde_json::from_str(&row.repeat).unwrap_or_default();
+            data.original_order = row
+                .original_order
+                .filter(|_| row.shuffle)
+                .and_then(|order| serde_json::from_str(&order).ok());
+        }
+        data.clamp_index();
+        data
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn clamp_index(&mut self) {
+        if self.current_index >= self.songs.len() {
+            self.current_index = 0;
+        }
+    }
+
+    /// Position right after the current song, where songs played next are inserted
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn next_position(&self) -> usize {
+        if self.songs.is_empty() {
+            0
+        } else {
+            self.current_index + 1
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    fn insert(&mut self, index: usize, songs: Vec<Song>) -> QueueChange {
+        let songs = with_ids(songs);
+        if songs.is_empty() {
+            return QueueChange::State;
+        }
+        if let Some(original_order) = &mut self.original_order {
+            original_order.extend(songs.iter().map(song_id));
+        }
+        let index = index.min(self.songs.len());
+        self.songs.splice(index..index, songs.iter().cloned());
+        QueueChange::Insert(index, songs)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn remove(&mut self, index: usize) -> QueueChange {
+        if index >= self.songs.len() {
+            return QueueChange::State;
+        }
+        let removed = self.songs.remove(index);
+        if let Some(original_order) = &mut self.original_order {
+            if let Some(pos) = original_order
+                .iter()
+                .position(|id| *id == song_id(&removed))
+            {
+                original_order.remove(pos);
+            }
+        }
+        if index < self.current_index {
+            self.current_index -= 1;
+        }
+        QueueChange::Remove(index)
+    }
+
+    /// Moves the song at `from` to `to`, the current song keeps playing
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn move_song(&mut self, from: usize, to: usize) -> QueueChange {
+        if from >= self.songs.len() {
+            return QueueChange::State;
+        }
+        let to = to.min(self.songs.len() - 1);
+        let song = self.songs.remove(from);
+        self.songs.insert(to, song);
+
+        if self.current_index == from {
+            self.current_index = to;
+        } else if from < self.current_index && to >= self.current_index {
+            self.current_index -= 1;
+        } else if from > self.current_index && to <= self.current_index {
+            self.current_index += 1;
+        }
+        QueueChange::Move(from, to)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn shuffle(&mut self) {
+        // Reshuffling keeps the order from before the first shuffle
+        if self.original_order.is_none() {
+            self.original_order = Some(self.songs.iter().map(song_id).collect());
+        }
+        if self.current_index >= self.songs.len() {
+            return;
+        }
+
+        // The current song keeps playing and everything else follows in random order
+        let current = self.songs.remove(self.current_index);
+        self.songs.shuffle(&mut rand::thread_rng());
+        self.songs.insert(0, current);
+        self.current_index = 0;
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn unshuffle(&mut self) {
+        let Some(original_order) = self.original_order.take() else {
+            return;
+        };
+
+        let current = self.songs.get(self.current_index).map(song_id);
+        let position = |song: &Song| {
+            original_order
+                .iter()
+                .position(|id| *id == song_id(song))
+                .unwrap_or(usize::MAX)
+        };
+        // Stable, so songs missing from the original order keep their relative order at the end
+        self.songs.sort_by_key(position);
+        self.current_index = current
+            .and_then(|id| self.songs.iter().position(|s| song_id(s) == id))
+            .unwrap_or_default();
+    }
+}
+
+/// Only songs with an id are queued, since the queue refers to songs by it
+#[tracing::instrument(level = "trace", skip(songs))]
+fn with_ids(songs: Vec<Song>) -> Vec<Song> {
+    songs
+        .into_iter()
+        .filter(|song| {
+            let has_id = song.song._id.as_ref().is_some_and(|id| !id.is_empty());
+            if !has_id {
+                tracing::warn!("Not queueing song without id: {:?}", song.song.title);
+            }
+            has_id
+        })
+        .collect()
+}
+
+#[tracing::instrument(level = "trace", skip(song))]
+fn song_id(song: &Song) -> String {
+    song.song._id.clone().unwrap_or_default()
+}
+
+#[tracing::instrument(level = "trace", skip(song))]
+fn mpris_details(song: &Song) -> MprisPlayerDetails {
+    MprisPlayerDetails {
+        id: song.song._id.clone(),
+        title: song.song.title.clone(),
+        artist_name: song.artists.as_ref().map(|artists| {
+            artists
+                .iter()
+                .filter_map(|a| a.artist_name.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+        album_name: song.album.as_ref().and_then(|a| a.album_name.clone()),
+        album_artist: None,
+        genres: None,
+        duration: song.song.duration,
+        thumbnail: song.song.song_cover_path_high.clone(),
+    }
+}
+
+/// Applies `f` to the queue and persists what it changed. The queue stays locked
+/// until the write is done, positional writes only line up when applied in order.
+#[tracing::instrument(level = "trace", skip(data, database, f))]
+fn apply_update<F>(
+    data: &Mutex<QueueData>,
+    database: &Database,
+    f: F,
+) -> Result<(QueueState, Option<Vec<Song>>)>
+where
+    F: FnOnce(&mut QueueData) -> QueueChange,
+{
+    let mut data = data.lock().unwrap();
+    let change = f(&mut data);
+    data.clamp_index();
+    let row = data.to_row()?;
+    let songs = (change != QueueChange::State).then(|| data.songs.clone());
+
+    match change {
+        QueueChange::State => database.save_queue_state(row)?,
+        QueueChange::Insert(index, inserted) => database.queue_insert(index, &inserted, row)?,
+        QueueChange::Remove(index) => database.queue_remove(index, row)?,
+        QueueChange::Move(from, to) => database.queue_move(from, to, row)?,
+        QueueChange::All => database.save_queue(songs.as_deref().unwrap_or_default(), row)?,
+    }
+    Ok((data.to_state(), songs))
+}
+
+/// Owns the play queue. Every change is persisted to the database and
+/// emitted as "queue-updated" for the UI to mirror.
+#[derive(Debug)]
+pub struct QueueManager {
+    app: AppHandle,
+    data: Mutex<QueueData>,
+}
+
+impl QueueManager {
+    #[tracing::instrument(level = "trace", skip(app))]
+    pub fn new(app: AppHandle) -> Self {
+        let database: State<Database> = app.state();
+        let data = match database.load_queue() {
+            Ok((songs, row)) => QueueData::from_db(songs, row),
+            Err(e) => {
+                tracing::error!("Failed to load queue: {:?}", e);
+                QueueData::default()
+            }
+        };
+
+        Self {
+            app,
+            data: Mutex::new(data),
+        }
+    }
+
+    /// Applies `f` to the queue, then persists what it changed and broadcasts the result
+    #[tracing::instrument(level = "trace", skip(self, f))]
+    fn update<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut QueueData) -> QueueChange,
+    {
+        let database: State<Database> = self.app.state();
+        let (state, songs) = apply_update(&self.data, &database, f)?;
+
+        let mpris: State<MprisHolder> = self.app.state();
+        let tray: State<TrayHandler> = self.app.state();
+        if let Some(songs) = &songs {
+            mpris.set_tracklist(songs.iter().map(mpris_details).collect())?;
+        }
+        mpris.set_current_index(state.current_index)?;
+        mpris.set_loop_status(state.repeat)?;
+        mpris.set_shuffle(state.shuffle)?;
+        tray.set_loop_status(state.repeat)?;
+        tray.set_shuffle(state.shuffle)?;
+
+        self.app.emit("queue-updated", state)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get_queue(&self) -> Result<QueueState> {
+        Ok(self.data.lock().unwrap().to_state())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn queue_add(&self, songs: Vec<Song>) -> Result<()> {
+        self.update(|data| {
+            let len = data.songs.len();
+            data.insert(len, songs)
+        })
+    }
+
+    /// Inserts `songs` after the current song and starts playing the first of them
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn queue_play_now(&self, songs: Vec<Song>) -> Result<()> {
+        self.update(|data| {
+            let position = data.next_position();
+            let change = data.insert(position, songs);
+            if let QueueChange::Insert(index, _) = &change {
+                data.current_index = *index;
+            }
+            change
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn queue_play_next(&self, songs: Vec<Song>) -> Result<()> {
+        self.update(|data| {
+            let position = data.next_position();
+            data.insert(position, songs)
+        })
+    }
+
+    /// Replaces the whole queue, e.g. when playing a playlist
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn queue_replace(&self, songs: Vec<Song>, current_index: usize) -> Result<()> {
+        self.update(|data| {
+            data.original_order = None;
+            data.songs = with_ids(songs);
+            data.current_index = current_index;
+            QueueChange::All
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_remove(&self, index: usize) -> Result<()> {
+        self.update(|data| data.remove(index))
+    }
+
+    /// Moves the song at `from` to `to`, the current song keeps playing
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_move(&self, from: usize, to: usize) -> Result<()> {
+        self.update(|data| data.move_song(from, to))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_set_index(&self, index: usize) -> Result<()> {
+        self.update(|data| {
+            data.current_index = index;
+            QueueChange::State
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_next(&self) -> Result<()> {
+        self.update(|data| {
+            data.current_index += 1;
+            QueueChange::State
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_prev(&self) -> Result<()> {
+        self.update(|data| {
+            data.current_index = if data.current_index == 0 {
+                data.songs.len().saturating_sub(1)
+            } else {
+                data.current_index - 1
+            };
+            QueueChange::State
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_clear(&self) -> Result<()> {
+        self.update(|data| {
+            data.songs.clear();
+            data.original_order = data.original_order.take().map(|_| vec![]);
+            data.current_index = 0;
+            QueueChange::All
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_clear_except_current(&self) -> Result<()> {
+        self.update(|data| {
+            let current = data.songs.get(data.current_index).cloned();
+            data.songs = current.into_iter().collect();
+            data.original_order = data
+                .original_order
+                .take()
+                .map(|_| data.songs.iter().map(song_id).collect());
+            data.current_index = 0;
+            QueueChange::All
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_set_repeat(&self, mode: RepeatModes) -> Result<()> {
+        self.update(|data| {
+            data.repeat = mode;
+            QueueChange::State
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn queue_set_shuffle(&self, shuffle: bool) -> Result<()> {
+        self.update(|data| {
+            if shuffle {
+                data.shuffle();
+            } else {
+                data.unshuffle();
+            }
+            QueueChange::All
+        })
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn get_queue_state(app: AppHandle) -> QueueManager {
+    QueueManager::new(app)
+}
+
+generate_command!(get_queue, QueueManager, QueueState,);
+generate_command!(queue_add, QueueManager, (), songs: Vec<Song>);
+generate_command!(queue_play_now, QueueManager, (), songs: Vec<Song>);
+generate_command!(queue_play_next, QueueManager, (), songs: Vec<Song>);
+generate_command!(queue_replace, QueueManager, (), songs: Vec<Song>, current_index: usize);
+generate_command!(queue_remove, QueueManager, (), index: usize);
+generate_command!(queue_move, QueueManager, (), from: usize, to: usize);
+generate_command!(queue_set_index, QueueManager, (), index: usize);
+generate_command!(queue_next, QueueManager, (),);
+generate_command!(queue_prev, QueueManager, (),);
+generate_command!(queue_clear, QueueManager, (),);
+generate_command!(queue_clear_except_current, QueueManager, (),);
+generate_command!(queue_set_repeat, QueueManager, (), mode: RepeatModes);
+generate_command!(queue_set_shuffle, QueueManager, (), shuffle: bool);
@@ -0,0 +1,239 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Mutex;
+
+use database::database::Database;
+use test_utils::TempDb;
+use types::songs::{QueryableSong, Song};
+
+use crate::queue::{apply_update, QueueChange, QueueData};
+
+fn song(id: &str) -> Song {
+    Song {
+        song: QueryableSong {
+            _id: Some(id.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn queue(ids: &[&str], current_index: usize) -> QueueData {
+    QueueData {
+        songs: ids.iter().map(|id| song(id)).collect(),
+        current_index,
+        ..Default::default()
+    }
+}
+
+fn ids(data: &QueueData) -> Vec<String> {
+    data.songs
+        .iter()
+        .map(|s| s.song._id.clone().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_remove_before_current() {
+    let mut data = queue(&["a", "b", "c", "d"], 2);
+    assert_eq!(data.remove(0), QueueChange::Remove(0));
+    assert_eq!(ids(&data), vec!["b", "c", "d"]);
+    assert_eq!(data.current_index, 1);
+}
+
+#[test]
+fn test_remove_after_current() {
+    let mut data = queue(&["a", "b", "c", "d"], 1);
+    data.remove(3);
+    assert_eq!(ids(&data), vec!["a", "b", "c"]);
+    assert_eq!(data.current_index, 1);
+}
+
+#[test]
+fn test_remove_current() {
+    // The next song takes the place of the removed one
+    let mut data = queue(&["a", "b", "c"], 1);
+    data.remove(1);
+    assert_eq!(ids(&data), vec!["a", "c"]);
+    assert_eq!(data.current_index, 1);
+
+    // Removing the last song wraps around
+    let mut data = queue(&["a", "b"], 1);
+    data.remove(1);
+    data.clamp_index();
+    assert_eq!(data.current_index, 0);
+}
+
+#[test]
+fn test_remove_out_of_range() {
+    let mut data = queue(&["a", "b"], 1);
+    assert_eq!(data.remove(2), QueueChange::State);
+    assert_eq!(ids(&data), vec!["a", "b"]);
+    assert_eq!(data.current_index, 1);
+}
+
+#[test]
+fn test_remove_updates_original_order() {
+    let mut data = queue(&["a", "b", "c"], 0);
+    data.original_order = Some(vec!["c".into(), "b".into(), "a".into()]);
+    data.remove(1);
+    assert_eq!(
+        data.original_order,
+        Some(vec!["c".to_string(), "a".to_string()])
+    );
+}
+
+#[test]
+fn test_move_current() {
+    let mut data = queue(&["a", "b", "c", "d"], 1);
+    assert_eq!(data.move_song(1, 3), QueueChange::Move(1, 3));
+    assert_eq!(ids(&data), vec!["a", "c", "d", "b"]);
+    assert_eq!(data.current_index, 3);
+}
+
+#[test]
+fn test_move_across_current() {
+    // From before to after the current song
+    let mut data = queue(&["a", "b", "c", "d"], 2);
+    data.move_song(0, 3);
+    assert_eq!(ids(&data), vec!["b", "c", "d", "a"]);
+    assert_eq!(data.current_index, 1);
+
+    // From after to before the current song
+    let mut data = queue(&["a", "b", "c", "d"], 1);
+    data.move_song(3, 0);
+    assert_eq!(ids(&data), vec!["d", "a", "b", "c"]);
+    assert_eq!(data.current_index, 2);
+
+    // Onto the current song's position
+    let mut data = queue(&["a", "b", "c", "d"], 1);
+    data.move_song(3, 1);
+    assert_eq!(ids(&data), vec!["a", "d", "b", "c"]);
+    assert_eq!(data.current_index, 2);
+}
+
+#[test]
+fn test_move_beside_current() {
+    let mut data = queue(&["a", "b", "c", "d"], 1);
+    data.move_song(3, 2);
+    assert_eq!(ids(&data), vec!["a", "b", "d", "c"]);
+    assert_eq!(data.current_index, 1);
+}
+
+#[test]
+fn test_move_clamps_target() {
+    let mut data = queue(&["a", "b", "c"], 0);
+    assert_eq!(data.move_song(0, 10), QueueChange::Move(0, 2));
+    assert_eq!(ids(&data), vec!["b", "c", "a"]);
+    assert_eq!(data.current_index, 2);
+
+    assert_eq!(data.move_song(5, 0), QueueChange::State);
+}
+
+#[test]
+fn test_insert_without_ids() {
+    let mut data = queue(&["a"], 0);
+    let change = data.insert(1, vec![song("b"), Song::default(), song("")]);
+    assert_eq!(change, QueueChange::Insert(1, vec![song("b")]));
+    assert_eq!(ids(&data), vec!["a", "b"]);
+
+    assert_eq!(data.insert(0, vec![Song::default()]), QueueChange::State);
+    assert_eq!(ids(&data), vec!["a", "b"]);
+}
+
+#[test]
+fn test_shuffle_keeps_current() {
+    let ids_before = ["a", "b", "c", "d", "e", "f"];
+    let mut data = queue(&ids_before, 3);
+    data.shuffle();
+
+    assert_eq!(data.current_index, 0);
+    assert_eq!(ids(&data)[0], "d");
+    let mut shuffled = ids(&data);
+    shuffled.sort();
+    assert_eq!(shuffled, ids_before);
+}
+
+#[test]
+fn test_unshuffle_restores_order() {
+    let mut data = queue(&["a", "b", "c", "d", "e"], 2);
+    data.shuffle();
+    data.current_index = 3;
+    let current = ids(&data)[3].clone();
+
+    data.unshuffle();
+    assert_eq!(ids(&data), vec!["a", "b", "c", "d", "e"]);
+    assert!(data.original_order.is_none());
+    // The song playing when unshuffling keeps playing
+    assert_eq!(ids(&data)[data.current_index], current);
+}
+
+#[test]
+fn test_reshuffle_keeps_original_order() {
+    let mut data = queue(&["a", "b", "c", "d"], 0);
+    data.shuffle();
+    data.shuffle();
+    data.unshuffle();
+    assert_eq!(ids(&data), vec!["a", "b", "c", "d"]);
+}
+
+#[test]
+fn test_unshuffle_with_changes_while_shuffled() {
+    let mut data = queue(&["a", "b", "c", "d"], 0);
+    data.shuffle();
+    data.insert(2, vec![song("x")]);
+    let index = ids(&data).iter().position(|id| id == "c").unwrap();
+    data.remove(index);
+
+    // Added songs go after the original ones, removed ones stay gone
+    data.unshuffle();
+    assert_eq!(ids(&data), vec!["a", "b", "d", "x"]);
+}
+
+#[test]
+fn test_concurrent_updates_persist_in_order() {
+    let db_file = TempDb::new("queue_concurrent");
+    let database = Database::new(db_file.path());
+    let data = Mutex::new(QueueData::default());
+
+    std::thread::scope(|scope| {
+        for thread in 0..4 {
+            let (data, database) = (&data, &database);
+            scope.spawn(move || {
+                for i in 0..25 {
+                    let id = format!("{}_{}", thread, i);
+                    apply_update(data, database, |data| data.insert(i % 3, vec![song(&id)]))
+                        .unwrap();
+                    apply_update(data, database, |data| {
+                        let last = data.songs.len() - 1;
+                        data.move_song(last, i % 5)
+                    })
+                    .unwrap();
+                }
+            });
+        }
+    });
+
+    let data = data.into_inner().unwrap();
+    let (songs, _) = database.load_queue().unwrap();
+    let persisted = songs
+        .into_iter()
+        .map(|s| s.song._id.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(data.songs.len(), 100);
+    assert_eq!(persisted, ids(&data));
+}
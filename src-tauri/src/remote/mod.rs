@@ -0,0 +1,198 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    io::ErrorKind,
+    net::{Ipv4Addr, SocketAddr},
+    sync::Mutex,
+    time::Duration,
+};
+
+use preferences::preferences::PreferenceConfig;
+use serde_json::Value;
+use tauri::{async_runtime, AppHandle, Listener, Manager, State};
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast, oneshot},
+    time::sleep,
+};
+use types::{preferences::CheckboxPreference, ui::extensions::ExtensionExtraEvent};
+
+mod routes;
+
+#[cfg(test)]
+mod test;
+
+pub const DEFAULT_PORT: u16 = 5748;
+const EVENT_BUFFER: usize = 64;
+const BIND_ATTEMPTS: u32 = 10;
+
+/// Opt-in HTTP / WebSocket server that lets other programs drive the player
+#[derive(Debug)]
+pub struct RemoteControl {
+    events: broadcast::Sender<ExtensionExtraEvent>,
+    /// Config of the running server and the handle to stop it
+    server: Mutex<Option<(RemoteConfig, oneshot::Sender<()>)>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RemoteConfig {
+    addr: SocketAddr,
+    token: String,
+}
+
+impl RemoteControl {
+    /// Forwards a player event to every connected event stream
+    #[tracing::instrument(level = "trace", skip(self, event))]
+    pub fn publish(&self, event: &ExtensionExtraEvent) {
+        if matches!(
+            event,
+            ExtensionExtraEvent::SongChanged(_)
+                | ExtensionExtraEvent::PlayerStateChanged(_)
+                | ExtensionExtraEvent::VolumeChanged(_)
+                | ExtensionExtraEvent::Seeked(_)
+                | ExtensionExtraEvent::SongQueueChanged(_)
+        ) {
+            // Fails only when nobody is listening
+            let _ = self.events.send(event.clone());
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn subscribe(&self) -> broadcast::Receiver<ExtensionExtraEvent> {
+        self.events.subscribe()
+    }
+
+    /// (Re)starts the server with the current preferences, or stops it if disabled
+    #[tracing::instrument(level = "trace", skip(self, app, preferences))]
+    pub fn configure(&self, app: AppHandle, preferences: &PreferenceConfig) {
+        let config = load_config(preferences);
+
+        let mut server = self.server.lock().unwrap();
+        if server.as_ref().map(|(running, _)| running) == config.as_ref() {
+            return;
+        }
+        if let Some((_, shutdown)) = server.take() {
+            tracing::info!("Stopping remote control server");
+            let _ = shutdown.send(());
+        }
+
+        let Some(config) = config else {
+            return;
+        };
+
+        let (tx, rx) = oneshot::channel();
+        *server = Some((config.clone(), tx));
+
+        async_runtime::spawn(async move {
+            let listener = match bind(config.addr).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    tracing::error!("Failed to bind remote control server: {:?}", e);
+                    return;
+                }
+            };
+
+            tracing::info!("Remote control listening on {}", config.addr);
+            let router = routes::router(app, config.token);
+            let res = axum::serve(listener, router)
+                .with_graceful_shutdown(async move {
+                    let _ = rx.await;
+                })
+                .await;
+            if let Err(e) = res {
+                tracing::error!("Remote control server stopped: {:?}", e);
+            }
+        });
+    }
+}
+
+/// Retries for a bit since a server being restarted may still hold the port
+#[tracing::instrument(level = "trace", skip())]
+async fn bind(addr: SocketAddr) -> std::io::Result<TcpListener> {
+    let mut attempts = 0;
+    loop {
+        match TcpListener::bind(addr).await {
+            Err(e) if e.kind() == ErrorKind::AddrInUse && attempts < BIND_ATTEMPTS => {
+                attempts += 1;
+                sleep(Duration::from_millis(200)).await;
+            }
+            res => return res,
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(preferences))]
+fn load_config(preferences: &PreferenceConfig) -> Option<RemoteConfig> {
+    let settings = preferences
+        .load_selective::<Vec<CheckboxPreference>>("remote_control".into())
+        .unwrap_or_default();
+    let enabled = |key: &str| settings.iter().any(|s| s.key == key && s.enabled);
+    if !enabled("enable") {
+        return None;
+    }
+
+    // Without a token anyone who can reach the port could control the player
+    let token = preferences
+        .get_secure::<String>("remote_control_token".into())
+        .ok()
+        .filter(|token| !token.trim().is_empty());
+    let Some(token) = token else {
+        tracing::warn!("Not starting remote control server without a token");
+        return None;
+    };
+
+    let host = if enabled("allow_lan") {
+        Ipv4Addr::UNSPECIFIED
+    } else {
+        Ipv4Addr::LOCALHOST
+    };
+    let port = preferences
+        .load_selective::<u16>("remote_control_port".into())
+        .unwrap_or(DEFAULT_PORT);
+
+    Some(RemoteConfig {
+        addr: SocketAddr::from((host, port)),
+        token,
+    })
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn get_remote_state(app: AppHandle) -> RemoteControl {
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let remote = RemoteControl {
+        events,
+        server: Mutex::new(None),
+    };
+
+    // Queue changes come from the backend itself, not through extension events
+    let events = remote.events.clone();
+    app.listen("queue-updated", move |event| {
+        if let Ok(queue) = serde_json::from_str::<Value>(event.payload()) {
+            let _ = events.send(ExtensionExtraEvent::SongQueueChanged([queue]));
+        }
+    });
+
+    remote
+}
+
+/// Applies the remote control preferences
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn configure_remote_control(app: &AppHandle) {
+    let remote: State<RemoteControl> = app.state();
+    let preferences: State<PreferenceConfig> = app.state();
+    remote.configure(app.clone(), &preferences);
+}
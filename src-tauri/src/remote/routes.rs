@@ -0,0 +1,508 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, Request, State,
+    },
+    http::{
+        header::{AUTHORIZATION, UPGRADE},
+        StatusCode,
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+    Json, Router,
+};
+use database::database::Database;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
+use types::{
+    entities::{
+        GetEntityOptions, QueryableAlbum, QueryableArtist, QueryableGenre, QueryablePlaylist,
+        SearchResult,
+    },
+    errors::MoosyncError,
+    extensions::{MainCommand, MainCommandResponse},
+    songs::{GetSongOptions, SearchableSong, Song},
+    ui::player_details::{PlayerState, QueueState, RepeatModes},
+};
+
+use super::RemoteControl;
+use crate::{
+    extensions::request_handler::ReplyHandler, providers::handler::ProviderHandler,
+    queue::QueueManager,
+};
+
+#[derive(Clone)]
+struct ServerState {
+    app: AppHandle,
+    token: Arc<String>,
+}
+
+struct ApiError(StatusCode, String);
+
+impl From<MoosyncError> for ApiError {
+    fn from(e: MoosyncError) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    }
+}
+
+impl From<tauri::Error> for ApiError {
+    fn from(e: tauri::Error) -> Self {
+        MoosyncError::from(e).into()
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+#[tracing::instrument(level = "trace", skip(app, token))]
+pub(super) fn router(app: AppHandle, token: String) -> Router {
+    let state = ServerState {
+        app,
+        token: Arc::new(token),
+    };
+
+    Router::new()
+        .route("/api/now-playing", get(now_playing))
+        .route("/api/player/seek", put(seek))
+        .route("/api/player/volume", put(volume))
+        .route("/api/player/repeat", put(repeat))
+        .route("/api/player/shuffle", put(shuffle))
+        .route("/api/player/{action}", post(player_action))
+        .route(
+            "/api/queue",
+            get(get_queue).post(add_to_queue).delete(clear_queue),
+        )
+        .route("/api/queue/index", put(set_queue_index))
+        .route("/api/queue/move", post(move_in_queue))
+        .route("/api/queue/{index}", delete(remove_from_queue))
+        .route("/api/search", get(search))
+        .route("/api/library/songs", get(all_songs).post(filter_songs))
+        .route("/api/library/{entity}", get(entities))
+        .route("/api/events", get(events))
+        .route_layer(middleware::from_fn_with_state(
+            state.token.clone(),
+            authorize,
+        ))
+        .with_state(state)
+}
+
+/// Accepts the token as a bearer header. WebSocket upgrades may pass it as a
+/// `token` query parameter instead, since browsers can't set headers on them.
+/// Elsewhere it would end up in logs and histories.
+#[tracing::instrument(level = "trace", skip(token, request, next))]
+pub(super) async fn authorize(
+    State(token): State<Arc<String>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let header = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.to_string());
+    let is_upgrade = request
+        .headers()
+        .get(UPGRADE)
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"websocket"));
+    let query = request
+        .uri()
+        .query()
+        .filter(|_| is_upgrade)
+        .and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "token")
+                .map(|(_, value)| value.into_owned())
+        });
+
+    match header.or(query) {
+        Some(given) if tokens_match(&given, &token) => next.run(request).await,
+        _ => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// Compares every byte of `expected` whatever `given` holds, so response
+/// times leak neither the token nor its length
+#[tracing::instrument(level = "trace", skip(given, expected))]
+pub(super) fn tokens_match(given: &str, expected: &str) -> bool {
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    let mut diff = given.len() ^ expected.len();
+    for (i, y) in expected.iter().enumerate() {
+        let x = given.get(i).copied().unwrap_or_default();
+        diff |= (x ^ y) as usize;
+    }
+    diff == 0
+}
+
+/// Sends a control event the same way MPRIS does, so the UI handles both alike
+#[tracing::instrument(level = "trace", skip(app))]
+fn press(app: &AppHandle, key: i32, value: Value) -> std::result::Result<(), ApiError> {
+    app.emit("media_button_press", (key, value))?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+async fn ask_ui(
+    app: &AppHandle,
+    command: MainCommand,
+) -> std::result::Result<MainCommandResponse, ApiError> {
+    ReplyHandler::new(app.clone())
+        .handle_request(command)
+        .await
+        .map_err(|e| ApiError(StatusCode::SERVICE_UNAVAILABLE, e.to_string()))
+}
+
+#[tracing::instrument(level = "trace", skip())]
+fn unexpected_reply() -> ApiError {
+    ApiError(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Unexpected reply from UI".into(),
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct NowPlaying {
+    song: Option<Song>,
+    state: PlayerState,
+    time: f64,
+    volume: f64,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn now_playing(State(state): State<ServerState>) -> ApiResult<NowPlaying> {
+    let MainCommandResponse::GetCurrentSong(song) =
+        ask_ui(&state.app, MainCommand::GetCurrentSong()).await?
+    else {
+        return Err(unexpected_reply());
+    };
+    let MainCommandResponse::GetPlayerState(player_state) =
+        ask_ui(&state.app, MainCommand::GetPlayerState()).await?
+    else {
+        return Err(unexpected_reply());
+    };
+    let MainCommandResponse::GetTime(time) = ask_ui(&state.app, MainCommand::GetTime()).await?
+    else {
+        return Err(unexpected_reply());
+    };
+    let MainCommandResponse::GetVolume(volume) =
+        ask_ui(&state.app, MainCommand::GetVolume()).await?
+    else {
+        return Err(unexpected_reply());
+    };
+
+    Ok(Json(NowPlaying {
+        song,
+        state: player_state,
+        time,
+        volume,
+    }))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn player_action(
+    State(state): State<ServerState>,
+    Path(action): Path<String>,
+) -> ApiResult<()> {
+    let key = match action.as_str() {
+        "play" => 0,
+        "pause" => 1,
+        "stop" => 2,
+        "next" => 6,
+        "previous" => 7,
+        "toggle" => 13,
+        _ => {
+            return Err(ApiError(
+                StatusCode::NOT_FOUND,
+                format!("Unknown player action {}", action),
+            ))
+        }
+    };
+    press(&state.app, key, Value::Null)?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct SeekRequest {
+    time: f64,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn seek(State(state): State<ServerState>, Json(request): Json<SeekRequest>) -> ApiResult<()> {
+    press(&state.app, 12, json!(request.time.max(0f64)))?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct VolumeRequest {
+    /// In the range 0-100, like the UI
+    volume: f64,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn volume(
+    State(state): State<ServerState>,
+    Json(request): Json<VolumeRequest>,
+) -> ApiResult<()> {
+    press(&state.app, 15, json!(request.volume.clamp(0f64, 100f64)))?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct RepeatRequest {
+    mode: RepeatModes,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn repeat(
+    State(state): State<ServerState>,
+    Json(request): Json<RepeatRequest>,
+) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    queue.queue_set_repeat(request.mode)?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct ShuffleRequest {
+    shuffle: bool,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn shuffle(
+    State(state): State<ServerState>,
+    Json(request): Json<ShuffleRequest>,
+) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    queue.queue_set_shuffle(request.shuffle)?;
+    Ok(Json(()))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn get_queue(State(state): State<ServerState>) -> ApiResult<QueueState> {
+    let queue = state.app.state::<QueueManager>();
+    Ok(Json(queue.get_queue()?))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum QueuePosition {
+    #[default]
+    End,
+    Next,
+    Now,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddToQueueRequest {
+    songs: Vec<Song>,
+    #[serde(default)]
+    position: QueuePosition,
+}
+
+#[tracing::instrument(level = "trace", skip(state, request))]
+async fn add_to_queue(
+    State(state): State<ServerState>,
+    Json(request): Json<AddToQueueRequest>,
+) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    match request.position {
+        QueuePosition::End => queue.queue_add(request.songs)?,
+        QueuePosition::Next => queue.queue_play_next(request.songs)?,
+        QueuePosition::Now => {
+            queue.queue_play_now(request.songs)?;
+            press(&state.app, 0, Value::Null)?;
+        }
+    }
+    Ok(Json(()))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn clear_queue(State(state): State<ServerState>) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    queue.queue_clear()?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexRequest {
+    index: usize,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn set_queue_index(
+    State(state): State<ServerState>,
+    Json(request): Json<IndexRequest>,
+) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    queue.queue_set_index(request.index)?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct MoveRequest {
+    from: usize,
+    to: usize,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn move_in_queue(
+    State(state): State<ServerState>,
+    Json(request): Json<MoveRequest>,
+) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    queue.queue_move(request.from, request.to)?;
+    Ok(Json(()))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn remove_from_queue(
+    State(state): State<ServerState>,
+    Path(index): Path<usize>,
+) -> ApiResult<()> {
+    let queue = state.app.state::<QueueManager>();
+    queue.queue_remove(index)?;
+    Ok(Json(()))
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    term: String,
+    /// Searches the library if not set
+    provider: Option<String>,
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn search(
+    State(state): State<ServerState>,
+    Query(query): Query<SearchQuery>,
+) -> ApiResult<SearchResult> {
+    let result = match query.provider {
+        Some(provider) => {
+            let provider_handler = state.app.state::<ProviderHandler>();
+            provider_handler
+                .provider_search(provider, query.term)
+                .await?
+        }
+        None => {
+            let database = state.app.state::<Database>();
+            database.search_all(query.term)?
+        }
+    };
+    Ok(Json(result))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn all_songs(State(state): State<ServerState>) -> ApiResult<Vec<Song>> {
+    let database = state.app.state::<Database>();
+    Ok(Json(database.get_songs_by_options(GetSongOptions {
+        song: Some(SearchableSong::default()),
+        ..Default::default()
+    })?))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn filter_songs(
+    State(state): State<ServerState>,
+    Json(options): Json<GetSongOptions>,
+) -> ApiResult<Vec<Song>> {
+    let database = state.app.state::<Database>();
+    Ok(Json(database.get_songs_by_options(options)?))
+}
+
+#[tracing::instrument(level = "trace", skip(state))]
+async fn entities(
+    State(state): State<ServerState>,
+    Path(entity): Path<String>,
+) -> ApiResult<Value> {
+    let options = match entity.as_str() {
+        "albums" => GetEntityOptions {
+            album: Some(QueryableAlbum::default()),
+            ..Default::default()
+        },
+        "artists" => GetEntityOptions {
+            artist: Some(QueryableArtist::default()),
+            ..Default::default()
+        },
+        "genres" => GetEntityOptions {
+            genre: Some(QueryableGenre::default()),
+            ..Default::default()
+        },
+        "playlists" => GetEntityOptions {
+            playlist: Some(QueryablePlaylist::default()),
+            ..Default::default()
+        },
+        _ => {
+            return Err(ApiError(
+                StatusCode::NOT_FOUND,
+                format!("Unknown library entity {}", entity),
+            ))
+        }
+    };
+
+    let database = state.app.state::<Database>();
+    Ok(Json(database.get_entity_by_options(options)?))
+}
+
+#[tracing::instrument(level = "trace", skip(state, upgrade))]
+async fn events(State(state): State<ServerState>, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(move |socket| stream_events(state.app, socket))
+}
+
+/// Pushes player events as JSON text frames until the client goes away
+#[tracing::instrument(level = "trace", skip(app, socket))]
+async fn stream_events(app: AppHandle, mut socket: WebSocket) {
+    let mut events = app.state::<RemoteControl>().subscribe();
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Remote event stream skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                let Ok(text) = serde_json::to_string(&event) else {
+                    continue;
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                // Incoming messages are ignored, only disconnects matter
+                match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    sync::Arc,
+};
+
+use axum::{middleware, routing::get, Router};
+
+use crate::remote::routes::{authorize, tokens_match};
+
+const TOKEN: &str = "secret-token";
+
+/// Serves a route behind [authorize] on a local port
+fn serve() -> SocketAddr {
+    let router = Router::new()
+        .route("/api/events", get(|| async { "ok" }))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(TOKEN.to_string()),
+            authorize,
+        ));
+    let listener =
+        tauri::async_runtime::block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+    let addr = listener.local_addr().unwrap();
+    tauri::async_runtime::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    addr
+}
+
+/// Status code of a GET on `target` with the extra `headers`
+fn status(addr: SocketAddr, target: &str, headers: &[&str]) -> u16 {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let mut request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n", target);
+    for header in headers {
+        request.push_str(header);
+        request.push_str("\r\n");
+    }
+    request.push_str("Connection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .unwrap()
+}
+
+#[test]
+fn test_tokens_match() {
+    assert!(tokens_match("token", "token"));
+    assert!(!tokens_match("tokem", "token"));
+    assert!(!tokens_match("tok", "token"));
+    assert!(!tokens_match("token2", "token"));
+    assert!(!tokens_match("", "token"));
+    assert!(!tokens_match("token", ""));
+    assert!(tokens_match("", ""));
+    // Missing bytes don't compare equal to zero bytes
+    assert!(!tokens_match("ab", "ab\0"));
+}
+
+#[test]
+fn test_authorize_header() {
+    let addr = serve();
+    assert_eq!(status(addr, "/api/events", &[]), 401);
+    assert_eq!(
+        status(addr, "/api/events", &["Authorization: Bearer secret-token"]),
+        200
+    );
+    assert_eq!(
+        status(addr, "/api/events", &["Authorization: Bearer wrong-token"]),
+        401
+    );
+    assert_eq!(
+        status(addr, "/api/events", &["Authorization: secret-token"]),
+        401
+    );
+}
+
+#[test]
+fn test_authorize_query_only_on_upgrade() {
+    let addr = serve();
+    assert_eq!(status(addr, "/api/events?token=secret-token", &[]), 401);
+    assert_eq!(
+        status(
+            addr,
+            "/api/events?token=secret-token",
+            &["Connection: Upgrade", "Upgrade: websocket"]
+        ),
+        200
+    );
+    assert_eq!(
+        status(
+            addr,
+            "/api/events?token=wrong-token",
+            &["Connection: Upgrade", "Upgrade: websocket"]
+        ),
+        401
+    );
+}
@@ -18,14 +18,24 @@ use std::thread;
 
 use futures::executor::block_on;
 use macros::generate_command_async;
+use preferences::preferences::PreferenceConfig;
 use rodio_player::RodioPlayer;
 use tauri::{AppHandle, Emitter, Manager, State};
 use types::errors::Result;
 
+const DEFAULT_CACHE_SIZE_MB: u64 = 512;
+
 #[tracing::instrument(level = "trace", skip())]
 pub fn get_rodio_state(app: AppHandle) -> RodioPlayer {
     let cache_dir = app.path().app_cache_dir().unwrap();
-    let rodio_player = RodioPlayer::new(cache_dir);
+    let preferences: State<PreferenceConfig> = app.state();
+    let output_device = preferences
+        .load_selective::<String>("audio_output_device".into())
+        .ok();
+    let cache_size = preferences
+        .load_selective::<u64>("audio_cache_size".into())
+        .unwrap_or(DEFAULT_CACHE_SIZE_MB);
+    let rodio_player = RodioPlayer::new(cache_dir, output_device, cache_size * 1024 * 1024);
 
     let events_rx = rodio_player.get_events_rx();
     thread::spawn(move || {
@@ -42,17 +52,29 @@ pub fn get_rodio_state(app: AppHandle) -> RodioPlayer {
     rodio_player
 }
 
-#[tracing::instrument(level = "trace", skip(app, src))]
+#[tracing::instrument(level = "trace", skip(app, src, id))]
 #[tauri::command(async)]
 #[tauri_invoke_proc::parse_tauri_command]
-pub fn rodio_load(app: AppHandle, src: String) -> Result<()> {
+pub fn rodio_load(app: AppHandle, src: String, id: Option<String>) -> Result<()> {
     tauri::async_runtime::spawn_blocking(move || {
         let rodio: State<'_, RodioPlayer> = app.state();
-        block_on(rodio.rodio_load(src)).unwrap();
+        block_on(rodio.rodio_load(src, id)).unwrap();
     });
     Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip(rodio, preferences))]
+#[tauri::command(async)]
+#[tauri_invoke_proc::parse_tauri_command]
+pub async fn rodio_set_output_device(
+    rodio: State<'_, RodioPlayer>,
+    preferences: State<'_, PreferenceConfig>,
+    device: Option<String>,
+) -> Result<()> {
+    preferences.save_selective("audio_output_device".into(), device.clone())?;
+    rodio.rodio_set_output_device(device).await
+}
+
 // generate_command_async!(rodio_load, RodioPlayer, (), src: String);
 generate_command_async!(rodio_play, RodioPlayer, (),);
 generate_command_async!(rodio_pause, RodioPlayer, (),);
@@ -60,3 +82,5 @@ generate_command_async!(rodio_stop, RodioPlayer, (),);
 generate_command_async!(rodio_seek, RodioPlayer, (), pos: f64);
 generate_command_async!(rodio_set_volume, RodioPlayer, (), volume: f32);
 generate_command_async!(rodio_get_volume, RodioPlayer, f32,);
+generate_command_async!(rodio_get_output_devices, RodioPlayer, Vec<String>,);
+generate_command_async!(rodio_clear_cache, RodioPlayer, (),);
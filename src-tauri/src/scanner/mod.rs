@@ -15,6 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::{
+    path::Path,
     sync::{atomic::AtomicBool, mpsc::channel, Arc, Mutex},
     thread::{self},
     time::Duration,
@@ -23,8 +24,15 @@ use std::{
 use database::database::Database;
 use file_scanner::ScannerHolder;
 use preferences::preferences::PreferenceConfig;
+use serde_json::Value;
 use tauri::{AppHandle, Manager, State};
-use types::{errors::Result, songs::Song};
+#[cfg(desktop)]
+use types::songs::{GetSongOptions, SearchableSong, SongType};
+use types::{entities::PlaylistImportResult, errors::Result, songs::Song};
+
+use crate::backup::rewrite_path_prefixes;
+#[cfg(desktop)]
+use crate::tagger::{fingerprint_enabled, fingerprint_library};
 
 #[tracing::instrument(level = "trace", skip())]
 pub fn get_scanner_state() -> ScannerHolder {
@@ -84,69 +92,256 @@ pub fn start_scan(app: AppHandle, paths: Option<Vec<String>>) -> Result<()> {
     start_scan_inner(app, paths)
 }
 
+/// Scanner options read from preferences
 #[cfg(desktop)]
-pub fn start_scan_inner(app: AppHandle, mut paths: Option<Vec<String>>) -> Result<()> {
-    let preferences = app.state::<PreferenceConfig>();
-    if paths.is_none() {
-        paths = Some(get_scan_paths(&preferences)?);
-    }
+#[derive(Debug, Clone)]
+pub struct ScanSettings {
+    pub thumbnail_dir: String,
+    pub artist_split: String,
+    pub scan_threads: f64,
+    /// Fingerprint new songs after scanning, see [fingerprint_library]
+    pub fingerprint: bool,
+}
 
-    let thumbnail_dir: String = preferences.load_selective("thumbnail_path".to_string())?;
-    tracing::debug!("Got thumbnail dir {:?}", thumbnail_dir);
+#[cfg(desktop)]
+impl ScanSettings {
+    #[tracing::instrument(level = "trace", skip(preferences))]
+    pub fn load(preferences: &PreferenceConfig) -> Result<Self> {
+        let thumbnail_dir: String = preferences.load_selective("thumbnail_path".to_string())?;
+        tracing::debug!("Got thumbnail dir {:?}", thumbnail_dir);
 
-    let artist_split: String = preferences
-        .load_selective("artist_splitter".to_string())
-        .unwrap_or(";".to_string());
+        let artist_split: String = preferences
+            .load_selective("artist_splitter".to_string())
+            .unwrap_or(";".to_string());
 
-    let scan_threads: f64 = preferences
-        .load_selective("scan_threads".to_string())
-        .unwrap_or(-1f64);
+        let scan_threads: f64 = preferences
+            .load_selective("scan_threads".to_string())
+            .unwrap_or(-1f64);
 
-    for path in paths.unwrap() {
-        tracing::info!("Scanning path: {}", path);
-
-        let (playlist_tx, playlist_rx) = channel();
-        let (song_tx, song_rx) = channel::<(Option<String>, Vec<Song>)>();
-
-        let app_clone = app.clone();
-        thread::spawn(move || {
-            let app = app_clone;
-            let database = app.state::<Database>();
-            for item in playlist_rx {
-                for playlist in item {
-                    let _ = database.create_playlist(playlist);
-                }
+        Ok(Self {
+            thumbnail_dir,
+            artist_split,
+            scan_threads,
+            fingerprint: fingerprint_enabled(preferences),
+        })
+    }
+}
+
+/// Scans `path` and writes the songs and playlists found into `database`.
+/// Returns the number of songs added.
+#[cfg(desktop)]
+#[tracing::instrument(level = "trace", skip(database, scanner, settings))]
+pub fn scan_into_database(
+    database: Database,
+    scanner: &ScannerHolder,
+    path: String,
+    settings: &ScanSettings,
+) -> Result<usize> {
+    tracing::info!("Scanning path: {}", path);
+
+    let known = database.local_file_stats()?;
+    let (playlist_tx, playlist_rx) = channel();
+    let (song_tx, song_rx) = channel::<(Option<String>, Vec<Song>)>();
+
+    let writer = thread::spawn(move || {
+        for item in playlist_rx {
+            for playlist in item {
+                let _ = database.create_playlist(playlist);
             }
+        }
 
-            for (playlist_id, songs) in song_rx {
-                let res = database.insert_songs(songs);
-                if let Ok(res) = res {
-                    if let Some(playlist_id) = playlist_id.as_ref() {
-                        for song in res {
-                            if let Some(song_id) = song.song._id {
-                                let _ =
-                                    database.add_to_playlist_bridge(playlist_id.clone(), song_id);
-                            }
+        let mut added = 0;
+        for (playlist_id, songs) in song_rx {
+            let res = database.insert_songs(songs);
+            if let Ok(res) = res {
+                added += res.len();
+                if let Some(playlist_id) = playlist_id.as_ref() {
+                    for song in res {
+                        if let Some(song_id) = song.song._id {
+                            let _ = database.add_to_playlist_bridge(playlist_id.clone(), song_id);
                         }
                     }
                 }
             }
-        });
+        }
+        added
+    });
 
-        let scanner = app.state::<ScannerHolder>();
-        scanner.start_scan(
-            path,
-            thumbnail_dir.clone(),
-            artist_split.clone(),
-            scan_threads,
-            song_tx,
-            playlist_tx,
-        )?;
+    scanner.start_scan(
+        path,
+        settings.thumbnail_dir.clone(),
+        settings.artist_split.clone(),
+        settings.scan_threads,
+        song_tx,
+        playlist_tx,
+        known,
+    )?;
+
+    writer.join().map_err(|_| "Scan writer panicked".into())
+}
+
+#[cfg(desktop)]
+pub fn start_scan_inner(app: AppHandle, mut paths: Option<Vec<String>>) -> Result<()> {
+    let preferences = app.state::<PreferenceConfig>();
+    if paths.is_none() {
+        paths = Some(get_scan_paths(&preferences)?);
+    }
+
+    let settings = ScanSettings::load(&preferences)?;
+    let database = app.state::<Database>();
+    let scanner = app.state::<ScannerHolder>();
+    for path in paths.unwrap() {
+        scan_into_database(database.inner().clone(), &scanner, path, &settings)?;
+    }
+
+    if settings.fingerprint {
+        if let Err(e) = fingerprint_library(&database, settings.scan_threads) {
+            tracing::error!("Fingerprinting failed: {:?}", e);
+        }
     }
 
     Ok(())
 }
 
+/// Points the library at a music folder that moved, e.g. to a new drive.
+/// Songs under `from` keep their play counts, lyrics and playlists.
+/// Returns the number of paths updated.
+#[tracing::instrument(level = "trace", skip(database, preferences))]
+pub fn relocate_library(
+    database: &Database,
+    preferences: &PreferenceConfig,
+    from: &str,
+    to: &str,
+) -> Result<usize> {
+    if !Path::new(to).is_dir() {
+        return Err(format!("{} is not a directory", to).into());
+    }
+
+    let changed = database.rewrite_path_prefix(from, to)?;
+
+    let mut music_paths: Value = preferences
+        .load_selective("music_paths".to_string())
+        .unwrap_or_default();
+    rewrite_path_prefixes(&mut music_paths, from, to);
+    if music_paths.is_array() {
+        preferences.save_selective("music_paths".to_string(), Some(music_paths))?;
+    }
+
+    tracing::info!("Relocated {} paths from {} to {}", changed, from, to);
+    Ok(changed)
+}
+
+#[tracing::instrument(level = "trace", skip(database, preferences))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn relocate_music_folder(
+    database: State<Database>,
+    preferences: State<PreferenceConfig>,
+    from: String,
+    to: String,
+) -> Result<usize> {
+    relocate_library(&database, &preferences, &from, &to)
+}
+
+/// Returns the library entry for the file at `path`, if there is one
+#[cfg(desktop)]
+#[tracing::instrument(level = "trace", skip(database))]
+pub fn find_song_by_path(database: &Database, path: &str) -> Result<Option<Song>> {
+    let songs = database.get_songs_by_options(GetSongOptions {
+        song: Some(SearchableSong {
+            path: Some(path.to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })?;
+    Ok(songs.into_iter().next())
+}
+
+/// Adds a playlist file and its songs to the library. Songs already in the
+/// library are reused, local files that aren't are read for their metadata.
+#[cfg(desktop)]
+#[tracing::instrument(level = "trace", skip(database, settings))]
+pub fn import_playlist_file(
+    database: &Database,
+    path: &Path,
+    settings: &ScanSettings,
+) -> Result<PlaylistImportResult> {
+    let imported = file_scanner::playlists::import_playlist(path)?;
+    let playlist_id = database.create_playlist(imported.playlist)?;
+
+    let mut songs = vec![];
+    let mut new_songs = vec![];
+    for song in imported.songs {
+        let existing = match song.song.type_ {
+            SongType::LOCAL => match song.song.path.as_deref() {
+                Some(path) => find_song_by_path(database, path)?,
+                None => None,
+            },
+            _ => database
+                .get_songs_by_options(GetSongOptions {
+                    song: Some(SearchableSong {
+                        _id: song.song._id.clone(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })?
+                .into_iter()
+                .next(),
+        };
+        if let Some(existing) = existing {
+            songs.push(existing);
+            continue;
+        }
+
+        // Keep what the playlist knows about the song if the file can't be read
+        let song = match song.song.path.as_deref() {
+            Some(song_path) if song.song.type_ == SongType::LOCAL => file_scanner::scan_song(
+                Path::new(song_path),
+                Path::new(&settings.thumbnail_dir),
+                &settings.artist_split,
+            )
+            .unwrap_or(song),
+            _ => song,
+        };
+        new_songs.push(song);
+    }
+    songs.extend(database.insert_songs(new_songs)?);
+
+    // Importing the same file again reuses its playlist
+    for song in &songs {
+        if let Some(song_id) = song.song._id.clone() {
+            if !database.is_song_in_playlist(playlist_id.clone(), song_id.clone())? {
+                database.add_to_playlist_bridge(playlist_id.clone(), song_id)?;
+            }
+        }
+    }
+
+    Ok(PlaylistImportResult {
+        playlist_id,
+        songs: songs.len(),
+        unresolved: imported.unresolved,
+    })
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub fn import_playlist(app: AppHandle, path: String) -> Result<PlaylistImportResult> {
+    import_playlist_inner(app, path)
+}
+
+#[cfg(desktop)]
+fn import_playlist_inner(app: AppHandle, path: String) -> Result<PlaylistImportResult> {
+    let settings = ScanSettings::load(&app.state::<PreferenceConfig>())?;
+    let database = app.state::<Database>();
+    import_playlist_file(&database, Path::new(&path), &settings)
+}
+
+#[cfg(mobile)]
+fn import_playlist_inner(_app: AppHandle, _path: String) -> Result<PlaylistImportResult> {
+    Err("Importing playlist files is not supported on mobile".into())
+}
+
 #[cfg(mobile)]
 pub fn start_scan_inner(app: AppHandle, mut paths: Option<Vec<String>>) -> Result<()> {
     use tauri_plugin_file_scanner::FileScannerExt;
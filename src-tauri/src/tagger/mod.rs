@@ -0,0 +1,299 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use database::database::Database;
+use preferences::preferences::PreferenceConfig;
+use tagger::{TagLookup, TagQuery, ACOUSTID_BASE_URL, COVER_ART_BASE_URL, MUSICBRAINZ_BASE_URL};
+use tauri::{App, Manager, State};
+use types::{
+    entities::{QueryableAlbum, QueryableArtist, SongFingerprint, TagSuggestion},
+    errors::Result,
+    songs::{GetSongOptions, SearchableSong, Song},
+};
+#[cfg(desktop)]
+use types::{preferences::CheckboxPreference, songs::SongType};
+
+/// Lookup service rebuilt whenever its endpoints or API key change
+pub struct TagLookupHolder {
+    lookup: RwLock<Arc<TagLookup>>,
+}
+
+impl TagLookupHolder {
+    #[tracing::instrument(level = "trace", skip(preferences))]
+    pub fn new(preferences: &PreferenceConfig) -> Self {
+        Self {
+            lookup: RwLock::new(Arc::new(build_lookup(preferences))),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, preferences))]
+    pub fn configure(&self, preferences: &PreferenceConfig) {
+        *self.lookup.write().unwrap() = Arc::new(build_lookup(preferences));
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn get(&self) -> Arc<TagLookup> {
+        self.lookup.read().unwrap().clone()
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(preferences))]
+pub fn build_lookup(preferences: &PreferenceConfig) -> TagLookup {
+    let load = |key: &str| {
+        preferences
+            .load_selective::<String>(key.into())
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+    };
+    TagLookup::new(
+        &load("acoustid_url").unwrap_or(ACOUSTID_BASE_URL.into()),
+        load("acoustid_api_key"),
+        &load("musicbrainz_url").unwrap_or(MUSICBRAINZ_BASE_URL.into()),
+        COVER_ART_BASE_URL,
+    )
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn get_tagger_state(app: &App) -> TagLookupHolder {
+    TagLookupHolder::new(app.state::<PreferenceConfig>().inner())
+}
+
+/// Whether songs should be fingerprinted after scans
+#[cfg(desktop)]
+#[tracing::instrument(level = "trace", skip(preferences))]
+pub fn fingerprint_enabled(preferences: &PreferenceConfig) -> bool {
+    preferences
+        .load_selective::<Vec<CheckboxPreference>>("tagging".into())
+        .unwrap_or_default()
+        .iter()
+        .any(|s| s.key == "fingerprint" && s.enabled)
+}
+
+/// Fingerprints the local songs which don't have one yet, on up to
+/// `threads` threads. Files that can't be decoded get an empty fingerprint
+/// so later scans don't try them again. Returns the number of songs done.
+#[cfg(desktop)]
+#[tracing::instrument(level = "trace", skip(database))]
+pub fn fingerprint_library(database: &Database, threads: f64) -> Result<usize> {
+    let songs = database.songs_without_fingerprint()?;
+    if songs.is_empty() {
+        return Ok(0);
+    }
+
+    let threads = if threads > 0f64 {
+        threads as usize
+    } else {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    };
+    let chunk_size = songs.len().div_ceil(threads);
+    tracing::info!("Fingerprinting {} songs", songs.len());
+
+    std::thread::scope(|scope| -> Result<()> {
+        let workers = songs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || -> Result<()> {
+                    for (song_id, path) in chunk {
+                        database.set_fingerprint(fingerprint_song(song_id, path))?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            worker.join().map_err(|_| "Fingerprint worker panicked")??;
+        }
+        Ok(())
+    })?;
+
+    Ok(songs.len())
+}
+
+#[cfg(desktop)]
+#[tracing::instrument(level = "trace", skip())]
+fn fingerprint_song(song_id: &str, path: &str) -> SongFingerprint {
+    let fingerprint = file_scanner::fingerprint_file(Path::new(path))
+        .inspect_err(|e| tracing::warn!("Failed to fingerprint {}: {:?}", path, e))
+        .unwrap_or_default();
+    SongFingerprint {
+        song_id: song_id.to_string(),
+        fingerprint: fingerprint.fingerprint,
+        duration: fingerprint.duration,
+    }
+}
+
+/// Fingerprint of a song, computing it off the async runtime if the song
+/// wasn't fingerprinted yet
+#[tracing::instrument(level = "trace", skip(database, song))]
+async fn get_fingerprint(database: &Database, song: &Song) -> Result<Option<SongFingerprint>> {
+    let Some(song_id) = song.song._id.clone() else {
+        return Ok(None);
+    };
+
+    let fingerprint = database.get_fingerprint(song_id.clone())?;
+    #[cfg(desktop)]
+    let fingerprint = match (fingerprint, &song.song.path) {
+        (None, Some(path)) if song.song.type_ == SongType::LOCAL => {
+            let path = path.clone();
+            let computed =
+                tauri::async_runtime::spawn_blocking(move || fingerprint_song(&song_id, &path))
+                    .await
+                    .map_err(|e| format!("Fingerprinting failed: {:?}", e))?;
+            database.set_fingerprint(computed.clone())?;
+            Some(computed)
+        }
+        (fingerprint, _) => fingerprint,
+    };
+    Ok(fingerprint.filter(|f| !f.fingerprint.is_empty()))
+}
+
+#[tracing::instrument(level = "trace", skip(database))]
+fn get_song(database: &Database, song_id: &str) -> Result<Song> {
+    database
+        .get_songs_by_options(GetSongOptions {
+            song: Some(SearchableSong {
+                _id: Some(song_id.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Song {} not found", song_id).into())
+}
+
+/// Proposes tags for a song without changing it
+#[tracing::instrument(level = "trace", skip(database, lookup))]
+pub async fn lookup_tags(
+    database: &Database,
+    lookup: &TagLookup,
+    song_id: &str,
+) -> Result<Vec<TagSuggestion>> {
+    let song = get_song(database, song_id)?;
+    let fingerprint = get_fingerprint(database, &song).await?;
+
+    let query = TagQuery {
+        song_id: song_id.to_string(),
+        title: song.song.title.clone(),
+        artists: song
+            .artists
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|a| a.artist_name)
+            .collect(),
+        album: song.album.and_then(|a| a.album_name),
+        duration: fingerprint
+            .as_ref()
+            .map(|f| f.duration)
+            .or(song.song.duration),
+        fingerprint: fingerprint.map(|f| f.fingerprint),
+    };
+    lookup.lookup(&query).await
+}
+
+/// Writes the tags of a suggestion the user confirmed to its song. The
+/// cover is stored with the thumbnails when the song doesn't have one.
+#[tracing::instrument(level = "trace", skip(database, lookup, thumbnail_dir))]
+pub async fn apply_suggestion(
+    database: &Database,
+    lookup: &TagLookup,
+    thumbnail_dir: &Path,
+    suggestion: TagSuggestion,
+) -> Result<Song> {
+    let mut song = get_song(database, &suggestion.song_id)?;
+
+    let mut cover = None;
+    if song.song.song_cover_path_high.is_none() {
+        if let Some(url) = &suggestion.cover_url {
+            match lookup.fetch_cover(url).await {
+                Ok(Some(data)) => {
+                    std::fs::create_dir_all(thumbnail_dir)?;
+                    cover = Some(file_scanner::store_cover(thumbnail_dir, &data)?);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to fetch cover {}: {:?}", url, e),
+            }
+        }
+    }
+    let cover = cover.map(|(high, low)| {
+        (
+            high.to_string_lossy().to_string(),
+            low.to_string_lossy().to_string(),
+        )
+    });
+
+    song.song.title = Some(suggestion.title);
+    if suggestion.year.is_some() {
+        song.song.year = suggestion.year.clone();
+    }
+    if let Some((high, low)) = cover.clone() {
+        song.song.song_cover_path_high = Some(high);
+        song.song.song_cover_path_low = Some(low);
+    }
+    song.album = suggestion.album.map(|album_name| QueryableAlbum {
+        album_name: Some(album_name),
+        album_artist: suggestion.artists.first().map(|a| a.name.clone()),
+        year: suggestion.year,
+        album_coverpath_high: cover.clone().map(|c| c.0),
+        album_coverpath_low: cover.map(|c| c.1),
+        ..Default::default()
+    });
+    song.artists = Some(
+        suggestion
+            .artists
+            .into_iter()
+            .map(|artist| QueryableArtist {
+                artist_name: Some(artist.name),
+                artist_mbid: artist.mbid,
+                ..Default::default()
+            })
+            .collect(),
+    );
+
+    database.retag_song(song.clone())?;
+    get_song(database, &suggestion.song_id)
+}
+
+#[tracing::instrument(level = "trace", skip(database, tagger))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub async fn lookup_song_tags(
+    database: State<'_, Database>,
+    tagger: State<'_, TagLookupHolder>,
+    song_id: String,
+) -> Result<Vec<TagSuggestion>> {
+    let lookup = tagger.get();
+    lookup_tags(&database, &lookup, &song_id).await
+}
+
+#[tracing::instrument(level = "trace", skip(database, tagger, preferences))]
+#[tauri_invoke_proc::parse_tauri_command]
+#[tauri::command(async)]
+pub async fn apply_tag_suggestion(
+    database: State<'_, Database>,
+    tagger: State<'_, TagLookupHolder>,
+    preferences: State<'_, PreferenceConfig>,
+    suggestion: TagSuggestion,
+) -> Result<Song> {
+    let thumbnail_dir: String = preferences.load_selective("thumbnail_path".to_string())?;
+    let lookup = tagger.get();
+    apply_suggestion(&database, &lookup, Path::new(&thumbnail_dir), suggestion).await
+}
@@ -19,7 +19,7 @@ use std::{collections::HashMap, fs, sync::mpsc::channel};
 use macros::{generate_command, generate_command_async};
 use tauri::{App, AppHandle, Emitter, Manager, State};
 use themes::themes::ThemeHolder;
-use types::{errors::Result, themes::ThemeDetails};
+use types::{errors::Result, themes::ThemeDetails, window::DialogFilter};
 
 use crate::window::handler::WindowHandler;
 
@@ -55,7 +55,14 @@ pub fn export_theme(
     window_handler: State<WindowHandler>,
     id: String,
 ) -> Result<()> {
-    let selected_file = window_handler.open_save_file(app)?;
+    let selected_file = window_handler.open_save_file(
+        app,
+        vec![DialogFilter {
+            name: "Moosync theme".into(),
+            extensions: vec!["mstx".into()],
+        }],
+        None,
+    )?;
     theme_handler.export_theme(id, selected_file)?;
     Ok(())
 }
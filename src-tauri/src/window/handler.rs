@@ -19,8 +19,7 @@ use std::path::PathBuf;
 
 use macros::{generate_command, generate_command_async};
 use preferences::preferences::PreferenceConfig;
-use serde_json::Value;
-use tauri::{App, AppHandle, Emitter, Manager, State, WebviewWindow, WebviewWindowBuilder, Window};
+use tauri::{AppHandle, Manager, State, WebviewWindow, WebviewWindowBuilder, Window};
 use tauri_plugin_dialog::{DialogExt, FilePath};
 use tauri_plugin_opener::OpenerExt;
 use types::errors::{MoosyncError, Result};
@@ -251,14 +250,32 @@ impl WindowHandler {
         }
     }
 
-    #[tracing::instrument(level = "trace", skip(self))]
-    pub fn open_save_file(&self, app: AppHandle) -> Result<PathBuf> {
-        #[cfg(any(target_os = "android", target_os = "ios"))]
-        {
-            let res = app.dialog().file().blocking_save_file();
-            if let Some(FilePath::Path(path)) = res {
-                return Ok(path.clone());
-            }
+    #[tracing::instrument(level = "trace", skip(self, app))]
+    pub fn open_save_file(
+        &self,
+        app: AppHandle,
+        filters: Vec<DialogFilter>,
+        file_name: Option<String>,
+    ) -> Result<PathBuf> {
+        let mut dialog = app.dialog().file();
+        for filter in filters {
+            dialog = dialog.add_filter(
+                filter.name,
+                filter
+                    .extensions
+                    .iter()
+                    .map(|e| e.as_str())
+                    .collect::<Vec<&str>>()
+                    .as_slice(),
+            );
+        }
+        if let Some(file_name) = file_name {
+            dialog = dialog.set_file_name(file_name);
+        }
+
+        let res = dialog.blocking_save_file();
+        if let Some(FilePath::Path(path)) = res {
+            return Ok(path);
         }
         Err("No file selected".into())
     }
@@ -281,71 +298,6 @@ pub fn handle_window_close(app: &AppHandle) -> Result<bool> {
     Ok(true)
 }
 
-#[tracing::instrument(level = "trace", skip(app))]
-pub fn build_tray_menu(app: &App) -> Result<()> {
-    #[cfg(not(any(target_os = "android", target_os = "ios")))]
-    {
-        use tauri::menu::MenuBuilder;
-        use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
-        let menu = MenuBuilder::new(app)
-            .icon(
-                "show",
-                "Show App",
-                app.default_window_icon().cloned().unwrap(),
-            )
-            .icon("play", "Play", app.default_window_icon().cloned().unwrap())
-            .icon(
-                "pause",
-                "Pause",
-                app.default_window_icon().cloned().unwrap(),
-            )
-            .icon("next", "Next", app.default_window_icon().cloned().unwrap())
-            .icon("prev", "Prev", app.default_window_icon().cloned().unwrap())
-            .icon("quit", "Quit", app.default_window_icon().cloned().unwrap())
-            .build()?;
-
-        tauri::tray::TrayIconBuilder::new()
-            .menu(&menu)
-            .on_menu_event(move |app, event| match event.id().as_ref() {
-                "show" => {
-                    let _ = app.get_webview_window("main").unwrap().show();
-                }
-                "play" => {
-                    let _ = app.emit("media_button_press", (0, Value::Null));
-                }
-                "pause" => {
-                    let _ = app.emit("media_button_press", (1, Value::Null));
-                }
-                "next" => {
-                    let _ = app.emit("media_button_press", (6, Value::Null));
-                }
-                "prev" => {
-                    let _ = app.emit("media_button_press", (7, Value::Null));
-                }
-                "quit" => {
-                    app.exit(0);
-                }
-                _ => (),
-            })
-            .on_tray_icon_event(|tray, event| {
-                if let TrayIconEvent::Click {
-                    button: MouseButton::Left,
-                    button_state: MouseButtonState::Up,
-                    ..
-                } = event
-                {
-                    let app = tray.app_handle();
-                    if let Some(webview_window) = app.get_webview_window("main") {
-                        let _ = webview_window.show();
-                        let _ = webview_window.set_focus();
-                    }
-                }
-            })
-            .build(app)?;
-    }
-    Ok(())
-}
-
 generate_command!(is_maximized, WindowHandler, bool, window: Window);
 generate_command!(has_frame, WindowHandler, bool,);
 generate_command!(close_window, WindowHandler, (), window: Window);
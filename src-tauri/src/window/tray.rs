@@ -0,0 +1,368 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Mutex;
+
+use macros::generate_command;
+use serde_json::{json, Value};
+use tauri::{App, AppHandle, Emitter, Manager, State};
+use types::{
+    entities::QueryablePlaylist,
+    errors::Result,
+    mpris::MprisPlayerDetails,
+    ui::{
+        player_details::{PlayerState, RepeatModes},
+        tray::TrayLabels,
+    },
+};
+
+const TRAY_ID: &str = "main";
+const MAX_RECENT_PLAYLISTS: usize = 5;
+const PLAYLIST_ITEM_PREFIX: &str = "playlist:";
+
+#[derive(Debug, Default, Clone)]
+struct TrayData {
+    metadata: Option<MprisPlayerDetails>,
+    state: PlayerState,
+    repeat: RepeatModes,
+    shuffle: bool,
+    recent_playlists: Vec<QueryablePlaylist>,
+    labels: TrayLabels,
+}
+
+impl TrayData {
+    fn now_playing(&self) -> String {
+        match &self.metadata {
+            Some(metadata) => {
+                let title = metadata
+                    .title
+                    .clone()
+                    .unwrap_or(self.labels.unknown.clone());
+                match &metadata.artist_name {
+                    Some(artist) if !artist.is_empty() => format!("{} - {}", title, artist),
+                    _ => title,
+                }
+            }
+            None => self.labels.nothing_playing.clone(),
+        }
+    }
+
+    fn next_repeat(&self) -> RepeatModes {
+        match self.repeat {
+            RepeatModes::None => RepeatModes::Once,
+            RepeatModes::Once => RepeatModes::Loop,
+            RepeatModes::Loop => RepeatModes::None,
+        }
+    }
+
+    fn repeat_label(&self) -> &str {
+        match self.repeat {
+            RepeatModes::None => &self.labels.repeat,
+            RepeatModes::Once => &self.labels.repeat_once,
+            RepeatModes::Loop => &self.labels.repeat_always,
+        }
+    }
+}
+
+/// Items of the tray menu, kept around so they can be updated in place
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+struct TrayMenu {
+    show: tauri::menu::MenuItem,
+    now_playing: tauri::menu::MenuItem,
+    toggle: tauri::menu::MenuItem,
+    next: tauri::menu::MenuItem,
+    prev: tauri::menu::MenuItem,
+    repeat: tauri::menu::CheckMenuItem,
+    shuffle: tauri::menu::CheckMenuItem,
+    like: tauri::menu::MenuItem,
+    playlists: tauri::menu::Submenu,
+    quit: tauri::menu::MenuItem,
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+impl std::fmt::Debug for TrayMenu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrayMenu").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+impl TrayMenu {
+    #[tracing::instrument(level = "trace", skip(app, data))]
+    fn new<M: Manager<tauri::Wry>>(app: &M, data: &TrayData) -> Result<Self> {
+        use tauri::menu::{CheckMenuItem, MenuItem, Submenu};
+
+        let menu = Self {
+            show: MenuItem::with_id(app, "show", "", true, None::<&str>)?,
+            now_playing: MenuItem::with_id(app, "now_playing", "", false, None::<&str>)?,
+            toggle: MenuItem::with_id(app, "toggle", "", false, None::<&str>)?,
+            next: MenuItem::with_id(app, "next", "", true, None::<&str>)?,
+            prev: MenuItem::with_id(app, "prev", "", true, None::<&str>)?,
+            repeat: CheckMenuItem::with_id(app, "repeat", "", true, false, None::<&str>)?,
+            shuffle: CheckMenuItem::with_id(app, "shuffle", "", true, false, None::<&str>)?,
+            like: MenuItem::with_id(app, "like", "", false, None::<&str>)?,
+            playlists: Submenu::with_id(app, "recent_playlists", "", false)?,
+            quit: MenuItem::with_id(app, "quit", "", true, None::<&str>)?,
+        };
+        menu.update(data)?;
+        Ok(menu)
+    }
+
+    fn build<M: Manager<tauri::Wry>>(&self, app: &M) -> Result<tauri::menu::Menu> {
+        use tauri::menu::{Menu, PredefinedMenuItem};
+
+        let menu = Menu::with_items(
+            app,
+            &[
+                &self.now_playing,
+                &PredefinedMenuItem::separator(app)?,
+                &self.show,
+                &self.toggle,
+                &self.next,
+                &self.prev,
+                &PredefinedMenuItem::separator(app)?,
+                &self.repeat,
+                &self.shuffle,
+                &self.like,
+                &self.playlists,
+                &PredefinedMenuItem::separator(app)?,
+                &self.quit,
+            ],
+        )?;
+        Ok(menu)
+    }
+
+    fn update(&self, data: &TrayData) -> Result<()> {
+        let labels = &data.labels;
+        let has_song = data.metadata.is_some();
+
+        self.show.set_text(&labels.show_app)?;
+        self.now_playing.set_text(data.now_playing())?;
+        self.toggle
+            .set_text(if data.state == PlayerState::Playing {
+                &labels.pause
+            } else {
+                &labels.play
+            })?;
+        self.toggle.set_enabled(has_song)?;
+        self.next.set_text(&labels.next)?;
+        self.prev.set_text(&labels.prev)?;
+        self.repeat.set_text(data.repeat_label())?;
+        self.repeat.set_checked(data.repeat != RepeatModes::None)?;
+        self.shuffle.set_text(&labels.shuffle)?;
+        self.shuffle.set_checked(data.shuffle)?;
+        self.like.set_text(&labels.favorite)?;
+        self.like.set_enabled(has_song)?;
+        self.playlists.set_text(&labels.recent_playlists)?;
+        self.quit.set_text(&labels.quit)?;
+        Ok(())
+    }
+
+    fn update_playlists(&self, app: &AppHandle, data: &TrayData) -> Result<()> {
+        use tauri::menu::MenuItem;
+
+        while self.playlists.remove_at(0)?.is_some() {}
+        for playlist in &data.recent_playlists {
+            if let Some(id) = &playlist.playlist_id {
+                let item = MenuItem::with_id(
+                    app,
+                    format!("{}{}", PLAYLIST_ITEM_PREFIX, id),
+                    &playlist.playlist_name,
+                    true,
+                    None::<&str>,
+                )?;
+                self.playlists.append(&item)?;
+            }
+        }
+        self.playlists
+            .set_enabled(!data.recent_playlists.is_empty())?;
+        Ok(())
+    }
+}
+
+/// Mirrors the now-playing state into the tray menu.
+/// Fed by the same commands which update [MprisHolder](mpris::MprisHolder).
+#[derive(Debug)]
+pub struct TrayHandler {
+    app: AppHandle,
+    data: Mutex<TrayData>,
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    menu: Mutex<Option<TrayMenu>>,
+}
+
+impl TrayHandler {
+    #[tracing::instrument(level = "trace", skip(app))]
+    pub fn new(app: AppHandle) -> Self {
+        Self {
+            app,
+            data: Default::default(),
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            menu: Default::default(),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_metadata(&self, metadata: MprisPlayerDetails) -> Result<()> {
+        self.data.lock().unwrap().metadata = Some(metadata);
+        self.refresh()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_playback_state(&self, state: PlayerState) -> Result<()> {
+        {
+            let mut data = self.data.lock().unwrap();
+            if data.state == state {
+                return Ok(());
+            }
+            data.state = state;
+        }
+        self.refresh()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_loop_status(&self, mode: RepeatModes) -> Result<()> {
+        self.data.lock().unwrap().repeat = mode;
+        self.refresh()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_shuffle(&self, shuffle: bool) -> Result<()> {
+        self.data.lock().unwrap().shuffle = shuffle;
+        self.refresh()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_tray_labels(&self, labels: TrayLabels) -> Result<()> {
+        self.data.lock().unwrap().labels = labels;
+        self.refresh()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn add_tray_recent_playlist(&self, playlist: QueryablePlaylist) -> Result<()> {
+        if playlist.playlist_id.is_none() {
+            return Ok(());
+        }
+
+        let data = {
+            let mut data = self.data.lock().unwrap();
+            data.recent_playlists.retain(|p| *p != playlist);
+            data.recent_playlists.insert(0, playlist);
+            data.recent_playlists.truncate(MAX_RECENT_PLAYLISTS);
+            data.clone()
+        };
+
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        if let Some(menu) = self.menu.lock().unwrap().as_ref() {
+            menu.update_playlists(&self.app, &data)?;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn refresh(&self) -> Result<()> {
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        {
+            // Menu updates are dispatched to the main thread, which may be waiting on the data lock
+            let data = self.data.lock().unwrap().clone();
+            if let Some(menu) = self.menu.lock().unwrap().as_ref() {
+                menu.update(&data)?;
+            }
+            if let Some(tray) = self.app.tray_by_id(TRAY_ID) {
+                tray.set_tooltip(Some(data.now_playing()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn get_tray_state(app: AppHandle) -> TrayHandler {
+    TrayHandler::new(app)
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tracing::instrument(level = "trace", skip(app))]
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    let emit = |code: i32, value: Value| {
+        let _ = app.emit("media_button_press", (code, value));
+    };
+
+    match id {
+        "show" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        "toggle" => emit(13, Value::Null),
+        "next" => emit(6, Value::Null),
+        "prev" => emit(7, Value::Null),
+        "repeat" => {
+            let tray: State<TrayHandler> = app.state();
+            let mode = tray.data.lock().unwrap().next_repeat();
+            emit(21, json!(mode));
+        }
+        "shuffle" => {
+            let tray: State<TrayHandler> = app.state();
+            let shuffle = !tray.data.lock().unwrap().shuffle;
+            emit(20, json!(shuffle));
+        }
+        "like" => emit(23, Value::Null),
+        "quit" => app.exit(0),
+        id => {
+            if let Some(playlist_id) = id.strip_prefix(PLAYLIST_ITEM_PREFIX) {
+                emit(24, Value::String(playlist_id.to_string()));
+            }
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(app))]
+pub fn build_tray_menu(app: &App) -> Result<()> {
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    {
+        use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
+
+        let tray: State<TrayHandler> = app.state();
+        let tray_menu = TrayMenu::new(app, &tray.data.lock().unwrap())?;
+        let menu = tray_menu.build(app)?;
+        *tray.menu.lock().unwrap() = Some(tray_menu);
+
+        tauri::tray::TrayIconBuilder::with_id(TRAY_ID)
+            .icon(app.default_window_icon().cloned().unwrap())
+            .menu(&menu)
+            .on_menu_event(move |app, event| handle_menu_event(app, event.id().as_ref()))
+            .on_tray_icon_event(|tray, event| {
+                if let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    let app = tray.app_handle();
+                    if let Some(webview_window) = app.get_webview_window("main") {
+                        let _ = webview_window.show();
+                        let _ = webview_window.set_focus();
+                    }
+                }
+            })
+            .build(app)?;
+    }
+    Ok(())
+}
+
+generate_command!(add_tray_recent_playlist, TrayHandler, (), playlist: QueryablePlaylist);
+generate_command!(set_tray_labels, TrayHandler, (), labels: TrayLabels);
@@ -0,0 +1,96 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::Deserialize;
+use types::errors::Result;
+
+use crate::TagLookup;
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdRecording {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdResult {
+    score: f64,
+    #[serde(default)]
+    recordings: Vec<AcoustIdRecording>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdResponse {
+    status: String,
+    #[serde(default)]
+    error: Option<AcoustIdError>,
+    #[serde(default)]
+    results: Vec<AcoustIdResult>,
+}
+
+impl TagLookup {
+    /// MusicBrainz recording ids matching a fingerprint with their score,
+    /// best first
+    #[tracing::instrument(level = "trace", skip(self, fingerprint))]
+    pub(crate) async fn acoustid_lookup(
+        &self,
+        fingerprint: &str,
+        duration: f64,
+    ) -> Result<Vec<(String, f64)>> {
+        let key = self
+            .acoustid_key
+            .clone()
+            .ok_or("Missing AcoustID API key")?;
+
+        self.throttle().await;
+        let body = self
+            .client
+            .get(format!("{}/v2/lookup", self.acoustid_url))
+            .query(&[
+                ("client", key),
+                ("meta", "recordingids".into()),
+                ("duration", (duration.round() as u64).to_string()),
+                ("fingerprint", fingerprint.to_string()),
+            ])
+            .send()
+            .await?
+            .text()
+            .await?;
+        let resp: AcoustIdResponse = serde_json::from_str(&body)?;
+        if resp.status != "ok" {
+            return Err(format!(
+                "AcoustID lookup failed: {}",
+                resp.error.map(|e| e.message).unwrap_or(resp.status)
+            )
+            .into());
+        }
+
+        let mut matches: Vec<(String, f64)> = vec![];
+        for result in resp.results {
+            for recording in result.recordings {
+                if !matches.iter().any(|(id, _)| *id == recording.id) {
+                    matches.push((recording.id, result.score));
+                }
+            }
+        }
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(matches)
+    }
+}
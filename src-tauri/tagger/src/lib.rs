@@ -0,0 +1,158 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use tokio::{
+    sync::Mutex,
+    time::{sleep_until, Instant},
+};
+use types::{entities::TagSuggestion, errors::Result};
+
+mod acoustid;
+mod musicbrainz;
+
+#[cfg(test)]
+mod test;
+
+pub const ACOUSTID_BASE_URL: &str = "https://api.acoustid.org";
+pub const MUSICBRAINZ_BASE_URL: &str = "https://musicbrainz.org";
+pub const COVER_ART_BASE_URL: &str = "https://coverartarchive.org";
+
+// MusicBrainz requires an identifying user agent
+const USER_AGENT: &str = "Moosync (https://github.com/Moosync/Moosync)";
+
+// Both services ask clients to stay below a request per second
+const REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+// Recordings looked up per song, AcoustID often returns many near duplicates
+const MAX_RECORDINGS: usize = 3;
+
+/// Song to find tags for
+#[derive(Debug, Default, Clone)]
+pub struct TagQuery {
+    pub song_id: String,
+    pub title: Option<String>,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    /// In seconds
+    pub duration: Option<f64>,
+    /// Compressed and base64 encoded Chromaprint fingerprint
+    pub fingerprint: Option<String>,
+}
+
+/// Proposes tags for songs by their fingerprint through AcoustID, or by
+/// their current tags through a MusicBrainz search
+#[derive(Debug)]
+pub struct TagLookup {
+    acoustid_url: String,
+    acoustid_key: Option<String>,
+    musicbrainz_url: String,
+    cover_art_url: String,
+    client: reqwest::Client,
+    next_request: Mutex<Instant>,
+    pub(crate) request_interval: Duration,
+}
+
+impl TagLookup {
+    #[tracing::instrument(level = "trace", skip(acoustid_key))]
+    pub fn new(
+        acoustid_url: &str,
+        acoustid_key: Option<String>,
+        musicbrainz_url: &str,
+        cover_art_url: &str,
+    ) -> Self {
+        Self {
+            acoustid_url: acoustid_url.trim_end_matches('/').to_string(),
+            acoustid_key: acoustid_key.filter(|k| !k.trim().is_empty()),
+            musicbrainz_url: musicbrainz_url.trim_end_matches('/').to_string(),
+            cover_art_url: cover_art_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::builder()
+                .user_agent(USER_AGENT)
+                .build()
+                .unwrap_or_default(),
+            next_request: Mutex::new(Instant::now()),
+            request_interval: REQUEST_INTERVAL,
+        }
+    }
+
+    /// Waits until the rate limit allows another request
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn throttle(&self) {
+        let mut next_request = self.next_request.lock().await;
+        sleep_until(*next_request).await;
+        *next_request = Instant::now() + self.request_interval;
+    }
+
+    /// Front cover of the release in the Cover Art Archive. It may not
+    /// exist, callers should treat a failed download as no cover.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn with_cover(&self, mut suggestion: TagSuggestion) -> TagSuggestion {
+        suggestion.cover_url = suggestion
+            .release_mbid
+            .as_ref()
+            .map(|id| format!("{}/release/{}/front-500", self.cover_art_url, id));
+        suggestion
+    }
+
+    /// Downloads a cover from [TagSuggestion::cover_url], [None] if the
+    /// release has no front cover
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn fetch_cover(&self, url: &str) -> Result<Option<Vec<u8>>> {
+        let resp = self.client.get(url).send().await?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(resp.error_for_status()?.bytes().await?.to_vec()))
+    }
+
+    /// Suggestions for `query` ordered by confidence. Fingerprint matches are
+    /// preferred, the search is only used when AcoustID knows nothing.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn lookup(&self, query: &TagQuery) -> Result<Vec<TagSuggestion>> {
+        let mut suggestions = vec![];
+
+        if let (Some(fingerprint), Some(duration)) = (&query.fingerprint, query.duration) {
+            if self.acoustid_key.is_some() {
+                let matches = self.acoustid_lookup(fingerprint, duration).await?;
+                for (recording_id, score) in matches.into_iter().take(MAX_RECORDINGS) {
+                    if let Some(suggestion) = self.recording(&recording_id).await? {
+                        suggestions.push(TagSuggestion {
+                            song_id: query.song_id.clone(),
+                            score,
+                            ..suggestion
+                        });
+                    }
+                }
+            }
+        }
+
+        if suggestions.is_empty() {
+            suggestions = self
+                .search(query)
+                .await?
+                .into_iter()
+                .map(|suggestion| TagSuggestion {
+                    song_id: query.song_id.clone(),
+                    ..suggestion
+                })
+                .collect();
+        }
+
+        Ok(suggestions)
+    }
+}
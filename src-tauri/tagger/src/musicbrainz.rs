@@ -0,0 +1,180 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use types::{
+    entities::{SuggestedArtist, TagSuggestion},
+    errors::Result,
+};
+
+use crate::{TagLookup, TagQuery};
+
+// Search results whose length differs by more seconds than this are another edit
+const DURATION_TOLERANCE: f64 = 5f64;
+
+const SEARCH_LIMIT: usize = 5;
+
+#[derive(Debug, Deserialize)]
+struct MbArtist {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbArtistCredit {
+    name: String,
+    #[serde(default)]
+    artist: Option<MbArtist>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRelease {
+    id: String,
+    title: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRecording {
+    id: String,
+    title: String,
+    /// In milliseconds
+    #[serde(default)]
+    length: Option<f64>,
+    /// Only set on search results, out of 100
+    #[serde(default)]
+    score: Option<f64>,
+    #[serde(default, rename = "first-release-date")]
+    first_release_date: Option<String>,
+    #[serde(default, rename = "artist-credit")]
+    artist_credit: Vec<MbArtistCredit>,
+    #[serde(default)]
+    releases: Vec<MbRelease>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbSearchResponse {
+    #[serde(default)]
+    recordings: Vec<MbRecording>,
+}
+
+impl From<MbRecording> for TagSuggestion {
+    #[tracing::instrument(level = "trace", skip(recording))]
+    fn from(mut recording: MbRecording) -> Self {
+        // Official releases first, then the earliest one
+        recording.releases.sort_by_key(|r| {
+            (
+                r.status.as_deref() != Some("Official"),
+                r.date.clone().filter(|d| !d.is_empty()).is_none(),
+                r.date.clone(),
+            )
+        });
+        let release = recording.releases.into_iter().next();
+        let date = recording
+            .first_release_date
+            .or_else(|| release.as_ref().and_then(|r| r.date.clone()));
+
+        TagSuggestion {
+            score: recording.score.map(|s| s / 100f64).unwrap_or(1f64),
+            recording_mbid: recording.id,
+            title: recording.title,
+            artists: recording
+                .artist_credit
+                .into_iter()
+                .map(|credit| SuggestedArtist {
+                    name: credit.name,
+                    mbid: credit.artist.map(|a| a.id),
+                })
+                .collect(),
+            album: release.as_ref().map(|r| r.title.clone()),
+            release_mbid: release.map(|r| r.id),
+            year: date.filter(|d| d.len() >= 4).map(|d| d[..4].to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Quotes a term for the Lucene syntax of MusicBrainz searches
+fn quote(term: &str) -> String {
+    format!("\"{}\"", term.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl TagLookup {
+    /// Tags of a MusicBrainz recording, [None] if it doesn't exist
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn recording(&self, id: &str) -> Result<Option<TagSuggestion>> {
+        self.throttle().await;
+        let resp = self
+            .client
+            .get(format!("{}/ws/2/recording/{}", self.musicbrainz_url, id))
+            .query(&[("inc", "artists+releases"), ("fmt", "json")])
+            .send()
+            .await?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body = resp.error_for_status()?.text().await?;
+        let recording: MbRecording = serde_json::from_str(&body)?;
+        Ok(Some(self.with_cover(recording.into())))
+    }
+
+    /// Recordings matching the current title and artists of a song
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn search(&self, query: &TagQuery) -> Result<Vec<TagSuggestion>> {
+        let Some(title) = query.title.as_ref().filter(|t| !t.trim().is_empty()) else {
+            return Ok(vec![]);
+        };
+
+        let mut terms = vec![format!("recording:{}", quote(title))];
+        if let Some(artist) = query.artists.first() {
+            terms.push(format!("artist:{}", quote(artist)));
+        }
+        if let Some(album) = &query.album {
+            terms.push(format!("release:{}", quote(album)));
+        }
+
+        self.throttle().await;
+        let body = self
+            .client
+            .get(format!("{}/ws/2/recording", self.musicbrainz_url))
+            .query(&[
+                ("query", terms.join(" AND ")),
+                ("limit", SEARCH_LIMIT.to_string()),
+                ("fmt", "json".into()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let resp: MbSearchResponse = serde_json::from_str(&body)?;
+
+        let matches_duration = |recording: &MbRecording| match (query.duration, recording.length) {
+            (Some(a), Some(b)) if a > 0f64 => (a - b / 1000f64).abs() <= DURATION_TOLERANCE,
+            _ => true,
+        };
+        Ok(resp
+            .recordings
+            .into_iter()
+            .filter(matches_duration)
+            .map(|recording| self.with_cover(recording.into()))
+            .collect())
+    }
+}
@@ -0,0 +1,166 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use test_utils::mock_server;
+use types::entities::SuggestedArtist;
+
+use crate::{TagLookup, TagQuery};
+
+fn lookup(base_url: &str, key: Option<&str>) -> TagLookup {
+    let mut lookup = TagLookup::new(base_url, key.map(String::from), base_url, base_url);
+    lookup.request_interval = Duration::ZERO;
+    lookup
+}
+
+fn query() -> TagQuery {
+    TagQuery {
+        song_id: "song".into(),
+        title: Some("track01".into()),
+        duration: Some(200f64),
+        fingerprint: Some("AQAAfingerprint".into()),
+        ..Default::default()
+    }
+}
+
+const RECORDING: &str = r#"{
+    "id": "rec-1",
+    "title": "Song",
+    "length": 200500,
+    "first-release-date": "1999-05-01",
+    "artist-credit": [
+        {"name": "Artist", "joinphrase": " feat. ", "artist": {"id": "artist-1", "name": "Artist"}},
+        {"name": "Guest", "artist": {"id": "artist-2", "name": "Guest"}}
+    ],
+    "releases": [
+        {"id": "bootleg", "title": "Live", "status": "Bootleg", "date": "1998"},
+        {"id": "compilation", "title": "Best of", "status": "Official", "date": "2005-01-01"},
+        {"id": "album", "title": "Album", "status": "Official", "date": "1999-05-01"}
+    ]
+}"#;
+
+#[tokio::test]
+async fn test_fingerprint_lookup() {
+    let base_url = mock_server(vec![
+        (
+            "/v2/lookup",
+            200,
+            r#"{"status": "ok", "results": [
+                {"id": "a", "score": 0.6, "recordings": [{"id": "missing"}]},
+                {"id": "b", "score": 0.95, "recordings": [{"id": "rec-1"}]}
+            ]}"#,
+        ),
+        ("/ws/2/recording/rec-1", 200, RECORDING),
+        ("/release/album/front-500", 200, "cover"),
+    ]);
+
+    let suggestions = lookup(&base_url, Some("key"))
+        .lookup(&query())
+        .await
+        .unwrap();
+    assert_eq!(suggestions.len(), 1);
+
+    let suggestion = &suggestions[0];
+    assert_eq!(suggestion.song_id, "song");
+    assert_eq!(suggestion.score, 0.95);
+    assert_eq!(suggestion.recording_mbid, "rec-1");
+    assert_eq!(suggestion.title, "Song");
+    assert_eq!(
+        suggestion.artists,
+        vec![
+            SuggestedArtist {
+                name: "Artist".into(),
+                mbid: Some("artist-1".into())
+            },
+            SuggestedArtist {
+                name: "Guest".into(),
+                mbid: Some("artist-2".into())
+            }
+        ]
+    );
+    assert_eq!(suggestion.album.as_deref(), Some("Album"));
+    assert_eq!(suggestion.release_mbid.as_deref(), Some("album"));
+    assert_eq!(suggestion.year.as_deref(), Some("1999"));
+    assert_eq!(
+        suggestion.cover_url,
+        Some(format!("{}/release/album/front-500", base_url))
+    );
+
+    let tagger = lookup(&base_url, None);
+    let cover = tagger
+        .fetch_cover(suggestion.cover_url.as_ref().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(cover.as_deref(), Some("cover".as_bytes()));
+    let missing = tagger
+        .fetch_cover(&format!("{}/release/missing/front-500", base_url))
+        .await
+        .unwrap();
+    assert_eq!(missing, None);
+}
+
+#[tokio::test]
+async fn test_search_fallback() {
+    let base_url = mock_server(vec![
+        ("/v2/lookup", 200, r#"{"status": "ok", "results": []}"#),
+        (
+            "/ws/2/recording",
+            200,
+            r#"{"recordings": [
+                {"id": "radio-edit", "title": "Track01", "length": 150000, "score": 100},
+                {"id": "rec-2", "title": "Track01", "length": 199000, "score": 80,
+                 "artist-credit": [{"name": "Band"}]}
+            ]}"#,
+        ),
+    ]);
+
+    // No fingerprint match, and a missing key skips AcoustID altogether
+    for key in [Some("key"), None] {
+        let suggestions = lookup(&base_url, key).lookup(&query()).await.unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].recording_mbid, "rec-2");
+        assert_eq!(suggestions[0].score, 0.8);
+        assert_eq!(suggestions[0].artists[0].mbid, None);
+        assert_eq!(suggestions[0].cover_url, None);
+    }
+
+    let untitled = TagQuery {
+        title: None,
+        fingerprint: None,
+        ..query()
+    };
+    assert!(lookup(&base_url, None)
+        .lookup(&untitled)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_acoustid_error() {
+    let base_url = mock_server(vec![(
+        "/v2/lookup",
+        200,
+        r#"{"status": "error", "error": {"code": 4, "message": "invalid API key"}}"#,
+    )]);
+
+    let err = lookup(&base_url, Some("bad"))
+        .lookup(&query())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid API key"));
+}
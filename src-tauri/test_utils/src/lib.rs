@@ -0,0 +1,132 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    ops::Deref,
+    path::{Path, PathBuf},
+    thread,
+};
+
+/// Empty directory under the system temp dir, removed with its contents on drop
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates `moosync_<name>` in the temp dir, replacing what a previous
+    /// run left behind. Tests running in parallel need different names.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("moosync_{}", name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Path of an SQLite database under the system temp dir. The database and
+/// its -shm and -wal files are removed on drop, so declare the guard before
+/// the connection using it.
+#[derive(Debug)]
+pub struct TempDb {
+    path: PathBuf,
+}
+
+impl TempDb {
+    /// Uses `moosync_<name>.db` in the temp dir, removing what a previous run
+    /// left behind. Tests running in parallel need different names.
+    pub fn new(name: &str) -> Self {
+        let db = Self {
+            path: std::env::temp_dir().join(format!("moosync_{}.db", name)),
+        };
+        db.remove();
+        db
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    fn remove(&self) {
+        for suffix in ["", "-shm", "-wal"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// Serves `routes` as (path, status, body) on a local port, returns the base url
+pub fn mock_server(routes: Vec<(&'static str, u16, &'static str)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request_line = String::new();
+            {
+                let mut reader = BufReader::new(&stream);
+                reader.read_line(&mut request_line).unwrap();
+                // Drain the headers
+                let mut header = String::new();
+                while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                    header.clear();
+                }
+            }
+
+            let target = request_line.split_whitespace().nth(1).unwrap_or("");
+            let path = target.split('?').next().unwrap_or("");
+            let (status, body) = routes
+                .iter()
+                .find(|(p, _, _)| *p == path)
+                .map(|(_, status, body)| (*status, *body))
+                .unwrap_or((404, "{}"));
+
+            let _ = stream.write_all(
+                format!(
+                    "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .as_bytes(),
+            );
+        }
+    });
+    base_url
+}
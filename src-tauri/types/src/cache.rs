@@ -31,4 +31,16 @@ pub struct CacheModel {
     pub url: String,
     pub blob: Vec<u8>,
     pub expires: i64,
+    pub last_accessed: i64,
+    pub size: i32,
+}
+
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+pub struct CacheStats {
+    pub entries: i64,
+    pub expired_entries: i64,
+    pub size: i64,
+    pub max_size: u64,
+    pub hits: u64,
+    pub misses: u64,
 }
@@ -22,5 +22,7 @@ diesel::table! {
         url -> Text,
         blob -> Binary,
         expires -> BigInt,
+        last_accessed -> BigInt,
+        size -> Integer,
     }
 }
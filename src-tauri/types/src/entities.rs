@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::path::Path;
+
 use bitcode::{Decode, Encode};
 #[cfg(feature = "core")]
 use diesel::{
@@ -29,8 +31,8 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "core")]
 use crate::schema::{
-    album_bridge, albums, analytics, artist_bridge, artists, genre_bridge, genres, playlist_bridge,
-    playlists,
+    album_bridge, albums, analytics, artist_bridge, artists, fingerprints, genre_bridge, genres,
+    playlist_bridge, playlists, queue_items, queue_state, track_mappings,
 };
 
 use super::{
@@ -400,6 +402,9 @@ pub struct QueryablePlaylist {
     pub extension: Option<String>,
     pub icon: Option<String>,
     pub library_item: Option<bool>,
+    /// Account of the provider the playlist was listed from
+    #[serde(default)]
+    pub account_id: Option<String>,
 }
 
 impl std::hash::Hash for QueryablePlaylist {
@@ -457,6 +462,23 @@ pub struct SearchResult {
     pub genres: Vec<QueryableGenre>,
 }
 
+/// Partial result of a federated search, emitted as each source completes
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct ProviderSearchResult {
+    pub term: String,
+    pub key: String,
+    pub result: SearchResult,
+}
+
+/// Fresh provider data which differs from the cached copy served earlier
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct ProviderDataUpdate {
+    pub key: String,
+    pub method: String,
+    pub request: String,
+    pub data: serde_json::Value,
+}
+
 #[derive(Deserialize, Serialize, Default, Clone, Debug)]
 #[cfg_attr(
     feature = "core",
@@ -470,3 +492,180 @@ pub struct Analytics {
     pub play_count: Option<i32>,
     pub play_time: Option<f64>,
 }
+
+/// Track on another provider which plays a song, e.g. the YouTube video used for a Spotify song
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+#[cfg_attr(
+    feature = "core",
+    derive(Insertable, Queryable, Identifiable, AsChangeset,)
+)]
+#[cfg_attr(feature = "core", diesel(table_name = track_mappings))]
+#[cfg_attr(feature = "core", diesel(primary_key(song_id, provider)))]
+pub struct TrackMapping {
+    pub song_id: String,
+    pub provider: String,
+    pub mapped_id: String,
+    pub score: f64,
+    pub user_override: bool,
+}
+
+/// Chromaprint fingerprint of a local song, as sent to AcoustID
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+#[cfg_attr(
+    feature = "core",
+    derive(Insertable, Queryable, Identifiable, AsChangeset,)
+)]
+#[cfg_attr(feature = "core", diesel(table_name = fingerprints))]
+#[cfg_attr(feature = "core", diesel(primary_key(song_id)))]
+pub struct SongFingerprint {
+    pub song_id: String,
+    /// Compressed and base64 encoded
+    pub fingerprint: String,
+    /// In seconds
+    pub duration: f64,
+}
+
+/// Song at `position` of the persisted play queue, stored as JSON since
+/// queued songs of providers aren't in the library
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+#[cfg_attr(feature = "core", derive(Insertable, Queryable, Identifiable))]
+#[cfg_attr(feature = "core", diesel(table_name = queue_items))]
+#[cfg_attr(feature = "core", diesel(primary_key(position)))]
+pub struct QueueItem {
+    pub position: i32,
+    pub song_id: String,
+    pub song: String,
+}
+
+/// Single row holding the position and modes of the persisted play queue
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "core",
+    derive(Insertable, Queryable, Identifiable, AsChangeset)
+)]
+#[cfg_attr(feature = "core", diesel(table_name = queue_state))]
+#[cfg_attr(feature = "core", diesel(primary_key(id)))]
+pub struct QueueStateRow {
+    pub id: i32,
+    pub current_index: i32,
+    pub repeat: String,
+    pub shuffle: bool,
+    pub original_order: Option<String>,
+}
+
+/// Totals across the whole library
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+pub struct LibraryStats {
+    pub songs: i64,
+    pub albums: i64,
+    pub artists: i64,
+    pub genres: i64,
+    pub playlists: i64,
+    /// In seconds
+    pub total_duration: f64,
+    /// In bytes
+    pub total_size: f64,
+}
+
+/// Playlist file formats that can be imported and exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistFormat {
+    M3u,
+    Pls,
+    Xspf,
+    Jspf,
+}
+
+impl PlaylistFormat {
+    pub const ALL: [PlaylistFormat; 4] = [Self::M3u, Self::Pls, Self::Xspf, Self::Jspf];
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::M3u => "M3U playlist",
+            Self::Pls => "PLS playlist",
+            Self::Xspf => "XSPF playlist",
+            Self::Jspf => "JSPF playlist",
+        }
+    }
+
+    /// The first extension is the one used when writing
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::M3u => &["m3u8", "m3u"],
+            Self::Pls => &["pls"],
+            Self::Xspf => &["xspf"],
+            Self::Jspf => &["jspf"],
+        }
+    }
+
+    /// Guesses the format from the file extension
+    #[tracing::instrument(level = "trace", skip())]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|f| f.extensions().contains(&extension.as_str()))
+    }
+}
+
+/// Outcome of importing a playlist file
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct PlaylistImportResult {
+    pub playlist_id: String,
+    pub songs: usize,
+    /// Entries that point to files that don't exist
+    pub unresolved: Vec<String>,
+}
+
+/// Songs that look like the same recording, grouped by how sure the match is.
+/// Each song is only reported in its most certain group.
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateReport {
+    /// Files with identical contents
+    pub exact: Vec<Vec<Song>>,
+    /// Songs with matching audio fingerprints, or the same title, artists
+    /// and album with about the same duration
+    pub likely: Vec<Vec<Song>>,
+    /// Same title and artists with about the same duration, e.g. a local
+    /// file and its YouTube upload
+    pub possible: Vec<Vec<Song>>,
+}
+
+/// Describes the contents of a library backup archive
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    /// Last database migration applied when the backup was made
+    pub schema_version: Option<String>,
+    /// Seconds since the unix epoch
+    pub created_at: u64,
+    pub platform: String,
+    /// Music folders configured on the machine the backup was made on
+    pub music_roots: Vec<String>,
+    pub thumbnail_dir: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq)]
+pub struct SuggestedArtist {
+    pub name: String,
+    pub mbid: Option<String>,
+}
+
+/// Tags of a MusicBrainz recording proposed for a song. Nothing is written
+/// until the user applies it.
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq)]
+pub struct TagSuggestion {
+    pub song_id: String,
+    /// Between 0 and 1, as reported by AcoustID or the search
+    pub score: f64,
+    pub recording_mbid: String,
+    pub title: String,
+    pub artists: Vec<SuggestedArtist>,
+    pub album: Option<String>,
+    pub release_mbid: Option<String>,
+    pub year: Option<String>,
+    pub cover_url: Option<String>,
+}
@@ -136,6 +136,9 @@ pub enum MoosyncError {
     #[cfg(feature = "core")]
     #[error("Transfer control to provider: {0}")]
     SwitchProviders(String),
+    #[cfg(feature = "core")]
+    #[error("Request cancelled")]
+    Cancelled,
     #[cfg_attr(feature = "core", error(transparent))]
     #[cfg(feature = "core")]
     HexError(#[from] FromHexError),
@@ -33,10 +33,10 @@ use crate::{
         extensions::{
             AccountLoginArgs, AddToPlaylistRequest, CustomRequestReturnType,
             ExtensionAccountDetail, ExtensionContextMenuItem, ExtensionDetail, ExtensionExtraEvent,
-            ExtensionExtraEventArgs, ExtensionUIRequest, PackageNameArgs,
-            PlaybackDetailsReturnType, PlaylistAndSongsReturnType, PlaylistReturnType,
-            PreferenceData, RecommendationsReturnType, SearchReturnType, SongReturnType,
-            SongsWithPageTokenReturnType,
+            ExtensionExtraEventArgs, ExtensionOAuthClient, ExtensionOAuthToken, ExtensionUIRequest,
+            PackageNameArgs, PlaybackDetailsReturnType, PlaylistAndSongsReturnType,
+            PlaylistReturnType, PreferenceData, RecommendationsReturnType, SearchReturnType,
+            SongReturnType, SongsWithPageTokenReturnType,
         },
         player_details::PlayerState,
     },
@@ -474,6 +474,11 @@ pub enum MainCommand {
     AddPlaylist(QueryablePlaylist),
     AddToPlaylist(AddToPlaylistRequest),
     RegisterOAuth(String),
+    RegisterOAuthClient(ExtensionOAuthClient),
+    // The package name of the extension, filled in by the extension runner
+    OAuthLogin(String),
+    GetOAuthToken(String),
+    OAuthSignout(String),
     OpenExternalUrl(String),
     UpdateAccounts(Option<String>),
     ExtensionsUpdated(),
@@ -500,6 +505,10 @@ pub enum MainCommandResponse {
     AddPlaylist(String),
     AddToPlaylist(bool),
     RegisterOAuth(bool),
+    RegisterOAuthClient(bool),
+    OAuthLogin(String),
+    GetOAuthToken(Option<ExtensionOAuthToken>),
+    OAuthSignout(bool),
     OpenExternalUrl(bool),
     UpdateAccounts(bool),
     ExtensionsUpdated(bool),
@@ -549,6 +558,18 @@ impl MainCommand {
                 ("addToPlaylist", serde_json::to_value(options)?)
             }
             MainCommand::RegisterOAuth(url) => ("registerOauth", Value::String(url.clone())),
+            MainCommand::RegisterOAuthClient(client) => {
+                ("registerOauthClient", serde_json::to_value(client)?)
+            }
+            MainCommand::OAuthLogin(package_name) => {
+                ("oauthLogin", Value::String(package_name.clone()))
+            }
+            MainCommand::GetOAuthToken(package_name) => {
+                ("getOauthToken", Value::String(package_name.clone()))
+            }
+            MainCommand::OAuthSignout(package_name) => {
+                ("oauthSignout", Value::String(package_name.clone()))
+            }
             MainCommand::OpenExternalUrl(url) => ("openExternal", Value::String(url.clone())),
             MainCommand::UpdateAccounts(key) => ("updateAccounts", serde_json::to_value(key)?),
             MainCommand::ExtensionsUpdated() => ("extensionsUpdated", Value::Null),
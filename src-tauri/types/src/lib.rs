@@ -21,6 +21,7 @@ pub mod canvaz;
 pub mod common;
 pub mod entities;
 pub mod errors;
+pub mod lyrics;
 
 #[cfg(not(feature = "extensions"))]
 pub mod mpris;
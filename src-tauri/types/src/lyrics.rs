@@ -0,0 +1,83 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+/// Lyrics of a song as returned by a lyrics source
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lyrics {
+    /// Key of the source the lyrics were fetched from
+    pub source: String,
+    pub plain: String,
+    /// Lyrics in LRC format, if the source provides timings
+    #[serde(default)]
+    pub synced: Option<String>,
+}
+
+/// A line of synced lyrics, sung from `time` seconds on
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncedLine {
+    pub time: f64,
+    pub text: String,
+}
+
+impl Lyrics {
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn is_empty(&self) -> bool {
+        let has_synced = self.synced.as_ref().is_some_and(|s| !s.trim().is_empty());
+        self.plain.trim().is_empty() && !has_synced
+    }
+
+    /// Lines of the LRC lyrics ordered by time. Metadata tags like [ar:...] are skipped
+    /// and lines with several timestamps are repeated for each of them.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn synced_lines(&self) -> Vec<SyncedLine> {
+        let Some(synced) = &self.synced else {
+            return vec![];
+        };
+
+        let mut lines = vec![];
+        for line in synced.lines() {
+            let mut rest = line.trim();
+            let mut times = vec![];
+            while let Some((tag, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+                let Some(time) = parse_timestamp(tag) else {
+                    break;
+                };
+                times.push(time);
+                rest = after;
+            }
+
+            for time in times {
+                lines.push(SyncedLine {
+                    time,
+                    text: rest.trim().to_string(),
+                });
+            }
+        }
+        lines.sort_by(|a, b| a.time.total_cmp(&b.time));
+        lines
+    }
+}
+
+/// Parses an LRC timestamp, mm:ss or mm:ss.xx, into seconds
+#[tracing::instrument(level = "trace", skip())]
+fn parse_timestamp(tag: &str) -> Option<f64> {
+    let (minutes, seconds) = tag.split_once(':')?;
+    let minutes = minutes.trim().parse::<u64>().ok()?;
+    let seconds = seconds.trim().replace(':', ".").parse::<f64>().ok()?;
+    Some(minutes as f64 * 60f64 + seconds)
+}
@@ -45,6 +45,7 @@ pub enum PreferenceTypes {
     TextField,
     InfoField,
     Dropdown,
+    Library,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +79,10 @@ pub struct PreferenceUIData {
     pub input_type: Option<InputType>,
     pub single: Option<bool>,
     pub items: Option<Vec<CheckboxItems>>,
+    /// Whether the items of a checkbox group can be reordered
+    pub orderable: Option<bool>,
+    /// Whether the value of a text input is stored encrypted
+    pub secure: Option<bool>,
     pub default: Option<Value>,
     pub mobile: Option<bool>,
 }
@@ -88,10 +88,13 @@ pub struct ProviderStatus {
     pub logged_in: bool,
     pub bg_color: String,
     pub account_id: String,
+    /// Whether requests of the provider are made as this account
+    #[serde(default)]
+    pub active: bool,
 }
 
 #[async_trait]
-pub trait GenericProvider: std::fmt::Debug + Send {
+pub trait GenericProvider: std::fmt::Debug + Send + Sync {
     async fn initialize(&mut self) -> Result<()>;
     fn key(&self) -> String;
     fn match_id(&self, id: String) -> bool;
@@ -102,6 +105,16 @@ pub trait GenericProvider: std::fmt::Debug + Send {
 
     async fn authorize(&mut self, code: String) -> Result<()>;
 
+    /// Renews the access token of an account, called shortly before it expires.
+    async fn refresh_login(&mut self, _account_id: String) -> Result<()> {
+        Ok(())
+    }
+
+    /// Makes requests go through another signed in account.
+    async fn set_active_account(&mut self, _account_id: String) -> Result<()> {
+        Err("Provider does not support multiple accounts".into())
+    }
+
     async fn fetch_user_playlists(
         &self,
         pagination: Pagination,
@@ -115,6 +128,12 @@ pub trait GenericProvider: std::fmt::Debug + Send {
 
     async fn search(&self, term: String) -> Result<SearchResult>;
 
+    /// Whether [GenericProvider::search] can currently return results.
+    /// Used to skip providers in federated searches.
+    fn can_search(&self) -> bool {
+        true
+    }
+
     async fn match_url(&self, url: String) -> Result<bool>;
     async fn playlist_from_url(&self, url: String) -> Result<QueryablePlaylist>;
     async fn song_from_url(&self, url: String) -> Result<Song>;
@@ -67,6 +67,7 @@ diesel::table! {
         show_in_library -> Nullable<Bool>,
         track_no -> Nullable<Double>,
         library_item -> Nullable<Bool>,
+        modified -> Nullable<Double>,
     }
 }
 
@@ -133,7 +134,44 @@ diesel::table! {
         playlist_path -> Nullable<Text>,
         extension -> Nullable<Text>,
         icon -> Nullable<Text>,
-        library_item -> Nullable<Bool>
+        library_item -> Nullable<Bool>,
+        account_id -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    track_mappings (song_id, provider) {
+        song_id -> Text,
+        provider -> Text,
+        mapped_id -> Text,
+        score -> Double,
+        user_override -> Bool,
+    }
+}
+
+diesel::table! {
+    fingerprints (song_id) {
+        song_id -> Text,
+        fingerprint -> Text,
+        duration -> Double,
+    }
+}
+
+diesel::table! {
+    queue_items (position) {
+        position -> Integer,
+        song_id -> Text,
+        song -> Text,
+    }
+}
+
+diesel::table! {
+    queue_state (id) {
+        id -> Integer,
+        current_index -> Integer,
+        repeat -> Text,
+        shuffle -> Bool,
+        original_order -> Nullable<Text>,
     }
 }
 
@@ -144,8 +182,12 @@ diesel::allow_tables_to_appear_in_same_query!(
     analytics,
     artist_bridge,
     artists,
+    fingerprints,
     genre_bridge,
     genres,
     playlist_bridge,
     playlists,
+    queue_items,
+    queue_state,
+    track_mappings,
 );
@@ -179,6 +179,8 @@ pub struct QueryableSong {
     pub show_in_library: Option<bool>,
     pub track_no: Option<f64>,
     pub library_item: Option<bool>,
+    /// When the file was last modified, in seconds since the unix epoch
+    pub modified: Option<f64>,
 }
 
 impl std::hash::Hash for QueryableSong {
@@ -220,6 +220,34 @@ pub struct AddToPlaylistRequest {
     pub songs: Vec<Song>,
 }
 
+/// OAuth client of an extension whose authorization code flow is run by the app.
+/// The app adds PKCE and state, exchanges the code and keeps and refreshes the tokens.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionOAuthClient {
+    /// Set by the extension runner, whatever the extension sent is replaced
+    #[serde(default)]
+    pub package_name: String,
+    pub auth_url: String,
+    pub token_url: String,
+    /// Deep link (moosync://...) or loopback url the provider redirects to
+    pub redirect_uri: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub extra_params: Vec<(String, String)>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionOAuthToken {
+    pub access_token: String,
+    /// Milliseconds since the epoch, 0 if the token doesn't expire
+    pub expires_at: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PreferenceData {
     pub key: String,
@@ -17,6 +17,7 @@
 pub mod extensions;
 pub mod player_details;
 pub mod song_details;
+pub mod tray;
 
 #[cfg(not(feature = "extensions"))]
 pub mod themes;
@@ -14,10 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use bitcode::{Decode, Encode};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::errors::MoosyncError;
+use crate::{errors::MoosyncError, songs::Song};
 
 #[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy, Encode, Decode)]
 #[serde(rename_all = "UPPERCASE")]
@@ -80,10 +82,21 @@ pub enum VolumeMode {
     PersistClamp,
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Encode, Decode)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Encode, Decode, Serialize, Deserialize)]
 pub enum RepeatModes {
     #[default]
     None,
     Once,
     Loop,
 }
+
+/// Play queue owned by the backend and mirrored by the UI
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueState {
+    /// Song ids in play order
+    pub song_queue: Vec<String>,
+    pub current_index: usize,
+    pub data: HashMap<String, Song>,
+    pub repeat: RepeatModes,
+    pub shuffle: bool,
+}
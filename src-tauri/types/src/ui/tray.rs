@@ -0,0 +1,57 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+/// Translated texts of the tray menu, supplied by the UI whenever the language changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrayLabels {
+    pub show_app: String,
+    pub play: String,
+    pub pause: String,
+    pub next: String,
+    pub prev: String,
+    pub repeat: String,
+    pub repeat_once: String,
+    pub repeat_always: String,
+    pub shuffle: String,
+    pub favorite: String,
+    pub recent_playlists: String,
+    pub nothing_playing: String,
+    pub unknown: String,
+    pub quit: String,
+}
+
+impl Default for TrayLabels {
+    fn default() -> Self {
+        Self {
+            show_app: "Show app".into(),
+            play: "Play".into(),
+            pause: "Pause".into(),
+            next: "Next".into(),
+            prev: "Prev".into(),
+            repeat: "Repeat".into(),
+            repeat_once: "Repeat once".into(),
+            repeat_always: "Repeat always".into(),
+            shuffle: "Shuffle".into(),
+            favorite: "Add to favorites".into(),
+            recent_playlists: "Recent playlists".into(),
+            nothing_playing: "Nothing playing".into(),
+            unknown: "Unknown".into(),
+            quit: "Quit".into(),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+const AUDIO_CODECS: &[&str] = &["opus", "mp4a", "vorbis", "ac-3", "ec-3"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioQuality {
+    #[default]
+    High,
+    DataSaver,
+}
+
+/// A single stream as listed in the streamingData of a video
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamFormat {
+    pub itag: u64,
+    #[serde(default)]
+    pub url: String,
+    pub mime_type: String,
+    #[serde(default)]
+    pub bitrate: u64,
+    #[serde(default)]
+    pub is_hls: bool,
+    #[serde(default)]
+    pub is_dash_mpd: bool,
+}
+
+/// Stream chosen for playback
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioFormat {
+    pub url: String,
+    pub mime_type: String,
+    pub container: Option<String>,
+    pub codec: Option<String>,
+    pub bitrate: u64,
+}
+
+impl StreamFormat {
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn codecs(&self) -> Vec<&str> {
+        self.mime_type
+            .split_once("codecs=")
+            .map(|(_, codecs)| {
+                codecs
+                    .trim_matches(|c: char| c == '"' || c.is_whitespace())
+                    .split(',')
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Base name of the audio codec, eg. "mp4a" for "mp4a.40.2"
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn audio_codec(&self) -> Option<String> {
+        self.codecs()
+            .into_iter()
+            .map(|c| c.split('.').next().unwrap_or(c).to_lowercase())
+            .find(|c| AUDIO_CODECS.contains(&c.as_str()))
+    }
+
+    /// Container of the stream, eg. "webm" for "audio/webm"
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn container(&self) -> Option<String> {
+        self.mime_type
+            .split(';')
+            .next()
+            .and_then(|m| m.split_once('/'))
+            .map(|(_, subtype)| subtype.trim().to_lowercase())
+            .filter(|c| !c.is_empty())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn is_audio_only(&self) -> bool {
+        self.mime_type.starts_with("audio/")
+    }
+}
+
+impl From<&rusty_ytdl::VideoFormat> for StreamFormat {
+    #[tracing::instrument(level = "trace", skip(value))]
+    fn from(value: &rusty_ytdl::VideoFormat) -> Self {
+        Self {
+            itag: value.itag,
+            url: value.url.clone(),
+            mime_type: format!(
+                "{}; codecs=\"{}\"",
+                value.mime_type.mime,
+                value.mime_type.codecs.join(", ")
+            ),
+            bitrate: value.bitrate,
+            is_hls: value.is_hls,
+            is_dash_mpd: value.is_dash_mpd,
+        }
+    }
+}
+
+impl From<&StreamFormat> for AudioFormat {
+    #[tracing::instrument(level = "trace", skip(value))]
+    fn from(value: &StreamFormat) -> Self {
+        Self {
+            url: value.url.clone(),
+            mime_type: value.mime_type.clone(),
+            container: value.container(),
+            codec: value.audio_codec(),
+            bitrate: value.bitrate,
+        }
+    }
+}
+
+/// Picks the stream to play from `formats`.
+///
+/// `codecs` lists the codecs the player can decode, most preferred first.
+/// Audio-only streams win over muxed ones, then codec preference decides
+/// and finally bitrate, highest or lowest depending on `quality`.
+/// Manifests (HLS / DASH) are only used when nothing else is available.
+#[tracing::instrument(level = "trace", skip(formats))]
+pub fn select_audio_format(
+    formats: &[StreamFormat],
+    codecs: &[&str],
+    quality: AudioQuality,
+) -> Option<AudioFormat> {
+    formats
+        .iter()
+        .filter(|f| !f.url.is_empty())
+        .filter_map(|f| {
+            let codec = f.audio_codec()?;
+            let codec_rank = codecs.iter().position(|c| *c == codec)?;
+            Some((f, codec_rank))
+        })
+        .max_by_key(|(f, codec_rank)| {
+            let bitrate = f.bitrate as i64;
+            (
+                !(f.is_hls || f.is_dash_mpd),
+                f.is_audio_only(),
+                std::cmp::Reverse(*codec_rank),
+                match quality {
+                    AudioQuality::High => bitrate,
+                    AudioQuality::DataSaver => -bitrate,
+                },
+            )
+        })
+        .map(|(f, _)| f.into())
+}
@@ -15,8 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use serde::{Deserialize, Serialize};
-
-use crate::types::Root;
+use serde_json::Value;
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Context {
@@ -24,6 +23,7 @@ pub struct Context {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Client {
     pub client_version: String,
     pub gl: String,
@@ -33,7 +33,7 @@ pub struct Client {
 }
 
 #[tracing::instrument(level = "trace", skip(body))]
-pub fn parse_body(body: &str) -> (Option<Root>, Option<String>, Context) {
+pub fn parse_body(body: &str) -> (Option<Value>, Option<String>, Context) {
     let json = json_after(body, "window[\"ytInitialData\"] = ")
         .or(json_after(body, "var ytInitialData = "));
     let api_key = between(body, "INNERTUBE_API_KEY\":\"", "\"").or(between(
@@ -68,7 +68,7 @@ pub fn parse_body(body: &str) -> (Option<Root>, Option<String>, Context) {
     // }
 
     (
-        json.map(|v| serde_json::from_str(v.as_str()).unwrap()),
+        json.and_then(|v| serde_json::from_str(v.as_str()).ok()),
         api_key,
         context,
     )
@@ -121,3 +121,93 @@ fn between(haystack: &str, left: &str, right: &str) -> Option<String> {
         None
     }
 }
+
+/// Collects every value stored under `key`, in document order.
+/// Matched values are not searched any further.
+#[tracing::instrument(level = "trace", skip(value, key))]
+pub fn find_all<'a>(value: &'a Value, key: &str) -> Vec<&'a Value> {
+    let mut ret = vec![];
+    collect(value, key, &mut ret);
+    ret
+}
+
+fn collect<'a>(value: &'a Value, key: &str, ret: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                if k == key {
+                    ret.push(v);
+                } else {
+                    collect(v, key, ret);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for v in items {
+                collect(v, key, ret);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Token of the last "load more" item in a page, if there is more to load
+#[tracing::instrument(level = "trace", skip(value))]
+pub fn next_continuation(value: &Value) -> Option<String> {
+    find_all(value, "continuationItemRenderer")
+        .iter()
+        .rev()
+        .find_map(|c| {
+            c.pointer("/continuationEndpoint/continuationCommand/token")
+                .and_then(|t| t.as_str())
+                .map(|t| t.to_string())
+        })
+}
+
+/// Reads a text field which is either a "simpleText" or a list of "runs"
+#[tracing::instrument(level = "trace", skip(value))]
+pub fn text(value: &Value) -> Option<String> {
+    if let Some(text) = value.get("simpleText").and_then(|t| t.as_str()) {
+        return Some(text.to_string());
+    }
+
+    let runs = value.get("runs")?.as_array()?;
+    Some(
+        runs.iter()
+            .filter_map(|r| r.get("text").and_then(|t| t.as_str()))
+            .collect::<String>(),
+    )
+}
+
+/// Parses durations in the form of "1:02:03" to seconds
+#[tracing::instrument(level = "trace", skip(duration))]
+pub fn parse_duration(duration: &str) -> Option<f64> {
+    duration.trim().split(':').try_fold(0f64, |acc, part| {
+        Some(acc * 60f64 + part.parse::<f64>().ok()?)
+    })
+}
+
+/// Thumbnail urls of a renderer, largest first
+#[tracing::instrument(level = "trace", skip(value))]
+pub fn thumbnails(value: &Value) -> Vec<String> {
+    let mut thumbnails = value
+        .get("thumbnails")
+        .and_then(|t| t.as_array())
+        .map(|t| {
+            t.iter()
+                .filter_map(|t| {
+                    let url = t.get("url")?.as_str()?;
+                    let width = t.get("width").and_then(|w| w.as_u64()).unwrap_or_default();
+                    let url = if url.starts_with("//") {
+                        format!("https:{}", url)
+                    } else {
+                        url.to_string()
+                    };
+                    Some((width, url))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    thumbnails.sort_by(|a, b| b.0.cmp(&a.0));
+    thumbnails.into_iter().map(|(_, url)| url).collect()
+}
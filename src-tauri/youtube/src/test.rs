@@ -14,9 +14,43 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use rusty_ytdl::VideoFormat;
+use serde_json::Value;
 use types::providers::generic::Pagination;
 
-use crate::youtube::YoutubeScraper;
+use crate::{
+    formats::{select_audio_format, AudioQuality, StreamFormat},
+    raw_parser::next_continuation,
+    types::ContinuationToken,
+    youtube::{append_search_page, YoutubeScraper},
+};
+
+/// Formats of a `VideoInfo` as rusty_ytdl parses them from the player response
+fn load_video_formats(fixture: &str) -> Vec<VideoFormat> {
+    let data: Value = serde_json::from_str(fixture).unwrap();
+    serde_json::from_value(data["formats"].clone()).unwrap()
+}
+
+fn load_formats(fixture: &str) -> Vec<StreamFormat> {
+    load_video_formats(fixture)
+        .iter()
+        .map(StreamFormat::from)
+        .collect()
+}
+
+// Stream urls carry the itag as a query parameter, manifests as a path segment
+fn itag(url: &str) -> Option<&str> {
+    let mut parts = url.split(['?', '&', '/']);
+    while let Some(part) = parts.next() {
+        if let Some(itag) = part.strip_prefix("itag=") {
+            return Some(itag);
+        }
+        if part == "itag" {
+            return parts.next();
+        }
+    }
+    None
+}
 
 #[tokio::test]
 async fn test_youtube_playlist_content() {
@@ -33,3 +67,143 @@ async fn test_youtube_playlist_content() {
     tracing::info!("res: {:?}", res);
     assert!(!res.songs.is_empty());
 }
+
+#[test]
+fn test_stream_format_from_video_format() {
+    let formats = load_video_formats(include_str!("fixtures/video_info.json"));
+    let by_itag = |itag: u64| StreamFormat::from(formats.iter().find(|f| f.itag == itag).unwrap());
+
+    let aac = by_itag(140);
+    assert_eq!(aac.mime_type, "audio/mp4; codecs=\"mp4a.40.2\"");
+    assert_eq!(aac.bitrate, 130483);
+    assert_eq!(aac.audio_codec().as_deref(), Some("mp4a"));
+    assert_eq!(aac.container().as_deref(), Some("mp4"));
+    assert!(aac.is_audio_only());
+    assert!(!aac.is_hls && !aac.is_dash_mpd);
+
+    let muxed = by_itag(18);
+    assert_eq!(
+        muxed.mime_type,
+        "video/mp4; codecs=\"avc1.42001E, mp4a.40.2\""
+    );
+    assert_eq!(muxed.audio_codec().as_deref(), Some("mp4a"));
+    assert!(!muxed.is_audio_only());
+
+    assert_eq!(by_itag(137).audio_codec(), None);
+    assert!(by_itag(250).url.is_empty());
+
+    let live = load_video_formats(include_str!("fixtures/video_info_live.json"));
+    let hls = StreamFormat::from(&live[0]);
+    assert!(hls.is_hls);
+    assert_eq!(hls.audio_codec().as_deref(), Some("mp4a"));
+    assert_eq!(hls.container().as_deref(), Some("ts"));
+}
+
+#[test]
+fn test_select_audio_format() {
+    let formats = load_formats(include_str!("fixtures/video_info.json"));
+
+    let best = select_audio_format(&formats, &["opus", "mp4a"], AudioQuality::High).unwrap();
+    assert_eq!(itag(&best.url), Some("251"));
+    assert_eq!(best.codec.as_deref(), Some("opus"));
+    assert_eq!(best.container.as_deref(), Some("webm"));
+    assert_eq!(best.bitrate, 141000);
+
+    let aac = select_audio_format(&formats, &["mp4a"], AudioQuality::High).unwrap();
+    assert_eq!(itag(&aac.url), Some("140"));
+    assert_eq!(aac.mime_type, "audio/mp4; codecs=\"mp4a.40.2\"");
+
+    // 250 would be the cheapest opus stream, but it has no url
+    let saver = select_audio_format(&formats, &["opus", "mp4a"], AudioQuality::DataSaver).unwrap();
+    assert_eq!(itag(&saver.url), Some("249"));
+
+    assert!(select_audio_format(&formats, &["vorbis"], AudioQuality::High).is_none());
+}
+
+#[test]
+fn test_select_audio_format_fallbacks() {
+    let formats = load_formats(include_str!("fixtures/video_info.json"));
+    let muxed = formats
+        .into_iter()
+        .filter(|f| !f.is_audio_only())
+        .collect::<Vec<_>>();
+    let best = select_audio_format(&muxed, &["opus", "mp4a"], AudioQuality::High).unwrap();
+    assert_eq!(itag(&best.url), Some("18"));
+
+    let live = load_formats(include_str!("fixtures/video_info_live.json"));
+    let best = select_audio_format(&live, &["mp4a"], AudioQuality::High).unwrap();
+    assert_eq!(itag(&best.url), Some("95"));
+    let saver = select_audio_format(&live, &["mp4a"], AudioQuality::DataSaver).unwrap();
+    assert_eq!(itag(&saver.url), Some("91"));
+}
+
+#[test]
+fn test_parse_playlist_continuation() {
+    let scraper = YoutubeScraper::default();
+    let data: Value =
+        serde_json::from_str(include_str!("fixtures/playlist_continuation.json")).unwrap();
+
+    let songs = scraper.parse_songs(&data);
+    assert_eq!(songs.len(), 2);
+    assert_eq!(songs[0].song.url.as_deref(), Some("dQw4w9WgXcQ"));
+    assert_eq!(songs[0].song.duration, Some(213f64));
+    assert!(songs[0]
+        .song
+        .song_cover_path_high
+        .as_deref()
+        .unwrap()
+        .ends_with("maxresdefault.jpg"));
+    assert_eq!(
+        songs[0].artists.as_ref().unwrap()[0].artist_id.as_deref(),
+        Some("youtube-artist:UCuAXFkgsw1L7xaCfnd5JJOw")
+    );
+    assert_eq!(songs[1].song.duration, Some(205f64));
+
+    let token = ContinuationToken {
+        continuation: next_continuation(&data),
+        ..Default::default()
+    };
+    assert_eq!(
+        token.continuation.as_deref(),
+        Some("4qmFsgJhEiRWTFBMTzBCYlZVS2h6bmRr")
+    );
+    assert_eq!(
+        ContinuationToken::decode(&token.encode().unwrap()).unwrap(),
+        token
+    );
+}
+
+#[test]
+fn test_parse_search_results() {
+    let scraper = YoutubeScraper::default();
+    let data: Value = serde_json::from_str(include_str!("fixtures/search_results.json")).unwrap();
+
+    let res = scraper.parse_search(&data);
+    assert_eq!(res.songs.len(), 1);
+    assert_eq!(res.songs[0].song.duration, Some(213f64));
+    assert_eq!(res.artists.len(), 1);
+    assert_eq!(
+        res.artists[0].artist_coverpath.as_deref(),
+        Some("https://yt3.ggpht.com/rick=s88")
+    );
+    assert_eq!(res.playlists.len(), 1);
+    assert_eq!(res.playlists[0].playlist_song_count, 1024f64);
+
+    assert_eq!(
+        next_continuation(&data).as_deref(),
+        Some("EpoDEhJyaWNrIGFzdGxleSBzb25ncw")
+    );
+}
+
+#[test]
+fn test_search_pages_deduped() {
+    let scraper = YoutubeScraper::default();
+    let data: Value = serde_json::from_str(include_str!("fixtures/search_results.json")).unwrap();
+
+    let mut res = scraper.parse_search(&data);
+    // A continuation repeating the first page adds nothing
+    append_search_page(&mut res, scraper.parse_search(&data));
+    assert_eq!(res.songs.len(), 1);
+    assert_eq!(res.artists.len(), 1);
+    assert_eq!(res.playlists.len(), 1);
+}
@@ -14,11 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use rusty_ytdl::{
-    search::{Channel, Playlist, SearchOptions, SearchType, YouTube},
-    VideoFormat,
-};
+use std::collections::HashSet;
 
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE};
+use serde_json::{json, Value};
 use types::{
     entities::{EntityInfo, QueryablePlaylist},
     errors::{MoosyncError, Result},
@@ -29,35 +28,86 @@ use types::{
     songs::{QueryableSong, Song, SongType},
 };
 
-use crate::types::PlaylistResponse;
+use crate::{
+    formats::{select_audio_format, AudioFormat, AudioQuality, StreamFormat},
+    raw_parser::{find_all, next_continuation, parse_body, parse_duration, text, thumbnails},
+    types::{ContinuationToken, PlaylistResponse, SearchResponse},
+};
+
+const YOUTUBE_URL: &str = "https://www.youtube.com";
+const INNERTUBE_URL: &str = "https://www.youtube.com/youtubei/v1";
+const USER_AGENT: &str =
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+
+/// Songs collected by [YoutubeScraper::search_yt], about as many as a single search used to return
+pub const SEARCH_RESULT_LIMIT: usize = 100;
 
 pub struct YoutubeScraper {
-    youtube: YouTube,
+    client: reqwest::Client,
 }
 
 impl Default for YoutubeScraper {
     #[tracing::instrument(level = "trace", skip())]
     fn default() -> YoutubeScraper {
+        let mut headers = HeaderMap::new();
+        // Skips the cookie consent page served in some regions
+        headers.insert(COOKIE, HeaderValue::from_static("CONSENT=YES+1"));
+
         YoutubeScraper {
-            youtube: YouTube::new().unwrap(),
+            client: reqwest::Client::builder()
+                .user_agent(USER_AGENT)
+                .default_headers(headers)
+                .build()
+                .unwrap(),
         }
     }
 }
 
 impl YoutubeScraper {
+    /// Parses a videoRenderer or playlistVideoRenderer
     #[tracing::instrument(level = "trace", skip(self, v))]
-    fn parse_song(&self, v: &rusty_ytdl::search::Video) -> Song {
-        Song {
+    pub(crate) fn parse_video(&self, v: &Value) -> Option<Song> {
+        let id = v.get("videoId")?.as_str()?;
+        let duration = v
+            .get("lengthSeconds")
+            .and_then(|l| l.as_str())
+            .and_then(|l| l.parse().ok())
+            .or_else(|| {
+                v.get("lengthText")
+                    .and_then(text)
+                    .and_then(|l| parse_duration(&l))
+            });
+        let covers = v.get("thumbnail").map(thumbnails).unwrap_or_default();
+
+        let channel = v
+            .get("ownerText")
+            .or(v.get("shortBylineText"))
+            .and_then(|o| o.pointer("/runs/0"));
+        let artists = channel.map(|c| {
+            vec![QueryableArtist {
+                artist_id: c
+                    .pointer("/navigationEndpoint/browseEndpoint/browseId")
+                    .and_then(|id| id.as_str())
+                    .map(|id| format!("youtube-artist:{}", id)),
+                artist_name: c
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .map(|t| t.to_string()),
+                ..Default::default()
+            }]
+        });
+
+        Some(Song {
             song: QueryableSong {
-                _id: Some(format!("youtube:{}", v.id.clone())),
+                _id: Some(format!("youtube:{}", id)),
                 deviceno: None,
-                title: Some(v.title.clone()),
-                duration: Some((v.duration / 1000) as f64),
+                title: v.get("title").and_then(text),
+                duration,
                 type_: SongType::YOUTUBE,
-                url: Some(v.id.clone()),
-                song_cover_path_high: v.thumbnails.first().map(|d| d.url.clone()),
-                song_cover_path_low: v.thumbnails.get(1).map(|d| d.url.clone()),
-                playback_url: Some(v.id.clone()),
+                url: Some(id.to_string()),
+                song_cover_path_high: covers.first().cloned(),
+                song_cover_path_low: covers.get(1).cloned(),
+                playback_url: Some(id.to_string()),
                 provider_extension: Some("youtube".into()),
                 ..Default::default()
             },
@@ -65,13 +115,9 @@ impl YoutubeScraper {
                 album_name: Some("Misc".to_string()),
                 ..Default::default()
             }),
-            artists: Some(vec![QueryableArtist {
-                artist_id: Some(format!("youtube-artist:{}", v.channel.id)),
-                artist_name: Some(v.channel.name.clone()),
-                ..Default::default()
-            }]),
+            artists: Some(artists.unwrap_or_default()),
             genre: Some(vec![]),
-        }
+        })
     }
 
     #[tracing::instrument(level = "trace", skip(self, v))]
@@ -104,52 +150,185 @@ impl YoutubeScraper {
         }
     }
 
+    /// Parses a playlistRenderer
     #[tracing::instrument(level = "trace", skip(self, playlist))]
-    fn parse_playlist(&self, playlist: &Playlist) -> QueryablePlaylist {
-        QueryablePlaylist {
-            playlist_id: Some(format!("youtube-playlist:{}", playlist.id)),
-            playlist_name: playlist.name.clone(),
-            playlist_coverpath: playlist.thumbnails.first().map(|v| v.url.clone()),
-            playlist_song_count: playlist.videos.capacity() as f64,
+    pub(crate) fn parse_playlist(&self, playlist: &Value) -> Option<QueryablePlaylist> {
+        let id = playlist.get("playlistId")?.as_str()?;
+        let covers = playlist
+            .pointer("/thumbnails/0")
+            .or(playlist.get("thumbnail"))
+            .map(thumbnails)
+            .unwrap_or_default();
+
+        Some(QueryablePlaylist {
+            playlist_id: Some(format!("youtube-playlist:{}", id)),
+            playlist_name: playlist.get("title").and_then(text).unwrap_or_default(),
+            playlist_coverpath: covers.first().cloned(),
+            playlist_song_count: playlist
+                .get("videoCount")
+                .and_then(|c| c.as_str())
+                .and_then(|c| c.replace(',', "").parse().ok())
+                .unwrap_or_default(),
             extension: Some("youtube".into()),
             ..Default::default()
-        }
+        })
     }
 
+    /// Parses a channelRenderer
     #[tracing::instrument(level = "trace", skip(self, artist))]
-    fn parse_artist(&self, artist: &Channel) -> QueryableArtist {
-        QueryableArtist {
-            artist_id: Some(format!("youtube-artist:{}", artist.id)),
-            artist_name: Some(artist.name.clone()),
+    pub(crate) fn parse_artist(&self, artist: &Value) -> Option<QueryableArtist> {
+        let id = artist.get("channelId")?.as_str()?;
+        let covers = artist.get("thumbnail").map(thumbnails).unwrap_or_default();
+
+        Some(QueryableArtist {
+            artist_id: Some(format!("youtube-artist:{}", id)),
+            artist_name: artist.get("title").and_then(text),
             artist_extra_info: Some(EntityInfo(format!(
                 r#"{{
                 "youtube": {{
                     "channel_id": "{}"
                 }}
             }}"#,
-                artist.id
+                id
             ))),
-            artist_coverpath: artist.icon.first().map(|v| v.url.clone()),
+            artist_coverpath: covers.first().cloned(),
             ..Default::default()
+        })
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, v))]
+    pub(crate) fn parse_songs(&self, v: &Value) -> Vec<Song> {
+        find_all(v, "playlistVideoRenderer")
+            .into_iter()
+            .chain(find_all(v, "videoRenderer"))
+            .filter_map(|v| self.parse_video(v))
+            .collect()
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, v))]
+    pub(crate) fn parse_search(&self, v: &Value) -> SearchResult {
+        let mut songs = self.parse_songs(v);
+        // Shelves may repeat videos already listed
+        let mut seen = HashSet::new();
+        songs.retain(|s| seen.insert(s.song._id.clone()));
+
+        SearchResult {
+            songs,
+            artists: find_all(v, "channelRenderer")
+                .into_iter()
+                .filter_map(|a| self.parse_artist(a))
+                .collect(),
+            playlists: find_all(v, "playlistRenderer")
+                .into_iter()
+                .filter_map(|p| self.parse_playlist(p))
+                .collect(),
+            albums: vec![],
+            genres: vec![],
         }
     }
 
-    #[tracing::instrument(level = "trace", skip(self, id))]
+    #[tracing::instrument(level = "trace", skip(self, url, query))]
+    async fn fetch_initial(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<(Value, Option<ContinuationToken>)> {
+        let body = self
+            .client
+            .get(url)
+            .query(query)
+            .query(&[("hl", "en")])
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        let (data, api_key, context) = parse_body(&body);
+        let data = data.ok_or(MoosyncError::String("No data found".into()))?;
+        let next = next_continuation(&data).map(|c| ContinuationToken {
+            continuation: Some(c),
+            api_key,
+            context,
+        });
+        Ok((data, next))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, endpoint, token))]
+    async fn fetch_continuation(
+        &self,
+        endpoint: &str,
+        token: ContinuationToken,
+    ) -> Result<(Value, Option<ContinuationToken>)> {
+        let mut url = format!("{}/{}?prettyPrint=false", INNERTUBE_URL, endpoint);
+        if let Some(api_key) = &token.api_key {
+            url.push_str(&format!("&key={}", api_key));
+        }
+
+        let body = json!({
+            "context": token.context,
+            "continuation": token.continuation,
+        });
+        let resp = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        let data: Value = serde_json::from_str(&resp)?;
+        let next = next_continuation(&data).map(|c| ContinuationToken {
+            continuation: Some(c),
+            ..token
+        });
+        Ok((data, next))
+    }
+
+    /// Fetches a page of a playlist. The first page is loaded when `pagination` carries no token,
+    /// following pages by passing the encoded [ContinuationToken] from the previous response.
+    #[tracing::instrument(level = "trace", skip(self, id, pagination))]
     pub async fn get_playlist_content(
         &self,
         id: String,
-        _: Pagination,
+        pagination: Pagination,
     ) -> Result<PlaylistResponse> {
-        let mut playlist = rusty_ytdl::search::Playlist::get(id, None).await?;
-        playlist.fetch(None).await;
-        let res = playlist.videos.iter().map(|v| self.parse_song(v)).collect();
+        let (data, next_page_token) = match &pagination.token {
+            Some(token) => {
+                self.fetch_continuation("browse", ContinuationToken::decode(token)?)
+                    .await?
+            }
+            None => {
+                self.fetch_initial(
+                    &format!("{}/playlist", YOUTUBE_URL),
+                    &[("list", id.as_str())],
+                )
+                .await?
+            }
+        };
 
         Ok(PlaylistResponse {
-            songs: res,
-            next_page_token: None,
+            songs: self.parse_songs(&data),
+            next_page_token,
         })
+    }
 
-        // Err(MoosyncError::String("No data found".to_string()))
+    /// Fetches a page of videos uploaded by a channel
+    #[tracing::instrument(level = "trace", skip(self, channel_id, pagination))]
+    pub async fn get_channel_uploads(
+        &self,
+        channel_id: String,
+        pagination: Pagination,
+    ) -> Result<PlaylistResponse> {
+        // Every channel has an auto-generated uploads playlist sharing its ID
+        let uploads_id = match channel_id.strip_prefix("UC") {
+            Some(id) => format!("UU{}", id),
+            None => return Err(format!("Invalid channel ID {}", channel_id).into()),
+        };
+        self.get_playlist_content(uploads_id, pagination).await
     }
 
     #[tracing::instrument(level = "trace", skip(self, id))]
@@ -159,64 +338,106 @@ impl YoutubeScraper {
         Ok(self.parse_video_info(&info))
     }
 
+    #[tracing::instrument(level = "trace", skip(self, query, pagination))]
+    pub async fn search_page(
+        &self,
+        query: impl Into<String>,
+        pagination: Pagination,
+    ) -> Result<SearchResponse> {
+        let query = query.into();
+        let (data, next_page_token) = match &pagination.token {
+            Some(token) => {
+                self.fetch_continuation("search", ContinuationToken::decode(token)?)
+                    .await?
+            }
+            None => {
+                self.fetch_initial(
+                    &format!("{}/results", YOUTUBE_URL),
+                    &[("search_query", query.as_str())],
+                )
+                .await?
+            }
+        };
+
+        Ok(SearchResponse {
+            result: self.parse_search(&data),
+            next_page_token,
+        })
+    }
+
+    /// Results for `query`, following continuations until [SEARCH_RESULT_LIMIT] songs are
+    /// collected or no pages are left. Use [search_page](Self::search_page) to page manually.
     #[tracing::instrument(level = "trace", skip(self, query))]
     pub async fn search_yt(&self, query: impl Into<String>) -> Result<SearchResult> {
-        let res = self
-            .youtube
-            .search(
-                query,
-                Some(&SearchOptions {
-                    limit: 100,
-                    search_type: SearchType::All,
-                    safe_search: false,
-                }),
-            )
-            .await?;
+        let query = query.into();
+        let mut ret = SearchResult::default();
+        let mut pagination = Pagination::new_token(None);
+        loop {
+            let page = self.search_page(query.as_str(), pagination.clone()).await?;
+            let is_empty = page.result.songs.is_empty()
+                && page.result.artists.is_empty()
+                && page.result.playlists.is_empty();
+
+            append_search_page(&mut ret, page.result);
 
-        let mut songs: Vec<Song> = vec![];
-        let mut playlists: Vec<QueryablePlaylist> = vec![];
-        let mut artists: Vec<QueryableArtist> = vec![];
-        for item in res.iter() {
-            match item {
-                rusty_ytdl::search::SearchResult::Video(v) => songs.push(self.parse_song(v)),
-                rusty_ytdl::search::SearchResult::Playlist(p) => {
-                    playlists.push(self.parse_playlist(p))
-                }
-                rusty_ytdl::search::SearchResult::Channel(a) => artists.push(self.parse_artist(a)),
+            if is_empty || ret.songs.len() >= SEARCH_RESULT_LIMIT {
+                break;
+            }
+            match page.next_page_token {
+                Some(token) => pagination = pagination.next_page_wtoken(Some(token.encode()?)),
+                None => break,
             }
         }
-        Ok(SearchResult {
-            songs,
-            artists,
-            playlists,
-            albums: vec![],
-            genres: vec![],
-        })
+
+        ret.songs.truncate(SEARCH_RESULT_LIMIT);
+        Ok(ret)
     }
 
     #[tracing::instrument(level = "trace", skip(self, id))]
-    pub async fn get_video_url(&self, id: String) -> Result<String> {
+    pub async fn get_video_url(
+        &self,
+        id: String,
+        codecs: &[&str],
+        quality: AudioQuality,
+    ) -> Result<AudioFormat> {
         let video = rusty_ytdl::Video::new(id)?;
         let info = video.get_info().await?;
 
-        let mut best_format: Option<VideoFormat> = None;
-        for format in info.formats {
-            if let Some(best) = best_format.clone() {
-                if format.has_audio
-                    && !format.is_dash_mpd
-                    && format.is_hls
-                    && format.audio_bitrate > best.audio_bitrate
-                {
-                    best_format = Some(format);
-                }
-            } else {
-                best_format = Some(format);
-            }
-        }
+        let formats = info
+            .formats
+            .iter()
+            .map(StreamFormat::from)
+            .collect::<Vec<_>>();
 
-        match best_format {
-            Some(f) => Ok(f.url.clone()),
+        match select_audio_format(&formats, codecs, quality) {
+            Some(f) => {
+                tracing::debug!("Selected format {} ({} bps)", f.mime_type, f.bitrate);
+                Ok(f)
+            }
             None => Err(MoosyncError::String("Unable to find URL".into())),
         }
     }
 }
+
+/// Appends the results of another page to `ret`. Continuations may repeat
+/// results of earlier pages, those are only kept once.
+#[tracing::instrument(level = "trace", skip(ret, page))]
+pub(crate) fn append_search_page(ret: &mut SearchResult, mut page: SearchResult) {
+    let mut seen: HashSet<_> = ret.songs.iter().map(|s| s.song._id.clone()).collect();
+    page.songs.retain(|s| seen.insert(s.song._id.clone()));
+    let mut seen: HashSet<_> = ret.artists.iter().map(|a| a.artist_id.clone()).collect();
+    page.artists.retain(|a| seen.insert(a.artist_id.clone()));
+    let mut seen: HashSet<_> = ret
+        .playlists
+        .iter()
+        .map(|p| p.playlist_id.clone())
+        .collect();
+    page.playlists
+        .retain(|p| seen.insert(p.playlist_id.clone()));
+
+    ret.songs.extend(page.songs);
+    ret.artists.extend(page.artists);
+    ret.playlists.extend(page.playlists);
+    ret.albums.extend(page.albums);
+    ret.genres.extend(page.genres);
+}
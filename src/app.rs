@@ -24,9 +24,14 @@ use crate::{
     players::librespot::LibrespotPlayer,
     store::ui_store::UiStore,
     utils::{
-        common::{emit, get_locale, listen_event},
-        invoke::{get_css, load_selective, load_theme, toggle_dev_tools},
+        common::{emit, get_locale, listen_event, listen_event_then},
+        db_utils::set_favorite,
+        invoke::{
+            get_css, load_selective, load_theme, media_listener_ready, set_tray_labels,
+            toggle_dev_tools,
+        },
         prefs::watch_preferences,
+        providers::page_scope,
     },
 };
 use leptos::{
@@ -39,13 +44,14 @@ use leptos_context_menu::provide_context_menu_state;
 use leptos_i18n::provide_i18n_context;
 use leptos_router::{
     components::{Outlet, ParentRoute, Redirect, Route, Router, Routes},
+    hooks::use_location,
     path,
 };
 use leptos_use::use_event_listener;
 use serde::Serialize;
 use types::{
-    preferences::CheckboxPreference, ui::extensions::ExtensionUIRequest,
-    ui::player_details::PlayerState,
+    entities::QueryablePlaylist, preferences::CheckboxPreference, songs::GetSongOptions,
+    ui::extensions::ExtensionUIRequest, ui::player_details::PlayerState, ui::tray::TrayLabels,
 };
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::spawn_local;
@@ -158,6 +164,21 @@ pub fn MainApp() -> impl IntoView {
         }
     });
 
+    // Results of provider requests made for the previous page are no longer needed
+    let pathname = use_location().pathname;
+    Effect::new(move |prev: Option<String>| {
+        pathname.track();
+        let scope = page_scope();
+        if let Some(prev) = prev.filter(|prev| *prev != scope) {
+            spawn_local(async move {
+                if let Err(e) = crate::utils::invoke::cancel_provider_requests(prev).await {
+                    tracing::error!("Failed to cancel provider requests {:?}", e);
+                }
+            });
+        }
+        scope
+    });
+
     let class = if is_mobile {
         "main-container main-container-mobile"
     } else {
@@ -285,6 +306,42 @@ pub fn App() -> impl IntoView {
 
     provide_i18n_context::<Locale>();
 
+    // The tray menu is built by the backend, which doesn't know the active language
+    let i18n = use_i18n();
+    Effect::new(move || {
+        let labels = TrayLabels {
+            show_app: i18n.get_keys().tray().show_app().build_string().into(),
+            play: i18n.get_keys().tray().play().build_string().into(),
+            pause: i18n.get_keys().tray().pause().build_string().into(),
+            next: i18n.get_keys().tray().next().build_string().into(),
+            prev: i18n.get_keys().tray().prev().build_string().into(),
+            repeat: i18n.get_keys().tray().repeat().build_string().into(),
+            repeat_once: i18n.get_keys().tray().repeat_once().build_string().into(),
+            repeat_always: i18n.get_keys().tray().repeat_always().build_string().into(),
+            shuffle: i18n.get_keys().tray().shuffle().build_string().into(),
+            favorite: i18n.get_keys().tray().favorite().build_string().into(),
+            recent_playlists: i18n
+                .get_keys()
+                .tray()
+                .recent_playlists()
+                .build_string()
+                .into(),
+            nothing_playing: i18n
+                .get_keys()
+                .tray()
+                .nothing_playing()
+                .build_string()
+                .into(),
+            unknown: i18n.get_keys().tray().unknown().build_string().into(),
+            quit: i18n.get_keys().tray().quit().build_string().into(),
+        };
+        spawn_local(async move {
+            if let Err(e) = set_tray_labels(labels).await {
+                tracing::error!("Failed to set tray labels {:?}", e);
+            }
+        });
+    });
+
     spawn_local(async move {
         let id = load_selective("themes.active_theme".into()).await.unwrap();
         handle_theme(serde_wasm_bindgen::from_value(id).unwrap());
@@ -334,13 +391,6 @@ pub fn App() -> impl IntoView {
                     .get_untracked();
                     send_reply(payload, data);
                 }
-                "getQueue" => {
-                    let data = create_read_slice(expect_context::<RwSignal<PlayerStore>>(), |p| {
-                        p.get_queue()
-                    })
-                    .get_untracked();
-                    send_reply(payload, data);
-                }
                 "getPlayerState" => {
                     let data = create_read_slice(expect_context::<RwSignal<PlayerStore>>(), |p| {
                         p.get_player_state()
@@ -380,27 +430,89 @@ pub fn App() -> impl IntoView {
         }
     });
 
-    let unlisten_mpris = listen_event("media_button_press", move |data: JsValue| {
-        let payload = js_sys::Reflect::get(&data, &JsValue::from_str("payload")).unwrap();
-        let (key, value): (i32, Option<f64>) = serde_wasm_bindgen::from_value(payload).unwrap();
-        let player_store: RwSignal<PlayerStore> = expect_context();
-
-        match key {
-            0 => player_store.update(|p| p.set_state(PlayerState::Playing)),
-            1 => player_store.update(|p| p.set_state(PlayerState::Paused)),
-            2 => player_store.update(|p| p.set_state(PlayerState::Stopped)),
-            6 => player_store.update(|p| p.next_song()),
-            7 => player_store.update(|p| p.prev_song()),
-            12 => player_store.update(|p| p.force_seek(value.unwrap_or_default())),
-            13 => player_store.update(|p| match p.get_player_state() {
-                PlayerState::Playing => p.set_state(PlayerState::Paused),
-                _ => p.set_state(PlayerState::Playing),
-            }),
-            15 => player_store.update(|p| p.set_volume(value.unwrap_or_default())),
-
-            _ => {}
-        }
-    });
+    let unlisten_mpris = listen_event_then(
+        "media_button_press",
+        move |data: JsValue| {
+            let payload = js_sys::Reflect::get(&data, &JsValue::from_str("payload")).unwrap();
+            let (key, value): (i32, Option<serde_json::Value>) =
+                serde_wasm_bindgen::from_value(payload).unwrap();
+            let number = value.as_ref().and_then(|v| v.as_f64());
+            let player_store: RwSignal<PlayerStore> = expect_context();
+
+            match key {
+                0 => player_store.update(|p| p.set_state(PlayerState::Playing)),
+                1 => player_store.update(|p| p.set_state(PlayerState::Paused)),
+                2 => player_store.update(|p| p.set_state(PlayerState::Stopped)),
+                6 => player_store.update(|p| p.next_song()),
+                7 => player_store.update(|p| p.prev_song()),
+                12 => player_store.update(|p| p.force_seek(number.unwrap_or_default())),
+                13 => player_store.update(|p| match p.get_player_state() {
+                    PlayerState::Playing => p.set_state(PlayerState::Paused),
+                    _ => p.set_state(PlayerState::Playing),
+                }),
+                15 => player_store.update(|p| p.set_volume(number.unwrap_or_default())),
+                19 => player_store.update(|p| {
+                    let new_time = (p.get_time() + number.unwrap_or_default()).max(0f64);
+                    p.force_seek(new_time)
+                }),
+                20 => {
+                    let shuffle = value.and_then(|v| v.as_bool()).unwrap_or_default();
+                    player_store.update(|p| p.set_shuffle(shuffle))
+                }
+                21 => {
+                    if let Some(mode) = value.and_then(|v| serde_json::from_value(v).ok()) {
+                        player_store.update(|p| p.set_repeat(mode))
+                    }
+                }
+                22 => {
+                    if let Some(index) = value.and_then(|v| v.as_u64()) {
+                        player_store.update(|p| p.change_index(index as usize, true))
+                    }
+                }
+                23 => {
+                    if let Some(song) = player_store.with_untracked(|p| p.get_current_song()) {
+                        spawn_local(async move {
+                            if let Err(e) = set_favorite(song, true).await {
+                                tracing::error!("Failed to add to favorites playlist {:?}", e);
+                            }
+                        });
+                    }
+                }
+                24 => {
+                    if let Some(id) = value.and_then(|v| v.as_str().map(|s| s.to_string())) {
+                        spawn_local(async move {
+                            let songs =
+                                crate::utils::invoke::get_songs_by_options(GetSongOptions {
+                                    playlist: Some(QueryablePlaylist {
+                                        playlist_id: Some(id),
+                                        ..Default::default()
+                                    }),
+                                    ..Default::default()
+                                })
+                                .await;
+                            match songs {
+                                Ok(songs) if !songs.is_empty() => {
+                                    player_store.update(|p| p.play_now_multiple(songs))
+                                }
+                                Ok(_) => {}
+                                Err(e) => tracing::error!("Failed to fetch playlist songs {:?}", e),
+                            }
+                        });
+                    }
+                }
+
+                _ => {}
+            }
+        },
+        // Events sent before now, e.g. playing the files the app was opened with
+        || {
+            spawn_local(async move {
+                if let Err(e) = media_listener_ready().await {
+                    tracing::error!("Failed to receive pending media events: {:?}", e);
+                }
+            });
+        },
+    );
 
     let window = window();
     if let Err(e) = window.add_event_listener_with_callback("beforeunload", &watch_prefs_unlisten) {
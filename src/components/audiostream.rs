@@ -263,6 +263,7 @@ impl PlayerHolder {
         } else {
             song.song.playback_url.clone().or(song.song.path.clone())
         };
+        let song_id = song.song._id.clone();
 
         let mut players = self.players.lock().await;
         let player = players.get_mut(pos).unwrap();
@@ -272,7 +273,7 @@ impl PlayerHolder {
         tracing::debug!("Active player: {}", player.key());
 
         let (resolver_tx, resolver_rx) = oneshot::channel();
-        player.load(src.unwrap(), autoplay, resolver_tx);
+        player.load(src.unwrap(), song_id, autoplay, resolver_tx);
 
         resolver_rx.await.expect("Load failed to resolve");
         tracing::debug!("Setting volume {}", current_volume);
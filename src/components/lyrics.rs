@@ -0,0 +1,91 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use leptos::{component, html::Pre, prelude::*, view, IntoView};
+use types::lyrics::Lyrics;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, ScrollBehavior, ScrollToOptions};
+
+use crate::store::player_store::PlayerStore;
+
+/// Plain lyrics, or synced lyrics following the current song with the sung line highlighted
+#[tracing::instrument(level = "trace", skip(lyrics))]
+#[component]
+pub fn LyricsView(#[prop()] lyrics: RwSignal<Option<Lyrics>>) -> impl IntoView {
+    let player_store = expect_context::<RwSignal<PlayerStore>>();
+    let current_time = create_read_slice(player_store, |p| p.get_time());
+
+    let lines = Memo::new(move |_| lyrics.get().map(|l| l.synced_lines()).unwrap_or_default());
+    let active_line = Memo::new(move |_| {
+        let time = current_time.get();
+        lines.with(|lines| lines.iter().rposition(|line| line.time <= time))
+    });
+
+    let pre_ref: NodeRef<Pre> = NodeRef::new();
+    Effect::new(move || {
+        let Some(index) = active_line.get() else {
+            return;
+        };
+        let Some(pre) = pre_ref.get_untracked() else {
+            return;
+        };
+        let Some(line) = pre
+            .children()
+            .item(index as u32)
+            .and_then(|l| l.dyn_into::<HtmlElement>().ok())
+        else {
+            return;
+        };
+
+        // Keep the sung line in the middle
+        let options = ScrollToOptions::new();
+        options.set_behavior(ScrollBehavior::Smooth);
+        options.set_top((line.offset_top() - pre.client_height() / 2) as f64);
+        pre.scroll_with_scroll_to_options(&options);
+    });
+
+    view! {
+        {move || {
+            let synced = lines.get();
+            if synced.is_empty() {
+                view! { <pre>{move || lyrics.get().map(|l| l.plain)}</pre> }.into_any()
+            } else {
+                view! {
+                    <pre class="synced-lyrics" node_ref=pre_ref>
+                        {synced
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, line)| {
+                                view! {
+                                    <span
+                                        class="lyrics-line"
+                                        class:lyrics-line-active=move || {
+                                            active_line.get() == Some(index)
+                                        }
+                                    >
+                                        {line.text}
+                                        "\n"
+                                    </span>
+                                }
+                            })
+                            .collect_view()}
+                    </pre>
+                }
+                    .into_any()
+            }
+        }}
+    }
+}
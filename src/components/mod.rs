@@ -19,6 +19,7 @@ pub mod audiostream;
 pub mod cardview;
 pub mod color_picker;
 pub mod low_img;
+pub mod lyrics;
 pub mod musicbar;
 pub mod musicbar_components;
 pub mod musicinfo;
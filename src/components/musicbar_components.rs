@@ -22,7 +22,7 @@ use leptos::{component, prelude::*, view, IntoView};
 use leptos_dom::helpers::TimeoutHandle;
 use leptos_use::{use_document, use_event_listener};
 use task::spawn_local;
-use types::entities::{QueryableArtist, QueryablePlaylist};
+use types::entities::QueryableArtist;
 use types::ui::player_details::PlayerState;
 
 use crate::components::artist_list::ArtistList;
@@ -38,6 +38,7 @@ use crate::icons::volume_icon::VolumeIcon;
 use crate::store::player_store::PlayerStore;
 use crate::store::ui_store::UiStore;
 use crate::utils::common::{format_duration, get_low_img};
+use crate::utils::db_utils::set_favorite;
 
 #[tracing::instrument(level = "trace", skip())]
 #[component]
@@ -124,8 +125,8 @@ pub fn Controls(
     let is_fav = RwSignal::new(false);
     let (repeat_mode, toggle_repeat) =
         create_slice(player_store, |p| p.get_repeat(), |p, _| p.toggle_repeat());
-    let is_shuffle = RwSignal::new(true);
-    let shuffle_queue = create_write_slice(player_store, |p, _| p.shuffle_queue());
+    let (is_shuffle, toggle_shuffle) =
+        create_slice(player_store, |p| p.get_shuffle(), |p, _| p.toggle_shuffle());
 
     let (current_time_sig, total_duration_sig) = if show_time {
         let current_time_sig =
@@ -178,25 +179,7 @@ pub fn Controls(
         let is_fav_val = is_fav.get();
         if let Some(current_song) = current_song {
             spawn_local(async move {
-                // Don't care if favorites playlist already exists
-                let _ = crate::utils::invoke::create_playlist(QueryablePlaylist {
-                    playlist_id: Some("favorite".into()),
-                    playlist_name: "Favorites".into(),
-                    playlist_coverpath: Some("favorites".into()),
-                    ..Default::default()
-                })
-                .await;
-
-                let res = if !is_fav_val {
-                    crate::utils::invoke::add_to_playlist("favorite".into(), vec![current_song])
-                        .await
-                } else {
-                    crate::utils::invoke::remove_from_playlist(
-                        "favorite".into(),
-                        vec![current_song.song._id.unwrap_or_default()],
-                    )
-                    .await
-                };
+                let res = set_favorite(current_song, !is_fav_val).await;
                 match res {
                     Err(e) => tracing::error!("Failed to add to favorites playlist {:?}", e),
                     Ok(_) => is_fav.set(!is_fav_val),
@@ -246,9 +229,9 @@ pub fn Controls(
             </div>
             <div class="col col-button shuffle-button">
                 <ShuffleIcon
-                    filled=is_shuffle.read_only()
+                    filled=is_shuffle
                     on:click=move |_| {
-                        shuffle_queue.set(());
+                        toggle_shuffle.set(());
                     }
                 />
             </div>
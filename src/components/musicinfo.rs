@@ -18,6 +18,7 @@ use leptos::html::Div;
 use leptos::task::spawn_local;
 use leptos::{component, prelude::*, view, IntoView};
 use leptos_virtual_scroller::VirtualScroller;
+use types::lyrics::Lyrics;
 use types::songs::{Song, SongType};
 use types::ui::player_details::PlayerState;
 use types::ui::song_details::SongDetailIcons;
@@ -25,6 +26,7 @@ use web_sys::{ScrollBehavior, ScrollToOptions};
 
 use crate::components::artist_list::ArtistList;
 use crate::components::audiostream::AudioStream;
+use crate::components::lyrics::LyricsView;
 use crate::components::musicbar_components::{Controls, Slider};
 use crate::icons::song_default_icon::SongDefaultIcon;
 use crate::modals::new_playlist_modal::PlaylistModalState;
@@ -201,7 +203,7 @@ pub fn MusicInfoMobile(
         }
     });
 
-    let selected_lyrics = RwSignal::new(None::<String>);
+    let selected_lyrics = RwSignal::new(None::<Lyrics>);
     Effect::new(move || {
         let song = current_song.get();
         spawn_local(async move {
@@ -371,7 +373,7 @@ pub fn MusicInfoMobile(
                                             <div class="lyrics-container">
                                                 <div class="lyrics-side-decoration"></div>
                                                 <div class="lyrics-background"></div>
-                                                <pre>{move || selected_lyrics.get()}</pre>
+                                                <LyricsView lyrics=selected_lyrics />
                                             </div>
                                         </div>
                                     </div>
@@ -30,8 +30,8 @@ use wasm_bindgen_futures::spawn_local;
 use crate::{
     i18n::use_i18n,
     icons::{
-        folder_icon::FolderIcon, new_theme_icon::NewThemeIcon, theme_view_icon::ThemeViewIcon,
-        tooltip::Tooltip,
+        expand_icon::ExpandIcon, folder_icon::FolderIcon, new_theme_icon::NewThemeIcon,
+        theme_view_icon::ThemeViewIcon, tooltip::Tooltip,
     },
     store::{
         modal_store::{ModalStore, Modals},
@@ -39,10 +39,13 @@ use crate::{
     },
     utils::{
         context_menu::{create_context_menu, ThemesContextMenu},
-        invoke::{get_installed_extensions, load_all_themes, remove_extension},
+        invoke::{
+            create_library_backup, find_duplicates, get_backup_manifest, get_installed_extensions,
+            load_all_themes, relocate_music_folder, remove_extension,
+        },
         prefs::{
-            load_selective, open_file_browser, open_file_browser_single, save_selective,
-            save_selective_number,
+            load_secure, load_selective, open_file_browser, open_file_browser_single, save_secure,
+            save_selective, save_selective_number,
         },
     },
 };
@@ -85,6 +88,30 @@ where
         })
     };
 
+    // Points the library at the new location of a moved music folder
+    let can_relocate = key == "music_paths";
+    let key_relocate = key.clone();
+    let relocate = move |from: String| {
+        let key = key_relocate.clone();
+        spawn_local(async move {
+            let folders = crate::utils::invoke::open_file_browser(true, false, vec![]).await;
+            let Some(to) = folders.ok().and_then(|f| f.into_iter().next()) else {
+                return;
+            };
+            if to.path == from {
+                return;
+            }
+
+            match relocate_music_folder(from, to.path).await {
+                Ok(moved) => {
+                    tracing::info!("Relocated {} songs", moved);
+                    load_selective(key, paths.write_only());
+                }
+                Err(e) => tracing::error!("Failed to relocate folder: {:?}", e),
+            }
+        });
+    };
+
     let key_clone = key.clone();
     Effect::new(move || {
         let value = paths.get();
@@ -124,12 +151,31 @@ where
                     each=move || paths.get()
                     key=|p| p.clone()
                     children=move |path: String| {
+                        let relocate = relocate.clone();
+                        let from = path.clone();
+                        let relocate_button = can_relocate
+                            .then(|| {
+                                view! {
+                                    <div class="col-auto align-self-center ml-auto mr-3">
+                                        <div
+                                            class="remove-button w-100"
+                                            on:click=move |_| relocate(from.clone())
+                                        >
+                                            {t!(i18n, settings.paths.relocate)}
+                                        </div>
+                                    </div>
+                                }
+                            });
                         view! {
                             <div class="row no-gutters mt-3 item w-100">
                                 <div class="col col-md-8 col-lg-9 align-self-center justify-content-start ml-3 no-checkbox-margin">
                                     <div class="item-text text-truncate">{path.clone()}</div>
                                 </div>
-                                <div class="col-auto align-self-center ml-auto">
+                                {relocate_button}
+                                <div
+                                    class="col-auto align-self-center"
+                                    class:ml-auto=!can_relocate
+                                >
                                     <div
                                         class="remove-button w-100"
                                         on:click=move |_| {
@@ -148,7 +194,10 @@ where
     }.into_any()
 }
 
-#[tracing::instrument(level = "trace", skip(key, title, tooltip, show_input, inp_type))]
+#[tracing::instrument(
+    level = "trace",
+    skip(key, title, tooltip, show_input, inp_type, secure)
+)]
 #[component()]
 pub fn InputPref<K, H, K1, H1>(
     #[prop()] key: String,
@@ -157,6 +206,9 @@ pub fn InputPref<K, H, K1, H1>(
     #[prop()] show_input: bool,
     #[prop()] inp_type: String,
     #[prop()] mobile: bool,
+    /// Reads and writes the value through the encrypted store
+    #[prop(optional)]
+    secure: bool,
 ) -> impl IntoView
 where
     K: Fn() -> H + Send + Sync + 'static,
@@ -174,7 +226,9 @@ where
     let pref_value = RwSignal::new(Default::default());
     let pref_key = key.clone();
 
-    if inp_type == "number" {
+    if secure {
+        load_secure(pref_key.clone(), pref_value.write_only());
+    } else if inp_type == "number" {
         let num_pref = RwSignal::new(f64::default());
         load_selective(pref_key.clone(), num_pref.write_only());
         Effect::new(move || {
@@ -193,7 +247,9 @@ where
         }
 
         tracing::debug!("Input type - {}", inp_type_clone);
-        if inp_type_clone.clone() == "number" {
+        if secure {
+            save_secure(pref_key.clone(), value);
+        } else if inp_type_clone.clone() == "number" {
             save_selective_number(pref_key.clone(), value);
         } else {
             save_selective(pref_key.clone(), value);
@@ -264,7 +320,7 @@ where
     }.into_any()
 }
 
-#[tracing::instrument(level = "trace", skip(key, title, tooltip, items, single))]
+#[tracing::instrument(level = "trace", skip(key, title, tooltip, items, single, orderable))]
 #[component()]
 pub fn CheckboxPref<K, H, K1, H1>(
     #[prop()] key: String,
@@ -273,6 +329,9 @@ pub fn CheckboxPref<K, H, K1, H1>(
     #[prop()] items: Vec<CheckboxItems>,
     #[prop()] single: bool,
     #[prop()] mobile: bool,
+    /// Items can be moved up and down, the saved value keeps their order
+    #[prop(optional)]
+    orderable: bool,
 ) -> impl IntoView
 where
     K: Fn() -> H + Send + Sync + 'static,
@@ -303,6 +362,52 @@ where
 
         save_selective(pref_key.clone(), value.clone());
     });
+
+    let items = StoredValue::new(items);
+    let ordered_items = move || {
+        let mut items = items.get_value();
+        if orderable {
+            let value = pref_value.get();
+            items.sort_by_key(|i| {
+                value
+                    .iter()
+                    .position(|v| v.key == i.key)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+        items
+    };
+    let move_item = move |key: String, up: bool| {
+        let ordered = untrack(ordered_items);
+        pref_value.update(|val| {
+            // Items never toggled aren't saved yet, they keep their place unchecked
+            let mut reordered = ordered
+                .iter()
+                .map(|i| {
+                    val.iter()
+                        .find(|v| v.key == i.key)
+                        .cloned()
+                        .unwrap_or(CheckboxPreference {
+                            key: i.key.clone(),
+                            enabled: false,
+                        })
+                })
+                .collect::<Vec<_>>();
+            let Some(pos) = reordered.iter().position(|v| v.key == key) else {
+                return;
+            };
+            let target = if up {
+                pos.checked_sub(1)
+            } else {
+                Some(pos + 1)
+            };
+            if let Some(target) = target.filter(|t| *t < reordered.len()) {
+                reordered.swap(pos, target);
+                *val = reordered;
+            }
+        });
+    };
+
     view! {
         <div class="container-fluid mt-4">
             <div class="row no-gutters">
@@ -315,11 +420,13 @@ where
             </div>
 
             <For
-                each=move || items.clone()
+                each=ordered_items
                 key=|p| p.key.clone()
                 children=move |item| {
                     let item_key_clone = item.key.clone();
                     let item_key_clone_1 = item_key_clone.clone();
+                    let item_key_up = item_key_clone.clone();
+                    let item_key_down = item_key_clone.clone();
                     let pref_key = pref_key_clone.clone();
                     view! {
                         <div class="row no-gutters item w-100 flex-nowrap">
@@ -386,6 +493,25 @@ where
                             <div class="col-md-8 col-lg-9 col align-self-center ml-3 justify-content-start">
                                 <div class="item-text text-truncate">{item.title}</div>
                             </div>
+                            {orderable
+                                .then(move || {
+                                    view! {
+                                        <div class="col-auto align-self-center d-flex order-buttons">
+                                            <div
+                                                class="order-up"
+                                                on:click=move |_| move_item(item_key_up.clone(), true)
+                                            >
+                                                <ExpandIcon />
+                                            </div>
+                                            <div
+                                                class="order-down ml-2"
+                                                on:click=move |_| move_item(item_key_down.clone(), false)
+                                            >
+                                                <ExpandIcon />
+                                            </div>
+                                        </div>
+                                    }
+                                })}
                         </div>
                     }
                 }
@@ -659,6 +785,7 @@ where
                                                     title=|| view! { title }
                                                     tooltip=|| view! { tooltip }
                                                     show_input=true
+                                                    secure=preference.secure.unwrap_or_default()
                                                     inp_type=serde_wasm_bindgen::to_value(
                                                             &preference.input_type,
                                                         )
@@ -691,6 +818,7 @@ where
                                                     tooltip=|| view! { tooltip }
                                                     items=preference.items.unwrap_or_default()
                                                     single=preference.single.unwrap_or_default()
+                                                    orderable=preference.orderable.unwrap_or_default()
                                                     mobile=true
                                                 />
                                             }
@@ -708,7 +836,8 @@ where
                                                 .into_any()
                                         }
                                         types::preferences::PreferenceTypes::ThemeSelector
-                                        | types::preferences::PreferenceTypes::Extensions => {
+                                        | types::preferences::PreferenceTypes::Extensions
+                                        | types::preferences::PreferenceTypes::Library => {
                                             ().into_any()
                                         }
                                         types::preferences::PreferenceTypes::ButtonGroup
@@ -823,3 +952,108 @@ where
     }
     .into_any()
 }
+
+/// Backing up and restoring the library
+#[tracing::instrument(level = "trace", skip(title, tooltip))]
+#[component]
+pub fn LibraryPref<K, H, K1, H1>(
+    #[prop()] title: K,
+    #[prop()] tooltip: K1,
+    #[prop()] mobile: bool,
+) -> impl IntoView
+where
+    K: Fn() -> H + Send + Sync + 'static,
+    H: IntoView + Copy + 'static,
+    K1: Fn() -> H1 + Send + Sync + 'static,
+    H1: IntoView + Copy + 'static,
+{
+    let ui_store = expect_context::<RwSignal<UiStore>>();
+    let is_mobile = create_read_slice(ui_store, |u| u.get_is_mobile()).get();
+    if is_mobile && !mobile {
+        return ().into_any();
+    }
+
+    let i18n = use_i18n();
+    let modal_store = expect_context::<RwSignal<ModalStore>>();
+    let backup_created = RwSignal::new(false);
+
+    let create_backup = move |_| {
+        spawn_local(async move {
+            match create_library_backup().await {
+                Ok(_) => backup_created.set(true),
+                Err(e) => tracing::error!("Failed to create backup: {:?}", e),
+            }
+        });
+    };
+
+    let restore_backup = move |_| {
+        spawn_local(async move {
+            let files = crate::utils::invoke::open_file_browser(
+                false,
+                false,
+                vec![DialogFilter {
+                    name: "Moosync backup".into(),
+                    extensions: vec!["zip".into()],
+                }],
+            )
+            .await;
+            let Some(file) = files.ok().and_then(|f| f.into_iter().next()) else {
+                return;
+            };
+
+            match get_backup_manifest(file.path.clone()).await {
+                Ok(manifest) => modal_store.update(|m| {
+                    m.set_active_modal(Modals::RestoreBackupModal(file.path, manifest))
+                }),
+                Err(e) => tracing::error!("Failed to read backup: {:?}", e),
+            }
+        });
+    };
+
+    let show_duplicates = move |_| {
+        spawn_local(async move {
+            match find_duplicates().await {
+                Ok(report) => {
+                    modal_store.update(|m| m.set_active_modal(Modals::DuplicatesModal(report)))
+                }
+                Err(e) => tracing::error!("Failed to find duplicates: {:?}", e),
+            }
+        });
+    };
+
+    view! {
+        <div class="container-fluid mt-4">
+            <div class="row no-gutters">
+                <div class="col-auto align-self-center title d-flex preference-title">
+                    {title()}
+                </div>
+                <div class="col-auto ml-2">
+                    <Tooltip>{tooltip()}</Tooltip>
+                </div>
+            </div>
+            <div class="row no-gutters mt-2 library-actions">
+                <div class="col-auto mr-4">
+                    <div class="add-directories-button" on:click=create_backup>
+                        {t!(i18n, settings.system.library.create_backup)}
+                    </div>
+                </div>
+                <div class="col-auto mr-4">
+                    <div class="add-directories-button" on:click=restore_backup>
+                        {t!(i18n, settings.system.library.restore_backup)}
+                    </div>
+                </div>
+                <div class="col-auto mr-4">
+                    <div class="add-directories-button" on:click=show_duplicates>
+                        {t!(i18n, settings.system.library.find_duplicates)}
+                    </div>
+                </div>
+                <Show when=move || backup_created.get()>
+                    <div class="col-auto file-picker-text">
+                        {t!(i18n, settings.system.library.backup_created)}
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+    .into_any()
+}
@@ -32,6 +32,7 @@ use types::{
         GetEntityOptions, QueryableAlbum, QueryableArtist, QueryableGenre, QueryablePlaylist,
     },
     errors::Result,
+    providers::generic::ProviderStatus,
     songs::{GetSongOptions, SearchableSong, Song},
 };
 use wasm_bindgen_futures::spawn_local;
@@ -40,8 +41,8 @@ use web_sys::SubmitEvent;
 use crate::{
     components::low_img::LowImg,
     icons::{
-        next_icon::NextIcon, person_icon::PersonIcon, prev_icon::PrevIcon, search_icon::SearchIcon,
-        settings_icon::SettingsIcon,
+        cross_icon::CrossIcon, next_icon::NextIcon, person_icon::PersonIcon, prev_icon::PrevIcon,
+        search_icon::SearchIcon, settings_icon::SettingsIcon,
     },
     store::{
         modal_store::{ModalStore, Modals},
@@ -117,14 +118,39 @@ pub fn Accounts() -> impl IntoView {
     let statuses = provider_store.get_all_statuses();
 
     let modal_store = expect_context::<RwSignal<ModalStore>>();
-    let show_login_modal = move |key: String, name: String, account_id: String, logged_in: bool| {
-        if logged_in {
+    let show_login_modal = move |status: ProviderStatus| {
+        let ProviderStatus {
+            key,
+            name,
+            account_id,
+            logged_in,
+            active,
+            ..
+        } = status;
+        if logged_in && !active {
+            spawn_local(async move {
+                if let Err(e) = crate::utils::invoke::switch_account(key, account_id).await {
+                    tracing::error!("Failed to switch account: {:?}", e);
+                }
+            });
+        } else if logged_in {
             modal_store.update(|m| m.set_active_modal(Modals::SignoutModal(key, name, account_id)))
         } else {
             modal_store.update(|m| m.set_active_modal(Modals::LoginModal(key, name, account_id)))
         }
     };
 
+    // Signs out any account, not just the active one
+    let show_signout_modal = move |status: ProviderStatus| {
+        modal_store.update(|m| {
+            m.set_active_modal(Modals::SignoutModal(
+                status.key,
+                status.user_name.unwrap_or(status.name),
+                status.account_id,
+            ))
+        })
+    };
+
     let target = NodeRef::new();
     let _ = on_click_outside(target, move |_| {
         if show_accounts_popover.get_untracked() {
@@ -147,11 +173,14 @@ pub fn Accounts() -> impl IntoView {
                                     binding
                                         .into_iter()
                                         .map(|status| {
-                                            let key = status.key.clone();
                                             let (title_out, title_in) = if status.logged_in {
                                                 (
                                                     status.user_name.clone().unwrap_or_default(),
-                                                    i18n.get_keys().accounts().sign_out().build_string().into(),
+                                                    if status.active {
+                                                        i18n.get_keys().accounts().sign_out().build_string().into()
+                                                    } else {
+                                                        i18n.get_keys().accounts().switch().build_string().into()
+                                                    },
                                                 )
                                             } else {
                                                 (
@@ -160,6 +189,8 @@ pub fn Accounts() -> impl IntoView {
                                                 )
                                             };
                                             let title = RwSignal::new(title_out.clone());
+                                            let clicked_status = status.clone();
+                                            let signout_status = status.clone();
                                             view! {
                                                 <div
                                                     class="button-bg d-flex ripple w-100"
@@ -169,12 +200,8 @@ pub fn Accounts() -> impl IntoView {
                                                     on:mouseout=move |_| {
                                                         title.set(title_out.clone());
                                                     }
-                                                    on:click=move |_| show_login_modal(
-                                                        key.clone(),
-                                                        status.name.clone(),
-                                                        status.account_id.clone(),
-                                                        status.logged_in,
-                                                    )
+                                                    class:active-account=status.active
+                                                    on:click=move |_| show_login_modal(clicked_status.clone())
                                                 >
 
                                                     <div
@@ -194,6 +221,27 @@ pub fn Accounts() -> impl IntoView {
                                                         <div class="title-wrapper flex-grow-1 my-auto text-truncate">
                                                             {title}
                                                         </div>
+
+                                                        {status
+                                                            .logged_in
+                                                            .then(move || {
+                                                                view! {
+                                                                    <div
+                                                                        class="signout-button my-auto"
+                                                                        title=i18n
+                                                                            .get_keys()
+                                                                            .accounts()
+                                                                            .sign_out()
+                                                                            .build_string()
+                                                                        on:click=move |ev| {
+                                                                            ev.stop_propagation();
+                                                                            show_signout_modal(signout_status.clone());
+                                                                        }
+                                                                    >
+                                                                        <CrossIcon />
+                                                                    </div>
+                                                                }
+                                                            })}
                                                     </div>
                                                 </div>
                                             }
@@ -0,0 +1,156 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use leptos::{component, prelude::*, task::spawn_local, view, IntoView};
+use leptos_i18n::t;
+use types::{entities::DuplicateReport, songs::Song};
+
+use crate::{
+    i18n::use_i18n,
+    modals::common::GenericModal,
+    store::modal_store::ModalStore,
+    utils::{common::format_duration, invoke::merge_songs},
+};
+
+#[derive(Clone, Copy)]
+enum Certainty {
+    Exact,
+    Likely,
+    Possible,
+}
+
+/// Lists groups of duplicate songs and merges each group into the song the
+/// user keeps
+#[tracing::instrument(level = "trace", skip(report))]
+#[component]
+pub fn DuplicatesModal(#[prop()] report: DuplicateReport) -> impl IntoView {
+    let i18n = use_i18n();
+    let modal_store: RwSignal<ModalStore> = expect_context();
+    let close_modal = move |_| modal_store.update(|m| m.clear_active_modal());
+
+    let groups = report
+        .exact
+        .into_iter()
+        .map(|g| (Certainty::Exact, g))
+        .chain(report.likely.into_iter().map(|g| (Certainty::Likely, g)))
+        .chain(
+            report
+                .possible
+                .into_iter()
+                .map(|g| (Certainty::Possible, g)),
+        )
+        .collect::<Vec<_>>();
+    let is_empty = groups.is_empty();
+
+    // Indices of the groups merged so far
+    let merged = RwSignal::new(Vec::<usize>::new());
+    let merge = move |index: usize, keep: String, duplicates: Vec<String>| {
+        spawn_local(async move {
+            match merge_songs(keep, duplicates).await {
+                Ok(_) => merged.update(|m| m.push(index)),
+                Err(e) => tracing::error!("Failed to merge songs: {:?}", e),
+            }
+        });
+    };
+
+    let song_view = move |index: usize, song: Song, ids: Vec<String>| {
+        let keep = song.song._id.clone().unwrap_or_default();
+        let others = ids.into_iter().filter(|id| *id != keep).collect::<Vec<_>>();
+        let location = song.song.path.clone().or(song.song.url.clone());
+        let duration = song.song.duration.map(format_duration).unwrap_or_default();
+        view! {
+            <div class="row no-gutters d-flex align-items-center mb-3">
+                <div class="col text-truncate">
+                    <div class="song-title text-truncate">{song.song.title.clone()}</div>
+                    <div class="song-subtitle text-truncate">{location}</div>
+                    <div class="song-subtitle text-truncate">{duration}</div>
+                </div>
+                <div class="col-auto">
+                    <button
+                        class="btn btn-secondary confirm-button"
+                        type="button"
+                        on:click=move |_| merge(index, keep.clone(), others.clone())
+                    >
+                        {t!(i18n, duplicates.keep)}
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <GenericModal size=move || "modal-lg".into()>
+            <div class="container-fluid p-0 mt-4">
+                <div class="row no-gutters d-flex">
+                    <div class="col">
+                        <h4>{t!(i18n, duplicates.title)}</h4>
+                        <h6 class="mt-3">
+                            {if is_empty {
+                                t!(i18n, duplicates.empty).into_any()
+                            } else {
+                                t!(i18n, duplicates.hint).into_any()
+                            }}
+                        </h6>
+                    </div>
+                </div>
+                <div class="row no-gutters mt-3 duplicate-groups">
+                    <div class="col">
+                        {groups
+                            .into_iter()
+                            .enumerate()
+                            .map(move |(index, (certainty, songs))| {
+                                let ids = songs
+                                    .iter()
+                                    .filter_map(|s| s.song._id.clone())
+                                    .collect::<Vec<_>>();
+                                move || {
+                                    if merged.with(|m| m.contains(&index)) {
+                                        return ().into_any();
+                                    }
+                                    view! {
+                                        <h6 class="mt-3 mb-2">
+                                            {match certainty {
+                                                Certainty::Exact => t!(i18n, duplicates.exact).into_any(),
+                                                Certainty::Likely => t!(i18n, duplicates.likely).into_any(),
+                                                Certainty::Possible => {
+                                                    t!(i18n, duplicates.possible).into_any()
+                                                }
+                                            }}
+                                        </h6>
+                                        {songs
+                                            .iter()
+                                            .map(|song| song_view(index, song.clone(), ids.clone()))
+                                            .collect_view()}
+                                    }
+                                        .into_any()
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+                <div class="row row-cols-auto mt-3 mr-4">
+                    <button
+                        on:click=close_modal
+                        class="btn btn-secondary confirm-button ml-auto"
+                        type="button"
+                    >
+                        {t!(i18n, buttons.close)}
+                    </button>
+                </div>
+            </div>
+        </GenericModal>
+    }
+}
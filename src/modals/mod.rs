@@ -16,9 +16,14 @@
 
 pub mod common;
 pub mod discover_extensions;
+pub mod duplicates_modal;
 pub mod login_modal;
 pub mod modal_manager;
 pub mod new_playlist_modal;
 pub mod new_theme_modal;
+pub mod playlist_import_modal;
+pub mod restore_backup_modal;
 pub mod signout_modal;
 pub mod song_from_url_modal;
+pub mod tag_suggestions_modal;
+pub mod track_match_modal;
@@ -18,9 +18,12 @@ use leptos::{component, prelude::*, view, IntoView};
 
 use crate::{
     modals::{
-        discover_extensions::DiscoverExtensionsModal, login_modal::LoginModal,
-        new_playlist_modal::NewPlaylistModal, new_theme_modal::NewThemeModal,
-        signout_modal::SignoutModal, song_from_url_modal::SongFromUrlModal,
+        discover_extensions::DiscoverExtensionsModal, duplicates_modal::DuplicatesModal,
+        login_modal::LoginModal, new_playlist_modal::NewPlaylistModal,
+        new_theme_modal::NewThemeModal, playlist_import_modal::PlaylistImportModal,
+        restore_backup_modal::RestoreBackupModal, signout_modal::SignoutModal,
+        song_from_url_modal::SongFromUrlModal, tag_suggestions_modal::TagSuggestionsModal,
+        track_match_modal::TrackMatchModal,
     },
     store::modal_store::{ModalStore, Modals},
 };
@@ -55,6 +58,21 @@ pub fn ModalManager() -> impl IntoView {
                     Modals::ThemeModal(initial_state) => {
                         view! { <NewThemeModal initial_state=initial_state /> }.into_any()
                     }
+                    Modals::TagSuggestionsModal(song) => {
+                        view! { <TagSuggestionsModal song=song /> }.into_any()
+                    }
+                    Modals::TrackMatchModal(song) => {
+                        view! { <TrackMatchModal song=song /> }.into_any()
+                    }
+                    Modals::PlaylistImportModal(result) => {
+                        view! { <PlaylistImportModal result=result /> }.into_any()
+                    }
+                    Modals::RestoreBackupModal(path, manifest) => {
+                        view! { <RestoreBackupModal path=path manifest=manifest /> }.into_any()
+                    }
+                    Modals::DuplicatesModal(report) => {
+                        view! { <DuplicatesModal report=report /> }.into_any()
+                    }
                 }
             }}
 
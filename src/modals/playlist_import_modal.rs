@@ -0,0 +1,66 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use leptos::{component, prelude::*, view, IntoView};
+use leptos_i18n::t;
+use types::entities::PlaylistImportResult;
+
+use crate::{i18n::use_i18n, modals::common::GenericModal, store::modal_store::ModalStore};
+
+/// Lists the entries of an imported playlist that could not be found
+#[tracing::instrument(level = "trace", skip(result))]
+#[component]
+pub fn PlaylistImportModal(#[prop()] result: PlaylistImportResult) -> impl IntoView {
+    let i18n = use_i18n();
+    let modal_store: RwSignal<ModalStore> = expect_context();
+    let close_modal = move |_| modal_store.update(|m| m.clear_active_modal());
+
+    view! {
+        <GenericModal size=move || "modal-lg".into()>
+            <div class="container-fluid p-0 mt-4">
+                <div class="row no-gutters d-flex">
+                    <div class="col">
+                        <h4>{t!(i18n, playlistImport.title)}</h4>
+                        <h6 class="mt-3">
+                            {t!(i18n, playlistImport.unresolved)}
+                            <span class="keyword ml-2">{result.unresolved.len()}</span>
+                        </h6>
+                    </div>
+                </div>
+                <div class="row no-gutters mt-3 unresolved-entries">
+                    <div class="col">
+                        {result
+                            .unresolved
+                            .into_iter()
+                            .map(|location| {
+                                view! { <div class="song-subtitle text-truncate">{location}</div> }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+                <div class="row row-cols-auto mt-3 mr-4">
+                    <button
+                        on:click=close_modal
+                        class="btn btn-secondary confirm-button ml-auto"
+                        type="button"
+                    >
+                        {t!(i18n, buttons.close)}
+                    </button>
+                </div>
+            </div>
+        </GenericModal>
+    }
+}
@@ -0,0 +1,142 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use leptos::{component, prelude::*, task::spawn_local, view, IntoView};
+use leptos_i18n::t;
+use types::entities::BackupManifest;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    i18n::use_i18n,
+    icons::folder_icon::FolderIcon,
+    modals::common::GenericModal,
+    store::modal_store::ModalStore,
+    utils::{invoke::restore_library_backup, prefs::open_file_browser_single},
+};
+
+/// Confirms restoring the backup at `path`, letting the user point its music
+/// folders at folders on this machine. The app restarts once it is restored.
+#[tracing::instrument(level = "trace", skip(path, manifest))]
+#[component]
+pub fn RestoreBackupModal(#[prop()] path: String, manifest: BackupManifest) -> impl IntoView {
+    let i18n = use_i18n();
+    let modal_store: RwSignal<ModalStore> = expect_context();
+    let close_modal = move |_| modal_store.update(|m| m.clear_active_modal());
+
+    // Music folder in the backup and the folder it is restored to
+    let music_roots = manifest
+        .music_roots
+        .iter()
+        .map(|root| (root.clone(), RwSignal::new(root.clone())))
+        .collect::<Vec<_>>();
+    let remaps = StoredValue::new(music_roots.clone());
+
+    let created_at = js_sys::Date::new(&JsValue::from_f64(manifest.created_at as f64 * 1000f64))
+        .to_locale_string("default", &JsValue::UNDEFINED)
+        .as_string()
+        .unwrap_or_default();
+
+    let restoring = RwSignal::new(false);
+    let path = StoredValue::new(path);
+    let restore = move |_| {
+        restoring.set(true);
+        let music_roots = remaps.with_value(|remaps| {
+            remaps
+                .iter()
+                .map(|(from, to)| (from.clone(), to.get_untracked()))
+                .filter(|(from, to)| !to.is_empty() && from != to)
+                .collect::<HashMap<_, _>>()
+        });
+        spawn_local(async move {
+            // The app restarts when this succeeds
+            if let Err(e) = restore_library_backup(path.get_value(), music_roots).await {
+                tracing::error!("Failed to restore backup: {:?}", e);
+                restoring.set(false);
+            }
+        });
+    };
+
+    view! {
+        <GenericModal size=move || "modal-lg".into()>
+            <div class="container-fluid p-0 mt-4">
+                <div class="row no-gutters d-flex">
+                    <div class="col">
+                        <h4>{t!(i18n, restoreBackup.title)}</h4>
+                        <h6 class="mt-3">
+                            {format!(
+                                "Moosync {} ({}), {}",
+                                manifest.app_version,
+                                manifest.platform,
+                                created_at,
+                            )}
+                        </h6>
+                        <h6 class="mt-3">{t!(i18n, restoreBackup.warning)}</h6>
+                    </div>
+                </div>
+                <Show when=move || !remaps.with_value(|r| r.is_empty())>
+                    <div class="row no-gutters mt-3">
+                        <div class="col">
+                            <h6>{t!(i18n, restoreBackup.music_roots)}</h6>
+                        </div>
+                    </div>
+                </Show>
+                {music_roots
+                    .into_iter()
+                    .map(|(from, to)| {
+                        view! {
+                            <div class="row no-gutters mt-2">
+                                <div class="col-12 song-subtitle text-truncate">{from}</div>
+                                <div class="col-12 input-prefs-background mt-1 d-flex align-content-center">
+                                    <div class="col-auto align-self-center ml-4 folder-icon">
+                                        <FolderIcon on:click=move |_| open_file_browser_single(
+                                            true,
+                                            vec![],
+                                            to,
+                                        ) />
+                                    </div>
+                                    <div class="col-auto ml-3 mr-3 align-self-center flex-grow-1 d-flex">
+                                        <div class="item-text text-truncate file-picker-text align-self-center">
+                                            {move || to.get()}
+                                        </div>
+                                    </div>
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+                <div class="row row-cols-auto mt-3 mr-4">
+                    <button
+                        on:click=close_modal
+                        class="btn btn-secondary cancel-button ml-auto"
+                        type="button"
+                    >
+                        {t!(i18n, buttons.cancel)}
+                    </button>
+                    <button
+                        on:click=restore
+                        class="btn btn-secondary confirm-button ml-3"
+                        type="button"
+                        disabled=move || restoring.get()
+                    >
+                        {t!(i18n, restoreBackup.confirm)}
+                    </button>
+                </div>
+            </div>
+        </GenericModal>
+    }
+}
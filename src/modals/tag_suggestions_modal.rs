@@ -0,0 +1,156 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use leptos::{component, prelude::*, task::spawn_local, view, IntoView};
+use leptos_i18n::t;
+use types::{entities::TagSuggestion, songs::Song};
+
+use crate::{
+    i18n::use_i18n,
+    modals::common::GenericModal,
+    store::modal_store::ModalStore,
+    utils::invoke::{apply_tag_suggestion, lookup_song_tags},
+};
+
+#[tracing::instrument(level = "trace", skip(song))]
+#[component]
+pub fn TagSuggestionsModal(#[prop()] song: Song) -> impl IntoView {
+    let i18n = use_i18n();
+    let modal_store: RwSignal<ModalStore> = expect_context();
+    let close_modal = move |_| modal_store.update(|m| m.clear_active_modal());
+
+    let loading = RwSignal::new(true);
+    let suggestions = RwSignal::new(Vec::<TagSuggestion>::new());
+
+    let song_id = song.song._id.clone().unwrap_or_default();
+    spawn_local(async move {
+        match lookup_song_tags(song_id).await {
+            Ok(res) => suggestions.set(res),
+            Err(e) => tracing::error!("Failed to look up tags: {:?}", e),
+        }
+        loading.set(false);
+    });
+
+    let apply = move |suggestion: TagSuggestion| {
+        spawn_local(async move {
+            if let Err(e) = apply_tag_suggestion(suggestion).await {
+                tracing::error!("Failed to apply tags: {:?}", e);
+            }
+            modal_store.update(|m| m.clear_active_modal());
+        });
+    };
+
+    let current_artists = song
+        .artists
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|a| a.artist_name)
+        .collect::<Vec<_>>();
+    let current_tags = std::iter::once(song.song.title.clone().unwrap_or_default())
+        .chain((!current_artists.is_empty()).then(|| current_artists.join(", ")))
+        .collect::<Vec<_>>()
+        .join(" - ");
+
+    view! {
+        <GenericModal size=move || "modal-lg".into()>
+            <div class="container-fluid p-0 mt-4">
+                <div class="row no-gutters d-flex">
+                    <div class="col">
+                        <h4>{t!(i18n, tagSuggestions.title)}</h4>
+                        <h6 class="mt-3 text-truncate">{current_tags}</h6>
+                    </div>
+                </div>
+                <div class="row no-gutters mt-3 tag-suggestions">
+                    <div class="col">
+                        {move || {
+                            if loading.get() {
+                                return view! { <h6>{t!(i18n, tagSuggestions.searching)}</h6> }
+                                    .into_any();
+                            }
+                            let items = suggestions.get();
+                            if items.is_empty() {
+                                return view! { <h6>{t!(i18n, tagSuggestions.empty)}</h6> }
+                                    .into_any();
+                            }
+                            items
+                                .into_iter()
+                                .map(|suggestion| {
+                                    let artists = suggestion
+                                        .artists
+                                        .iter()
+                                        .map(|a| a.name.clone())
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                    let album = suggestion
+                                        .album
+                                        .clone()
+                                        .map(|album| match &suggestion.year {
+                                            Some(year) => format!("{} ({})", album, year),
+                                            None => album,
+                                        })
+                                        .unwrap_or_default();
+                                    let score = format!("{}%", (suggestion.score * 100f64).round());
+                                    view! {
+                                        <div class="row no-gutters d-flex align-items-center mb-3">
+                                            <div class="col-auto mr-3">
+                                                {suggestion
+                                                    .cover_url
+                                                    .clone()
+                                                    .map(|url| {
+                                                        view! {
+                                                            <img class="tag-suggestion-cover" width="56" height="56" src=url />
+                                                        }
+                                                    })}
+                                            </div>
+                                            <div class="col text-truncate">
+                                                <div class="song-title text-truncate">
+                                                    {suggestion.title.clone()}
+                                                </div>
+                                                <div class="song-subtitle text-truncate">{artists}</div>
+                                                <div class="song-subtitle text-truncate">{album}</div>
+                                            </div>
+                                            <div class="col-auto mr-3">{score}</div>
+                                            <div class="col-auto">
+                                                <button
+                                                    class="btn btn-secondary confirm-button"
+                                                    type="button"
+                                                    on:click=move |_| apply(suggestion.clone())
+                                                >
+                                                    {t!(i18n, tagSuggestions.apply)}
+                                                </button>
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                                .into_any()
+                        }}
+                    </div>
+                </div>
+                <div class="row row-cols-auto mt-3 mr-4">
+                    <button
+                        on:click=close_modal
+                        class="btn btn-secondary cancel-button ml-auto"
+                        type="button"
+                    >
+                        {t!(i18n, buttons.cancel)}
+                    </button>
+                </div>
+            </div>
+        </GenericModal>
+    }
+}
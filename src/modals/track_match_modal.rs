@@ -0,0 +1,207 @@
+// Moosync
+// Copyright (C) 2024, 2025  Moosync <support@moosync.app>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use leptos::{component, prelude::*, task::spawn_local, view, IntoView};
+use leptos_i18n::t;
+use types::{entities::TrackMapping, songs::Song};
+
+use crate::{
+    i18n::use_i18n,
+    modals::common::GenericModal,
+    store::modal_store::ModalStore,
+    utils::{
+        common::format_duration,
+        invoke::{get_track_mapping, provider_search, remove_track_mapping, set_track_mapping},
+    },
+};
+
+const MATCH_PROVIDER: &str = "youtube";
+
+#[tracing::instrument(level = "trace", skip(song))]
+#[component]
+pub fn TrackMatchModal(#[prop()] song: Song) -> impl IntoView {
+    let i18n = use_i18n();
+    let modal_store: RwSignal<ModalStore> = expect_context();
+    let close_modal = move |_| modal_store.update(|m| m.clear_active_modal());
+
+    let loading = RwSignal::new(true);
+    let candidates = RwSignal::new(Vec::<Song>::new());
+    let mapping = RwSignal::new(None::<TrackMapping>);
+
+    let song_id = StoredValue::new(song.song._id.clone().unwrap_or_default());
+    let artists = song
+        .artists
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|a| a.artist_name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let title = song.song.title.clone().unwrap_or_default();
+    let term = format!("{} - {}", artists, title);
+
+    spawn_local(async move {
+        match get_track_mapping(song_id.get_value(), MATCH_PROVIDER.into()).await {
+            Ok(res) => mapping.set(res),
+            Err(e) => tracing::error!("Failed to get track mapping: {:?}", e),
+        }
+        match provider_search(MATCH_PROVIDER.into(), term).await {
+            Ok(res) => candidates.set(res.songs),
+            Err(e) => tracing::error!("Failed to search match candidates: {:?}", e),
+        }
+        loading.set(false);
+    });
+
+    let choose = move |candidate: Song| {
+        spawn_local(async move {
+            let res = set_track_mapping(TrackMapping {
+                song_id: song_id.get_value(),
+                provider: MATCH_PROVIDER.into(),
+                mapped_id: candidate.song.url.unwrap_or_default(),
+                score: 1f64,
+                user_override: true,
+            })
+            .await;
+            if let Err(e) = res {
+                tracing::error!("Failed to set track mapping: {:?}", e);
+            }
+            modal_store.update(|m| m.clear_active_modal());
+        });
+    };
+
+    let reset = move |_| {
+        spawn_local(async move {
+            if let Err(e) = remove_track_mapping(song_id.get_value(), MATCH_PROVIDER.into()).await {
+                tracing::error!("Failed to remove track mapping: {:?}", e);
+            }
+            modal_store.update(|m| m.clear_active_modal());
+        });
+    };
+
+    let current_tags = if artists.is_empty() {
+        title
+    } else {
+        format!("{} - {}", title, artists)
+    };
+
+    view! {
+        <GenericModal size=move || "modal-lg".into()>
+            <div class="container-fluid p-0 mt-4">
+                <div class="row no-gutters d-flex">
+                    <div class="col">
+                        <h4>{t!(i18n, trackMatch.title)}</h4>
+                        <h6 class="mt-3 text-truncate">{current_tags}</h6>
+                    </div>
+                </div>
+                <div class="row no-gutters mt-3 tag-suggestions">
+                    <div class="col">
+                        {move || {
+                            if loading.get() {
+                                return view! { <h6>{t!(i18n, trackMatch.searching)}</h6> }
+                                    .into_any();
+                            }
+                            let items = candidates.get();
+                            if items.is_empty() {
+                                return view! { <h6>{t!(i18n, trackMatch.empty)}</h6> }
+                                    .into_any();
+                            }
+                            let current = mapping.with(|m| m.as_ref().map(|m| m.mapped_id.clone()));
+                            items
+                                .into_iter()
+                                .map(|candidate| {
+                                    let channel = candidate
+                                        .artists
+                                        .clone()
+                                        .unwrap_or_default()
+                                        .into_iter()
+                                        .filter_map(|a| a.artist_name)
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                    let duration = candidate
+                                        .song
+                                        .duration
+                                        .map(format_duration)
+                                        .unwrap_or_default();
+                                    let is_current = current.is_some()
+                                        && candidate.song.url == current;
+                                    view! {
+                                        <div class="row no-gutters d-flex align-items-center mb-3">
+                                            <div class="col-auto mr-3">
+                                                {candidate
+                                                    .song
+                                                    .song_cover_path_low
+                                                    .clone()
+                                                    .map(|url| {
+                                                        view! {
+                                                            <img class="tag-suggestion-cover" width="56" height="56" src=url />
+                                                        }
+                                                    })}
+                                            </div>
+                                            <div class="col text-truncate">
+                                                <div class="song-title text-truncate">
+                                                    {candidate.song.title.clone()}
+                                                </div>
+                                                <div class="song-subtitle text-truncate">{channel}</div>
+                                                <div class="song-subtitle text-truncate">{duration}</div>
+                                            </div>
+                                            <div class="col-auto">
+                                                <button
+                                                    class="btn btn-secondary confirm-button"
+                                                    type="button"
+                                                    disabled=is_current
+                                                    on:click=move |_| choose(candidate.clone())
+                                                >
+                                                    {if is_current {
+                                                        t!(i18n, trackMatch.current).into_any()
+                                                    } else {
+                                                        t!(i18n, trackMatch.choose).into_any()
+                                                    }}
+                                                </button>
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                                .into_any()
+                        }}
+                    </div>
+                </div>
+                <div class="row row-cols-auto mt-3 mr-4">
+                    <div class="ml-auto d-flex">
+                        <Show when=move || {
+                            mapping.with(|m| m.as_ref().is_some_and(|m| m.user_override))
+                        }>
+                            <button
+                                on:click=reset
+                                class="btn btn-secondary cancel-button mr-3"
+                                type="button"
+                            >
+                                {t!(i18n, trackMatch.reset)}
+                            </button>
+                        </Show>
+                        <button
+                            on:click=close_modal
+                            class="btn btn-secondary cancel-button"
+                            type="button"
+                        >
+                            {t!(i18n, buttons.cancel)}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </GenericModal>
+    }
+}
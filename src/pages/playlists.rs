@@ -121,6 +121,14 @@ pub fn SinglePlaylist() -> impl IntoView {
     let add_to_queue_setter = create_write_slice(player_store, |p, songs| p.add_to_queue(songs));
 
     let play_songs = move || {
+        if let Some(playlist) = playlist.get_untracked() {
+            spawn_local(async move {
+                if let Err(e) = crate::utils::invoke::add_tray_recent_playlist(playlist).await {
+                    tracing::error!("Failed to add recent playlist to tray {:?}", e);
+                }
+            });
+        }
+
         let selected_songs = if selected_songs.get().is_empty() {
             filtered_songs.get()
         } else {
@@ -187,6 +195,19 @@ pub fn AllPlaylists() -> impl IntoView {
 
     refresh_playlist_items.as_ref()();
 
+    let data_updated = expect_context::<Arc<ProviderStore>>().data_updated;
+    let refresh_item_clone = refresh_playlist_items.clone();
+    // Updates from before this page was opened are already part of what it loads
+    Effect::new(move |prev: Option<()>| {
+        let updated = data_updated.with(|u| {
+            u.as_ref()
+                .is_some_and(|u| u.method == "fetch_user_playlists")
+        });
+        if prev.is_some() && updated {
+            refresh_item_clone.as_ref()();
+        }
+    });
+
     let modal_manager = expect_context::<RwSignal<ModalStore>>();
     let refresh_item_clone = refresh_playlist_items.clone();
     let open_new_playlist_modal = move |_| {
@@ -14,12 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
-use crate::{
-    components::cardview::{CardView, SimplifiedCardItem},
-    utils::invoke::{provider_search, search_all},
-};
+use crate::components::cardview::{CardView, SimplifiedCardItem};
 use colors_transform::{Color, Rgb};
 use leptos::{component, ev::wheel, html::Div, prelude::*, view, IntoView, Params};
 use leptos_router::{hooks::use_query, params::Params};
@@ -235,9 +232,9 @@ pub fn Search() -> impl IntoView {
         })
     };
 
-    let search_results = RwSignal::new(HashMap::new());
-
     let provider_store = expect_context::<Arc<ProviderStore>>();
+    let search_results = provider_store.search_results;
+    let search_pending = provider_store.search_pending;
     let mut keys = provider_store.get_provider_keys();
     keys.insert(0, "Local".into());
 
@@ -256,8 +253,6 @@ pub fn Search() -> impl IntoView {
     }
     selected_category.set(vec![category_keys.first().unwrap().clone()]);
 
-    let keys_clone = keys.clone();
-    let is_loading = RwSignal::new(HashMap::new());
     Effect::new(move || {
         let search_term = term();
         if let Some(search_term) = search_term {
@@ -265,39 +260,7 @@ pub fn Search() -> impl IntoView {
                 return;
             }
             tracing::debug!("Searching for: {}", search_term);
-
-            let keys = keys_clone.clone();
-            spawn_local(async move {
-                for key in keys {
-                    let key_cl = key.clone();
-                    is_loading.update(move |map| {
-                        map.insert(key_cl, true);
-                    });
-                    let res = if key == "Local" {
-                        search_all(search_term.clone()).await
-                    } else {
-                        provider_search(key.clone(), search_term.clone()).await
-                    };
-                    match res {
-                        Ok(res) => {
-                            search_results.update(|map| {
-                                map.insert(key.clone(), res);
-                            });
-                        }
-                        Err(err) => {
-                            tracing::error!(
-                                "Error searching for {} ({}): {:?}",
-                                search_term,
-                                key,
-                                err
-                            );
-                        }
-                    }
-                    is_loading.update(move |map| {
-                        map.remove(&key);
-                    });
-                }
-            });
+            provider_store.search_everywhere(search_term);
         }
     });
 
@@ -321,16 +284,17 @@ pub fn Search() -> impl IntoView {
                             view! {
                                 <Show
                                     when=move || {
-                                        is_loading
-                                            .with(move |map| {
-                                                let binding = selected_provider.get();
-                                                let active_provider = binding.first();
-                                                if let Some(active_provider) = active_provider {
-                                                    *map.get(active_provider).unwrap_or(&false)
-                                                } else {
-                                                    false
-                                                }
-                                            })
+                                        search_pending.get()
+                                            && search_results
+                                                .with(move |map| {
+                                                    let binding = selected_provider.get();
+                                                    let active_provider = binding.first();
+                                                    if let Some(active_provider) = active_provider {
+                                                        !map.contains_key(active_provider)
+                                                    } else {
+                                                        false
+                                                    }
+                                                })
                                     }
                                     fallback=move || ()
                                 >
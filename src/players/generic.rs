@@ -28,7 +28,13 @@ use types::{
 pub trait GenericPlayer: std::fmt::Debug + DynClone {
     fn initialize(&self, element: NodeRef<Div>);
     fn key(&self) -> String;
-    fn load(&self, src: String, autoplay: bool, resolver: OneShotSender<()>);
+    fn load(
+        &self,
+        src: String,
+        song_id: Option<String>,
+        autoplay: bool,
+        resolver: OneShotSender<()>,
+    );
     fn stop(&mut self) -> Result<()>;
     fn play(&self) -> Result<()>;
     fn pause(&self) -> Result<()>;
@@ -211,7 +211,13 @@ impl GenericPlayer for LibrespotPlayer {
     }
 
     #[tracing::instrument(level = "trace", skip(self, src, resolver))]
-    fn load(&self, src: String, autoplay: bool, resolver: tokio::sync::oneshot::Sender<()>) {
+    fn load(
+        &self,
+        src: String,
+        _song_id: Option<String>,
+        autoplay: bool,
+        resolver: tokio::sync::oneshot::Sender<()>,
+    ) {
         let player_state_tx = self.player_state_tx.clone();
         spawn_local(async move {
             let res = librespot_load(src.clone(), false).await;
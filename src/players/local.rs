@@ -127,7 +127,13 @@ impl GenericPlayer for LocalPlayer {
     }
 
     #[tracing::instrument(level = "trace", skip(self, src, resolver))]
-    fn load(&self, src: String, autoplay: bool, resolver: OneShotSender<()>) {
+    fn load(
+        &self,
+        src: String,
+        _song_id: Option<String>,
+        autoplay: bool,
+        resolver: OneShotSender<()>,
+    ) {
         let mut src = convert_file_src(src);
         tracing::debug!("Loading audio {}", src);
 
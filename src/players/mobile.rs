@@ -117,7 +117,13 @@ impl GenericPlayer for MobilePlayer {
     }
 
     #[tracing::instrument(level = "trace", skip(self, src, resolver))]
-    fn load(&self, src: String, autoplay: bool, resolver: OneShotSender<()>) {
+    fn load(
+        &self,
+        src: String,
+        _song_id: Option<String>,
+        autoplay: bool,
+        resolver: OneShotSender<()>,
+    ) {
         tracing::debug!("Loading audio {}", src);
 
         let key = self.key.clone();
@@ -59,9 +59,15 @@ impl GenericPlayer for RodioPlayer {
     }
 
     #[tracing::instrument(level = "trace", skip(self, src, resolver))]
-    fn load(&self, src: String, autoplay: bool, resolver: tokio::sync::oneshot::Sender<()>) {
+    fn load(
+        &self,
+        src: String,
+        song_id: Option<String>,
+        autoplay: bool,
+        resolver: tokio::sync::oneshot::Sender<()>,
+    ) {
         spawn_local(async move {
-            let res = rodio_load(src).await;
+            let res = rodio_load(src, song_id).await;
             if let Err(err) = res {
                 tracing::error!("Rodio error {:?}", err);
             } else {
@@ -114,7 +114,7 @@ fn generate_component(config: &PreferenceUIFile) -> proc_macro2::TokenStream {
 
     quote! {
         use crate::components::{
-            prefs::components::{CheckboxPref, ExtensionPref, InputPref, PathsPref, ThemesPref, DropdownPref},
+            prefs::components::{CheckboxPref, ExtensionPref, InputPref, PathsPref, ThemesPref, DropdownPref, LibraryPref},
             sidebar::{Sidebar, Tab},
         };
         use crate::i18n::*;
@@ -179,6 +179,7 @@ fn generate_children(data: &[PreferenceUIData]) -> Vec<(syn::Ident, proc_macro2:
             types::preferences::PreferenceTypes::ThemeSelector => generate_themes(item),
             types::preferences::PreferenceTypes::Extensions => generate_extensions(item),
             types::preferences::PreferenceTypes::Dropdown => generate_dropdowns(item),
+            types::preferences::PreferenceTypes::Library => generate_library(item),
             types::preferences::PreferenceTypes::ButtonGroup
             | types::preferences::PreferenceTypes::InfoField
             | types::preferences::PreferenceTypes::ProgressBar
@@ -207,6 +208,7 @@ fn generate_checkbox(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::Token
     );
 
     let single = data.single.unwrap_or(false);
+    let orderable = data.orderable.unwrap_or(false);
 
     let mut checkboxes = vec![];
     for items in data.items.clone().unwrap() {
@@ -242,6 +244,7 @@ fn generate_checkbox(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::Token
                     tooltip=t!(i18n, #tooltip)
                     items=checkbox_items
                     single=#single
+                    orderable=#orderable
                 />
 
             }
@@ -270,6 +273,8 @@ fn generate_input(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::TokenStr
         _ => (true, ""),
     };
 
+    let secure = data.secure.unwrap_or(false);
+
     let fn_name = syn::Ident::new(
         format!("Input{}Pref", data.key).replace(".", "").as_str(),
         proc_macro2::Span::call_site(),
@@ -281,7 +286,7 @@ fn generate_input(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::TokenStr
             let i18n = use_i18n();
 
             view! {
-                <InputPref key=#key.to_string() title=t!(i18n, #name) tooltip=t!(i18n, #tooltip) show_input=#show_input inp_type=#inp_type.to_string() mobile=#mobile />
+                <InputPref key=#key.to_string() title=t!(i18n, #name) tooltip=t!(i18n, #tooltip) show_input=#show_input inp_type=#inp_type.to_string() mobile=#mobile secure=#secure />
             }
         }
     };
@@ -343,6 +348,33 @@ fn generate_themes(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::TokenSt
     (fn_name, stream)
 }
 
+#[tracing::instrument(level = "trace", skip(data))]
+fn generate_library(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::TokenStream) {
+    let mobile = data.mobile.unwrap_or(true);
+
+    let name = get_path(data.title.clone());
+
+    let tooltip = get_path(data.description.clone());
+
+    let fn_name = syn::Ident::new(
+        format!("Library{}Pref", data.key).replace(".", "").as_str(),
+        proc_macro2::Span::call_site(),
+    );
+
+    let stream = quote! {
+        #[component]
+        pub fn #fn_name() -> impl IntoView {
+            let i18n = use_i18n();
+
+            view! {
+                <LibraryPref title=t!(i18n, #name) tooltip=t!(i18n, #tooltip) mobile=#mobile />
+            }
+        }
+    };
+
+    (fn_name, stream)
+}
+
 #[tracing::instrument(level = "trace", skip(data))]
 fn generate_extensions(data: &PreferenceUIData) -> (syn::Ident, proc_macro2::TokenStream) {
     let name = get_path(data.title.clone());
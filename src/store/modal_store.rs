@@ -16,7 +16,11 @@
 
 use std::sync::Arc;
 
-use types::{songs::Song, ui::themes::ThemeModalState};
+use types::{
+    entities::{BackupManifest, DuplicateReport, PlaylistImportResult},
+    songs::Song,
+    ui::themes::ThemeModalState,
+};
 
 use crate::modals::new_playlist_modal::PlaylistModalState;
 
@@ -28,6 +32,11 @@ pub enum Modals {
     NewPlaylistModal(PlaylistModalState, Option<Vec<Song>>),
     SongFromUrlModal,
     ThemeModal(ThemeModalState),
+    TagSuggestionsModal(Song),
+    TrackMatchModal(Song),
+    PlaylistImportModal(PlaylistImportResult),
+    RestoreBackupModal(String, BackupManifest),
+    DuplicatesModal(DuplicateReport),
 }
 
 #[derive(Clone, Default)]
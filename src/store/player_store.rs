@@ -15,28 +15,77 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use bitcode::{Decode, Encode};
+use futures::{
+    channel::mpsc::{unbounded, UnboundedSender},
+    future::LocalBoxFuture,
+    Future, FutureExt, StreamExt,
+};
 use indexed_db_futures::{database::Database, prelude::*};
 use leptos::prelude::*;
-use rand::seq::SliceRandom;
 use serde::Serialize;
-use std::{cmp::min, collections::HashMap};
+use std::{collections::HashMap, fmt::Debug};
 use types::{
     preferences::CheckboxPreference,
     songs::Song,
     ui::extensions::ExtensionExtraEvent,
-    ui::player_details::{PlayerState, RepeatModes, VolumeMode},
+    ui::player_details::{PlayerState, QueueState, RepeatModes, VolumeMode},
 };
+use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::{
     store::ui_store::UiStore,
     utils::{
+        common::listen_event,
         db_utils::{read_from_indexed_db, write_to_indexed_db},
         extensions::send_extension_event,
-        mpris::{set_playback_state, set_position},
+        invoke::{
+            get_queue, queue_add, queue_clear, queue_clear_except_current, queue_next,
+            queue_play_next, queue_play_now, queue_prev, queue_remove, queue_replace,
+            queue_set_index, queue_set_repeat, queue_set_shuffle,
+        },
+        mpris::{seeked, set_playback_state, set_position, set_volume as set_mpris_volume},
     },
 };
 
+type QueueCommand = LocalBoxFuture<'static, types::errors::Result<()>>;
+
+/// Sends queue commands to the backend one at a time, so they are applied
+/// in the order they were issued
+#[derive(Clone)]
+struct QueueCommands(UnboundedSender<QueueCommand>);
+
+impl QueueCommands {
+    #[tracing::instrument(level = "trace", skip())]
+    fn new() -> Self {
+        let (tx, mut rx) = unbounded::<QueueCommand>();
+        spawn_local(async move {
+            while let Some(command) = rx.next().await {
+                if let Err(e) = command.await {
+                    tracing::error!("Failed to update queue: {:?}", e);
+                }
+            }
+        });
+        Self(tx)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, command))]
+    fn send<F>(&self, command: F)
+    where
+        F: Future<Output = types::errors::Result<()>> + 'static,
+    {
+        if let Err(e) = self.0.unbounded_send(command.boxed_local()) {
+            tracing::error!("Failed to send queue command: {:?}", e);
+        }
+    }
+}
+
+impl Debug for QueueCommands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueueCommands").finish()
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Encode, Decode)]
 pub struct Queue {
     pub song_queue: Vec<String>,
@@ -73,6 +122,10 @@ pub struct PlayerStore {
     scrobble_time: f64,
     scrobbled: bool,
     is_mobile: bool,
+    queue_commands: QueueCommands,
+    /// Force reload the song once the backend reports the queue change
+    force_next: bool,
+    shuffle: bool,
 }
 
 impl PlayerStore {
@@ -89,16 +142,60 @@ impl PlayerStore {
             scrobble_time: 0f64,
             scrobbled: false,
             is_mobile,
+            queue_commands: QueueCommands::new(),
+            force_next: false,
+            shuffle: false,
         };
 
         tracing::debug!("Created player store {:?}", player_store);
         let signal = RwSignal::new(player_store);
 
-        Self::load_state_from_idb(signal);
+        let unlisten = listen_event("queue-updated", move |data: JsValue| {
+            let payload = js_sys::Reflect::get(&data, &JsValue::from_str("payload")).unwrap();
+            match serde_wasm_bindgen::from_value::<QueueState>(payload) {
+                Ok(state) => signal.update(|s| s.apply_queue(state)),
+                Err(e) => tracing::error!("Received invalid queue: {:?}", e),
+            }
+        });
+        on_cleanup(move || {
+            let _ = unlisten.call0(&JsValue::NULL);
+        });
+
+        spawn_local(async move {
+            let restored = match get_queue().await {
+                Ok(state) => {
+                    let restored = !state.song_queue.is_empty();
+                    signal.update(|s| s.apply_queue(state));
+                    restored
+                }
+                Err(e) => {
+                    tracing::error!("Failed to fetch queue: {:?}", e);
+                    false
+                }
+            };
+
+            // Only the player details are restored once the backend owns the queue
+            Self::load_state_from_idb(signal, restored);
+        });
 
         signal
     }
 
+    /// Mirrors the queue owned by the backend
+    #[tracing::instrument(level = "trace", skip(self, state))]
+    fn apply_queue(&mut self, state: QueueState) {
+        self.data.queue = Queue {
+            song_queue: state.song_queue,
+            current_index: state.current_index,
+            data: state.data,
+        };
+        self.data.player_details.repeat = state.repeat;
+        self.shuffle = state.shuffle;
+
+        let force = std::mem::take(&mut self.force_next);
+        self.update_current_song(force);
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn get_current_song(&self) -> Option<Song> {
         self.data.current_song.clone()
@@ -198,41 +295,17 @@ impl PlayerStore {
 
     #[tracing::instrument(level = "trace", skip(self, songs))]
     pub fn add_to_queue(&mut self, songs: Vec<Song>) {
-        self.add_to_queue_at_index(songs, self.data.queue.song_queue.len());
-        self.update_current_song(false);
-    }
-
-    #[tracing::instrument(level = "trace", skip(self, songs, index))]
-    fn add_to_queue_at_index(&mut self, songs: Vec<Song>, index: usize) {
-        let mut index = index;
-        for song in songs {
-            self.insert_song_at_index(song, index);
-            index += 1;
-        }
+        self.queue_commands.send(queue_add(songs));
     }
 
     #[tracing::instrument(level = "trace", skip(self, index))]
     pub fn remove_from_queue(&mut self, index: usize) {
-        self.data.queue.song_queue.remove(index);
-        self.dump_store();
-    }
-
-    #[tracing::instrument(level = "trace", skip(self, song, index))]
-    fn insert_song_at_index(&mut self, song: Song, index: usize) {
-        let song_id = song.song._id.clone().unwrap();
-        self.data.queue.data.insert(song_id.clone(), song);
-        let insertion_index = min(self.data.queue.song_queue.len(), index);
-        self.data.queue.song_queue.insert(insertion_index, song_id);
-
-        self.dump_store();
+        self.queue_commands.send(queue_remove(index));
     }
 
     #[tracing::instrument(level = "trace", skip(self, song))]
     pub fn play_now(&mut self, song: Song) {
-        self.set_state(PlayerState::Playing);
-        self.insert_song_at_index(song, self.data.queue.current_index + 1);
-        self.data.queue.current_index += 1;
-        self.update_current_song(true);
+        self.play_now_multiple(vec![song]);
     }
 
     #[tracing::instrument(level = "trace", skip(self, songs))]
@@ -241,19 +314,14 @@ impl PlayerStore {
             return;
         }
 
-        let first_song = songs.first();
-        if let Some(first_song) = first_song {
-            self.play_now(first_song.clone())
-        }
-
-        if songs.len() > 1 {
-            self.add_to_queue_at_index(songs[1..].to_vec(), self.data.queue.current_index + 1);
-        }
+        self.set_state(PlayerState::Playing);
+        self.force_next = true;
+        self.queue_commands.send(queue_play_now(songs));
     }
 
     #[tracing::instrument(level = "trace", skip(self, song))]
     pub fn play_next(&mut self, song: Song) {
-        self.insert_song_at_index(song, self.data.queue.current_index + 1);
+        self.play_next_multiple(vec![song]);
     }
 
     #[tracing::instrument(level = "trace", skip(self, songs))]
@@ -262,20 +330,31 @@ impl PlayerStore {
             return;
         }
 
-        let first_song = songs.first();
-        if let Some(first_song) = first_song {
-            self.play_next(first_song.clone())
-        }
+        self.queue_commands.send(queue_play_next(songs));
+    }
 
-        if songs.len() > 1 {
-            self.add_to_queue_at_index(songs[1..].to_vec(), self.data.queue.current_index + 1);
+    /// Replaces the whole queue and starts playing from its first song
+    #[tracing::instrument(level = "trace", skip(self, songs))]
+    pub fn replace_queue(&mut self, songs: Vec<Song>) {
+        if songs.is_empty() {
+            return;
         }
+
+        self.set_state(PlayerState::Playing);
+        self.force_next = true;
+        self.queue_commands.send(queue_replace(songs, 0));
     }
 
     #[tracing::instrument(level = "trace", skip(self, new_index))]
     pub fn change_index(&mut self, new_index: usize, force: bool) {
-        self.data.queue.current_index = new_index;
-        self.update_current_song(force);
+        if new_index == self.data.queue.current_index {
+            // The backend won't report a change, so reload right away
+            self.update_current_song(force);
+            return;
+        }
+
+        self.force_next |= force;
+        self.queue_commands.send(queue_set_index(new_index));
     }
 
     #[tracing::instrument(level = "trace", skip(self, new_time))]
@@ -308,12 +387,14 @@ impl PlayerStore {
 
         tracing::debug!("Got seek {}", new_time);
         self.data.player_details.force_seek = new_time;
+        seeked(new_time);
         // send_extension_event(ExtensionExtraEvent::Seeked([new_time]))
     }
 
     #[tracing::instrument(level = "trace", skip(self, new_time))]
     pub fn force_seek(&mut self, new_time: f64) {
         self.data.player_details.force_seek = new_time;
+        seeked(new_time);
         // send_extension_event(ExtensionExtraEvent::Seeked([new_time]))
     }
 
@@ -351,6 +432,7 @@ impl PlayerStore {
         self.data.player_details.volume = volume;
 
         self.dump_store();
+        set_mpris_volume(volume);
         send_extension_event(ExtensionExtraEvent::VolumeChanged([volume]))
     }
 
@@ -445,21 +527,12 @@ impl PlayerStore {
 
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn next_song(&mut self) {
-        self.data.queue.current_index += 1;
-        if self.data.queue.current_index >= self.data.queue.song_queue.len() {
-            self.data.queue.current_index = 0;
-        }
-        self.update_current_song(false);
+        self.queue_commands.send(queue_next());
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn prev_song(&mut self) {
-        if self.data.queue.current_index == 0 {
-            self.data.queue.current_index = self.data.queue.song_queue.len() - 1;
-        } else {
-            self.data.queue.current_index -= 1;
-        }
-        self.update_current_song(false);
+        self.queue_commands.send(queue_prev());
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
@@ -470,48 +543,38 @@ impl PlayerStore {
             RepeatModes::Loop => RepeatModes::None,
         };
 
-        self.data.player_details.repeat = new_mode;
-        self.dump_store();
+        self.set_repeat(new_mode);
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn shuffle_queue(&mut self) {
-        let binding = self.data.queue.song_queue.clone();
-        let current_song = binding.get(self.data.queue.current_index).unwrap();
-        let mut rng = rand::thread_rng();
-        self.data.queue.song_queue.shuffle(&mut rng);
-        let new_index = self
-            .data
-            .queue
-            .song_queue
-            .iter()
-            .position(|v| v == current_song)
-            .unwrap();
-        self.data.queue.current_index = new_index;
-        self.dump_store();
+    pub fn set_repeat(&mut self, mode: RepeatModes) {
+        self.data.player_details.repeat = mode;
+        self.queue_commands.send(queue_set_repeat(mode));
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn clear_queue(&mut self) {
-        self.data.queue.song_queue.clear();
-        self.data.queue.current_index = 0;
-        self.update_current_song(false);
+    pub fn get_shuffle(&self) -> bool {
+        self.shuffle
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn clear_queue_except_current(&mut self) {
-        let current_song = self.get_current_song();
+    pub fn toggle_shuffle(&mut self) {
+        self.set_shuffle(!self.shuffle);
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.queue_commands.send(queue_set_shuffle(shuffle));
+    }
 
-        let only_one_song = self.get_queue().song_queue.len() == 1;
-        self.data.queue.song_queue.clear();
-        self.data.queue.current_index = 0;
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn clear_queue(&mut self) {
+        self.queue_commands.send(queue_clear());
+    }
 
-        if !only_one_song {
-            if let Some(current_song) = current_song {
-                self.add_to_queue(vec![current_song]);
-            }
-        }
-        self.update_current_song(false);
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn clear_queue_except_current(&mut self) {
+        self.queue_commands.send(queue_clear_except_current());
     }
 
     #[tracing::instrument(level = "trace", skip(self, key))]
@@ -528,7 +591,7 @@ impl PlayerStore {
         self.data.player_blacklist.clear();
     }
 
-    pub fn load_state_from_idb(signal: RwSignal<PlayerStore>) {
+    pub fn load_state_from_idb(signal: RwSignal<PlayerStore>, has_queue: bool) {
         spawn_local(async move {
             match Database::open("moosync")
                 .with_on_upgrade_needed(move |_, db| {
@@ -548,8 +611,26 @@ impl PlayerStore {
                     Effect::new(move || {
                         let data = data_signal.get();
                         signal.update(|s| {
-                            if let Some(data) = data {
+                            if let Some(mut data) = data {
                                 tracing::debug!("Restored player store data {:?}", data);
+
+                                // Queues saved before the backend owned them are handed over once
+                                let restored_queue = std::mem::replace(
+                                    &mut data.queue,
+                                    std::mem::take(&mut s.data.queue),
+                                );
+                                if !has_queue && !restored_queue.song_queue.is_empty() {
+                                    let songs = restored_queue
+                                        .song_queue
+                                        .iter()
+                                        .filter_map(|id| restored_queue.data.get(id).cloned())
+                                        .collect();
+                                    s.queue_commands
+                                        .send(queue_replace(songs, restored_queue.current_index));
+                                }
+
+                                data.current_song = s.data.current_song.take();
+                                data.player_details.repeat = s.data.player_details.repeat;
                                 s.data = data;
                                 s.data.player_details.current_time = 0f64;
                             }
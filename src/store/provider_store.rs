@@ -19,6 +19,7 @@ use std::fmt::Debug;
 
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use types::entities::{ProviderDataUpdate, ProviderSearchResult, SearchResult};
 use types::errors::Result;
 use types::providers::generic::ProviderStatus;
 use wasm_bindgen::JsValue;
@@ -28,7 +29,9 @@ use crate::store::modal_store::{ModalStore, Modals};
 use crate::utils::common::listen_event;
 use crate::utils::invoke::{
     get_all_status, get_provider_key_by_id, get_provider_keys, initialize_all_providers,
+    search_everywhere,
 };
+use crate::utils::providers::page_scope;
 
 #[derive(Debug, Default)]
 pub struct ProviderStore {
@@ -36,6 +39,11 @@ pub struct ProviderStore {
     statuses: RwSignal<Vec<ProviderStatus>>,
     // unlisten_provider_key: Option<js_sys::Function>,
     pub is_initialized: RwSignal<bool>,
+    search_term: RwSignal<String>,
+    pub search_results: RwSignal<HashMap<String, SearchResult>>,
+    pub search_pending: RwSignal<bool>,
+    /// Last refreshed provider response which differed from the cached one
+    pub data_updated: RwSignal<Option<ProviderDataUpdate>>,
 }
 
 impl ProviderStore {
@@ -78,10 +86,42 @@ impl ProviderStore {
                 modal_store.update(|m| m.clear_active_modal());
             }
 
-            if let Some(spotify) = provider_status.get("spotify") {
-                if spotify.user_name.is_some() {
-                    LibrespotPlayer::set_initialized(true);
+            if provider_status
+                .values()
+                .any(|s| s.key == "spotify" && s.active && s.user_name.is_some())
+            {
+                LibrespotPlayer::set_initialized(true);
+            }
+        });
+
+        listen_event("search-result", move |data: JsValue| {
+            let payload = js_sys::Reflect::get(&data, &JsValue::from_str("payload")).unwrap();
+            let res: ProviderSearchResult = match serde_wasm_bindgen::from_value(payload) {
+                Ok(res) => res,
+                Err(e) => {
+                    tracing::error!("Failed to parse search result {:?}", e);
+                    return;
                 }
+            };
+
+            // Results of an older search may still arrive after the term changed
+            if res.term != store.search_term.get_untracked() {
+                return;
+            }
+
+            store.search_results.update(|map| {
+                map.insert(res.key, res.result);
+            });
+        });
+
+        listen_event("provider-data-updated", move |data: JsValue| {
+            let payload = js_sys::Reflect::get(&data, &JsValue::from_str("payload")).unwrap();
+            match serde_wasm_bindgen::from_value::<ProviderDataUpdate>(payload) {
+                Ok(update) => {
+                    tracing::debug!("Provider data updated {} {}", update.key, update.method);
+                    store.data_updated.set(Some(update));
+                }
+                Err(e) => tracing::error!("Failed to parse provider data update {:?}", e),
             }
         });
 
@@ -120,6 +160,26 @@ impl ProviderStore {
         self.keys.get_untracked()
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn search_everywhere(&self, term: String) {
+        let search_term = self.search_term;
+        let search_pending = self.search_pending;
+
+        search_term.set(term.clone());
+        self.search_results.set(HashMap::new());
+        search_pending.set(true);
+
+        let scope = page_scope();
+        spawn_local(async move {
+            if let Err(e) = search_everywhere(term.clone(), Some(scope)).await {
+                tracing::error!("Error searching for {}: {:?}", term, e);
+            }
+            if search_term.get_untracked() == term {
+                search_pending.set(false);
+            }
+        });
+    }
+
     pub fn get_provider_name_by_key(&self, key: String) -> Option<ProviderStatus> {
         self.statuses
             .get_untracked()
@@ -199,7 +199,7 @@ pub fn get_high_img(song: &Song) -> String {
 }
 
 macro_rules! fetch_infinite {
-    ($provider:expr, $fetch_content:ident, $update_signal:expr, $next_page_signal:ident, $is_loading:ident, $($arg:expr),*) => {
+    ($provider:expr, $fetch_content:ident, $update_signal:expr, $next_page_signal:ident, $is_loading:ident, $scope:expr, $($arg:expr),*) => {
             'fetch: {
                 use types::providers::generic::Pagination;
                 use types::common::Unique;
@@ -231,7 +231,7 @@ macro_rules! fetch_infinite {
                     map.insert(key, true);
                 });
 
-                let res = crate::utils::invoke::$fetch_content($provider.clone(), $($arg,)* pagination.clone()).await;
+                let res = crate::utils::invoke::$fetch_content($provider.clone(), $($arg,)* pagination.clone(), $scope).await;
                 if res.is_err() {
                     tracing::error!("Error fetching content {:?}", res);
                     break 'fetch Err(res.unwrap_err());
@@ -286,12 +286,29 @@ macro_rules! fetch_infinite {
 pub fn listen_event<F>(event: &str, cb: F) -> js_sys::Function
 where
     F: Fn(JsValue) + 'static,
+{
+    listen_event_then(event, cb, || {})
+}
+
+/// Same as [listen_event], calling `on_listening` once the listener is registered
+#[tracing::instrument(level = "trace", skip(event, cb, on_listening))]
+pub fn listen_event_then<F, R>(event: &str, cb: F, on_listening: R) -> js_sys::Function
+where
+    F: Fn(JsValue) + 'static,
+    R: FnOnce() + 'static,
 {
     let closure = Closure::wrap(Box::new(move |data: JsValue| {
         cb(data);
     }) as Box<dyn Fn(JsValue)>);
     let res = listen(event, closure.into_js_value());
 
+    let registered = wasm_bindgen_futures::JsFuture::from(res.clone());
+    spawn_local(async move {
+        if registered.await.is_ok() {
+            on_listening();
+        }
+    });
+
     let event = event.to_string();
     let data = Box::new(move || {
         let event = event.clone();
@@ -26,7 +26,7 @@ use leptos_router::{
 };
 use types::{
     entities::{QueryableArtist, QueryablePlaylist},
-    songs::Song,
+    songs::{Song, SongType},
 };
 use wasm_bindgen_futures::spawn_local;
 
@@ -43,8 +43,8 @@ use crate::{
 
 use super::{
     db_utils::{
-        add_songs_to_library, add_to_playlist, create_playlist, export_playlist, remove_playlist,
-        remove_songs_from_library,
+        add_songs_to_library, add_to_playlist, create_playlist, export_playlist,
+        import_playlist_file, remove_playlist, remove_songs_from_library,
     },
     invoke::load_theme,
     songs::get_sort_cx_items,
@@ -104,10 +104,7 @@ where
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn clear_queue_and_play(&self) {
         let player_store = use_context::<RwSignal<PlayerStore>>().unwrap();
-        player_store.update(|store| {
-            store.clear_queue();
-            store.play_now_multiple(self.current_or_list())
-        });
+        player_store.update(|store| store.replace_queue(self.current_or_list()));
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
@@ -125,6 +122,26 @@ where
         add_to_playlist(id, self.current_or_list());
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn find_tags(&self) {
+        if let Some(song) = self.current_song.clone() {
+            let modal_store: RwSignal<ModalStore> = expect_context();
+            let refresh_cb = self.refresh_cb.clone();
+            modal_store.update(|m| {
+                m.set_active_modal(Modals::TagSuggestionsModal(song));
+                m.on_modal_close(move || refresh_cb.as_ref()());
+            });
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn choose_match(&self) {
+        if let Some(song) = self.current_song.clone() {
+            let modal_store: RwSignal<ModalStore> = expect_context();
+            modal_store.update(|m| m.set_active_modal(Modals::TrackMatchModal(song)));
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn goto_album(&self) {
         let navigate = use_navigate();
@@ -316,6 +333,40 @@ where
             ),
         ];
 
+        if self
+            .current_song
+            .as_ref()
+            .is_some_and(|s| s.song.type_ == SongType::LOCAL)
+        {
+            ret.push(ContextMenuItemInner::new_with_handler(
+                i18n.get_keys()
+                    .contextMenu()
+                    .song()
+                    .findTags()
+                    .build_string()
+                    .into(),
+                |_, cx| cx.find_tags(),
+                None,
+            ));
+        }
+
+        if self
+            .current_song
+            .as_ref()
+            .is_some_and(|s| s.song.type_ == SongType::SPOTIFY)
+        {
+            ret.push(ContextMenuItemInner::new_with_handler(
+                i18n.get_keys()
+                    .contextMenu()
+                    .song()
+                    .chooseMatch()
+                    .build_string()
+                    .into(),
+                |_, cx| cx.choose_match(),
+                None,
+            ));
+        }
+
         let location = window().location().pathname().unwrap();
         if location.contains("playlists/single") {
             ret.insert(
@@ -455,6 +506,16 @@ impl ContextMenuData<Self> for PlaylistContextMenu {
                 |_, cx| cx.open_import_from_url_modal(),
                 None,
             ),
+            ContextMenuItemInner::new_with_handler(
+                i18n.get_keys()
+                    .contextMenu()
+                    .playlist()
+                    .importFile()
+                    .build_string()
+                    .into(),
+                |_, cx| import_playlist_file(cx.refresh_cb.clone()),
+                None,
+            ),
             ContextMenuItemInner::new(
                 i18n.get_keys()
                     .contextMenu()
@@ -489,9 +550,9 @@ impl PlaylistItemContextMenu {
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    fn export_playlist(&self) {
+    fn export_playlist(&self, relative: bool) {
         if let Some(playlist) = &self.playlist {
-            export_playlist(playlist.clone());
+            export_playlist(playlist.clone(), relative);
         }
     }
 }
@@ -521,7 +582,17 @@ impl ContextMenuData<Self> for PlaylistItemContextMenu {
                                 .export()
                                 .build_string()
                                 .into(),
-                            |_, cx| cx.export_playlist(),
+                            |_, cx| cx.export_playlist(false),
+                            None,
+                        ),
+                        ContextMenuItemInner::new_with_handler(
+                            i18n.get_keys()
+                                .contextMenu()
+                                .playlist()
+                                .exportRelative()
+                                .build_string()
+                                .into(),
+                            |_, cx| cx.export_playlist(true),
                             None,
                         ),
                     ];
@@ -26,13 +26,16 @@ use types::entities::QueryableAlbum;
 use types::entities::QueryableArtist;
 use types::entities::QueryableGenre;
 use types::{
-    entities::{GetEntityOptions, QueryablePlaylist},
+    entities::{GetEntityOptions, PlaylistFormat, QueryablePlaylist},
     songs::{GetSongOptions, Song},
+    window::DialogFilter,
 };
 use wasm_bindgen::JsValue;
 use web_sys::DomException;
 use web_sys::IdbTransactionMode;
 
+use crate::store::modal_store::{ModalStore, Modals};
+
 #[tracing::instrument(level = "trace", skip(options, setter))]
 #[cfg(not(feature = "mock"))]
 pub fn get_songs_by_option(options: GetSongOptions, setter: impl Set<Value = Vec<Song>> + 'static) {
@@ -178,6 +181,7 @@ where
     let next_page_tokens: RwSignal<
         HashMap<String, Arc<Mutex<types::providers::generic::Pagination>>>,
     > = RwSignal::new(HashMap::new());
+    let scope = crate::utils::providers::page_scope();
 
     spawn_local(async move {
         let res = super::invoke::get_entity_by_options(GetEntityOptions {
@@ -195,6 +199,7 @@ where
         tracing::debug!("provider keys {:?}", provider_store.get_provider_keys());
         for key in provider_store.get_provider_keys() {
             tracing::debug!("Fetching playlists from {}", key);
+            let scope = scope.clone();
             spawn_local(async move {
                 let mut should_fetch = true;
                 while should_fetch {
@@ -205,6 +210,7 @@ where
                         setter,
                         next_page_tokens,
                         is_loading,
+                        Some(scope.clone()),
                     );
                     match res {
                         Err(e) => {
@@ -332,6 +338,29 @@ pub fn add_to_playlist(id: String, songs: Vec<Song>) {
     });
 }
 
+/// Adds the song to, or removes it from, the favorites playlist which is created on first use
+#[tracing::instrument(level = "trace", skip(song))]
+pub async fn set_favorite(song: Song, favorite: bool) -> types::errors::Result<()> {
+    // Don't care if favorites playlist already exists
+    let _ = super::invoke::create_playlist(QueryablePlaylist {
+        playlist_id: Some("favorite".into()),
+        playlist_name: "Favorites".into(),
+        playlist_coverpath: Some("favorites".into()),
+        ..Default::default()
+    })
+    .await;
+
+    if favorite {
+        super::invoke::add_to_playlist("favorite".into(), vec![song]).await
+    } else {
+        super::invoke::remove_from_playlist(
+            "favorite".into(),
+            vec![song.song._id.unwrap_or_default()],
+        )
+        .await
+    }
+}
+
 #[tracing::instrument(level = "trace", skip(playlist))]
 pub fn create_playlist(playlist: QueryablePlaylist, songs: Option<Vec<Song>>) {
     spawn_local(async move {
@@ -368,15 +397,48 @@ pub fn remove_playlist(playlist: QueryablePlaylist, refresh_cb: Arc<Box<dyn Fn()
 }
 
 #[tracing::instrument(level = "trace", skip(playlist))]
-pub fn export_playlist(playlist: QueryablePlaylist) {
+pub fn export_playlist(playlist: QueryablePlaylist, relative: bool) {
     spawn_local(async move {
-        let res = super::invoke::export_playlist(playlist.playlist_id.unwrap()).await;
+        let res = super::invoke::export_playlist(playlist.playlist_id.unwrap(), relative).await;
         if let Err(res) = res {
             tracing::error!("Failed to export playlist: {:?}", res);
         }
     });
 }
 
+#[tracing::instrument(level = "trace", skip(refresh_cb))]
+pub fn import_playlist_file(refresh_cb: Arc<Box<dyn Fn() + Send + Sync>>) {
+    let modal_store: RwSignal<ModalStore> = expect_context();
+    spawn_local(async move {
+        let files = super::invoke::open_file_browser(
+            false,
+            false,
+            vec![DialogFilter {
+                name: "Playlists".into(),
+                extensions: PlaylistFormat::ALL
+                    .iter()
+                    .flat_map(|f| f.extensions())
+                    .map(|e| e.to_string())
+                    .collect(),
+            }],
+        )
+        .await;
+        let Some(file) = files.ok().and_then(|f| f.into_iter().next()) else {
+            return;
+        };
+
+        match super::invoke::import_playlist(file.path).await {
+            Ok(res) => {
+                refresh_cb.as_ref()();
+                if !res.unresolved.is_empty() {
+                    modal_store.update(|m| m.set_active_modal(Modals::PlaylistImportModal(res)));
+                }
+            }
+            Err(e) => tracing::error!("Failed to import playlist: {:?}", e),
+        }
+    });
+}
+
 #[tracing::instrument(level = "trace", skip(db, store, key, value))]
 pub async fn write_to_indexed_db(
     db: Database,
@@ -22,8 +22,8 @@ use leptos::task::spawn_local;
 use types::{mpris::MprisPlayerDetails, songs::Song, ui::player_details::PlayerState};
 
 #[tracing::instrument(level = "trace", skip(song))]
-pub fn set_metadata(song: &Song) {
-    let metadata = MprisPlayerDetails {
+fn get_mpris_details(song: &Song) -> MprisPlayerDetails {
+    MprisPlayerDetails {
         title: song.song.title.clone(),
         id: song.song._id.clone(),
         artist_name: Some(get_artist_string(song.artists.clone())),
@@ -32,7 +32,12 @@ pub fn set_metadata(song: &Song) {
         genres: None,
         duration: song.song.duration,
         thumbnail: song.song.song_cover_path_high.clone(),
-    };
+    }
+}
+
+#[tracing::instrument(level = "trace", skip(song))]
+pub fn set_metadata(song: &Song) {
+    let metadata = get_mpris_details(song);
     spawn_local(async move {
         let res = crate::utils::invoke::set_metadata(metadata).await;
         if let Err(err) = res {
@@ -75,3 +80,24 @@ pub fn set_position(duration: f64) {
         });
     }
 }
+
+#[tracing::instrument(level = "trace", skip(duration))]
+pub fn seeked(duration: f64) {
+    *last_time_update.lock().unwrap() = wasm_timer::Instant::now();
+    spawn_local(async move {
+        let res = crate::utils::invoke::set_position(duration).await;
+        if let Err(err) = res {
+            tracing::error!("Failed to set mpris position {:?}", err);
+        }
+    });
+}
+
+#[tracing::instrument(level = "trace", skip(volume))]
+pub fn set_volume(volume: f64) {
+    spawn_local(async move {
+        let res = crate::utils::invoke::set_volume(volume / 100f64).await;
+        if let Err(err) = res {
+            tracing::error!("Failed to set mpris volume {:?}", err);
+        }
+    });
+}
@@ -40,6 +40,33 @@ where
     });
 }
 
+#[tracing::instrument(level = "trace", skip(key, setter))]
+pub fn load_secure<T>(key: String, setter: impl Set<Value = T> + 'static)
+where
+    T: DeserializeOwned,
+{
+    spawn_local(async move {
+        let res = super::invoke::get_secure(key.clone()).await;
+        match res {
+            Ok(value) => setter.set(serde_wasm_bindgen::from_value(value).unwrap()),
+            Err(e) => tracing::error!("Failed to load secure preference: {}: {:?}", key, e),
+        }
+    });
+}
+
+#[tracing::instrument(level = "trace", skip(key, value))]
+pub fn save_secure<T>(key: String, value: T)
+where
+    T: Serialize + 'static,
+{
+    spawn_local(async move {
+        let res = super::invoke::set_secure(key.clone(), Some(value)).await;
+        if let Err(e) = res {
+            tracing::error!("Error saving secure preference {}: {:?}", key, e);
+        }
+    });
+}
+
 #[tracing::instrument(level = "trace", skip(key, value))]
 pub fn save_selective_number(key: String, value: String) {
     let val = value.parse::<f64>().unwrap();
@@ -14,6 +14,30 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::cell::RefCell;
+
+thread_local! {
+    static PAGE_SCOPE: RefCell<(String, u64)> = RefCell::new((String::new(), 0));
+}
+
+/// Identifies the page currently shown. Provider requests made with it are
+/// cancelled once the user navigates to another page, see [crate::app::MainApp].
+#[tracing::instrument(level = "trace", skip())]
+pub fn page_scope() -> String {
+    let path = leptos::prelude::window()
+        .location()
+        .pathname()
+        .unwrap_or_default();
+    // Revisiting a page must not cancel the requests it makes this time
+    PAGE_SCOPE.with_borrow_mut(|(last_path, generation)| {
+        if *last_path != path {
+            *last_path = path.clone();
+            *generation += 1;
+        }
+        format!("{}#{}", path, generation)
+    })
+}
+
 #[macro_export]
 macro_rules! dyn_provider_songs {
     ($selected_providers:ident, $entity:ident, $songs:ident, $fetch_fn: ident) => {{
@@ -26,7 +50,41 @@ macro_rules! dyn_provider_songs {
         > = RwSignal::new(HashMap::new());
 
         let is_loading = RwSignal::new(HashMap::new());
+        let scope = $crate::utils::providers::page_scope();
+
+        let fetch_provider = move |provider: String, entity| {
+            let scope = scope.clone();
+            spawn_local(async move {
+                let provider_songs_inner = provider_songs.get_untracked();
+
+                if !provider_songs_inner.contains_key(&provider.clone()) {
+                    let key = provider.clone();
+                    provider_songs.update(|p| {
+                        p.insert(key, RwSignal::new(vec![]));
+                    });
+                }
+
+                let binding = provider_songs.get_untracked();
+                let binding = binding.get(&provider.clone());
+
+                tracing::debug!("fetching infinite");
+                let res = fetch_infinite!(
+                    provider,
+                    $fetch_fn,
+                    *binding.unwrap(),
+                    next_page_tokens,
+                    is_loading,
+                    Some(scope),
+                    entity
+                );
+
+                if let Err(e) = res {
+                    tracing::error!("Error fetching content: {:?}", e);
+                }
+            });
+        };
 
+        let fetch = fetch_provider.clone();
         let fetch_selected_providers = Arc::new(Box::new(move || {
             let selected_providers = $selected_providers.get();
 
@@ -42,35 +100,7 @@ macro_rules! dyn_provider_songs {
                 entity
             );
             for provider in selected_providers {
-                let entity = entity.clone();
-
-                spawn_local(async move {
-                    let provider_songs_inner = provider_songs.get_untracked();
-
-                    if !provider_songs_inner.contains_key(&provider.clone()) {
-                        let key = provider.clone();
-                        provider_songs.update(|p| {
-                            p.insert(key, RwSignal::new(vec![]));
-                        });
-                    }
-
-                    let binding = provider_songs.get_untracked();
-                    let binding = binding.get(&provider.clone());
-
-                    tracing::debug!("fetching infinite");
-                    let res = fetch_infinite!(
-                        provider,
-                        $fetch_fn,
-                        *binding.unwrap(),
-                        next_page_tokens,
-                        is_loading,
-                        entity.clone()
-                    );
-
-                    if let Err(e) = res {
-                        tracing::error!("Error fetching content: {:?}", e);
-                    }
-                });
+                fetch(provider, entity.clone());
             }
         }));
 
@@ -79,6 +109,32 @@ macro_rules! dyn_provider_songs {
             fetch.as_ref()();
         });
 
+        // A stale cached response was served earlier, load the refreshed one from the start
+        let data_updated =
+            expect_context::<Arc<$crate::store::provider_store::ProviderStore>>().data_updated;
+        Effect::new(move |prev: Option<()>| {
+            let update = data_updated.get();
+            let (Some(update), Some(_)) = (update, prev) else {
+                return;
+            };
+            if update.method != stringify!($fetch_fn)
+                || !$selected_providers.get_untracked().contains(&update.key)
+            {
+                return;
+            }
+            let Some(entity) = $entity.get_untracked() else {
+                return;
+            };
+
+            next_page_tokens.update_untracked(|tokens| {
+                tokens.remove(&update.key);
+            });
+            if let Some(songs) = provider_songs.get_untracked().get(&update.key) {
+                songs.set(vec![]);
+            }
+            fetch_provider(update.key, entity);
+        });
+
         let get_collective_songs = Memo::new(move |_| {
             let mut ret = vec![];
             ret.extend($songs.get());
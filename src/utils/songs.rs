@@ -16,7 +16,7 @@
 
 use leptos::prelude::*;
 use leptos_context_menu::ContextMenuItemInner;
-use types::songs::Song;
+use types::{lyrics::Lyrics, songs::Song};
 
 use crate::{
     store::ui_store::{SongSortBy, SongSortByColumns, UiStore},
@@ -121,31 +121,36 @@ where
     ]
 }
 
-pub async fn fetch_lyrics(song: Option<Song>) -> Option<String> {
+/// Lyrics of `song`, the ones stored with it or else fetched from the lyrics sources
+pub async fn fetch_lyrics(song: Option<Song>) -> Option<Lyrics> {
     tracing::debug!("Fetching lyrics");
-    if let Some(song) = song {
-        let lyrics = song.song.lyrics.clone();
-        if lyrics.is_none() {
-            let res = get_lyrics(
-                song.song._id.clone().unwrap_or_default(),
-                song.song.playback_url.clone().unwrap_or_default(),
-                song.artists
-                    .clone()
-                    .unwrap_or_default()
-                    .iter()
-                    .map(|a| a.artist_name.clone().unwrap_or_default())
-                    .collect::<Vec<String>>(),
-                song.song.title.clone().unwrap_or_default(),
-            )
-            .await;
-            if let Ok(lyrics) = res {
-                return Some(lyrics);
-            } else {
-                tracing::error!("Failed to fetch lyrics: {:?}", res.unwrap_err());
-            }
-        }
-        return lyrics;
+    let song = song?;
+    if let Some(plain) = song.song.lyrics.clone() {
+        return Some(Lyrics {
+            plain,
+            ..Default::default()
+        });
     }
 
-    return None;
+    let res = get_lyrics(
+        song.song._id.clone().unwrap_or_default(),
+        song.song.playback_url.clone().unwrap_or_default(),
+        song.artists
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .map(|a| a.artist_name.clone().unwrap_or_default())
+            .collect::<Vec<String>>(),
+        song.song.title.clone().unwrap_or_default(),
+        song.album.as_ref().and_then(|a| a.album_name.clone()),
+        song.song.duration,
+    )
+    .await;
+    match res {
+        Ok(lyrics) => Some(lyrics),
+        Err(e) => {
+            tracing::error!("Failed to fetch lyrics: {:?}", e);
+            None
+        }
+    }
 }